@@ -0,0 +1,16 @@
+//! The `tillers` CLI: an IPC client and config editor, nothing more.
+//!
+//! The daemon is the separate `tillersd` binary, so CLI invocations never
+//! initialize the runtime, observers, or UI services — startup stays fast
+//! enough for shell prompts and completion helpers.
+
+use clap::Parser;
+
+use tillers::cli::{self, Cli};
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(err) = cli::run(cli) {
+        cli::exit_with(err);
+    }
+}
@@ -0,0 +1,29 @@
+//! The `tillersd` daemon: owns the event loop, the IPC socket, and every
+//! side effect. Launched by launchd (or by hand); the `tillers` CLI talks
+//! to it over the socket.
+
+use clap::Parser;
+
+use tillers::daemon::{self, OperationMode};
+
+/// TilleRS daemon — manages windows and serves the CLI over IPC.
+#[derive(Debug, Parser)]
+#[command(name = "tillersd", version, about)]
+struct TillersdArgs {
+    /// Run as a read-only observer: detect windows and evaluate rules, log
+    /// what would happen, but never move a window or grab a hotkey.
+    #[arg(long)]
+    observe: bool,
+}
+
+fn main() {
+    let args = TillersdArgs::parse();
+    let mode = if args.observe {
+        OperationMode::Observe
+    } else {
+        OperationMode::Daemon
+    };
+    if let Err(err) = daemon::run(mode) {
+        tillers::cli::exit_with(err);
+    }
+}
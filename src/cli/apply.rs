@@ -0,0 +1,120 @@
+//! `tillers apply` — converge the running system to a declared state.
+//!
+//! The state file is the desired truth for workspaces and rules; apply
+//! computes the difference, shows it, and executes it. Re-applying an
+//! already-converged file does nothing, so the file can live in git and
+//! be applied from a hook or a provisioning script.
+
+use clap::Args;
+
+use crate::errors::Result;
+use crate::models::ActionType;
+use crate::workspace::converge::{self, DesiredState, PlanStep};
+
+#[derive(Debug, Args)]
+pub struct ApplyArgs {
+    /// Path to the desired-state TOML file.
+    pub state: std::path::PathBuf,
+    /// Print the plan without changing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Skip the confirmation prompt for removals.
+    #[arg(long)]
+    pub yes: bool,
+}
+
+pub fn run(args: ApplyArgs) -> Result<()> {
+    let desired = DesiredState::load(&args.state)?;
+    let mut manager = crate::config::ConfigManager::load_default()?;
+
+    // Rules converge against the config on disk; workspaces against the
+    // daemon's live list. Without a running daemon every declared
+    // workspace gets an (idempotent) ensure step and pruning is skipped,
+    // since there is no current set to prune against.
+    let (current_workspaces, daemon_reachable) = match crate::ipc::IpcClient::connect() {
+        Ok(mut client) => (client.query_workspaces()?.0, true),
+        Err(_) => (Vec::new(), false),
+    };
+    let plan = converge::plan(&current_workspaces, &manager.config().rules, &desired);
+
+    if plan.is_empty() {
+        println!("Already converged; nothing to do.");
+        return Ok(());
+    }
+    for step in &plan.steps {
+        println!("{}", describe(step));
+    }
+    if !daemon_reachable && !desired.workspaces.is_empty() {
+        println!("note: daemon unreachable; workspace pruning was skipped.");
+    }
+    if args.dry_run {
+        println!("Dry run; nothing changed.");
+        return Ok(());
+    }
+
+    let removals = plan.destructive_steps().count();
+    if removals > 0
+        && !args.yes
+        && !super::window::confirm(&format!("apply {removals} removal(s)?"))?
+    {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    let mut actions = Vec::new();
+    let mut config_changed = false;
+    for step in &plan.steps {
+        match step {
+            PlanStep::CreateWorkspace(w) | PlanStep::UpdateWorkspace(w) => {
+                actions.push(ActionType::EnsureWorkspace {
+                    workspace: w.name.clone(),
+                    layout: w.layout,
+                    display: w.display.clone(),
+                    quiet: w.quiet,
+                });
+            }
+            PlanStep::RemoveWorkspace { name } => {
+                actions.push(ActionType::RemoveWorkspace {
+                    workspace: name.clone(),
+                });
+            }
+            PlanStep::AddRule(rule) => {
+                manager.add_rule(rule.clone())?;
+                config_changed = true;
+            }
+            PlanStep::UpdateRule(rule) => {
+                manager.remove_rule(&rule.name)?;
+                manager.add_rule(rule.clone())?;
+                config_changed = true;
+            }
+            PlanStep::RemoveRule { name } => {
+                manager.remove_rule(name)?;
+                config_changed = true;
+            }
+        }
+    }
+    if config_changed {
+        manager.save_with_reason(&format!("Apply {}", args.state.display()))?;
+    }
+    if !actions.is_empty() {
+        super::dispatch_transaction(actions)?;
+    }
+    println!("Applied {} step(s).", plan.steps.len());
+    Ok(())
+}
+
+/// One plan step as a diff-style line.
+fn describe(step: &PlanStep) -> String {
+    match step {
+        PlanStep::CreateWorkspace(w) => {
+            format!("+ workspace '{}' (layout {})", w.name, w.layout)
+        }
+        PlanStep::UpdateWorkspace(w) => {
+            format!("~ workspace '{}' (layout {})", w.name, w.layout)
+        }
+        PlanStep::RemoveWorkspace { name } => format!("- workspace '{name}'"),
+        PlanStep::AddRule(rule) => format!("+ rule '{}'", rule.name),
+        PlanStep::UpdateRule(rule) => format!("~ rule '{}'", rule.name),
+        PlanStep::RemoveRule { name } => format!("- rule '{name}'"),
+    }
+}
@@ -0,0 +1,88 @@
+//! Shell completion generation, including dynamic values.
+//!
+//! `tillers completions <shell>` emits the static clap-derived script.
+//! The scripts call back into the hidden `tillers __complete <what>`
+//! helper for values only the live system knows — workspace names, window
+//! ids, pattern names — so interactive completion stays current.
+
+use clap::{CommandFactory, Subcommand, ValueEnum};
+use clap_complete::{generate, Shell};
+
+use crate::errors::Result;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CompletionShell {
+    Zsh,
+    Bash,
+    Fish,
+}
+
+/// What the dynamic helper should enumerate.
+#[derive(Debug, Subcommand)]
+pub enum CompleteTarget {
+    /// Workspace names, one per line.
+    Workspaces,
+    /// Window ids with a title comment, one per line.
+    Windows,
+    /// Layout pattern names, one per line.
+    Patterns,
+}
+
+/// Emit the static completion script for `shell` on stdout.
+pub fn generate_script(shell: CompletionShell) -> Result<()> {
+    let shell = match shell {
+        CompletionShell::Zsh => Shell::Zsh,
+        CompletionShell::Bash => Shell::Bash,
+        CompletionShell::Fish => Shell::Fish,
+    };
+    let mut command = super::Cli::command();
+    generate(shell, &mut command, "tillers", &mut std::io::stdout());
+    Ok(())
+}
+
+/// Write roff man pages for the whole command tree into `out_dir`.
+///
+/// Packaging invokes this at build time (`tillers mangen --out-dir man/`)
+/// so the pages always match the clap definitions they are generated from.
+pub fn generate_man_pages(out_dir: &std::path::Path) -> Result<()> {
+    std::fs::create_dir_all(out_dir)?;
+    let command = super::Cli::command();
+    clap_mangen::generate_to(command, out_dir).map_err(crate::errors::TilleRSError::Io)?;
+    println!("Wrote man pages to {}", out_dir.display());
+    Ok(())
+}
+
+/// Print dynamic completion values; consumed by the shell scripts.
+pub fn run_complete(target: CompleteTarget) -> Result<()> {
+    match target {
+        CompleteTarget::Workspaces => {
+            // Workspace names live in the daemon; the config's rules give
+            // a useful approximation until the IPC query is available.
+            let manager = crate::config::ConfigManager::load_default()?;
+            let mut names: Vec<&str> = manager
+                .config()
+                .rules
+                .iter()
+                .filter_map(|r| r.workspace.as_deref())
+                .collect();
+            names.sort_unstable();
+            names.dedup();
+            for name in names {
+                println!("{name}");
+            }
+        }
+        CompleteTarget::Windows =>
+        {
+            #[cfg(target_os = "macos")]
+            for w in crate::macos::list_windows()? {
+                println!("{}\t{} — {}", w.id, w.app_bundle_id, w.title);
+            }
+        }
+        CompleteTarget::Patterns => {
+            for pattern in ["tall", "wide", "grid", "monocle"] {
+                println!("{pattern}");
+            }
+        }
+    }
+    Ok(())
+}
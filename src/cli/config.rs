@@ -0,0 +1,109 @@
+//! `tillers config` — config inspection, history, and rollback.
+
+use clap::Subcommand;
+
+use crate::config::{ConfigManager, GitVersioning};
+use crate::errors::{Result, TilleRSError};
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Load and validate a config file; the exit code reflects validity
+    /// (0 valid, 30 otherwise), for CI of dotfiles repos.
+    Check {
+        /// Config file to check; defaults to the active config.
+        file: Option<std::path::PathBuf>,
+        /// Validate entirely against the simulation backend — no macOS
+        /// API is touched, so this runs on any platform.
+        #[arg(long)]
+        headless: bool,
+        /// Emit findings as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show recent config changes (requires git_versioning).
+    History {
+        /// Maximum number of entries to show.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Emit JSON instead of the human-readable list.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Restore the config directory to an earlier revision.
+    Rollback {
+        /// Revision from `config history`.
+        rev: String,
+    },
+}
+
+pub fn run(command: ConfigCommand) -> Result<()> {
+    // `check` must work without a config directory (CI checks a file
+    // straight out of a dotfiles repo), so it never opens versioning.
+    if let ConfigCommand::Check {
+        file,
+        headless,
+        json,
+    } = command
+    {
+        return check(file, headless, json);
+    }
+
+    let config_dir = ConfigManager::default_path()
+        .parent()
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| TilleRSError::Config("config path has no parent directory".into()))?;
+    let versioning = GitVersioning::open(&config_dir)?;
+
+    match command {
+        ConfigCommand::Check { .. } => unreachable!("handled above"),
+        ConfigCommand::History { limit, json } => {
+            let entries = versioning.history(limit)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(());
+            }
+            if entries.is_empty() {
+                println!("No config history yet. Set git_versioning = true to record changes.");
+            }
+            for entry in entries {
+                println!("{}  {}  {}", entry.rev, entry.timestamp, entry.message);
+            }
+            Ok(())
+        }
+        ConfigCommand::Rollback { rev } => {
+            versioning.rollback(&rev)?;
+            println!("Rolled config back to {rev}");
+            Ok(())
+        }
+    }
+}
+
+/// Fully load and validate a config file. Validation itself is always
+/// platform-independent; `--headless` asserts the intent for CI and is
+/// accepted on any platform.
+fn check(file: Option<std::path::PathBuf>, headless: bool, json: bool) -> Result<()> {
+    let path = file.unwrap_or_else(ConfigManager::default_path);
+    let raw = std::fs::read_to_string(&path)?;
+    let config = crate::config::parse_config(&path, &raw)?;
+
+    let findings = crate::config::check::check(&config);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&findings)?);
+    } else {
+        for finding in &findings {
+            eprintln!("{}: {}", finding.path, finding.message);
+        }
+    }
+    if !findings.is_empty() {
+        return Err(TilleRSError::Validation(format!(
+            "{} problem(s) in {}",
+            findings.len(),
+            path.display()
+        )));
+    }
+    if !json {
+        let mode = if headless { " (headless)" } else { "" };
+        println!("{} is valid{mode}.", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,519 @@
+//! `tillers diagnostics` — inspection and debugging commands.
+
+use clap::Subcommand;
+
+use crate::diagnostics::conflicts;
+use crate::diagnostics::environment::{self, CheckStatus};
+use crate::errors::Result;
+
+#[derive(Debug, Subcommand)]
+pub enum DiagnosticsCommand {
+    /// Check macOS settings known to conflict with tiling.
+    Environment {
+        /// Emit JSON instead of the human-readable report.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Detect other running window managers and snapping tools.
+    Conflicts {
+        /// Emit JSON instead of the human-readable report.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Serialize the current displays, apps, and windows into a scene file
+    /// replayable by the headless simulation backend.
+    CaptureScene {
+        /// Output path for the scene JSON.
+        #[arg(long, default_value = "scene.json")]
+        output: std::path::PathBuf,
+    },
+    /// Liveness and load summary from the running daemon.
+    Health {
+        /// Emit JSON instead of the human-readable report.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Handshake with the running daemon and measure round-trip latency.
+    ApiCheck {
+        /// Emit JSON instead of the human-readable report.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Ask the daemon to reconcile its window model against CGWindowList
+    /// now, instead of waiting for the periodic pass.
+    Reconcile {
+        /// Emit the repair report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report phase timings from the last daemon startup.
+    StartupTimings {
+        /// Emit JSON instead of the human-readable report.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show recent focus transitions from the focus journal, with what
+    /// triggered each one — the tool for catching focus-stealing apps.
+    FocusHistory {
+        /// Number of transitions to show.
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Write a Passive focus-stealing override into this app's
+        /// profile, so its uninvited focus grabs are reverted.
+        #[arg(long, value_name = "BUNDLE_ID")]
+        quarantine: Option<String>,
+        /// Emit JSON instead of the human-readable table.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report keyboard binding usage: most frequent actions, or dead
+    /// bindings to prune with `--unused`.
+    Shortcuts {
+        /// List configured bindings not used within `--since`.
+        #[arg(long)]
+        unused: bool,
+        /// Look-back window, e.g. `30d`, `12h`.
+        #[arg(long, default_value = "30d")]
+        since: String,
+        /// Emit JSON instead of the human-readable report.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Replay a recorded event journal through the orchestrator on the
+    /// simulation backend; reports final state and invariant violations.
+    Replay {
+        /// Journal file, one JSON event per line.
+        journal: std::path::PathBuf,
+        /// Scene file seeding displays and initial windows; a synthetic
+        /// single display is used when omitted.
+        #[arg(long)]
+        scene: Option<std::path::PathBuf>,
+        /// Emit the replay report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Walk through granting missing macOS permissions: opens the right
+    /// System Settings pane and continues automatically once granted.
+    Permissions {
+        /// Also recover the optional Screen Recording permission.
+        #[arg(long)]
+        screen_recording: bool,
+        /// Seconds to wait for each grant before giving up.
+        #[arg(long, default_value_t = 120)]
+        timeout: u64,
+    },
+    /// Report workspace usage from the local statistics store.
+    Usage {
+        /// Render an ASCII heatmap of usage by weekday and hour.
+        #[arg(long)]
+        heatmap: bool,
+        /// Emit JSON instead of the human-readable report.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub fn run(command: DiagnosticsCommand) -> Result<()> {
+    match command {
+        DiagnosticsCommand::Environment { json } => {
+            let checks = environment::run_checks();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&checks)?);
+                return Ok(());
+            }
+            for check in &checks {
+                let marker = match check.status {
+                    CheckStatus::Ok => "ok",
+                    CheckStatus::Warning => "WARN",
+                    CheckStatus::Unknown => "?",
+                };
+                println!("[{marker:>4}] {}: {}", check.name, check.message);
+                if let Some(suggestion) = &check.suggestion {
+                    println!("       fix: {suggestion}");
+                }
+            }
+            Ok(())
+        }
+        DiagnosticsCommand::Conflicts { json } => {
+            let monitor = conflicts::ConflictMonitor::default();
+            let detected = monitor.scan();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&detected)?);
+                return Ok(());
+            }
+            if detected.is_empty() {
+                println!("No conflicting window managers detected.");
+            }
+            for conflict in &detected {
+                println!(
+                    "[WARN] {} is running (pid {}); two window managers will \
+                     fight over the same windows.",
+                    conflict.name, conflict.pid
+                );
+            }
+            Ok(())
+        }
+        DiagnosticsCommand::CaptureScene { output } => capture_scene(&output),
+        DiagnosticsCommand::Health { json } => {
+            let report = crate::ipc::IpcClient::connect()?.health()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            println!("daemon:         v{}", report.version);
+            println!("uptime:         {}s", report.uptime_secs);
+            println!("workspaces:     {}", report.workspaces);
+            println!("windows:        {}", report.windows);
+            println!("subscribers:    {}", report.subscribers);
+            println!("lagged events:  {}", report.lagged_events);
+            println!("orchestrator:   {}", report.orchestrator);
+            if !report.conflicts.is_empty() {
+                println!("conflicts:      {}", report.conflicts.join(", "));
+            }
+            Ok(())
+        }
+        DiagnosticsCommand::ApiCheck { json } => {
+            let report = crate::ipc::api_check()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            println!("socket:         {}", report.socket.display());
+            println!(
+                "protocol:       cli v{}, daemon v{} -> speaking v{}",
+                report.cli_version, report.daemon_version, report.negotiated.version
+            );
+            println!(
+                "capabilities:   {}",
+                report.negotiated.capabilities.join(", ")
+            );
+            if !report.negotiated.peer_only.is_empty() {
+                println!(
+                    "daemon-only:    {} (upgrade the CLI to use these)",
+                    report.negotiated.peer_only.join(", ")
+                );
+            }
+            println!("round trip:     {}us", report.round_trip_us);
+            Ok(())
+        }
+        DiagnosticsCommand::Reconcile { json } => {
+            // TODO: trigger the daemon's pass over IPC once the socket
+            // lands; until then reconcile a fresh local model so the
+            // report shape is stable for scripts.
+            let mut manager = crate::workspace::WindowManager::new();
+            let windows = crate::cli::window::query_windows()?;
+            let report = manager.reconcile(&windows);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            if report.is_clean() {
+                println!("Window model is consistent with CGWindowList.");
+            } else {
+                println!(
+                    "Repaired drift: {} adopted, {} removed, {} frames re-asserted.",
+                    report.adopted, report.removed, report.drifted
+                );
+            }
+            Ok(())
+        }
+        DiagnosticsCommand::StartupTimings { json } => {
+            let report = crate::daemon::startup::load_last_report()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            for phase in &report.phases {
+                println!(
+                    "{:>6}ms +{:<5} {}{}",
+                    phase.started_ms,
+                    format!("{}ms", phase.duration_ms),
+                    phase.name,
+                    if phase.deferred { " (deferred)" } else { "" }
+                );
+            }
+            match (report.ready_ms, report.within_budget()) {
+                (Some(ready), Some(true)) => {
+                    println!("ready to tile in {ready}ms (budget {}ms)", report.target_ms)
+                }
+                (Some(ready), _) => println!(
+                    "ready to tile in {ready}ms — OVER the {}ms budget",
+                    report.target_ms
+                ),
+                _ => println!("daemon never reached ready-to-tile"),
+            }
+            Ok(())
+        }
+        DiagnosticsCommand::FocusHistory {
+            limit,
+            quarantine,
+            json,
+        } => focus_history(limit, quarantine, json),
+        DiagnosticsCommand::Shortcuts {
+            unused,
+            since,
+            json,
+        } => shortcuts(unused, &since, json),
+        DiagnosticsCommand::Replay {
+            journal,
+            scene,
+            json,
+        } => replay(&journal, scene.as_deref(), json),
+        DiagnosticsCommand::Permissions {
+            screen_recording,
+            timeout,
+        } => permissions(screen_recording, std::time::Duration::from_secs(timeout)),
+        DiagnosticsCommand::Usage { heatmap, json } => {
+            use crate::diagnostics::usage;
+            use crate::stats::StatisticsStore;
+
+            let store = StatisticsStore::load_default()?;
+            let reports = usage::build_reports(&store);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&reports)?);
+                return Ok(());
+            }
+            if reports.is_empty() {
+                println!("No usage recorded yet.");
+                return Ok(());
+            }
+            for report in &reports {
+                if heatmap {
+                    let usage_data = &store.workspaces()[report.workspace];
+                    println!("{}", usage::render_heatmap(report.workspace, usage_data));
+                } else {
+                    println!(
+                        "{:<20} {:>6}h  top apps: {}",
+                        report.workspace,
+                        report.total_seconds / 3600,
+                        report
+                            .top_apps
+                            .iter()
+                            .map(|(app, secs)| format!("{app} ({}h)", secs / 3600))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Replay an event journal headlessly and print the outcome.
+fn replay(journal: &std::path::Path, scene: Option<&std::path::Path>, json: bool) -> Result<()> {
+    use crate::diagnostics::replay as replay_mod;
+    use crate::window_system::scene::Scene;
+
+    let events = replay_mod::load_journal(journal)?;
+    let scene = scene.map(Scene::load).transpose()?;
+    let report = replay_mod::replay(&events, scene)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    println!(
+        "Replayed {} events ({} backend operations).",
+        report.events, report.operations
+    );
+    println!(
+        "Final state: {} workspaces ({} active), {} windows.",
+        report.workspaces.len(),
+        report.active.as_deref().unwrap_or("none"),
+        report.windows
+    );
+    if report.is_clean() {
+        println!("No invariant violations.");
+        return Ok(());
+    }
+    println!("{} invariant violation(s):", report.violations.len());
+    for violation in &report.violations {
+        println!(
+            "  event {:>4}: {}",
+            violation.event_index, violation.message
+        );
+    }
+    Err(crate::errors::TilleRSError::Validation(format!(
+        "{} invariant violation(s) during replay",
+        report.violations.len()
+    )))
+}
+
+/// Walk each missing permission through the interactive recovery flow.
+fn permissions(screen_recording: bool, timeout: std::time::Duration) -> Result<()> {
+    use crate::diagnostics::permissions::{MacPermission, RecoveryAssistant};
+
+    let mut wanted = vec![MacPermission::Accessibility];
+    if screen_recording {
+        wanted.push(MacPermission::ScreenRecording);
+    }
+    let mut all_granted = true;
+    for permission in wanted {
+        if permission.granted() {
+            println!("{}: granted.", permission.label());
+            continue;
+        }
+        all_granted &= RecoveryAssistant::new(permission).run(timeout)?;
+    }
+    if !all_granted {
+        return Err(crate::errors::TilleRSError::Permission(
+            "not every permission was granted".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Report binding usage from the statistics store against the configured
+/// keymap.
+fn shortcuts(unused: bool, since: &str, json: bool) -> Result<()> {
+    use crate::stats::StatisticsStore;
+
+    let horizon = parse_since(since)?;
+    let cutoff = std::time::SystemTime::now() - horizon;
+    let store = StatisticsStore::load_default()?;
+    let config = crate::config::ConfigManager::load_default()?;
+
+    if unused {
+        let dead: Vec<_> = config
+            .config()
+            .keybindings
+            .iter()
+            .filter(|mapping| {
+                store
+                    .shortcuts()
+                    .get(&mapping.stat_key())
+                    .is_none_or(|usage| usage.last_used < cutoff)
+            })
+            .collect();
+        if json {
+            println!("{}", serde_json::to_string_pretty(&dead)?);
+            return Ok(());
+        }
+        if dead.is_empty() {
+            println!("Every binding was used within {since}.");
+            return Ok(());
+        }
+        println!("Bindings unused within {since}:");
+        for mapping in dead {
+            println!("  {:<28} {:?}", mapping.chord_label(), mapping.action);
+        }
+        return Ok(());
+    }
+
+    let mut ranked: Vec<_> = store
+        .shortcuts()
+        .values()
+        .filter(|usage| usage.last_used >= cutoff)
+        .collect();
+    ranked.sort_by_key(|usage| std::cmp::Reverse(usage.count));
+    if json {
+        println!("{}", serde_json::to_string_pretty(&ranked)?);
+        return Ok(());
+    }
+    if ranked.is_empty() {
+        println!("No shortcut usage recorded within {since}.");
+        return Ok(());
+    }
+    for usage in ranked {
+        println!("{:>8}  {}", usage.count, usage.action);
+    }
+    Ok(())
+}
+
+/// Parse a look-back spec like `30d` or `12h` into a duration.
+fn parse_since(since: &str) -> Result<std::time::Duration> {
+    super::parse_duration_spec(since).map_err(|_| {
+        crate::errors::TilleRSError::Validation(format!(
+            "invalid --since '{since}'; expected e.g. 30d or 12h"
+        ))
+    })
+}
+
+/// Print recent focus transitions and, with `--quarantine`, write a
+/// Passive focus-stealing override for the offender.
+fn focus_history(limit: usize, quarantine: Option<String>, json: bool) -> Result<()> {
+    use crate::diagnostics::focus_journal;
+
+    if let Some(bundle_id) = quarantine {
+        use crate::models::app_profile::{FocusStealingBehavior, ProfileStore};
+
+        let mut store = ProfileStore::load_default()?;
+        let profile = store.entry(&bundle_id);
+        profile.focus_stealing = FocusStealingBehavior::Passive;
+        profile.add_note("quarantined via `diagnostics focus-history --quarantine`");
+        store.save()?;
+        println!("{bundle_id}: focus stealing set to passive.");
+        return Ok(());
+    }
+
+    let entries = focus_journal::recent(limit)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+    if entries.is_empty() {
+        println!("No focus transitions recorded yet.");
+        return Ok(());
+    }
+    for entry in &entries {
+        let at = entry
+            .at
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        println!(
+            "{at:>12}  {:<14} {:<10} {:<20} {}",
+            format!("{:?}", entry.trigger).to_lowercase(),
+            entry.window,
+            entry.workspace,
+            entry.app_bundle_id
+        );
+    }
+    let offenders = focus_journal::offenders(&entries);
+    if let Some((worst, grabs)) = offenders.first() {
+        println!(
+            "\n{worst} grabbed focus uninvited {grabs} time(s); quarantine it with\n  \
+             tillers diagnostics focus-history --quarantine {worst}"
+        );
+    }
+    Ok(())
+}
+
+/// Snapshot the live window environment into a scene file.
+fn capture_scene(output: &std::path::Path) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        use crate::window_system::scene::{Scene, SceneApp};
+
+        let displays = crate::macos::list_displays()?;
+        let windows = crate::macos::list_windows()?;
+        let apps = crate::macos::running_applications()
+            .into_iter()
+            .filter_map(|(pid, name, bundle)| {
+                bundle.map(|bundle_id| SceneApp {
+                    bundle_id,
+                    name,
+                    pid,
+                })
+            })
+            .collect();
+        let scene = Scene::new(displays, apps, windows);
+        scene.save(output)?;
+        println!(
+            "Captured {} displays and {} windows to {}",
+            scene.displays.len(),
+            scene.windows.len(),
+            output.display()
+        );
+        Ok(())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = output;
+        Err(crate::errors::TilleRSError::Validation(
+            "capture-scene requires macOS; use an existing scene file with \
+             the simulation backend instead"
+                .into(),
+        ))
+    }
+}
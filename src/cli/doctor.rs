@@ -0,0 +1,289 @@
+//! `tillers doctor` — one command that answers "why isn't it working?".
+//!
+//! Runs every diagnostic that matters for a broken installation —
+//! permissions, environment sanity, the IPC round trip, an AX capability
+//! probe against a real window, display enumeration, and config
+//! validation — and reports a prioritized problem list with fixes, plus
+//! an overall pass/fail for scripts and bug reports.
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::diagnostics::environment::{self, CheckStatus};
+use crate::diagnostics::permissions::MacPermission;
+use crate::errors::Result;
+
+#[derive(Debug, Args)]
+pub struct DoctorArgs {
+    /// Emit JSON instead of the human-readable report.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// How urgently a finding needs attention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// TilleRS cannot function until this is fixed.
+    Critical,
+    /// Works, but degraded or about to surprise the user.
+    Warning,
+    /// Worth knowing; no action required.
+    Info,
+}
+
+/// One diagnosed problem (or confirmation), with a suggested fix.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorFinding {
+    pub severity: Severity,
+    /// The subsystem checked, e.g. `permissions`, `ipc`, `config`.
+    pub area: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+/// The full report; `passed` is false when anything critical was found.
+#[derive(Debug, Serialize)]
+pub struct DoctorReport {
+    pub findings: Vec<DoctorFinding>,
+    pub passed: bool,
+}
+
+pub fn run(args: DoctorArgs) -> Result<()> {
+    let mut findings = Vec::new();
+    check_permissions(&mut findings);
+    check_environment(&mut findings);
+    check_ipc(&mut findings);
+    check_displays(&mut findings);
+    check_ax_probe(&mut findings);
+    check_config(&mut findings);
+
+    // Prioritized: critical problems first, confirmations last.
+    findings.sort_by_key(|f| f.severity);
+    let passed = findings.iter().all(|f| f.severity != Severity::Critical);
+    let report = DoctorReport { findings, passed };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for finding in &report.findings {
+            let marker = match finding.severity {
+                Severity::Critical => "FAIL",
+                Severity::Warning => "WARN",
+                Severity::Info => "ok",
+            };
+            println!("[{marker:>4}] {}: {}", finding.area, finding.message);
+            if let Some(fix) = &finding.fix {
+                println!("       fix: {fix}");
+            }
+        }
+        println!();
+        println!(
+            "{}",
+            if report.passed {
+                "Overall: PASS — no critical problems."
+            } else {
+                "Overall: FAIL — fix the critical problems above."
+            }
+        );
+    }
+    if !report.passed {
+        return Err(crate::errors::TilleRSError::Validation(
+            "doctor found critical problems".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn check_permissions(findings: &mut Vec<DoctorFinding>) {
+    if MacPermission::Accessibility.granted() {
+        findings.push(DoctorFinding {
+            severity: Severity::Info,
+            area: "permissions",
+            message: "Accessibility permission granted.".into(),
+            fix: None,
+        });
+    } else {
+        findings.push(DoctorFinding {
+            severity: Severity::Critical,
+            area: "permissions",
+            message: "Accessibility permission is missing; no window can be managed.".into(),
+            fix: Some("run `tillers diagnostics permissions` for guided recovery".into()),
+        });
+    }
+    if !MacPermission::ScreenRecording.granted() {
+        findings.push(DoctorFinding {
+            severity: Severity::Warning,
+            area: "permissions",
+            message: "Screen Recording not granted; thumbnails fall back to app icons.".into(),
+            fix: Some("run `tillers diagnostics permissions --screen-recording`".into()),
+        });
+    }
+}
+
+fn check_environment(findings: &mut Vec<DoctorFinding>) {
+    for check in environment::run_checks() {
+        let severity = match check.status {
+            CheckStatus::Ok => Severity::Info,
+            CheckStatus::Warning => Severity::Warning,
+            CheckStatus::Unknown => continue,
+        };
+        findings.push(DoctorFinding {
+            severity,
+            area: "environment",
+            message: check.message,
+            fix: check.suggestion,
+        });
+    }
+}
+
+fn check_ipc(findings: &mut Vec<DoctorFinding>) {
+    match crate::ipc::api_check() {
+        Ok(report) => findings.push(DoctorFinding {
+            severity: Severity::Info,
+            area: "ipc",
+            message: format!(
+                "daemon reachable, round trip {}us (protocol v{}).",
+                report.round_trip_us, report.negotiated.version
+            ),
+            fix: None,
+        }),
+        Err(err) => findings.push(DoctorFinding {
+            // Not critical: the CLI is often run before the daemon.
+            severity: Severity::Warning,
+            area: "ipc",
+            message: format!("daemon not reachable: {err}"),
+            fix: Some("start the daemon with `tillersd`".into()),
+        }),
+    }
+}
+
+fn check_displays(findings: &mut Vec<DoctorFinding>) {
+    #[cfg(target_os = "macos")]
+    {
+        match crate::macos::list_displays() {
+            Ok(displays) if displays.is_empty() => findings.push(DoctorFinding {
+                severity: Severity::Critical,
+                area: "displays",
+                message: "no displays enumerated; nothing can be tiled.".into(),
+                fix: None,
+            }),
+            Ok(displays) => findings.push(DoctorFinding {
+                severity: Severity::Info,
+                area: "displays",
+                message: format!(
+                    "{} display(s): {}.",
+                    displays.len(),
+                    displays
+                        .iter()
+                        .map(|d| d.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                fix: None,
+            }),
+            Err(err) => findings.push(DoctorFinding {
+                severity: Severity::Critical,
+                area: "displays",
+                message: format!("display enumeration failed: {err}"),
+                fix: None,
+            }),
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        findings.push(DoctorFinding {
+            severity: Severity::Warning,
+            area: "displays",
+            message: "not running on macOS; display checks skipped.".into(),
+            fix: None,
+        });
+    }
+}
+
+/// Probe AX move/resize against one real window, when there is one.
+fn check_ax_probe(findings: &mut Vec<DoctorFinding>) {
+    #[cfg(target_os = "macos")]
+    {
+        if !MacPermission::Accessibility.granted() {
+            return; // already reported; probing would only fail noisily.
+        }
+        let sample = crate::macos::list_windows()
+            .ok()
+            .and_then(|windows| windows.into_iter().next());
+        let Some(window) = sample else {
+            findings.push(DoctorFinding {
+                severity: Severity::Info,
+                area: "ax",
+                message: "no window available to probe AX operations against.".into(),
+                fix: None,
+            });
+            return;
+        };
+        match crate::macos::probe::probe_window(window.id) {
+            Ok(caps) => {
+                let strategy = caps.strategy();
+                findings.push(DoctorFinding {
+                    severity: Severity::Info,
+                    area: "ax",
+                    message: format!(
+                        "probed '{}' (window {}): strategy {:?}.",
+                        window.app_bundle_id, window.id, strategy
+                    ),
+                    fix: None,
+                });
+            }
+            Err(err) => findings.push(DoctorFinding {
+                severity: Severity::Warning,
+                area: "ax",
+                message: format!("AX probe against window {} failed: {err}", window.id),
+                fix: None,
+            }),
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = findings;
+    }
+}
+
+fn check_config(findings: &mut Vec<DoctorFinding>) {
+    let path = crate::config::ConfigManager::default_path();
+    if !path.exists() {
+        findings.push(DoctorFinding {
+            severity: Severity::Info,
+            area: "config",
+            message: format!("no config at {}; defaults apply.", path.display()),
+            fix: None,
+        });
+        return;
+    }
+    match crate::config::ConfigManager::load(&path) {
+        Ok(manager) => {
+            let problems = crate::config::check::check(manager.config());
+            if problems.is_empty() {
+                findings.push(DoctorFinding {
+                    severity: Severity::Info,
+                    area: "config",
+                    message: "config parses and validates cleanly.".into(),
+                    fix: None,
+                });
+            }
+            for problem in problems {
+                findings.push(DoctorFinding {
+                    severity: Severity::Warning,
+                    area: "config",
+                    message: format!("{}: {}", problem.path, problem.message),
+                    fix: Some("see `tillers config check`".into()),
+                });
+            }
+        }
+        Err(err) => findings.push(DoctorFinding {
+            severity: Severity::Critical,
+            area: "config",
+            message: format!("config does not parse: {err}"),
+            fix: Some("fix the reported location, or move the file aside".into()),
+        }),
+    }
+}
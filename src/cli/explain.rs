@@ -0,0 +1,108 @@
+//! Extended documentation behind the `--explain` flag.
+//!
+//! Every subcommand can print its full semantics, examples, and the exact
+//! IPC calls it performs, keeping the CLI self-documenting in code. Man
+//! pages are generated from the same clap definitions by the hidden
+//! `mangen` command, which packaging runs at build time.
+
+/// Extended help for one subcommand path, e.g. `rules list`.
+pub struct Explanation {
+    pub path: &'static str,
+    pub semantics: &'static str,
+    pub examples: &'static [&'static str],
+    /// IPC calls the command performs against the daemon, in order.
+    pub ipc_calls: &'static [&'static str],
+}
+
+/// The registry, ordered as in `tillers help`.
+pub const EXPLANATIONS: &[Explanation] = &[
+    Explanation {
+        path: "rules",
+        semantics: "Reads and edits [[rules]] entries in the config file. \
+                    Edits are format-preserving and, with git_versioning, \
+                    committed with a descriptive message. The running \
+                    daemon picks changes up on its next config reload.",
+        examples: &[
+            "tillers rules list --workspace coding --json",
+            "tillers rules add slack --app com.tinyspeck.slackmacgap --workspace comms",
+            "tillers rules pause com.apple.iWork.Keynote --minutes 45",
+        ],
+        ipc_calls: &["none (config file only); pause/resume: dispatch_action"],
+    },
+    Explanation {
+        path: "window tile",
+        semantics: "Re-tiles the active workspace with a pattern. With \
+                    --preview, frames are computed locally and drawn as \
+                    translucent overlays for 3 seconds; nothing moves.",
+        examples: &[
+            "tillers window tile tall",
+            "tillers window tile grid --preview --count 6",
+        ],
+        ipc_calls: &["dispatch_action(Retile)"],
+    },
+    Explanation {
+        path: "window stale",
+        semantics: "Lists windows whose last focus is older than --days. \
+                    --close and --move-to act on every listed window after \
+                    one confirmation.",
+        examples: &["tillers window stale --days 7 --move-to Archive"],
+        ipc_calls: &["query windows", "dispatch_action per window"],
+    },
+    Explanation {
+        path: "apply",
+        semantics: "Treats the state file as the desired set of workspaces \
+                    and rules and converges the system to it: creates what \
+                    is missing, updates what differs, removes what is no \
+                    longer declared (after confirmation). Re-applying a \
+                    converged file is a no-op.",
+        examples: &[
+            "tillers apply state.toml --dry-run",
+            "tillers apply state.toml --yes",
+        ],
+        ipc_calls: &["dispatch_transaction(EnsureWorkspace/RemoveWorkspace)"],
+    },
+    Explanation {
+        path: "batch",
+        semantics: "Parses every line before executing anything, then \
+                    applies all commands as one transaction with a single \
+                    arrange pass. A parse error anywhere aborts the batch.",
+        examples: &["tillers batch - <<EOF\nmove 4121 coding\nswitch coding\nEOF"],
+        ipc_calls: &["dispatch_transaction"],
+    },
+    Explanation {
+        path: "diagnostics environment",
+        semantics: "Reads macOS defaults known to conflict with tiling and \
+                    prints actionable defaults-write remedies.",
+        examples: &["tillers diagnostics environment --json"],
+        ipc_calls: &["none"],
+    },
+    Explanation {
+        path: "config",
+        semantics: "History and rollback are wrappers over a git repository \
+                    in the config directory, maintained automatically when \
+                    git_versioning is enabled.",
+        examples: &[
+            "tillers config history --limit 5",
+            "tillers config rollback a1b2c3d",
+        ],
+        ipc_calls: &["none"],
+    },
+];
+
+/// Print the explanation for a subcommand path, if registered.
+pub fn explain(path: &str) -> bool {
+    let Some(entry) = EXPLANATIONS.iter().find(|e| e.path == path) else {
+        return false;
+    };
+    println!("tillers {}\n", entry.path);
+    println!("{}\n", entry.semantics);
+    println!("Examples:");
+    for example in entry.examples {
+        println!("  {example}");
+    }
+    println!("\nIPC calls:");
+    for call in entry.ipc_calls {
+        println!("  {call}");
+    }
+    true
+}
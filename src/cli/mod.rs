@@ -0,0 +1,257 @@
+//! Command-line interface definitions and handlers.
+
+pub mod apply;
+pub mod batch;
+pub mod completions;
+pub mod config;
+pub mod diagnostics;
+pub mod doctor;
+pub mod explain;
+pub mod monitor;
+pub mod palette;
+pub mod rules;
+pub mod service;
+pub mod window;
+pub mod workspace;
+
+use clap::{Parser, Subcommand};
+
+use crate::errors::Result;
+use crate::models::ActionType;
+
+/// TilleRS — a tiling window manager for macOS.
+#[derive(Debug, Parser)]
+#[command(
+    name = "tillers",
+    version,
+    about,
+    after_help = "The daemon is the separate `tillersd` binary."
+)]
+pub struct Cli {
+    /// Print extended semantics, examples, and the exact IPC calls a
+    /// subcommand performs, instead of running it.
+    #[arg(long, global = true)]
+    pub explain: bool,
+
+    /// Without a subcommand the CLI prints help; the daemon ships as the
+    /// separate `tillersd` binary, so plain `tillers` never pays its
+    /// startup cost.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Manage window rules.
+    Rules {
+        #[command(subcommand)]
+        command: rules::RuleCommand,
+    },
+    /// Run every health check at once and report a prioritized problem
+    /// list with fixes.
+    Doctor(doctor::DoctorArgs),
+    /// Inspect and debug the environment and daemon state.
+    Diagnostics {
+        #[command(subcommand)]
+        command: diagnostics::DiagnosticsCommand,
+    },
+    /// Operate on windows.
+    Window {
+        #[command(subcommand)]
+        command: window::WindowCommand,
+    },
+    /// Operate on displays and arrangement presets.
+    Monitor {
+        #[command(subcommand)]
+        command: monitor::MonitorCommand,
+    },
+    /// Fuzzy-search actions, workspaces, and windows; optionally run the
+    /// best match.
+    Palette(palette::PaletteArgs),
+    /// Operate on workspaces.
+    Workspace {
+        #[command(subcommand)]
+        command: workspace::WorkspaceCommand,
+    },
+    /// Control the running daemon.
+    Service {
+        #[command(subcommand)]
+        command: service::ServiceCommand,
+    },
+    /// Inspect and version the configuration.
+    Config {
+        #[command(subcommand)]
+        command: config::ConfigCommand,
+    },
+    /// Converge workspaces and rules to a declared desired state.
+    Apply(apply::ApplyArgs),
+    /// Apply a list of commands from a file or stdin as one transaction.
+    Batch(batch::BatchArgs),
+    /// Generate shell completions.
+    Completions { shell: completions::CompletionShell },
+    /// Dynamic completion helper used by the generated scripts.
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        #[command(subcommand)]
+        target: completions::CompleteTarget,
+    },
+    /// Generate man pages (used by packaging at build time).
+    #[command(hide = true)]
+    Mangen {
+        #[arg(long, default_value = "man")]
+        out_dir: std::path::PathBuf,
+    },
+}
+
+/// Dispatch a parsed CLI invocation to its handler.
+pub fn run(cli: Cli) -> Result<()> {
+    let Some(command) = cli.command else {
+        use clap::CommandFactory;
+        Cli::command().print_help()?;
+        return Ok(());
+    };
+    if cli.explain {
+        let path = explain_path(&command);
+        if !explain::explain(path) {
+            println!("No extended documentation for 'tillers {path}' yet.");
+        }
+        return Ok(());
+    }
+    match command {
+        Command::Rules { command } => rules::run(command),
+        Command::Doctor(args) => doctor::run(args),
+        Command::Diagnostics { command } => diagnostics::run(command),
+        Command::Window { command } => window::run(command),
+        Command::Monitor { command } => monitor::run(command),
+        Command::Palette(args) => palette::run(args),
+        Command::Workspace { command } => workspace::run(command),
+        Command::Service { command } => service::run(command),
+        Command::Config { command } => config::run(command),
+        Command::Apply(args) => apply::run(args),
+        Command::Batch(args) => batch::run(args),
+        Command::Completions { shell } => completions::generate_script(shell),
+        Command::Complete { target } => completions::run_complete(target),
+        Command::Mangen { out_dir } => completions::generate_man_pages(&out_dir),
+    }
+}
+
+/// The explanation-registry key for a parsed command.
+fn explain_path(command: &Command) -> &'static str {
+    match command {
+        Command::Rules { .. } => "rules",
+        Command::Doctor(_) => "doctor",
+        Command::Diagnostics { command } => match command {
+            diagnostics::DiagnosticsCommand::Environment { .. } => "diagnostics environment",
+            _ => "diagnostics",
+        },
+        Command::Window { command } => match command {
+            window::WindowCommand::List(_) => "window list",
+            window::WindowCommand::Tile(_) => "window tile",
+            window::WindowCommand::Stale(_) => "window stale",
+            window::WindowCommand::Export(_) => "window export",
+            window::WindowCommand::Move(_) => "window move",
+            window::WindowCommand::Archive(_) => "window archive",
+            window::WindowCommand::Tab(_) => "window tab",
+        },
+        Command::Monitor { .. } => "monitor preset",
+        Command::Palette(_) => "palette",
+        Command::Workspace { .. } => "workspace",
+        Command::Service { .. } => "service",
+        Command::Config { .. } => "config",
+        Command::Apply(_) => "apply",
+        Command::Batch(_) => "batch",
+        Command::Completions { .. } => "completions",
+        Command::Complete { .. } | Command::Mangen { .. } => "internal",
+    }
+}
+
+/// Print an error and exit with its stable code. Commands that were asked
+/// for JSON get their errors as JSON too, so scripts never have to parse
+/// stderr prose. Shared by the `tillers` and `tillersd` binaries.
+pub fn exit_with(err: crate::errors::TilleRSError) -> ! {
+    let exit_code = err.exit_code();
+    if std::env::args().any(|arg| arg == "--json") {
+        eprintln!(
+            "{}",
+            serde_json::json!({
+                "error": err.to_string(),
+                "code": err.code_name(),
+                "exit_code": exit_code,
+            })
+        );
+    } else {
+        eprintln!("error: {err}");
+    }
+    std::process::exit(exit_code);
+}
+
+/// Parse a duration spec like `2h`, `45m`, or `7d`.
+pub(crate) fn parse_duration_spec(spec: &str) -> Result<std::time::Duration> {
+    // Split on the last character's boundary, not the last byte: a
+    // multi-byte trailing character (say `30µ`) must be a clean error.
+    let (value, unit) = match spec.char_indices().last() {
+        Some((index, _)) => spec.split_at(index),
+        None => ("", spec),
+    };
+    let value: u64 = value.parse().map_err(|_| {
+        crate::errors::TilleRSError::Validation(format!(
+            "invalid duration '{spec}'; expected e.g. 2h, 45m, or 7d"
+        ))
+    })?;
+    let secs = match unit {
+        "d" => value * 24 * 60 * 60,
+        "h" => value * 60 * 60,
+        "m" => value * 60,
+        _ => {
+            return Err(crate::errors::TilleRSError::Validation(format!(
+                "invalid duration unit in '{spec}'; use d, h, or m"
+            )))
+        }
+    };
+    Ok(std::time::Duration::from_secs(secs))
+}
+
+/// Hand an action to the running daemon over IPC. Fails with exit code 40
+/// when no daemon is listening; the daemon's own errors come back with
+/// their original error class.
+pub(crate) fn dispatch_action(action: ActionType) -> Result<()> {
+    tracing::debug!(?action, "dispatching action");
+    crate::ipc::IpcClient::connect()?.action(action)
+}
+
+/// Hand a list of actions to the daemon as one all-or-nothing transaction
+/// with a single arrange pass at the end.
+pub(crate) fn dispatch_transaction(actions: Vec<ActionType>) -> Result<()> {
+    tracing::debug!(count = actions.len(), "dispatching transaction");
+    crate::ipc::IpcClient::connect()?.transaction(actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_duration_spec;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_day_hour_minute_specs() {
+        assert_eq!(
+            parse_duration_spec("2h").unwrap(),
+            Duration::from_secs(7200)
+        );
+        assert_eq!(
+            parse_duration_spec("45m").unwrap(),
+            Duration::from_secs(2700)
+        );
+        assert_eq!(
+            parse_duration_spec("7d").unwrap(),
+            Duration::from_secs(7 * 24 * 60 * 60)
+        );
+    }
+
+    #[test]
+    fn rejects_garbage_without_panicking() {
+        // The multi-byte trailing character used to panic the byte split.
+        for bad in ["", "h", "30µ", "µ", "x5m", "-1h"] {
+            assert!(parse_duration_spec(bad).is_err(), "{bad:?} should fail");
+        }
+    }
+}
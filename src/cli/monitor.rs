@@ -0,0 +1,129 @@
+//! `tillers monitor` — display arrangement presets.
+
+use clap::Subcommand;
+
+use crate::errors::Result;
+use crate::workspace::monitor_presets::{MonitorPreset, PresetStore};
+
+#[derive(Debug, Subcommand)]
+pub enum MonitorCommand {
+    /// Save and activate named display arrangements.
+    Preset {
+        #[command(subcommand)]
+        command: PresetCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PresetCommand {
+    /// Save the current arrangement and workspace pinnings under a name.
+    Save { name: String },
+    /// Re-apply a saved arrangement's workspace pinnings, warning about
+    /// displays that are no longer attached and applying what matches.
+    Activate { name: String },
+    /// List saved presets.
+    List,
+    /// Delete a preset.
+    Delete { name: String },
+}
+
+pub fn run(command: MonitorCommand) -> Result<()> {
+    let MonitorCommand::Preset { command } = command;
+    match command {
+        PresetCommand::Save { name } => save(name),
+        PresetCommand::Activate { name } => activate(name),
+        PresetCommand::List => list(),
+        PresetCommand::Delete { name } => delete(name),
+    }
+}
+
+fn save(name: String) -> Result<()> {
+    let displays = attached_displays()?;
+    // TODO: fetch the daemon's workspace pinnings over IPC; until then the
+    // preset records the arrangement with whatever pinnings the daemon has
+    // written back to disk.
+    let preset = MonitorPreset::capture(&displays, &[]);
+    let mut store = PresetStore::load_default()?;
+    store.insert(&name, preset);
+    store.save()?;
+    println!("Saved preset '{name}' ({} display(s)).", displays.len());
+    Ok(())
+}
+
+fn activate(name: String) -> Result<()> {
+    let store = PresetStore::load_default()?;
+    let preset = store.get(&name)?;
+    let plan = preset.plan(&attached_displays()?);
+
+    for display in &plan.missing_displays {
+        eprintln!("warning: display '{display}' from the preset is not attached");
+    }
+    for workspace in &plan.skipped_workspaces {
+        eprintln!("warning: workspace '{workspace}' keeps its current display");
+    }
+    if plan.pin.is_empty() {
+        println!("Nothing from preset '{name}' applies to the attached displays.");
+        return Ok(());
+    }
+
+    let actions = plan
+        .pin
+        .iter()
+        .map(
+            |(workspace, display)| crate::models::ActionType::PinWorkspaceToDisplay {
+                workspace: workspace.clone(),
+                display: display.clone(),
+            },
+        )
+        .collect();
+    super::dispatch_transaction(actions)?;
+
+    let scope = if plan.is_exact() {
+        "fully"
+    } else {
+        "partially"
+    };
+    println!("Activated preset '{name}' {scope}:");
+    for (workspace, display) in &plan.pin {
+        println!("  {workspace} -> {display}");
+    }
+    Ok(())
+}
+
+fn list() -> Result<()> {
+    let store = PresetStore::load_default()?;
+    let mut any = false;
+    for name in store.names() {
+        let preset = store.get(name)?;
+        println!(
+            "{:<20} {} display(s), {} pinning(s)",
+            name,
+            preset.displays.len(),
+            preset.assignments.len()
+        );
+        any = true;
+    }
+    if !any {
+        println!("No presets saved.");
+    }
+    Ok(())
+}
+
+fn delete(name: String) -> Result<()> {
+    let mut store = PresetStore::load_default()?;
+    store.remove(&name)?;
+    store.save()?;
+    println!("Deleted preset '{name}'.");
+    Ok(())
+}
+
+fn attached_displays() -> Result<Vec<crate::models::display::DisplayInfo>> {
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::list_displays()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,54 @@
+//! `tillers palette` — fuzzy search over actions, workspaces, and windows.
+//!
+//! The terminal front end for [`crate::ui::palette`]: the same index and
+//! ranking the overlay uses, driven from a shell. Listing is the default;
+//! `--run` executes the best match through the ordinary action dispatch
+//! path, so a palette pick behaves exactly like the bound shortcut.
+
+use clap::Args;
+
+use crate::errors::Result;
+use crate::ui::palette::PaletteIndex;
+
+#[derive(Debug, Args)]
+pub struct PaletteArgs {
+    /// Fuzzy query; every query character must appear in order. Empty
+    /// lists all entries.
+    #[arg(default_value = "")]
+    pub query: String,
+    /// Execute the best match instead of listing candidates.
+    #[arg(long)]
+    pub run: bool,
+    /// Show at most this many matches.
+    #[arg(long, default_value_t = 10, conflicts_with = "run")]
+    pub limit: usize,
+}
+
+pub fn run(args: PaletteArgs) -> Result<()> {
+    // Workspaces need the daemon (the local model knows nothing); windows
+    // fall back to direct enumeration like `window list` does.
+    let workspaces = match crate::ipc::IpcClient::connect() {
+        Ok(mut client) => client.query_workspaces()?.0,
+        Err(_) => Vec::new(),
+    };
+    let windows = super::window::query_windows()?;
+    let index = PaletteIndex::build(&workspaces, &windows);
+    let ranked = index.search(&args.query);
+
+    if ranked.is_empty() {
+        println!("No matches for '{}'.", args.query);
+        return Ok(());
+    }
+
+    if args.run {
+        let best = &ranked[0];
+        super::dispatch_action(best.entry.action.clone())?;
+        println!("{}", best.entry.label);
+        return Ok(());
+    }
+
+    for ranked in ranked.iter().take(args.limit) {
+        println!("{}", ranked.entry.label);
+    }
+    Ok(())
+}
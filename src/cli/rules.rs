@@ -0,0 +1,163 @@
+//! `tillers rules` — list and edit window rules without hand-editing the
+//! config file.
+
+use clap::{Args, Subcommand};
+
+use crate::config::ConfigManager;
+use crate::errors::Result;
+use crate::models::{ActionType, WindowRule};
+
+#[derive(Debug, Subcommand)]
+pub enum RuleCommand {
+    /// List rules, optionally filtered by workspace or application.
+    List(RuleFilter),
+    /// Add a new rule.
+    Add(AddRuleArgs),
+    /// Remove a rule by name.
+    Remove { name: String },
+    /// Enable a rule by name.
+    Enable { name: String },
+    /// Disable a rule by name (it stays in the config but never matches).
+    Disable { name: String },
+    /// Temporarily suspend all rules and tiling for an application.
+    Pause(PauseArgs),
+    /// Lift a temporary suspension early.
+    Resume {
+        /// Application bundle id to resume.
+        app: String,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct PauseArgs {
+    /// Application bundle id to suspend, e.g. `com.apple.iWork.Keynote`.
+    pub app: String,
+    /// Suspension length; management resumes automatically afterwards.
+    #[arg(long, default_value_t = 30)]
+    pub minutes: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct RuleFilter {
+    /// Only show rules targeting this workspace.
+    #[arg(long)]
+    pub workspace: Option<String>,
+    /// Only show rules matching this application bundle id.
+    #[arg(long)]
+    pub app: Option<String>,
+    /// Emit JSON instead of the human-readable table.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct AddRuleArgs {
+    /// Unique rule name.
+    pub name: String,
+    /// Application bundle id to match, e.g. `com.apple.Safari`.
+    #[arg(long)]
+    pub app: Option<String>,
+    /// Regular expression matched against the window title.
+    #[arg(long)]
+    pub title: Option<String>,
+    /// Workspace the matched window is sent to.
+    #[arg(long)]
+    pub workspace: Option<String>,
+    /// Float matched windows instead of tiling them.
+    #[arg(long)]
+    pub floating: bool,
+}
+
+/// Entry point for `tillers rules ...`.
+pub fn run(command: RuleCommand) -> Result<()> {
+    let mut manager = ConfigManager::load_default()?;
+    match command {
+        RuleCommand::List(filter) => list(&manager, &filter),
+        RuleCommand::Add(args) => {
+            let mut rule = WindowRule::named(&args.name);
+            rule.app_bundle_id = args.app;
+            rule.title_pattern = args.title;
+            rule.workspace = args.workspace;
+            rule.floating = args.floating;
+            manager.add_rule(rule)?;
+            manager.save_with_reason(&format!("Add rule '{}'", args.name))?;
+            println!("Added rule '{}'", args.name);
+            Ok(())
+        }
+        RuleCommand::Remove { name } => {
+            manager.remove_rule(&name)?;
+            manager.save_with_reason(&format!("Remove rule '{name}'"))?;
+            println!("Removed rule '{name}'");
+            Ok(())
+        }
+        RuleCommand::Enable { name } => {
+            manager.set_rule_enabled(&name, true)?;
+            manager.save_with_reason(&format!("Enable rule '{name}'"))?;
+            println!("Enabled rule '{name}'");
+            Ok(())
+        }
+        RuleCommand::Disable { name } => {
+            manager.set_rule_enabled(&name, false)?;
+            manager.save_with_reason(&format!("Disable rule '{name}'"))?;
+            println!("Disabled rule '{name}'");
+            Ok(())
+        }
+        RuleCommand::Pause(args) => {
+            super::dispatch_action(ActionType::SuspendAppRules {
+                bundle_id: args.app.clone(),
+                minutes: args.minutes,
+            })?;
+            println!(
+                "Paused rules for '{}' for {} minutes",
+                args.app, args.minutes
+            );
+            Ok(())
+        }
+        RuleCommand::Resume { app } => {
+            super::dispatch_action(ActionType::ResumeAppRules {
+                bundle_id: app.clone(),
+            })?;
+            println!("Resumed rules for '{app}'");
+            Ok(())
+        }
+    }
+}
+
+fn list(manager: &ConfigManager, filter: &RuleFilter) -> Result<()> {
+    let rules: Vec<&WindowRule> = manager
+        .config()
+        .rules
+        .iter()
+        .filter(|r| match &filter.workspace {
+            Some(ws) => r.workspace.as_deref() == Some(ws.as_str()),
+            None => true,
+        })
+        .filter(|r| match &filter.app {
+            Some(app) => r.app_bundle_id.as_deref() == Some(app.as_str()),
+            None => true,
+        })
+        .collect();
+
+    if filter.json {
+        println!("{}", serde_json::to_string_pretty(&rules)?);
+        return Ok(());
+    }
+
+    if rules.is_empty() {
+        println!("No rules match the given filters.");
+        return Ok(());
+    }
+    for rule in rules {
+        let state = if rule.enabled { "enabled" } else { "disabled" };
+        println!(
+            "{:<24} {:<10} app={} title={} workspace={} floating={}",
+            rule.name,
+            state,
+            rule.app_bundle_id.as_deref().unwrap_or("*"),
+            rule.title_pattern.as_deref().unwrap_or("*"),
+            rule.workspace.as_deref().unwrap_or("-"),
+            rule.floating,
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! `tillers service` — control the running daemon.
+
+use clap::Subcommand;
+
+use crate::errors::Result;
+use crate::models::ActionType;
+
+#[derive(Debug, Subcommand)]
+pub enum ServiceCommand {
+    /// Soft-reload the daemon: re-read config, rebuild keymaps,
+    /// re-register observers, and re-arrange workspaces — without
+    /// dropping state or losing hidden windows.
+    Reload,
+    /// Flip the hook kill switch: `off` stops launching event hooks
+    /// without a restart, `on` resumes them. The escape hatch when a
+    /// buggy hook is thrashing.
+    Hooks {
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+    },
+}
+
+pub fn run(command: ServiceCommand) -> Result<()> {
+    match command {
+        ServiceCommand::Reload => {
+            crate::cli::dispatch_action(ActionType::SoftReload)?;
+            println!("Reload requested; the daemon logs what it picked up.");
+            Ok(())
+        }
+        ServiceCommand::Hooks { state } => {
+            let enabled = state == "on";
+            crate::cli::dispatch_action(ActionType::SetHooksEnabled { enabled })?;
+            println!("Hooks {}.", if enabled { "enabled" } else { "disabled" });
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,410 @@
+//! `tillers window` — operations on individual windows.
+
+use clap::{Args, Subcommand};
+
+use crate::errors::Result;
+use crate::tiling::{LayoutPattern, TilingEngine};
+
+#[derive(Debug, Subcommand)]
+pub enum WindowCommand {
+    /// List every managed window: id, app, workspace, frame, and title.
+    List(ListArgs),
+    /// Tile the active workspace with the given pattern.
+    Tile(TileArgs),
+    /// List windows that have not been focused for a while.
+    Stale(StaleArgs),
+    /// Export the full window inventory for scripting.
+    Export(ExportArgs),
+    /// Move all windows matching the selectors to a workspace.
+    Move(MoveArgs),
+    /// Run the auto-archival sweep now, or undo the last one.
+    Archive(ArchiveArgs),
+    /// Pull the focused native tab out into its own tiled window, or merge
+    /// the app's windows into one tab group.
+    Tab(TabArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct TabArgs {
+    /// Merge the focused app's windows into one native tab group instead
+    /// of pulling the focused tab out.
+    #[arg(long)]
+    pub merge: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ArchiveArgs {
+    /// Undo the last sweep, returning its windows to the workspaces they
+    /// came from.
+    #[arg(long)]
+    pub undo: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Only windows on this workspace.
+    #[arg(long)]
+    pub workspace: Option<String>,
+    /// Only windows of this app (bundle id).
+    #[arg(long)]
+    pub app: Option<String>,
+    /// Emit JSON instead of the human-readable table.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct MoveArgs {
+    /// Target workspace: name, 1-based index, or UUID.
+    pub workspace: crate::models::WorkspaceRef,
+    /// Select windows of this app (bundle id).
+    #[arg(long)]
+    pub app: Option<String>,
+    /// Select windows whose title matches this regex.
+    #[arg(long)]
+    pub title: Option<String>,
+    /// Select windows currently on this workspace.
+    #[arg(long = "from")]
+    pub from_workspace: Option<String>,
+    /// Required to operate on every match; without it a multi-window
+    /// selection is an error, protecting against overly broad selectors.
+    #[arg(long)]
+    pub all: bool,
+}
+
+/// Output format for `window export`.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Output format.
+    #[arg(long, value_enum, default_value = "csv")]
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Args)]
+pub struct StaleArgs {
+    /// Windows unfocused for at least this many days count as stale.
+    #[arg(long, default_value_t = 7)]
+    pub days: u64,
+    /// Close every stale window (asks for confirmation).
+    #[arg(long, conflicts_with = "move_to")]
+    pub close: bool,
+    /// Move every stale window to this workspace (name, 1-based index,
+    /// or UUID; asks for confirmation).
+    #[arg(long)]
+    pub move_to: Option<crate::models::WorkspaceRef>,
+    /// Skip the confirmation prompt.
+    #[arg(long)]
+    pub yes: bool,
+    /// Emit JSON instead of the human-readable table.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct TileArgs {
+    /// Layout pattern: tall, wide, grid, monocle, or center-master.
+    pub pattern: LayoutPattern,
+    /// Show a 3-second on-screen preview of where windows would go
+    /// instead of moving anything.
+    #[arg(long)]
+    pub preview: bool,
+    /// Number of windows to include in a preview.
+    #[arg(long, default_value_t = 4, requires = "preview")]
+    pub count: usize,
+}
+
+pub fn run(command: WindowCommand) -> Result<()> {
+    match command {
+        WindowCommand::List(args) => list(args),
+        WindowCommand::Tile(args) => tile(args),
+        WindowCommand::Stale(args) => stale(args),
+        WindowCommand::Export(args) => export(args),
+        WindowCommand::Move(args) => move_windows(args),
+        WindowCommand::Archive(args) => archive(args),
+        WindowCommand::Tab(args) => tab(args),
+    }
+}
+
+/// Dispatch a native-tab manipulation; only works for apps whose Window
+/// menu offers the tab items (Safari, Finder, Terminal, ...).
+fn tab(args: TabArgs) -> Result<()> {
+    if args.merge {
+        crate::cli::dispatch_action(crate::models::ActionType::MergeIntoTabs)?;
+        println!("Merged the focused app's windows into a tab group.");
+    } else {
+        crate::cli::dispatch_action(crate::models::ActionType::PullTabOut)?;
+        println!("Pulled the focused tab out into its own window.");
+    }
+    Ok(())
+}
+
+/// Dispatch an archival sweep (or its undo) to the daemon; the policy in
+/// `[archival]` decides thresholds and the target.
+fn archive(args: ArchiveArgs) -> Result<()> {
+    if args.undo {
+        crate::cli::dispatch_action(crate::models::ActionType::ArchiveUndo)?;
+        println!("Returned the last sweep's windows to their original workspaces.");
+    } else {
+        crate::cli::dispatch_action(crate::models::ActionType::ArchiveSweep)?;
+        println!("Archival sweep ran; check the daemon log for what moved.");
+    }
+    Ok(())
+}
+
+/// Print the current window inventory.
+fn list(args: ListArgs) -> Result<()> {
+    let windows: Vec<_> = query_windows()?
+        .into_iter()
+        .filter(|w| args.workspace.as_ref().is_none_or(|ws| &w.workspace == ws))
+        .filter(|w| args.app.as_ref().is_none_or(|app| &w.app_bundle_id == app))
+        .collect();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&windows)?);
+        return Ok(());
+    }
+    if windows.is_empty() {
+        println!("No windows.");
+        return Ok(());
+    }
+    for w in &windows {
+        println!(
+            "{:<10} {:<32} {:<16} {:>4}x{:<4} @ {:>5},{:<5} {}{}{}",
+            w.id,
+            w.app_bundle_id,
+            w.workspace,
+            w.frame.width as i64,
+            w.frame.height as i64,
+            w.frame.x as i64,
+            w.frame.y as i64,
+            if w.floating { "[float] " } else { "" },
+            if w.locked { "[lock] " } else { "" },
+            w.title
+        );
+    }
+    Ok(())
+}
+
+/// Move every window matching the selectors in one transaction.
+fn move_windows(args: MoveArgs) -> Result<()> {
+    if args.app.is_none() && args.title.is_none() && args.from_workspace.is_none() {
+        return Err(crate::errors::TilleRSError::Validation(
+            "at least one selector (--app, --title, --from) is required".into(),
+        ));
+    }
+    let title_re = args
+        .title
+        .as_deref()
+        .map(regex::Regex::new)
+        .transpose()
+        .map_err(|e| {
+            crate::errors::TilleRSError::Validation(format!("invalid --title regex: {e}"))
+        })?;
+
+    let matched: Vec<_> = query_windows()?
+        .into_iter()
+        .filter(|w| args.app.as_ref().is_none_or(|app| &w.app_bundle_id == app))
+        .filter(|w| title_re.as_ref().is_none_or(|re| re.is_match(&w.title)))
+        .filter(|w| {
+            args.from_workspace
+                .as_ref()
+                .is_none_or(|ws| &w.workspace == ws)
+        })
+        .collect();
+
+    if matched.is_empty() {
+        println!("No windows match.");
+        return Ok(());
+    }
+    if matched.len() > 1 && !args.all {
+        return Err(crate::errors::TilleRSError::Validation(format!(
+            "{} windows match; pass --all to move all of them",
+            matched.len()
+        )));
+    }
+
+    let actions = matched
+        .iter()
+        .map(|w| crate::models::ActionType::MoveWindowToWorkspace {
+            window_id: w.id,
+            // Forwarded as written; the daemon resolves the reference
+            // through `WorkspaceManager::resolve`.
+            workspace: args.workspace.to_string(),
+        })
+        .collect();
+    super::dispatch_transaction(actions)?;
+
+    println!("Moved {} window(s) to '{}':", matched.len(), args.workspace);
+    for w in &matched {
+        println!("  {:<10} {:<32} {}", w.id, w.app_bundle_id, w.title);
+    }
+    Ok(())
+}
+
+/// Dump every window with the fields scripts clean up on: app, title,
+/// workspace, frame, flags, and last focus time.
+fn export(args: ExportArgs) -> Result<()> {
+    let windows = query_windows()?;
+    match args.format {
+        ExportFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&windows)?);
+        }
+        ExportFormat::Csv => {
+            println!("id,app,title,workspace,x,y,width,height,floating,locked,last_focused_unix");
+            for w in &windows {
+                let last_focused = w
+                    .last_focused_at
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                println!(
+                    "{},{},{},{},{},{},{},{},{},{},{}",
+                    w.id,
+                    csv_field(&w.app_bundle_id),
+                    csv_field(&w.title),
+                    csv_field(&w.workspace),
+                    w.frame.x,
+                    w.frame.y,
+                    w.frame.width,
+                    w.frame.height,
+                    w.floating,
+                    w.locked,
+                    last_focused
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// List (and optionally act on) windows unfocused for `--days`.
+fn stale(args: StaleArgs) -> Result<()> {
+    let windows = query_windows()?;
+    let cutoff =
+        std::time::SystemTime::now() - std::time::Duration::from_secs(args.days * 24 * 60 * 60);
+    let stale: Vec<_> = windows
+        .into_iter()
+        .filter(|w| w.last_focused_at <= cutoff)
+        .collect();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&stale)?);
+        return Ok(());
+    }
+    if stale.is_empty() {
+        println!("No windows stale for {} days.", args.days);
+        return Ok(());
+    }
+    for w in &stale {
+        println!(
+            "{:<10} {:<32} {:<20} {}",
+            w.id, w.app_bundle_id, w.workspace, w.title
+        );
+    }
+
+    let action_label = if args.close {
+        Some("close".to_string())
+    } else {
+        args.move_to.as_ref().map(|ws| format!("move to '{ws}'"))
+    };
+    let Some(label) = action_label else {
+        return Ok(());
+    };
+
+    if !args.yes && !confirm(&format!("{} {} window(s)?", label, stale.len()))? {
+        println!("Aborted.");
+        return Ok(());
+    }
+    for w in &stale {
+        let action = if args.close {
+            crate::models::ActionType::CloseWindow { window_id: w.id }
+        } else {
+            crate::models::ActionType::MoveWindowToWorkspace {
+                window_id: w.id,
+                workspace: args.move_to.as_ref().unwrap().to_string(),
+            }
+        };
+        super::dispatch_action(action)?;
+    }
+    println!("Done: {} window(s) {}.", stale.len(), label);
+    Ok(())
+}
+
+/// Ask a yes/no question on stdin.
+pub(crate) fn confirm(question: &str) -> Result<bool> {
+    use std::io::{BufRead, Write};
+    print!("{question} [y/N] ");
+    std::io::stdout().flush()?;
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    Ok(matches!(line.trim(), "y" | "Y" | "yes"))
+}
+
+/// Current window inventory.
+///
+/// Preferred source is the daemon's model, which knows focus history.
+/// Without a running daemon this falls back to direct enumeration, which
+/// reports windows but not when they were last focused.
+pub(crate) fn query_windows() -> Result<Vec<crate::models::WindowInfo>> {
+    match crate::ipc::IpcClient::connect() {
+        Ok(mut client) => client.query_windows(),
+        Err(_) => {
+            tracing::debug!("daemon unreachable; enumerating windows directly");
+            #[cfg(target_os = "macos")]
+            {
+                crate::macos::list_windows()
+            }
+            #[cfg(not(target_os = "macos"))]
+            {
+                Ok(Vec::new())
+            }
+        }
+    }
+}
+
+fn tile(args: TileArgs) -> Result<()> {
+    if args.preview {
+        return preview(args.pattern, args.count);
+    }
+    super::dispatch_action(crate::models::ActionType::Retile)?;
+    println!("Applied pattern '{}'", args.pattern);
+    Ok(())
+}
+
+/// Draw the would-be frames as translucent rectangles for a few seconds.
+fn preview(pattern: LayoutPattern, count: usize) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        use crate::ui::{preview, theme::Theme};
+
+        let work_area = crate::macos::main_display_work_area()?;
+        let engine = TilingEngine::new();
+        let frames = engine.compute_frames(pattern, work_area, count);
+        let theme = Theme::from_system();
+        preview::show_layout_preview(&theme, &frames)?;
+        println!("Previewed pattern '{pattern}' for {count} windows");
+        Ok(())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (pattern, count, TilingEngine::new());
+        Err(crate::errors::TilleRSError::Validation(
+            "layout preview requires macOS".into(),
+        ))
+    }
+}
@@ -0,0 +1,189 @@
+//! `tillers workspace` — per-workspace operations.
+
+use clap::Subcommand;
+
+use crate::errors::Result;
+use crate::models::{ActionType, WorkspaceRef};
+
+#[derive(Debug, Subcommand)]
+pub enum WorkspaceCommand {
+    /// List the daemon's workspaces; the active one is marked.
+    List {
+        /// Emit JSON instead of the table.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Switch to a workspace.
+    Switch {
+        /// Workspace name, 1-based index, or UUID.
+        workspace: WorkspaceRef,
+    },
+    /// Create a workspace.
+    Create {
+        /// Workspace name.
+        name: String,
+        /// Self-destruct after this long (e.g. `2h`, `45m`): leftover
+        /// windows move to the configured fallback workspace, then the
+        /// workspace is removed. Promote with `workspace promote` to
+        /// keep it.
+        #[arg(long, value_name = "DURATION")]
+        temporary: Option<String>,
+    },
+    /// Lift a temporary workspace's time box, making it permanent.
+    Promote {
+        /// Workspace name, 1-based index, or UUID.
+        workspace: WorkspaceRef,
+    },
+    /// Stop tiling and rule enforcement for one workspace; its windows
+    /// become free-floating until resumed.
+    Pause {
+        /// Workspace name, 1-based index, or UUID.
+        workspace: WorkspaceRef,
+    },
+    /// Re-adopt and re-tile a paused workspace's windows.
+    Resume {
+        /// Workspace name, 1-based index, or UUID.
+        workspace: WorkspaceRef,
+    },
+    /// Toggle quiet mode: new windows float where the app placed them,
+    /// already-tiled windows stay managed.
+    Quiet {
+        /// Workspace name, 1-based index, or UUID.
+        workspace: WorkspaceRef,
+    },
+    /// Manage workspace groups.
+    Group {
+        #[command(subcommand)]
+        command: GroupCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum GroupCommand {
+    /// List configured groups and their member workspaces.
+    List,
+    /// Activate a group, re-binding the numeric shortcuts (opt+1..9) to
+    /// its workspaces.
+    Switch {
+        /// Group name.
+        name: String,
+    },
+}
+
+pub fn run(command: WorkspaceCommand) -> Result<()> {
+    match command {
+        WorkspaceCommand::List { json } => list(json),
+        // References are forwarded as written; the daemon resolves them
+        // against the live workspace list via `WorkspaceManager::resolve`.
+        WorkspaceCommand::Switch { workspace } => {
+            crate::cli::dispatch_action(ActionType::SwitchWorkspace {
+                workspace: workspace.to_string(),
+            })?;
+            println!("Switched to '{workspace}'.");
+            Ok(())
+        }
+        WorkspaceCommand::Create { name, temporary } => match temporary {
+            Some(spec) => {
+                let duration = crate::cli::parse_duration_spec(&spec)?;
+                let minutes = (duration.as_secs() / 60).max(1);
+                crate::cli::dispatch_action(ActionType::CreateTemporaryWorkspace {
+                    workspace: name.clone(),
+                    minutes,
+                })?;
+                println!("Created '{name}'; it self-destructs in {spec} unless promoted.");
+                Ok(())
+            }
+            None => {
+                crate::cli::dispatch_action(ActionType::EnsureWorkspace {
+                    workspace: name.clone(),
+                    layout: crate::tiling::LayoutPattern::Tall,
+                    display: None,
+                    quiet: false,
+                })?;
+                println!("Created '{name}'.");
+                Ok(())
+            }
+        },
+        WorkspaceCommand::Promote { workspace } => {
+            crate::cli::dispatch_action(ActionType::PromoteTemporaryWorkspace {
+                workspace: workspace.to_string(),
+            })?;
+            println!("'{workspace}' is now permanent.");
+            Ok(())
+        }
+        WorkspaceCommand::Pause { workspace } => {
+            crate::cli::dispatch_action(ActionType::PauseWorkspace {
+                workspace: workspace.to_string(),
+            })?;
+            println!("Paused tiling on '{workspace}'; windows there are free-floating.");
+            Ok(())
+        }
+        WorkspaceCommand::Resume { workspace } => {
+            crate::cli::dispatch_action(ActionType::ResumeWorkspace {
+                workspace: workspace.to_string(),
+            })?;
+            println!("Resumed tiling on '{workspace}'.");
+            Ok(())
+        }
+        WorkspaceCommand::Quiet { workspace } => {
+            crate::cli::dispatch_action(ActionType::ToggleQuietMode {
+                workspace: workspace.to_string(),
+            })?;
+            println!("Toggled quiet mode on '{workspace}'.");
+            Ok(())
+        }
+        WorkspaceCommand::Group { command } => group(command),
+    }
+}
+
+/// Print the live workspace list from the daemon.
+fn list(json: bool) -> Result<()> {
+    let (workspaces, active) = crate::ipc::IpcClient::connect()?.query_workspaces()?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&workspaces)?);
+        return Ok(());
+    }
+    if workspaces.is_empty() {
+        println!("No workspaces.");
+        return Ok(());
+    }
+    for (index, workspace) in workspaces.iter().enumerate() {
+        let marker = if active.as_deref() == Some(&workspace.name) {
+            "*"
+        } else {
+            " "
+        };
+        println!(
+            "{marker} {:>2}  {:<20} {:<14} {}",
+            index + 1,
+            workspace.name,
+            workspace.layout,
+            workspace.id
+        );
+    }
+    Ok(())
+}
+
+fn group(command: GroupCommand) -> Result<()> {
+    match command {
+        GroupCommand::List => {
+            let manager = crate::config::ConfigManager::load_default()?;
+            let groups = &manager.config().groups;
+            if groups.is_empty() {
+                println!("No groups configured; see the [[groups]] config section.");
+                return Ok(());
+            }
+            for group in groups {
+                println!("{:<20} {}", group.name, group.workspaces.join(", "));
+            }
+            Ok(())
+        }
+        GroupCommand::Switch { name } => {
+            crate::cli::dispatch_action(ActionType::SwitchGroup {
+                group: name.clone(),
+            })?;
+            println!("Switched to group '{name}'; opt+1..9 now address its workspaces.");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Semantic config validation beyond what deserialization catches.
+//!
+//! Parsing rejects malformed TOML and unknown enum variants; this pass
+//! catches the mistakes that parse fine but misbehave at runtime —
+//! duplicate rule names, invalid regexes, unknown geometry presets,
+//! conflicting triggers. It needs no macOS API: geometry presets are
+//! resolved against a simulated display, so the same check runs in CI on
+//! Linux (`tillers config check --headless`).
+
+use std::collections::HashSet;
+
+use crate::window_system::{Scene, SimulatedWindowSystem, WindowSystem};
+
+use super::TilleRSConfig;
+
+/// One validation finding, with enough context to locate it in the file.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Finding {
+    /// Config path the finding is about, e.g. `rules.float-pip`.
+    pub path: String,
+    pub message: String,
+}
+
+/// Validate a parsed config. Empty result means valid.
+pub fn check(config: &TilleRSConfig) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let work_area = simulated_work_area();
+
+    let mut names = HashSet::new();
+    for rule in &config.rules {
+        let path = format!("rules.{}", rule.name);
+        if !names.insert(rule.name.as_str()) {
+            findings.push(Finding {
+                path: path.clone(),
+                message: "duplicate rule name".into(),
+            });
+        }
+        if let Some(pattern) = &rule.title_pattern {
+            if let Err(e) = regex::Regex::new(pattern) {
+                findings.push(Finding {
+                    path: path.clone(),
+                    message: format!("invalid title_pattern: {e}"),
+                });
+            }
+        }
+        if let Some(opacity) = rule.opacity {
+            if !(0.0..=1.0).contains(&opacity) {
+                findings.push(Finding {
+                    path: path.clone(),
+                    message: format!("opacity {opacity} is outside 0.0..=1.0"),
+                });
+            }
+        }
+        if let Some(geometry) = &rule.fixed_geometry {
+            if geometry.resolve(&work_area).is_none() {
+                findings.push(Finding {
+                    path: path.clone(),
+                    message: "fixed_geometry names an unknown preset".into(),
+                });
+            }
+            if !rule.floating {
+                findings.push(Finding {
+                    path,
+                    message: "fixed_geometry has no effect without floating = true".into(),
+                });
+            }
+        }
+    }
+
+    if config.catch_all.enabled && config.catch_all.workspace.is_empty() {
+        findings.push(Finding {
+            path: "catch_all.workspace".into(),
+            message: "catch_all is enabled but names no workspace".into(),
+        });
+    }
+
+    for probe in &config.startup.readiness {
+        if let crate::workspace::readiness::ReadinessCondition::TitleAppears { pattern } =
+            &probe.condition
+        {
+            if let Err(e) = regex::Regex::new(pattern) {
+                findings.push(Finding {
+                    path: format!("startup.readiness.{}", probe.bundle_id),
+                    message: format!("invalid wait_for pattern: {e}"),
+                });
+            }
+        }
+    }
+
+    let mut corners = HashSet::new();
+    for trigger in config.triggers.hot_corners.iter().filter(|t| t.enabled) {
+        if !corners.insert(trigger.corner) {
+            findings.push(Finding {
+                path: "triggers.hot_corners".into(),
+                message: format!("corner {:?} is bound more than once", trigger.corner),
+            });
+        }
+    }
+    let mut gestures = HashSet::new();
+    for trigger in config.triggers.gestures.iter().filter(|g| g.enabled) {
+        if !gestures.insert((trigger.fingers, trigger.direction)) {
+            findings.push(Finding {
+                path: "triggers.gestures".into(),
+                message: format!(
+                    "{}-finger swipe {:?} is bound more than once",
+                    trigger.fingers, trigger.direction
+                ),
+            });
+        }
+    }
+
+    findings
+}
+
+/// Work area of a synthetic 1920x1080 display from the simulation
+/// backend, used to resolve geometry without touching NSScreen.
+fn simulated_work_area() -> crate::models::Rect {
+    let display = crate::models::display::DisplayInfo {
+        id: 1,
+        name: "Simulated".into(),
+        frame: crate::models::Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 1920.0,
+            height: 1080.0,
+        },
+        work_area: crate::models::Rect {
+            x: 0.0,
+            y: 25.0,
+            width: 1920.0,
+            height: 1055.0,
+        },
+    };
+    let sim = SimulatedWindowSystem::from_scene(Scene::new(vec![display], Vec::new(), Vec::new()));
+    sim.displays()
+        .ok()
+        .and_then(|d| d.first().map(|d| d.work_area))
+        .expect("simulated scene has one display")
+}
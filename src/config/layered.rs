@@ -0,0 +1,170 @@
+//! Layered configuration for synced directories.
+//!
+//! The canonical config can live in a synced location (iCloud Drive,
+//! Dropbox, a dotfiles repo). Machine-specific differences go into
+//! `overrides.<hostname>.toml` next to it, merged over the base at the
+//! TOML-table level, so one synced file serves several Macs.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::errors::{Result, TilleRSError};
+
+use super::TilleRSConfig;
+
+/// Where the layered config comes from and which files feed it.
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    /// The shared, synced config file.
+    pub base: PathBuf,
+    /// Machine-specific override file, merged over `base` when present.
+    pub overrides: Option<PathBuf>,
+    /// Modification times at last load, for change detection.
+    loaded_mtimes: Vec<(PathBuf, SystemTime)>,
+}
+
+impl ConfigSource {
+    /// Build a source rooted at a synced directory. The override file is
+    /// `overrides.<hostname>.toml`, used only if it exists.
+    pub fn in_directory(dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref();
+        let base = dir.join("config.toml");
+        let overrides = hostname()
+            .map(|h| dir.join(format!("overrides.{h}.toml")))
+            .filter(|p| p.exists());
+        ConfigSource {
+            base,
+            overrides,
+            loaded_mtimes: Vec::new(),
+        }
+    }
+
+    /// Load and merge the layers.
+    ///
+    /// Sync-conflict artifacts (e.g. Dropbox "conflicted copy" files) next
+    /// to the base file abort the load: silently picking one side of a
+    /// conflict is how people lose config edits.
+    pub fn load(&mut self) -> Result<TilleRSConfig> {
+        self.check_sync_conflicts()?;
+
+        let base_raw = std::fs::read_to_string(&self.base)?;
+        let mut merged: toml::Value = toml::from_str(&base_raw)?;
+        self.loaded_mtimes = vec![(self.base.clone(), mtime(&self.base)?)];
+
+        if let Some(path) = &self.overrides {
+            let over_raw = std::fs::read_to_string(path)?;
+            let over: toml::Value = toml::from_str(&over_raw)?;
+            merge_tables(&mut merged, over);
+            self.loaded_mtimes.push((path.clone(), mtime(path)?));
+        }
+
+        merged.try_into().map_err(|e: toml::de::Error| {
+            TilleRSError::Config(format!("merged config is invalid: {e}"))
+        })
+    }
+
+    /// Whether any layer changed on disk since the last load. Used by the
+    /// reload loop to pick up edits made by the sync client underneath us.
+    pub fn changed_on_disk(&self) -> bool {
+        self.loaded_mtimes.iter().any(|(path, loaded)| {
+            mtime(path)
+                .map(|current| current != *loaded)
+                .unwrap_or(true)
+        })
+    }
+
+    /// Look for sync-conflict siblings of the base file.
+    fn check_sync_conflicts(&self) -> Result<()> {
+        let Some(dir) = self.base.parent() else {
+            return Ok(());
+        };
+        let Some(stem) = self.base.file_stem().and_then(|s| s.to_str()) else {
+            return Ok(());
+        };
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let is_conflict = name.starts_with(stem)
+                && (name.contains("conflicted copy") // Dropbox
+                    || name.contains("conflict") // generic
+                    || name.ends_with(".icloud")); // iCloud placeholder
+            if is_conflict && name.as_ref() != self.base.file_name().unwrap().to_string_lossy() {
+                return Err(TilleRSError::Config(format!(
+                    "sync conflict detected: '{}' exists next to the config; \
+                     resolve it before reloading",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Recursively merge `over` into `base`: tables merge per key, everything
+/// else (including arrays) is replaced wholesale.
+fn merge_tables(base: &mut toml::Value, over: toml::Value) {
+    match (base, over) {
+        (toml::Value::Table(base_table), toml::Value::Table(over_table)) => {
+            for (key, over_value) in over_table {
+                match base_table.get_mut(&key) {
+                    Some(base_value) if base_value.is_table() && over_value.is_table() => {
+                        merge_tables(base_value, over_value);
+                    }
+                    _ => {
+                        base_table.insert(key, over_value);
+                    }
+                }
+            }
+        }
+        (base_slot, over_value) => *base_slot = over_value,
+    }
+}
+
+fn mtime(path: &Path) -> Result<SystemTime> {
+    Ok(std::fs::metadata(path)?.modified()?)
+}
+
+fn hostname() -> Option<String> {
+    std::process::Command::new("hostname")
+        .arg("-s")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|h| !h.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_merge_per_key_and_scalars_are_replaced() {
+        let mut base: toml::Value = toml::from_str(
+            "auto_balance_on_close = true\n\
+             [gaps]\ninner = 8\nouter = 12\n",
+        )
+        .unwrap();
+        let over: toml::Value = toml::from_str(
+            "auto_balance_on_close = false\n\
+             [gaps]\ninner = 4\n",
+        )
+        .unwrap();
+        merge_tables(&mut base, over);
+        assert_eq!(base["auto_balance_on_close"].as_bool(), Some(false));
+        // Overridden key replaced, untouched sibling preserved.
+        assert_eq!(base["gaps"]["inner"].as_integer(), Some(4));
+        assert_eq!(base["gaps"]["outer"].as_integer(), Some(12));
+    }
+
+    #[test]
+    fn arrays_are_replaced_wholesale_not_appended() {
+        let mut base: toml::Value =
+            toml::from_str("[[rules]]\nname = \"a\"\n\n[[rules]]\nname = \"b\"\n").unwrap();
+        let over: toml::Value = toml::from_str("[[rules]]\nname = \"c\"\n").unwrap();
+        merge_tables(&mut base, over);
+        let rules = base["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["name"].as_str(), Some("c"));
+    }
+}
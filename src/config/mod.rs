@@ -0,0 +1,291 @@
+//! Configuration loading, validation, and persistence.
+
+pub mod check;
+pub mod layered;
+pub mod parse_error;
+pub mod versioning;
+pub mod writer;
+
+pub use layered::ConfigSource;
+pub use parse_error::ConfigParseError;
+pub use versioning::GitVersioning;
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::WindowRule;
+use crate::ui::theme::ThemeSpec;
+
+/// Root of the on-disk TOML configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TilleRSConfig {
+    /// Window rules evaluated in declaration order; first match wins.
+    pub rules: Vec<WindowRule>,
+    /// Keyboard shortcuts, compiled into the event-tap lookup table on
+    /// load; for the same chord and tap count, later entries win.
+    pub keybindings: Vec<crate::keyboard::KeyboardMapping>,
+    /// Gaps and margins, in points or as display-relative percentages,
+    /// with optional per-display overrides.
+    pub gaps: crate::tiling::GapsConfig,
+    /// Center-master layout tuning and ultrawide auto-selection.
+    pub center_master: crate::tiling::CenterMasterConfig,
+    /// Workspace groups; switching one re-binds the numeric shortcuts.
+    pub groups: Vec<crate::workspace::groups::WorkspaceGroup>,
+    /// Theme shared by the tray, focus border, and OSD.
+    pub theme: ThemeSpec,
+    /// Pause tiling automatically while a conflicting window manager
+    /// (yabai, Rectangle, ...) is running.
+    pub auto_pause_on_conflict: bool,
+    /// Auto-archival of windows untouched for a configurable number of days.
+    pub archival: crate::workspace::ArchivalPolicy,
+    /// Experimental multi-machine workspace sync.
+    pub sync: crate::sync::SyncConfig,
+    /// Commit the config directory to git on every successful change.
+    pub git_versioning: bool,
+    /// Deadline budgets for visible work per operation.
+    pub budgets: crate::workspace::DeadlineBudgets,
+    /// Thresholds below which new windows are never tiled (splash screens,
+    /// popups, tool palettes).
+    pub creation_guard: crate::workspace::creation_guard::CreationGuardConfig,
+    /// Catch-all workspace for windows matching no rule.
+    pub catch_all: crate::workspace::catch_all::CatchAllConfig,
+    /// Fallback for windows left on an expiring temporary workspace.
+    pub temporary: crate::workspace::temporary::TemporaryConfig,
+    /// What focusing a window raises; overridable per application profile.
+    pub raise_policy: crate::models::app_profile::RaisePolicy,
+    /// Warp the cursor to windows focused via keyboard navigation.
+    pub warp_cursor: crate::workspace::cursor_warp::WarpCursorConfig,
+    /// Rebalance split ratios automatically when a window closes, instead
+    /// of letting the survivors inherit skewed shares.
+    pub auto_balance_on_close: bool,
+    /// IPC client authorization: default scope and tokens.
+    pub ipc: crate::ipc::auth::IpcAuthConfig,
+    /// Adaptive damping of windows that keep resizing themselves.
+    pub damping: crate::workspace::damping::DampingConfig,
+    /// Startup grace period and per-app readiness probes gating the
+    /// first adoption/arrange pass.
+    pub startup: crate::workspace::readiness::StartupConfig,
+    /// Hot-corner and trackpad-gesture triggers; all off by default.
+    pub triggers: crate::keyboard::triggers::TriggerConfig,
+    /// Event hooks and the protective limits they run under.
+    pub hooks: crate::integrations::hooks::HooksConfig,
+    /// Stacking rules applied after frame placement, keyed by layout
+    /// pattern name; patterns without an entry use `ZOrderConfig::default`.
+    pub z_order: std::collections::BTreeMap<String, crate::tiling::ZOrderConfig>,
+}
+
+/// Parse raw TOML into a config, reporting file/line/column on failure.
+///
+/// Parsing goes through `toml_edit` so both syntax errors and
+/// deserialization errors carry a source span; a malformed file can never
+/// panic the daemon, only produce a located [`ConfigParseError`].
+pub fn parse_config(path: &Path, raw: &str) -> Result<TilleRSConfig> {
+    let document =
+        toml_edit::ImDocument::parse(raw.to_owned()).map_err(|e: toml_edit::TomlError| {
+            ConfigParseError::from_toml(path.to_path_buf(), raw, &e)
+        })?;
+    toml_edit::de::from_document(document).map_err(|e: toml_edit::de::Error| {
+        ConfigParseError::from_toml(path.to_path_buf(), raw, &e.clone().into()).into()
+    })
+}
+
+/// Owns the canonical config path and mediates all reads and writes.
+///
+/// Everything that mutates configuration goes through `ConfigManager` so a
+/// single component is responsible for validation and persistence.
+pub struct ConfigManager {
+    path: PathBuf,
+    config: TilleRSConfig,
+    /// Format-preserving document mirror of `config`. Mutations edit both
+    /// so saving never destroys user comments or formatting.
+    document: toml_edit::DocumentMut,
+    /// The layered source behind `config` when it was opened from a
+    /// directory: tracked for machine overrides and on-disk change
+    /// detection. `None` for configs opened from an explicit path.
+    source: Option<ConfigSource>,
+}
+
+impl ConfigManager {
+    /// Default config location: `~/.config/tillers/config.toml`.
+    pub fn default_path() -> PathBuf {
+        let home = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        home.join(".config").join("tillers").join("config.toml")
+    }
+
+    /// Load configuration from `path`, falling back to defaults when the
+    /// file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let (config, document) = if path.exists() {
+            let raw = std::fs::read_to_string(&path)?;
+            let config = parse_config(&path, &raw)?;
+            let document = raw.parse().map_err(|e: toml_edit::TomlError| {
+                ConfigParseError::from_toml(path.clone(), &raw, &e)
+            })?;
+            (config, document)
+        } else {
+            (TilleRSConfig::default(), toml_edit::DocumentMut::new())
+        };
+        Ok(ConfigManager {
+            path,
+            config,
+            document,
+            source: None,
+        })
+    }
+
+    /// Load from the default location, layering any machine override from
+    /// the same directory. The directory may be a symlink into a synced
+    /// folder (iCloud Drive, Dropbox, a dotfiles repo); one shared
+    /// `config.toml` plus `overrides.<hostname>.toml` then serves several
+    /// machines.
+    pub fn load_default() -> Result<Self> {
+        let path = Self::default_path();
+        if !path.exists() {
+            return Self::load(path);
+        }
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Self::load_layered(dir)
+    }
+
+    /// Load `config.toml` from a directory, merging
+    /// `overrides.<hostname>.toml` over it when present. Sync-conflict
+    /// artifacts next to the base file abort the load rather than silently
+    /// picking a side.
+    pub fn load_layered(dir: impl AsRef<Path>) -> Result<Self> {
+        let mut source = ConfigSource::in_directory(dir);
+        let config = source.load()?;
+        let path = source.base.clone();
+        // The document mirrors the base layer only: mutators write to the
+        // shared file, never into a machine override.
+        let raw = std::fs::read_to_string(&path)?;
+        let document = raw.parse().map_err(|e: toml_edit::TomlError| {
+            ConfigParseError::from_toml(path.clone(), &raw, &e)
+        })?;
+        Ok(ConfigManager {
+            path,
+            config,
+            document,
+            source: Some(source),
+        })
+    }
+
+    /// Re-read this manager's config from disk the same way it was
+    /// opened: layered sources re-merge their overrides, plain paths are
+    /// re-read directly.
+    pub fn reload(&mut self) -> Result<()> {
+        let fresh = match &self.source {
+            Some(source) => {
+                let dir = source.base.parent().unwrap_or_else(|| Path::new("."));
+                Self::load_layered(dir)?
+            }
+            None => Self::load(&self.path)?,
+        };
+        *self = fresh;
+        Ok(())
+    }
+
+    /// Whether any config layer changed on disk since it was loaded —
+    /// e.g. a sync client replaced the file underneath us. Always `false`
+    /// for configs opened from an explicit path.
+    pub fn changed_on_disk(&self) -> bool {
+        self.source
+            .as_ref()
+            .is_some_and(ConfigSource::changed_on_disk)
+    }
+
+    pub fn config(&self) -> &TilleRSConfig {
+        &self.config
+    }
+
+    /// Mutable access to the typed config.
+    ///
+    /// Prefer the named mutators (`add_rule`, ...) which keep the
+    /// format-preserving document in sync; changes made through this
+    /// reference are not persisted by `save`.
+    pub fn config_mut(&mut self) -> &mut TilleRSConfig {
+        &mut self.config
+    }
+
+    /// Set a scalar config key by dotted path, preserving formatting.
+    pub fn set_value(&mut self, path: &str, value: toml_edit::Value) -> Result<()> {
+        writer::set_path(&mut self.document, path, value)?;
+        // Re-derive the typed config from the edited document so both
+        // views stay consistent.
+        self.config = parse_config(&self.path, &self.document.to_string())?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Persist the current in-memory configuration back to disk.
+    pub fn save(&self) -> Result<()> {
+        self.save_with_reason("Update config")
+    }
+
+    /// Persist, recording `reason` as the git commit message when
+    /// versioning is enabled.
+    pub fn save_with_reason(&self, reason: &str) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, self.document.to_string())?;
+        if self.config.git_versioning {
+            if let Some(dir) = self.path.parent() {
+                GitVersioning::open(dir)?.commit_change(reason)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a rule, rejecting duplicate names.
+    pub fn add_rule(&mut self, rule: WindowRule) -> Result<()> {
+        if self.config.rules.iter().any(|r| r.name == rule.name) {
+            return Err(TilleRSError::Validation(format!(
+                "a rule named '{}' already exists",
+                rule.name
+            )));
+        }
+        writer::append_rule(&mut self.document, &rule)?;
+        self.config.rules.push(rule);
+        Ok(())
+    }
+
+    /// Remove the rule with the given name, returning it.
+    pub fn remove_rule(&mut self, name: &str) -> Result<WindowRule> {
+        let idx = self
+            .config
+            .rules
+            .iter()
+            .position(|r| r.name == name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "rule",
+                name: name.to_string(),
+            })?;
+        writer::remove_rule(&mut self.document, name)?;
+        Ok(self.config.rules.remove(idx))
+    }
+
+    /// Enable or disable the rule with the given name.
+    pub fn set_rule_enabled(&mut self, name: &str, enabled: bool) -> Result<()> {
+        let rule = self
+            .config
+            .rules
+            .iter_mut()
+            .find(|r| r.name == name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "rule",
+                name: name.to_string(),
+            })?;
+        rule.enabled = enabled;
+        writer::set_rule_enabled(&mut self.document, name, enabled)?;
+        Ok(())
+    }
+}
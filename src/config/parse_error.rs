@@ -0,0 +1,79 @@
+//! Precise, located config parse errors.
+//!
+//! Syntax and semantic errors both surface as [`ConfigParseError`] with
+//! file, line, column, and (for semantic errors) the offending key, so the
+//! CLI and daemon log can point the user at the exact spot. Malformed
+//! files are always an error, never a panic.
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// A parse or validation error anchored to a location in the config file.
+#[derive(Debug, Clone)]
+pub struct ConfigParseError {
+    pub file: PathBuf,
+    /// 1-based line, when the error can be located.
+    pub line: Option<usize>,
+    /// 1-based column, when the error can be located.
+    pub column: Option<usize>,
+    /// Dotted key path for semantic errors, e.g. `theme.palette.accent`.
+    pub key: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, ":{line}:{column}")?;
+        }
+        if let Some(key) = &self.key {
+            write!(f, " (key '{key}')")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for ConfigParseError {}
+
+impl ConfigParseError {
+    /// Build from a `toml_edit` error, resolving its byte span to
+    /// line/column against the source text.
+    pub fn from_toml(file: PathBuf, source: &str, err: &toml_edit::TomlError) -> Self {
+        let (line, column) = err
+            .span()
+            .map(|span| offset_to_line_col(source, span.start))
+            .map(|(l, c)| (Some(l), Some(c)))
+            .unwrap_or((None, None));
+        ConfigParseError {
+            file,
+            line,
+            column,
+            key: None,
+            message: err.message().to_string(),
+        }
+    }
+
+    /// Build a semantic error for a specific key.
+    pub fn for_key(file: PathBuf, key: impl Into<String>, message: impl Into<String>) -> Self {
+        ConfigParseError {
+            file,
+            line: None,
+            column: None,
+            key: Some(key.into()),
+            message: message.into(),
+        }
+    }
+}
+
+/// Translate a byte offset into 1-based (line, column).
+fn offset_to_line_col(source: &str, offset: usize) -> (usize, usize) {
+    let clamped = offset.min(source.len());
+    let before = &source[..clamped];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map(|nl| clamped - nl)
+        .unwrap_or(clamped + 1);
+    (line, column)
+}
@@ -0,0 +1,93 @@
+//! Optional git versioning of the config directory.
+//!
+//! When enabled, every successful programmatic config change is committed
+//! with a message describing what changed, so layout experiments are always
+//! reversible via `tillers config rollback`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::errors::{Result, TilleRSError};
+
+/// One entry from `tillers config history`.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEntry {
+    /// Abbreviated commit hash, usable with `config rollback`.
+    pub rev: String,
+    pub timestamp: String,
+    pub message: String,
+}
+
+/// Wraps a git repository rooted at the config directory.
+pub struct GitVersioning {
+    dir: PathBuf,
+}
+
+impl GitVersioning {
+    /// Open (initializing on first use) the repo in the config directory.
+    pub fn open(config_dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = config_dir.as_ref().to_path_buf();
+        if !dir.join(".git").exists() {
+            run_git(&dir, &["init", "--quiet"])?;
+        }
+        Ok(GitVersioning { dir })
+    }
+
+    /// Commit the current state of the config directory.
+    ///
+    /// A no-op when nothing changed, so callers can commit after every
+    /// save without polluting history.
+    pub fn commit_change(&self, message: &str) -> Result<()> {
+        run_git(&self.dir, &["add", "-A"])?;
+        let status = run_git(&self.dir, &["status", "--porcelain"])?;
+        if status.trim().is_empty() {
+            return Ok(());
+        }
+        run_git(&self.dir, &["commit", "--quiet", "-m", message])?;
+        Ok(())
+    }
+
+    /// Recent history, newest first.
+    pub fn history(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        let raw = run_git(
+            &self.dir,
+            &[
+                "log",
+                &format!("-{limit}"),
+                "--pretty=format:%h%x09%ci%x09%s",
+            ],
+        )?;
+        Ok(raw
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                Some(HistoryEntry {
+                    rev: parts.next()?.to_string(),
+                    timestamp: parts.next()?.to_string(),
+                    message: parts.next()?.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Restore the config directory to `rev`, recorded as a new commit so
+    /// the rollback itself is also reversible.
+    pub fn rollback(&self, rev: &str) -> Result<()> {
+        run_git(&self.dir, &["checkout", rev, "--", "."])?;
+        self.commit_change(&format!("Roll back config to {rev}"))
+    }
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git").current_dir(dir).args(args).output()?;
+    if !output.status.success() {
+        return Err(TilleRSError::Config(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,135 @@
+//! Format-preserving edits to the config document.
+//!
+//! Programmatic changes (`config set`, rule edits, migrations) go through
+//! the `toml_edit` document model so user comments, ordering, and
+//! formatting survive: only the keys actually changed are touched.
+
+use toml_edit::{value, ArrayOfTables, DocumentMut, Item, Table};
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::WindowRule;
+
+/// Append a rule as a new `[[rules]]` table, leaving the rest of the
+/// document untouched.
+pub fn append_rule(document: &mut DocumentMut, rule: &WindowRule) -> Result<()> {
+    let rules = rules_array(document)?;
+    let mut table = Table::new();
+    table["name"] = value(rule.name.as_str());
+    if let Some(app) = &rule.app_bundle_id {
+        table["app_bundle_id"] = value(app.as_str());
+    }
+    if let Some(pattern) = &rule.title_pattern {
+        table["title_pattern"] = value(pattern.as_str());
+    }
+    if let Some(workspace) = &rule.workspace {
+        table["workspace"] = value(workspace.as_str());
+    }
+    if rule.floating {
+        table["floating"] = value(true);
+    }
+    if rule.locked {
+        table["locked"] = value(true);
+    }
+    if !rule.enabled {
+        table["enabled"] = value(false);
+    }
+    rules.push(table);
+    Ok(())
+}
+
+/// Remove the `[[rules]]` entry with the given name.
+pub fn remove_rule(document: &mut DocumentMut, name: &str) -> Result<()> {
+    let rules = rules_array(document)?;
+    let index = position_of(rules, name)?;
+    rules.remove(index);
+    Ok(())
+}
+
+/// Flip only the `enabled` key of one rule.
+pub fn set_rule_enabled(document: &mut DocumentMut, name: &str, enabled: bool) -> Result<()> {
+    let rules = rules_array(document)?;
+    let index = position_of(rules, name)?;
+    let table = rules.get_mut(index).expect("index from position_of");
+    if enabled {
+        // `enabled` defaults to true; remove the key rather than writing
+        // redundant `enabled = true` lines into the user's file.
+        table.remove("enabled");
+    } else {
+        table["enabled"] = value(false);
+    }
+    Ok(())
+}
+
+/// Set a scalar key by dotted path, e.g. `theme.font_size`.
+pub fn set_path(document: &mut DocumentMut, path: &str, new_value: toml_edit::Value) -> Result<()> {
+    let mut current: &mut Item = document.as_item_mut();
+    let segments: Vec<&str> = path.split('.').collect();
+    let (last, parents) = segments
+        .split_last()
+        .ok_or_else(|| TilleRSError::Validation("empty config key path".into()))?;
+    for segment in parents {
+        current = &mut current[segment];
+        if current.is_none() {
+            *current = Item::Table(Table::new());
+        }
+    }
+    current[last] = Item::Value(new_value);
+    Ok(())
+}
+
+/// The `[[rules]]` array, created when absent. A document where `rules`
+/// exists with some other type (say `rules = 1`) is the user's mistake,
+/// not ours: report it as a config error instead of panicking.
+fn rules_array(document: &mut DocumentMut) -> Result<&mut ArrayOfTables> {
+    document
+        .entry("rules")
+        .or_insert(Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .ok_or_else(|| {
+            TilleRSError::Config(
+                "'rules' exists but is not an array of [[rules]] tables; fix or remove it before \
+                 editing rules programmatically"
+                    .into(),
+            )
+        })
+}
+
+fn position_of(rules: &ArrayOfTables, name: &str) -> Result<usize> {
+    rules
+        .iter()
+        .position(|t| t.get("name").and_then(|v| v.as_str()) == Some(name))
+        .ok_or_else(|| TilleRSError::NotFound {
+            kind: "rule",
+            name: name.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str) -> WindowRule {
+        toml::from_str(&format!("name = \"{name}\"")).unwrap()
+    }
+
+    /// A scalar `rules` key is a user error, not a panic.
+    #[test]
+    fn wrong_rules_type_is_an_error() {
+        let mut document: DocumentMut = "rules = 1\n".parse().unwrap();
+        assert!(matches!(
+            append_rule(&mut document, &rule("slack")),
+            Err(TilleRSError::Config(_))
+        ));
+    }
+
+    /// Edits only touch the keys they change; comments survive.
+    #[test]
+    fn append_preserves_surrounding_document() {
+        let raw = "# my config\n[theme]\nfont_size = 13 # just right\n";
+        let mut document: DocumentMut = raw.parse().unwrap();
+        append_rule(&mut document, &rule("slack")).unwrap();
+        let written = document.to_string();
+        assert!(written.starts_with(raw));
+        assert!(written.contains("[[rules]]"));
+    }
+}
@@ -0,0 +1,40 @@
+//! The embeddable core: TilleRS logic without the daemon.
+//!
+//! This facade re-exports the platform-neutral surface of the crate —
+//! models, the tiling engine, and the workspace logic that runs against
+//! any [`WindowSystem`] implementation. Nothing here requires tokio,
+//! AppKit, or a running daemon, so other tools can embed layout
+//! computation and rule evaluation headlessly (the simulation backend is
+//! included for exactly that).
+//!
+//! Everything re-exported below is semver-stable: additions are minor
+//! releases, removals and signature changes are major. Daemon internals —
+//! orchestration, the event bus, IPC — are deliberately absent; reach for
+//! the full module tree if you need those and accept its faster-moving
+//! API.
+
+pub use crate::models::{
+    ActionCondition, ActionType, ApplicationProfile, FloatGeometry, ProfileStore, Rect, WindowId,
+    WindowInfo, WindowRule, Workspace, WorkspaceRef,
+};
+
+pub use crate::models::app_profile::{
+    AxCapabilities, CapabilityStatus, RaisePolicy, TilingStrategy,
+};
+pub use crate::models::display::{DisplayId, DisplayInfo};
+
+pub use crate::tiling::{
+    GapValue, Gaps, GapsConfig, LayoutPattern, SplitRatios, TilingEngine, WindowLayout,
+    ZOrderConfig,
+};
+
+pub use crate::window_system::scene::Scene;
+pub use crate::window_system::simulation::SimulatedWindowSystem;
+pub use crate::window_system::WindowSystem;
+
+pub use crate::workspace::catch_all::{place, CatchAllConfig, Placement};
+pub use crate::workspace::relations::WindowRelations;
+pub use crate::workspace::tabs::{collapse_tab_groups, TabGroup};
+pub use crate::workspace::topology::DisplayTopology;
+
+pub use crate::errors::{AxErrorCode, AxRecovery, Result, TilleRSError};
@@ -0,0 +1,266 @@
+//! The single gateway for daemon side effects.
+//!
+//! Everything that changes the user's session — moving or hiding windows,
+//! grabbing hotkeys — goes through [`Effects`]. In observer mode each call
+//! logs the intended effect and returns without doing it, which is what
+//! makes `tillersd --observe` trustworthy: read-only is enforced here, not
+//! by scattered `if` checks at call sites.
+
+use crate::errors::Result;
+use crate::models::display::DisplayId;
+use crate::models::{Rect, WindowId};
+
+use super::OperationMode;
+
+/// Side-effect gateway; cheap to clone and hand to subsystems.
+#[derive(Debug, Clone, Copy)]
+pub struct Effects {
+    mode: OperationMode,
+}
+
+impl Effects {
+    pub fn new(mode: OperationMode) -> Self {
+        Effects { mode }
+    }
+
+    /// Whether effects are actually applied.
+    pub fn is_live(&self) -> bool {
+        self.mode == OperationMode::Daemon
+    }
+
+    /// Move/resize a window to `frame`.
+    pub fn set_window_frame(&self, window: WindowId, frame: Rect) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!(window, ?frame, "observe: would set window frame");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::set_window_frame(window, frame)
+        }
+        #[cfg(not(target_os = "macos"))]
+        Ok(())
+    }
+
+    /// Move/resize a window and verify the frame stuck, retrying transient
+    /// failures. Returns whether the window ended up within tolerance;
+    /// `false` means the app accepted the calls but never applied them.
+    pub fn set_window_frame_verified(&self, window: WindowId, frame: Rect) -> Result<bool> {
+        if !self.is_live() {
+            tracing::info!(window, ?frame, "observe: would set window frame");
+            return Ok(true);
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::set_window_frame_verified(window, frame)
+        }
+        #[cfg(not(target_os = "macos"))]
+        Ok(true)
+    }
+
+    /// Hide a window (used when its workspace deactivates).
+    pub fn hide_window(&self, window: WindowId) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!(window, "observe: would hide window");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::hide_window(window)
+        }
+        #[cfg(not(target_os = "macos"))]
+        Ok(())
+    }
+
+    /// Raise a window to the front of its layer. Used when rebuilding
+    /// z-order on workspace switch-back.
+    pub fn raise_window(&self, window: WindowId) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!(window, "observe: would raise window");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::accessibility::raise_window(window)
+        }
+        #[cfg(not(target_os = "macos"))]
+        Ok(())
+    }
+
+    /// Give a window keyboard focus, raising according to the policy:
+    /// just the window, its whole app, or nothing at all.
+    pub fn focus_window(
+        &self,
+        window: WindowId,
+        pid: i32,
+        policy: crate::models::app_profile::RaisePolicy,
+    ) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!(window, pid, ?policy, "observe: would focus window");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::focus_window(window, pid, policy)
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (pid, policy);
+            Ok(())
+        }
+    }
+
+    /// Teleport the mouse cursor; used by warp-on-focus.
+    pub fn warp_cursor(&self, x: f64, y: f64) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!(x, y, "observe: would warp cursor");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::warp_cursor(x, y)
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (x, y);
+            Ok(())
+        }
+    }
+
+    /// Close a window through its AX close button, so apps keep their
+    /// chance to prompt for unsaved changes.
+    pub fn close_window(&self, window: WindowId) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!(window, "observe: would close window");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::accessibility::close_window(window)
+        }
+        #[cfg(not(target_os = "macos"))]
+        Ok(())
+    }
+
+    /// Set or clear a window's minimized state.
+    pub fn set_window_minimized(&self, window: WindowId, minimized: bool) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!(window, minimized, "observe: would change minimized state");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::accessibility::set_minimized(window, minimized)
+        }
+        #[cfg(not(target_os = "macos"))]
+        Ok(())
+    }
+
+    /// Set a window's alpha. Returns whether the change was applied;
+    /// `false` means the private API is unavailable on this system and
+    /// the window stays opaque.
+    pub fn set_window_alpha(&self, window: WindowId, alpha: f64) -> Result<bool> {
+        if !self.is_live() {
+            tracing::info!(window, alpha, "observe: would set window alpha");
+            return Ok(false);
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::cosmetics::set_window_alpha(window, alpha)
+        }
+        #[cfg(not(target_os = "macos"))]
+        Ok(false)
+    }
+
+    /// Pull an app's focused native tab out into its own window, via the
+    /// Window-menu route — the only supported one. The target window must
+    /// be raised first so the menu acts on it.
+    pub fn pull_tab_out(&self, pid: i32) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!(pid, "observe: would pull tab out");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::tabs::press_window_menu_item(
+                pid,
+                crate::macos::tabs::MOVE_TAB_TO_NEW_WINDOW,
+            )
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = pid;
+            Ok(())
+        }
+    }
+
+    /// Merge all of an app's windows into one native tab group, where the
+    /// app supports it.
+    pub fn merge_into_tabs(&self, pid: i32) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!(pid, "observe: would merge windows into tabs");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::tabs::press_window_menu_item(pid, crate::macos::tabs::MERGE_ALL_WINDOWS)
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = pid;
+            Ok(())
+        }
+    }
+
+    /// Set the desktop image of one display. NSWorkspace requires the
+    /// main thread, so this must be called from the event loop.
+    pub fn set_desktop_image(&self, display: DisplayId, path: &std::path::Path) -> Result<()> {
+        if !self.is_live() {
+            // `display` spelled out: the tracing macro's own `display`
+            // helper shadows a bare local of that name.
+            let display_id = display;
+            tracing::info!(
+                display = display_id,
+                ?path,
+                "observe: would set desktop image"
+            );
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::set_desktop_image(display, path)
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (display, path);
+            Ok(())
+        }
+    }
+
+    /// Register global hotkeys: install the event tap that probes `keymap`
+    /// and routes matched chords through `dispatch`. Holding the bare
+    /// `hold_modifier` (the workspace modifier) is reported via `on_hold`
+    /// for the quick-switch HUD. Observer mode never grabs keys so the
+    /// user's real bindings keep working.
+    pub fn register_hotkeys(
+        &self,
+        keymap: std::sync::Arc<std::sync::Mutex<crate::keyboard::KeyboardMappingSet>>,
+        hold_modifier: Option<u8>,
+        dispatch: impl Fn(&crate::models::ActionType, &str) + Send + 'static,
+        on_hold: impl Fn(crate::keyboard::hold::HoldEvent) + Send + 'static,
+    ) -> Result<()> {
+        if !self.is_live() {
+            tracing::info!("observe: would register global hotkeys");
+            return Ok(());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::register_event_tap(keymap, hold_modifier, dispatch, on_hold)
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (keymap, hold_modifier, dispatch, on_hold);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,2948 @@
+//! Executes IPC requests against the daemon's live state.
+//!
+//! The [`RequestHandler`] implementation the IPC server calls into. It
+//! owns the daemon's managers behind mutexes; the server threads lock per
+//! request, so a long query never blocks the accept loop, only its own
+//! connection.
+
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::config::ConfigManager;
+use crate::errors::{Result, TilleRSError};
+use crate::events::{Event, EventBus, WindowEvent, WorkspaceEvent};
+use crate::integrations::hooks::HookRunner;
+use crate::ipc::protocol::{action_tag, HealthReport, Request, Response};
+use crate::ipc::server::RequestHandler;
+use crate::models::{ActionType, WindowId, WorkspaceRef};
+use crate::tiling::TilingEngine;
+use crate::workspace::groups::GroupRegistry;
+use crate::workspace::orchestrator::WorkspaceOrchestrator;
+use crate::workspace::pause::WorkspacePauseRegistry;
+use crate::workspace::sequence::Rollback;
+use crate::workspace::suspension::SuspensionRegistry;
+use crate::workspace::temporary::TemporaryRegistry;
+use crate::workspace::{WindowManager, WorkspaceManager};
+
+use super::{Effects, OperationMode};
+
+/// The daemon's request executor.
+pub struct DaemonHandler {
+    mode: OperationMode,
+    started: Instant,
+    effects: Effects,
+    config: Mutex<ConfigManager>,
+    // Behind `Arc` so action rollbacks — `'static` closures queued by the
+    // sequence runner — can capture handles to the state they restore.
+    workspaces: Arc<Mutex<WorkspaceManager>>,
+    windows: Arc<Mutex<WindowManager>>,
+    temporary: Arc<Mutex<TemporaryRegistry>>,
+    groups: Arc<Mutex<GroupRegistry>>,
+    paused: Arc<Mutex<WorkspacePauseRegistry>>,
+    suspensions: Arc<Mutex<SuspensionRegistry>>,
+    focus_timer: Arc<Mutex<crate::workspace::focus_timer::FocusTimer>>,
+    clipboard: Arc<Mutex<crate::workspace::clipboard::WindowClipboard>>,
+    plugins: Arc<Mutex<crate::plugins::PluginHost>>,
+    hooks: Arc<Mutex<HookRunner>>,
+    /// Compiled hotkey table, shared with the event tap; recompiled in
+    /// place on config reload so the tap needs no re-registration.
+    keymap: Arc<Mutex<crate::keyboard::KeyboardMappingSet>>,
+    clamshell: Mutex<crate::workspace::clamshell::ClamshellTracker>,
+    /// Logical display topology with mirror sets collapsed; re-derived on
+    /// every display event so mirroring toggles are caught immediately.
+    topology: Mutex<crate::workspace::topology::DisplayTopology>,
+    /// Usage statistics; `None` when the store failed to load, in which
+    /// case recording is disabled rather than failing dispatch.
+    stats: Mutex<Option<crate::stats::StatisticsStore>>,
+    stats_dirty: std::sync::atomic::AtomicBool,
+    /// The accruing focus interval: (workspace, app, start). Closed and
+    /// recorded whenever focus or the active workspace changes.
+    focus_interval: Mutex<Option<(String, String, Instant)>>,
+    /// Last focus observed via AX, so reconcile passes only stamp the
+    /// model (and publish `Focused`) when focus actually moved.
+    last_focus: Mutex<Option<WindowId>>,
+    /// Focus journal; `None` when it failed to open, in which case focus
+    /// changes go unjournaled rather than failing the daemon.
+    journal: Mutex<Option<crate::diagnostics::focus_journal::FocusJournal>>,
+    /// Sweeps stale windows into the archive target and remembers the
+    /// last sweep for undo.
+    archiver: Mutex<crate::workspace::archival::Archiver>,
+    /// Reverts uninvited focus grabs per the app-profile overrides.
+    focus_guard: Mutex<crate::workspace::focus_guard::FocusGuard>,
+    /// Pointer positions per window, for warp-on-focus destinations.
+    warper: Mutex<crate::workspace::cursor_warp::CursorWarper>,
+    /// Applied cosmetic rule effects (opacity, corner masks), remembered
+    /// so they can be undone when a rule stops matching.
+    cosmetics: Mutex<crate::workspace::cosmetics::CosmeticsRegistry>,
+    /// Per-workspace visibility snapshots, captured on deactivation and
+    /// replayed on switch-back.
+    visibility: Mutex<crate::workspace::visibility::VisibilityStore>,
+    /// Sheets and modal children glued to their parents; the layout engine
+    /// never tiles them.
+    relations: Mutex<crate::workspace::WindowRelations>,
+    /// Strikes per window that ignores AX resize; crossing the threshold
+    /// reclassifies the window as floating.
+    compliance: Mutex<crate::workspace::compliance::ResizeComplianceTracker>,
+    /// Holds new windows out of tiling until they look real (size and
+    /// lifetime thresholds), so splash screens and palettes never churn
+    /// the layout.
+    creation_guard: Mutex<crate::workspace::creation_guard::CreationGuard>,
+    /// Re-arrange delay handed to the event loop when a new window was
+    /// held back; the loop arms the one-shot timer.
+    creation_timer: Mutex<Option<std::time::Duration>>,
+    /// Counts re-tiles per window; a window that keeps fighting the tiler
+    /// auto-floats for a cool-down instead of tugging at several frames
+    /// per second.
+    damper: Mutex<crate::workspace::damping::RetileDamper>,
+    /// Watches for other window managers; feeds the health report and,
+    /// when configured, the conflict auto-pause.
+    conflicts: Mutex<crate::diagnostics::conflicts::ConflictMonitor>,
+    /// Set while tiling is auto-paused because a conflicting tool runs;
+    /// arrange passes become no-ops until it clears.
+    conflict_paused: std::sync::atomic::AtomicBool,
+    /// Buffers window-destroy events per app, so an app quit costs one
+    /// arrange pass instead of one per window.
+    destroys: Mutex<crate::workspace::coalesce::DestroyCoalescer>,
+    /// Flush delay handed to the event loop when a destroy opened a new
+    /// batch; the loop arms the one-shot timer, because it owns the `Arc`
+    /// the timer thread needs.
+    destroy_timer: Mutex<Option<std::time::Duration>>,
+    /// Arrange passes since start, and how many exceeded their deadline
+    /// budget; the metrics thread copies both into its gauges.
+    arrange_passes: std::sync::atomic::AtomicU64,
+    degraded_passes: std::sync::atomic::AtomicU64,
+    /// Set when a budgeted pass deferred frames; the event loop picks it
+    /// up and finishes the remainder off the visible path.
+    deferred_arrange: std::sync::atomic::AtomicBool,
+    /// App icons by bundle id, cached in memory and on disk; resolving
+    /// one fresh costs an NSWorkspace round-trip.
+    icons: Mutex<crate::ui::icons::IconService>,
+    /// Per-app profiles (focus-stealing behavior, AX capabilities).
+    profiles: Mutex<crate::models::ProfileStore>,
+    orchestrator: Mutex<WorkspaceOrchestrator>,
+    bus: EventBus,
+}
+
+/// A computed arrange pass: the display it targets and the layout to
+/// realize — tiled frames in application order plus the raise sequence
+/// for the pattern's z-order policy.
+struct ArrangePlan {
+    display: crate::models::display::DisplayInfo,
+    layout: crate::tiling::WindowLayout,
+}
+
+/// Direction of a keyboard focus move.
+#[derive(Debug, Clone, Copy)]
+enum FocusDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// The candidate whose center lies in `direction` of the origin's center
+/// and is closest to it. Ties and diagonal neighbours resolve by plain
+/// euclidean distance, which matches what the eye expects.
+fn nearest_in_direction(
+    origin: &crate::models::Rect,
+    candidates: &[(WindowId, crate::models::Rect)],
+    direction: FocusDirection,
+) -> Option<(WindowId, crate::models::Rect)> {
+    let center =
+        |frame: &crate::models::Rect| (frame.x + frame.width / 2.0, frame.y + frame.height / 2.0);
+    let (ox, oy) = center(origin);
+    candidates
+        .iter()
+        .filter(|(_, frame)| {
+            let (cx, cy) = center(frame);
+            match direction {
+                FocusDirection::Left => cx < ox,
+                FocusDirection::Right => cx > ox,
+                FocusDirection::Up => cy < oy,
+                FocusDirection::Down => cy > oy,
+            }
+        })
+        .min_by(|(_, a), (_, b)| {
+            let distance = |frame: &crate::models::Rect| {
+                let (cx, cy) = center(frame);
+                (cx - ox).powi(2) + (cy - oy).powi(2)
+            };
+            distance(a).total_cmp(&distance(b))
+        })
+        .copied()
+}
+
+/// A configured budget in milliseconds as a duration; `0` disables the
+/// deadline rather than deferring everything.
+fn budget_duration(ms: u64) -> std::time::Duration {
+    if ms == 0 {
+        std::time::Duration::MAX
+    } else {
+        std::time::Duration::from_millis(ms)
+    }
+}
+
+impl DaemonHandler {
+    pub fn new(
+        mode: OperationMode,
+        effects: Effects,
+        config: ConfigManager,
+        bus: EventBus,
+    ) -> Self {
+        // Seed the model from a fresh enumeration, routing every
+        // pre-existing window through the same rule evaluation new windows
+        // get — managing only post-startup windows would leave the session
+        // half tiled after every restart. The reconcile loop and AX
+        // observers keep the model current from here.
+        #[cfg(target_os = "macos")]
+        let (windows, workspaces) = {
+            let mut windows = WindowManager::new();
+            let mut workspaces = WorkspaceManager::new(bus.clone());
+            if let Ok(actual) = crate::macos::list_windows() {
+                windows.reconcile(&actual);
+                let snapshot: Vec<crate::models::WindowInfo> = windows.windows().cloned().collect();
+                // No workspace is active yet, so leftovers stay unassigned
+                // (empty workspace) until one activates.
+                let summary = crate::workspace::adoption::adopt_existing(
+                    &snapshot,
+                    &config.config().rules,
+                    &config.config().catch_all,
+                    "",
+                );
+                for adoption in summary.adoptions.iter().filter(|a| !a.workspace.is_empty()) {
+                    if workspaces.get(&adoption.workspace).is_none() {
+                        if let Err(err) = workspaces
+                            .create(crate::models::Workspace::new(adoption.workspace.clone()))
+                        {
+                            tracing::warn!(%err, "could not create adoption workspace");
+                        }
+                    }
+                    if let Some(mut info) = windows.get(adoption.window).cloned() {
+                        info.workspace = adoption.workspace.clone();
+                        if let Some(rule) = adoption
+                            .rule
+                            .as_deref()
+                            .and_then(|name| config.config().rules.iter().find(|r| r.name == name))
+                        {
+                            info.floating = rule.floating;
+                            info.locked = rule.locked;
+                        }
+                        windows.insert(info);
+                    }
+                }
+            }
+            (windows, workspaces)
+        };
+        #[cfg(not(target_os = "macos"))]
+        let (windows, workspaces) = (WindowManager::new(), WorkspaceManager::new(bus.clone()));
+        let groups = GroupRegistry::new(config.config().groups.clone());
+        let hooks = HookRunner::new(config.config().hooks.limits);
+        let keymap = crate::keyboard::KeyboardMappingSet::compile(&config.config().keybindings);
+        let archiver = crate::workspace::archival::Archiver::new(config.config().archival.clone());
+        let creation_guard = crate::workspace::creation_guard::CreationGuard::new(
+            config.config().creation_guard.clone(),
+        );
+        let conflicts = crate::diagnostics::conflicts::ConflictMonitor::new(
+            config.config().auto_pause_on_conflict,
+        );
+        DaemonHandler {
+            mode,
+            started: Instant::now(),
+            effects,
+            config: Mutex::new(config),
+            workspaces: Arc::new(Mutex::new(workspaces)),
+            windows: Arc::new(Mutex::new(windows)),
+            temporary: Arc::new(Mutex::new(TemporaryRegistry::new())),
+            groups: Arc::new(Mutex::new(groups)),
+            paused: Arc::new(Mutex::new(WorkspacePauseRegistry::new())),
+            suspensions: Arc::new(Mutex::new(SuspensionRegistry::new())),
+            focus_timer: Arc::new(Mutex::new(crate::workspace::focus_timer::FocusTimer::new())),
+            clipboard: Arc::new(Mutex::new(
+                crate::workspace::clipboard::WindowClipboard::new(),
+            )),
+            plugins: Arc::new(Mutex::new(crate::plugins::PluginHost::new())),
+            hooks: Arc::new(Mutex::new(hooks)),
+            keymap: Arc::new(Mutex::new(keymap)),
+            clamshell: Mutex::new(crate::workspace::clamshell::ClamshellTracker::default()),
+            topology: Mutex::new(crate::workspace::topology::DisplayTopology::default()),
+            stats: Mutex::new(match crate::stats::StatisticsStore::load_default() {
+                Ok(store) => Some(store),
+                Err(err) => {
+                    tracing::warn!(%err, "statistics store failed to load; recording disabled");
+                    None
+                }
+            }),
+            stats_dirty: std::sync::atomic::AtomicBool::new(false),
+            focus_interval: Mutex::new(None),
+            last_focus: Mutex::new(None),
+            journal: Mutex::new(
+                match crate::diagnostics::focus_journal::FocusJournal::open_default() {
+                    Ok(journal) => Some(journal),
+                    Err(err) => {
+                        tracing::warn!(%err, "focus journal failed to open; journaling disabled");
+                        None
+                    }
+                },
+            ),
+            archiver: Mutex::new(archiver),
+            focus_guard: Mutex::new(crate::workspace::focus_guard::FocusGuard::new()),
+            warper: Mutex::new(crate::workspace::cursor_warp::CursorWarper::new()),
+            cosmetics: Mutex::new(crate::workspace::cosmetics::CosmeticsRegistry::new()),
+            visibility: Mutex::new(crate::workspace::visibility::VisibilityStore::new()),
+            relations: Mutex::new(crate::workspace::WindowRelations::new()),
+            compliance: Mutex::new(crate::workspace::compliance::ResizeComplianceTracker::new()),
+            creation_guard: Mutex::new(creation_guard),
+            creation_timer: Mutex::new(None),
+            damper: Mutex::new(crate::workspace::damping::RetileDamper::new()),
+            conflicts: Mutex::new(conflicts),
+            conflict_paused: std::sync::atomic::AtomicBool::new(false),
+            destroys: Mutex::new(crate::workspace::coalesce::DestroyCoalescer::new()),
+            destroy_timer: Mutex::new(None),
+            arrange_passes: std::sync::atomic::AtomicU64::new(0),
+            degraded_passes: std::sync::atomic::AtomicU64::new(0),
+            deferred_arrange: std::sync::atomic::AtomicBool::new(false),
+            icons: Mutex::new(crate::ui::icons::IconService::new()),
+            profiles: Mutex::new(crate::models::ProfileStore::load_default().unwrap_or_else(
+                |err| {
+                    tracing::warn!(%err, "app profiles failed to load; using defaults");
+                    crate::models::ProfileStore::default()
+                },
+            )),
+            orchestrator: Mutex::new(WorkspaceOrchestrator::new()),
+            bus,
+        }
+    }
+
+    pub fn bus(&self) -> &EventBus {
+        &self.bus
+    }
+
+    /// Expire overdue temporary workspaces: leftover windows move to the
+    /// configured fallback, then the workspace is removed. Called from
+    /// the periodic tick and opportunistically before each request, so
+    /// expiry also works while the daemon is otherwise idle.
+    pub fn sweep_temporaries(&self) {
+        let expired = self.temporary.lock().unwrap().tick();
+        if expired.is_empty() {
+            return;
+        }
+        let fallback = self
+            .config
+            .lock()
+            .unwrap()
+            .config()
+            .temporary
+            .fallback
+            .clone();
+        for entry in expired {
+            tracing::info!(
+                workspace = %entry.workspace,
+                %fallback,
+                "temporary workspace expired"
+            );
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let mut windows = self.windows.lock().unwrap();
+            let leftovers: Vec<crate::models::WindowInfo> = windows
+                .windows()
+                .filter(|w| w.workspace == entry.workspace)
+                .cloned()
+                .collect();
+            if !leftovers.is_empty() {
+                if workspaces.get(&fallback).is_none() {
+                    if let Err(err) =
+                        workspaces.create(crate::models::Workspace::new(fallback.clone()))
+                    {
+                        tracing::warn!(%err, "could not create fallback workspace");
+                        continue;
+                    }
+                }
+                for mut window in leftovers {
+                    window.workspace = fallback.clone();
+                    let id = window.id;
+                    windows.insert(window);
+                    windows.invalidate(id);
+                }
+            }
+            if let Err(err) = workspaces.remove(&entry.workspace) {
+                tracing::warn!(%err, "expired temporary workspace could not be removed");
+            }
+        }
+    }
+
+    /// Lift expired app-rule suspensions and re-adopt those applications'
+    /// windows. Called from the periodic tick and before each request.
+    pub fn sweep_suspensions(&self) {
+        let expired = self.suspensions.lock().unwrap().purge_expired();
+        if expired.is_empty() {
+            return;
+        }
+        for bundle in &expired {
+            tracing::info!(app = %bundle, "app-rule suspension expired");
+            self.invalidate_app_windows(bundle);
+        }
+        self.arrange_active();
+    }
+
+    /// Advance the focus timer; an expired session unlocks switching and
+    /// is announced once. Called from the periodic tick and before each
+    /// request, like the other sweeps.
+    pub fn sweep_focus_timer(&self) {
+        if let Some(event) = self.focus_timer.lock().unwrap().tick() {
+            tracing::info!(?event, "focus session completed");
+        }
+    }
+
+    /// Count one keyboard-binding invocation in the statistics store; the
+    /// write reaches disk on the next flush.
+    pub fn record_shortcut(&self, chord: &str, action: &ActionType) {
+        if let Some(stats) = self.stats.lock().unwrap().as_mut() {
+            stats.record_shortcut(chord, &action_tag(action), std::time::SystemTime::now());
+            self.stats_dirty
+                .store(true, std::sync::atomic::Ordering::Release);
+        }
+    }
+
+    /// Append one focus transition to the journal; write failures are
+    /// logged, never surfaced, since journaling is best-effort diagnostics.
+    fn journal_focus(
+        &self,
+        info: &crate::models::WindowInfo,
+        trigger: crate::diagnostics::focus_journal::FocusTrigger,
+    ) {
+        if let Some(journal) = self.journal.lock().unwrap().as_mut() {
+            let entry = crate::diagnostics::focus_journal::FocusEntry {
+                window: info.id,
+                app_bundle_id: info.app_bundle_id.clone(),
+                workspace: info.workspace.clone(),
+                at: std::time::SystemTime::now(),
+                trigger,
+            };
+            if let Err(err) = journal.record(&entry) {
+                tracing::debug!(%err, "focus journal write failed");
+            }
+        }
+    }
+
+    /// Close the accruing focus interval, attributing its seconds to the
+    /// workspace and app that held focus, and start a new one for the
+    /// given context. A checkpoint in an unchanged context keeps accruing.
+    fn checkpoint_usage(&self, workspace: String, app: String) {
+        let now = Instant::now();
+        let mut current = self.focus_interval.lock().unwrap();
+        if let Some((prev_ws, prev_app, _)) = current.as_ref() {
+            if *prev_ws == workspace && *prev_app == app {
+                return;
+            }
+        }
+        if let Some((prev_ws, prev_app, since)) = current.take() {
+            let elapsed = now.duration_since(since);
+            if !elapsed.is_zero() {
+                if let Some(stats) = self.stats.lock().unwrap().as_mut() {
+                    stats.record_focus(&prev_ws, &prev_app, elapsed, std::time::SystemTime::now());
+                    self.stats_dirty
+                        .store(true, std::sync::atomic::Ordering::Release);
+                }
+            }
+        }
+        *current = Some((workspace, app, now));
+    }
+
+    /// Flush the statistics store to disk if anything was recorded since
+    /// the last flush. Called from the periodic tick and at shutdown.
+    pub fn flush_stats(&self) {
+        if !self
+            .stats_dirty
+            .swap(false, std::sync::atomic::Ordering::AcqRel)
+        {
+            return;
+        }
+        if let Some(stats) = self.stats.lock().unwrap().as_ref() {
+            if let Err(err) = stats.save() {
+                tracing::warn!(%err, "statistics flush failed");
+            }
+        }
+    }
+
+    /// Run the archival policy if it is enabled; called from the periodic
+    /// tick on its own (long) interval. Quiet when nothing is stale.
+    pub fn sweep_archival(&self) {
+        if !self.archiver.lock().unwrap().policy().enabled {
+            return;
+        }
+        match self.archive_sweep() {
+            Ok(0) => {}
+            Ok(moved) => {
+                tracing::info!(moved, "archival sweep moved stale windows");
+                self.arrange_active();
+            }
+            Err(err) => tracing::warn!(%err, "archival sweep failed"),
+        }
+    }
+
+    /// One archival sweep: stale windows move to the configured target and
+    /// the report is kept for [`archive_undo`](Self::archive_undo).
+    /// Returns how many windows moved.
+    fn archive_sweep(&self) -> Result<usize> {
+        use crate::workspace::archival::{ArchiveTarget, ArchivedWindow};
+
+        let policy = self.archiver.lock().unwrap().policy().clone();
+        if !policy.enabled {
+            return Err(TilleRSError::Validation(
+                "archival is disabled; set `[archival] enabled = true` first".into(),
+            ));
+        }
+        let now = std::time::SystemTime::now();
+        let stale: Vec<crate::models::WindowInfo> = {
+            let windows = self.windows.lock().unwrap();
+            let all: Vec<crate::models::WindowInfo> = windows.windows().cloned().collect();
+            self.archiver
+                .lock()
+                .unwrap()
+                .select_stale(&all, now)
+                .into_iter()
+                .cloned()
+                .collect()
+        };
+        if stale.is_empty() {
+            return Ok(0);
+        }
+        if let ArchiveTarget::Workspace(name) = &policy.target {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            if workspaces.get(name).is_none() {
+                workspaces.create(crate::models::Workspace::new(name.clone()))?;
+            }
+        }
+        let mut moved = Vec::with_capacity(stale.len());
+        {
+            let mut windows = self.windows.lock().unwrap();
+            for window in &stale {
+                let mut info = window.clone();
+                moved.push(ArchivedWindow {
+                    window_id: info.id,
+                    title: info.title.clone(),
+                    app_bundle_id: info.app_bundle_id.clone(),
+                    original_workspace: info.workspace.clone(),
+                });
+                match &policy.target {
+                    ArchiveTarget::Workspace(name) => info.workspace = name.clone(),
+                    ArchiveTarget::Minimize => info.minimized = true,
+                }
+                windows.insert(info);
+                windows.invalidate(window.id);
+            }
+        }
+        if policy.target == ArchiveTarget::Minimize {
+            for window in &stale {
+                if let Err(err) = self.effects.hide_window(window.id) {
+                    tracing::warn!(window = window.id, %err, "could not minimize stale window");
+                }
+            }
+        }
+        let count = moved.len();
+        self.archiver.lock().unwrap().record_sweep(moved, now);
+        Ok(count)
+    }
+
+    /// Undo the last archival sweep: its windows return to the workspaces
+    /// they came from. Returns how many came back.
+    fn archive_undo(&self) -> Result<usize> {
+        use crate::workspace::archival::ArchiveTarget;
+
+        let report = self
+            .archiver
+            .lock()
+            .unwrap()
+            .take_undo()
+            .ok_or(TilleRSError::NotFound {
+                kind: "archival sweep",
+                name: "last".to_string(),
+            })?;
+        {
+            let mut windows = self.windows.lock().unwrap();
+            for entry in &report.moved {
+                if let Some(mut info) = windows.get(entry.window_id).cloned() {
+                    info.workspace = entry.original_workspace.clone();
+                    info.minimized = false;
+                    windows.insert(info);
+                    windows.invalidate(entry.window_id);
+                }
+            }
+        }
+        if report.target == ArchiveTarget::Minimize {
+            for entry in &report.moved {
+                if let Err(err) = self.effects.set_window_minimized(entry.window_id, false) {
+                    tracing::warn!(window = entry.window_id, %err, "could not restore stale window");
+                }
+            }
+        }
+        Ok(report.moved.len())
+    }
+
+    /// The state the tray icon reflects, derived from live registries.
+    pub fn tray_status(&self) -> crate::ui::tray::TrayStatus {
+        let apps: Vec<String> = self
+            .suspensions
+            .lock()
+            .unwrap()
+            .active()
+            .iter()
+            .map(|s| s.bundle_id.clone())
+            .collect();
+        if apps.is_empty() {
+            crate::ui::tray::TrayStatus::Active
+        } else {
+            crate::ui::tray::TrayStatus::RulesSuspended { apps }
+        }
+    }
+
+    /// Open the native preferences window over the live configuration;
+    /// the tray menu's Settings item lands here via the event loop.
+    #[cfg(target_os = "macos")]
+    pub fn open_settings(&self) -> Result<()> {
+        let mut config = self.config.lock().unwrap();
+        crate::ui::settings::open_settings_window(&mut config)
+    }
+
+    /// Windows shown in the tray menu: the active workspace's, with hidden
+    /// (minimized) ones excluded.
+    pub fn tray_windows(&self) -> Vec<crate::models::WindowInfo> {
+        let Some(active) = self.workspaces.lock().unwrap().active().map(str::to_string) else {
+            return Vec::new();
+        };
+        self.windows
+            .lock()
+            .unwrap()
+            .windows()
+            .filter(|w| w.workspace == active && !w.minimized)
+            .cloned()
+            .collect()
+    }
+
+    /// Workspace names and the active workspace, for UI surfaces that
+    /// seed a model before consuming the bus.
+    pub fn workspace_snapshot(&self) -> (Vec<String>, Option<String>) {
+        let workspaces = self.workspaces.lock().unwrap();
+        let names: Vec<String> = workspaces
+            .workspaces()
+            .iter()
+            .map(|w| w.name.clone())
+            .collect();
+        (names, workspaces.active().map(str::to_string))
+    }
+
+    /// Per-subsystem object counts for the metrics thread.
+    pub fn object_counts(&self) -> crate::metrics::ObjectCounts {
+        #[cfg(target_os = "macos")]
+        let cached_ax_handles = crate::macos::accessibility::cached_element_count();
+        #[cfg(not(target_os = "macos"))]
+        let cached_ax_handles = 0;
+        crate::metrics::ObjectCounts {
+            cached_ax_handles,
+            journal_entries: 0,
+            tracked_windows: self.windows.lock().unwrap().windows().count(),
+        }
+    }
+
+    /// The plugin host; loaded by deferred startup, consulted for custom
+    /// actions and event fan-out.
+    pub fn plugins(&self) -> &Arc<Mutex<crate::plugins::PluginHost>> {
+        &self.plugins
+    }
+
+    /// The hook runner; the hooks thread launches through it and the
+    /// kill-switch action flips its state.
+    pub fn hook_runner(&self) -> &Arc<Mutex<HookRunner>> {
+        &self.hooks
+    }
+
+    /// The compiled hotkey table the event tap probes; handed to
+    /// [`Effects::register_hotkeys`] at startup.
+    pub fn keymap(&self) -> Arc<Mutex<crate::keyboard::KeyboardMappingSet>> {
+        Arc::clone(&self.keymap)
+    }
+
+    /// Rows for the quick-switch HUD: the current numeric binding order —
+    /// the active group's members or the global workspace order — with
+    /// live window counts. Rebuilt on every show so rows never go stale.
+    pub fn hud_entries(&self) -> Vec<crate::ui::hud::HudEntry> {
+        let (order, active) = {
+            let groups = self.groups.lock().unwrap();
+            let workspaces = self.workspaces.lock().unwrap();
+            let order = match groups.active() {
+                Some(group) => group.workspaces.clone(),
+                None => workspaces
+                    .workspaces()
+                    .iter()
+                    .map(|w| w.name.clone())
+                    .collect(),
+            };
+            (order, workspaces.active().map(str::to_string))
+        };
+        let windows: Vec<crate::models::WindowInfo> =
+            self.windows.lock().unwrap().windows().cloned().collect();
+        crate::ui::hud::build(&order, active.as_deref().unwrap_or(""), &windows)
+    }
+
+    /// The configured theme, for overlay surfaces driven by the event loop.
+    pub fn theme_spec(&self) -> crate::ui::theme::ThemeSpec {
+        self.config.lock().unwrap().config().theme.clone()
+    }
+
+    /// Hooks bound to one event name in the current config.
+    pub fn hooks_for(&self, event: &str) -> Vec<crate::integrations::hooks::HookConfig> {
+        self.config
+            .lock()
+            .unwrap()
+            .config()
+            .hooks
+            .hooks
+            .iter()
+            .filter(|hook| hook.event == event)
+            .cloned()
+            .collect()
+    }
+
+    /// Drop the applied frames of one application's windows so the next
+    /// arrange pass re-asserts their targets.
+    fn invalidate_app_windows(&self, bundle_id: &str) {
+        let mut windows = self.windows.lock().unwrap();
+        let ids: Vec<WindowId> = windows
+            .windows()
+            .filter(|w| w.app_bundle_id == bundle_id)
+            .map(|w| w.id)
+            .collect();
+        for id in ids {
+            windows.invalidate(id);
+        }
+    }
+
+    /// One arrange pass: compute target frames for a workspace's tiled
+    /// windows and apply them through the effects gateway. Floating,
+    /// minimized, and locked windows are left alone. A pass that cannot
+    /// start because another is in flight is skipped, not an error — the
+    /// in-flight pass (or the one that preempted it) realizes the same
+    /// model state.
+    pub fn arrange(&self, name: &str) -> Result<()> {
+        let budget = self.config.lock().unwrap().config().budgets.arrange_ms;
+        self.arrange_with_budget(name, budget_duration(budget))
+    }
+
+    /// [`arrange`](Self::arrange) with an explicit deadline budget; the
+    /// switch path passes `switch_ms` so its visible work is bounded by
+    /// the switch budget, not the looser arrange one.
+    fn arrange_with_budget(&self, name: &str, budget: std::time::Duration) -> Result<()> {
+        let Some(ArrangePlan { layout, .. }) = self.plan_workspace(name)? else {
+            return Ok(());
+        };
+
+        let token = match self.orchestrator.lock().unwrap().begin_arrange(name) {
+            Ok(token) => token,
+            Err(err) => {
+                tracing::debug!(%err, "arrange skipped");
+                return Ok(());
+            }
+        };
+        let outcome = self.windows.lock().unwrap().apply_assignments(
+            &self.effects,
+            &layout.tiled,
+            &token,
+            budget,
+        );
+        if let Err(err) = self.orchestrator.lock().unwrap().finish_arrange() {
+            tracing::debug!(%err, "arrange finished from an unexpected state");
+        }
+        let report = outcome?;
+        if report.deferred == 0 {
+            // Z-order runs only once every frame landed; a deferred pass
+            // leaves it to the follow-up, so raises never fight moves.
+            if let Err(err) = layout.apply_z_order(&self.effects) {
+                tracing::warn!(workspace = name, %err, "z-order pass failed");
+            }
+        }
+        self.arrange_passes
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        if report.deferred > 0 {
+            self.degraded_passes
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            self.deferred_arrange
+                .store(true, std::sync::atomic::Ordering::Release);
+        }
+        self.bus
+            .publish(Event::Workspace(WorkspaceEvent::ArrangeCompleted {
+                name: name.to_string(),
+                windows: report.applied(),
+            }));
+        self.record_compliance(name, &report);
+        self.sync_cosmetics(name);
+        Ok(())
+    }
+
+    /// Re-apply cosmetic rule effects for every window on a workspace.
+    /// Runs after each arrange pass, once the model's frames are current,
+    /// so corner masks track the windows they decorate.
+    fn sync_cosmetics(&self, workspace: &str) {
+        let rules = self.config.lock().unwrap().config().rules.clone();
+        let windows: Vec<crate::models::WindowInfo> = self
+            .windows
+            .lock()
+            .unwrap()
+            .windows()
+            .filter(|w| w.workspace == workspace)
+            .cloned()
+            .collect();
+        let mut cosmetics = self.cosmetics.lock().unwrap();
+        for window in &windows {
+            if let Err(err) = cosmetics.sync(window, &rules, &self.effects) {
+                tracing::warn!(window = window.id, %err, "cosmetic sync failed");
+            }
+        }
+    }
+
+    /// Compute one workspace's target frames without applying them: the
+    /// planning half of [`arrange`](Self::arrange), shared with the
+    /// concurrent multi-display path. `None` means there is nothing to
+    /// plan — the workspace is paused or no display is available.
+    fn plan_workspace(&self, name: &str) -> Result<Option<ArrangePlan>> {
+        if self.paused.lock().unwrap().is_paused(name) {
+            tracing::debug!(workspace = name, "workspace paused; arrange skipped");
+            return Ok(None);
+        }
+        if self
+            .conflict_paused
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            tracing::debug!(
+                workspace = name,
+                "tiling auto-paused by a conflicting window manager; arrange skipped"
+            );
+            return Ok(None);
+        }
+        let workspace = {
+            let workspaces = self.workspaces.lock().unwrap();
+            match workspaces.get(name) {
+                Some(workspace) => workspace.clone(),
+                None => {
+                    return Err(TilleRSError::NotFound {
+                        kind: "workspace",
+                        name: name.to_string(),
+                    })
+                }
+            }
+        };
+        let Some(display) = self.display_for(workspace.display.as_deref()) else {
+            tracing::debug!(workspace = name, "no display available; arrange skipped");
+            return Ok(None);
+        };
+
+        let (gaps, pattern) = {
+            let config = self.config.lock().unwrap();
+            let config = config.config();
+            (
+                config.gaps.resolve(&display),
+                config
+                    .center_master
+                    .effective_pattern(workspace.layout, &display),
+            )
+        };
+
+        let mut tiled: Vec<crate::models::WindowInfo> = {
+            let suspensions = self.suspensions.lock().unwrap();
+            // Attached children (sheets, modal dialogs) move with their
+            // parents; tiling them would tear them off.
+            let relations = self.relations.lock().unwrap();
+            // The creation guard keeps splash screens and young windows
+            // out of the count until they prove real.
+            let guard = self.creation_guard.lock().unwrap();
+            self.windows
+                .lock()
+                .unwrap()
+                .windows()
+                .filter(|w| {
+                    w.workspace == name
+                        && !w.floating
+                        && !w.minimized
+                        && !w.locked
+                        && !suspensions.is_suspended(&w.app_bundle_id)
+                        && !relations.is_attached_child(w.id)
+                        && guard.admits(w)
+                })
+                .cloned()
+                .collect()
+        };
+        // Deterministic order: the same model state always produces the
+        // same frame for the same window.
+        tiled.sort_unstable_by_key(|w| w.id);
+        // Native tab groups collapse to their visible representative:
+        // tiling each stacked tab would assign N slots to what the user
+        // sees as one window.
+        let tiled: Vec<crate::models::WindowInfo> = {
+            let (tileable, tab_groups) = crate::workspace::tabs::collapse_tab_groups(&tiled);
+            if !tab_groups.is_empty() {
+                tracing::debug!(groups = tab_groups.len(), "collapsed native tab groups");
+            }
+            tileable.into_iter().cloned().collect()
+        };
+
+        // A plugin layout gets first go; any failure falls back to the
+        // built-in pattern so a bad plugin costs its layout, not tiling.
+        let plugin_frames = workspace.layout_plugin.as_deref().and_then(|plugin| {
+            match self
+                .plugins
+                .lock()
+                .unwrap()
+                .compute_layout(plugin, &display, &tiled)
+            {
+                Ok(frames) => Some(frames),
+                Err(err) => {
+                    tracing::warn!(
+                        workspace = name,
+                        plugin,
+                        %err,
+                        "layout plugin failed; using the built-in pattern"
+                    );
+                    None
+                }
+            }
+        });
+        let frames = plugin_frames.unwrap_or_else(|| {
+            let engine = TilingEngine {
+                gaps,
+                main_area_ratio: workspace.splits.main,
+            };
+            engine.compute_frames_with_splits(
+                pattern,
+                display.work_area,
+                tiled.len(),
+                &workspace.splits,
+            )
+        });
+        let assignments: Vec<_> = tiled.into_iter().map(|w| w.id).zip(frames).collect();
+        // Floating windows share the workspace; the pattern's z-order
+        // policy decides whether they stay above the tiled plane. Most
+        // recently focused first, so the raise sequence preserves the
+        // order the user last saw.
+        let mut floating: Vec<crate::models::WindowInfo> = self
+            .windows
+            .lock()
+            .unwrap()
+            .windows()
+            .filter(|w| w.workspace == name && w.floating && !w.minimized)
+            .cloned()
+            .collect();
+        floating.sort_by_key(|w| std::cmp::Reverse(w.last_focused_at));
+        let z_order = self
+            .config
+            .lock()
+            .unwrap()
+            .config()
+            .z_order
+            .get(&pattern.to_string())
+            .copied()
+            .unwrap_or_default();
+        let layout = crate::tiling::WindowLayout::new(
+            assignments,
+            floating.into_iter().map(|w| w.id).collect(),
+            z_order,
+        );
+        Ok(Some(ArrangePlan { display, layout }))
+    }
+
+    /// Arrange a group's member workspaces concurrently, one task per
+    /// display, with a join barrier before the per-workspace completion
+    /// events. Members without a display of their own — or sharing one an
+    /// earlier member already claimed — are skipped: only one workspace is
+    /// visible per display.
+    fn arrange_group(&self, members: &[String]) {
+        let mut plans: Vec<crate::workspace::multi_display::DisplayArrangePlan> = Vec::new();
+        for name in members {
+            let planned = match self.plan_workspace(name) {
+                Ok(planned) => planned,
+                Err(err) => {
+                    tracing::debug!(workspace = %name, %err, "group arrange skipped a member");
+                    continue;
+                }
+            };
+            let Some(plan) = planned else {
+                continue;
+            };
+            if plans.iter().any(|p| p.display == plan.display.id) {
+                continue;
+            }
+            plans.push(crate::workspace::multi_display::DisplayArrangePlan {
+                display: plan.display.id,
+                workspace: name.clone(),
+                assignments: plan.layout.tiled,
+            });
+        }
+        if plans.len() < 2 {
+            // Zero or one display: the ordinary arrange path covers it.
+            if let Some(plan) = plans.pop() {
+                if let Err(err) = self.arrange(&plan.workspace) {
+                    tracing::warn!(workspace = %plan.workspace, %err, "arrange pass failed");
+                }
+            }
+            return;
+        }
+        let label = plans
+            .iter()
+            .map(|p| p.workspace.as_str())
+            .collect::<Vec<_>>()
+            .join("+");
+        let token = match self.orchestrator.lock().unwrap().begin_arrange(label) {
+            Ok(token) => token,
+            Err(err) => {
+                tracing::debug!(%err, "group arrange skipped");
+                return;
+            }
+        };
+        let displays = plans.len();
+        let outcome = match tokio::runtime::Builder::new_current_thread().build() {
+            Ok(runtime) => runtime.block_on(crate::workspace::multi_display::arrange_displays(
+                self.effects,
+                &self.bus,
+                plans,
+                &token,
+            )),
+            Err(err) => Err(TilleRSError::Validation(format!(
+                "group arrange runtime: {err}"
+            ))),
+        };
+        if let Err(err) = self.orchestrator.lock().unwrap().finish_arrange() {
+            tracing::debug!(%err, "arrange finished from an unexpected state");
+        }
+        match outcome {
+            Ok(applied) => tracing::debug!(applied, displays, "group arrange completed"),
+            Err(err) => tracing::warn!(%err, "group arrange failed"),
+        }
+    }
+
+    /// Feed one pass's verification outcomes into the compliance tracker.
+    /// A window that keeps ignoring AX resize is reclassified as floating,
+    /// the finding lands in its app's profile, and the survivors reclaim
+    /// its slot with a follow-up arrange.
+    fn record_compliance(
+        &self,
+        name: &str,
+        report: &crate::workspace::window_manager::ApplyReport,
+    ) {
+        if report.verified.is_empty() && report.unverified.is_empty() {
+            return;
+        }
+        let offenders: Vec<(WindowId, String)> = {
+            let windows = self.windows.lock().unwrap();
+            report
+                .unverified
+                .iter()
+                .filter_map(|id| windows.get(*id).map(|w| (*id, w.app_bundle_id.clone())))
+                .collect()
+        };
+        let reclassify: Vec<WindowId> = {
+            let mut compliance = self.compliance.lock().unwrap();
+            for id in &report.verified {
+                compliance.record_success(*id);
+            }
+            let mut profiles = self.profiles.lock().unwrap();
+            offenders
+                .into_iter()
+                .filter(|(id, bundle)| compliance.record_failure(*id, bundle, &mut profiles))
+                .map(|(id, _)| id)
+                .collect()
+        };
+        if reclassify.is_empty() {
+            return;
+        }
+        {
+            let mut windows = self.windows.lock().unwrap();
+            for id in reclassify {
+                if let Some(mut info) = windows.get(id).cloned() {
+                    info.floating = true;
+                    windows.insert(info);
+                    windows.invalidate(id);
+                }
+            }
+        }
+        // Bounded recursion: the reclassified windows are floating now and
+        // no longer part of the tiled set this pass computes from.
+        if let Err(err) = self.arrange(name) {
+            tracing::debug!(workspace = name, %err, "arrange after reclassification failed");
+        }
+    }
+
+    /// Arrange the active workspace, if any. Failures are logged, not
+    /// surfaced: this runs opportunistically after requests and events.
+    pub fn arrange_active(&self) {
+        let active = self.workspaces.lock().unwrap().active().map(str::to_string);
+        if let Some(name) = active {
+            if let Err(err) = self.arrange(&name) {
+                tracing::warn!(workspace = %name, %err, "arrange pass failed");
+            }
+        }
+    }
+
+    /// Switch the desktop image for an activating workspace. Lives apart
+    /// from [`on_event`](Self::on_event) because NSWorkspace's desktop
+    /// API needs the main thread: the event loop owns `switcher` and
+    /// calls this before the general event handling.
+    pub fn apply_wallpaper(
+        &self,
+        switcher: &mut crate::integrations::wallpaper::WallpaperSwitcher,
+        name: &str,
+    ) {
+        let Some(workspace) = self.workspaces.lock().unwrap().get(name).cloned() else {
+            return;
+        };
+        if workspace.wallpaper.is_none() {
+            return;
+        }
+        let Some(display) = self.display_for(workspace.display.as_deref()) else {
+            return;
+        };
+        if let Err(err) = switcher.on_workspace_activated(&self.effects, &workspace, display.id) {
+            tracing::warn!(workspace = name, %err, "wallpaper switch failed");
+        }
+    }
+
+    /// Fan an event out to plugins that observe events. Only the event
+    /// kinds in the plugin ABI are forwarded; the rest stay internal.
+    fn broadcast_to_plugins(&self, event: &Event) {
+        use crate::plugins::PluginEvent;
+
+        let plugin_event = match event {
+            Event::Window(WindowEvent::Created(info)) => PluginEvent::WindowCreated(info.clone()),
+            Event::Window(WindowEvent::Destroyed(id)) => PluginEvent::WindowDestroyed(*id),
+            Event::Workspace(WorkspaceEvent::Activated { name }) => {
+                PluginEvent::WorkspaceActivated(name.clone())
+            }
+            Event::Display(crate::events::DisplayEvent::ClamshellChanged { closed }) => {
+                PluginEvent::ClamshellChanged(*closed)
+            }
+            _ => return,
+        };
+        self.plugins.lock().unwrap().broadcast_event(&plugin_event);
+    }
+
+    /// React to one bus event. This is the daemon's event loop body: the
+    /// runtime thread blocks on the bus and feeds every event through here.
+    pub fn on_event(&self, event: &Event) {
+        self.broadcast_to_plugins(event);
+        match event {
+            Event::Workspace(WorkspaceEvent::Activated { name }) => {
+                // Close the departing workspace's usage interval; the app
+                // is refined by the next observed focus change.
+                let app = {
+                    let windows = self.windows.lock().unwrap();
+                    windows
+                        .windows()
+                        .filter(|w| w.workspace == *name)
+                        .max_by_key(|w| w.last_focused_at)
+                        .map(|w| w.app_bundle_id.clone())
+                        .unwrap_or_default()
+                };
+                self.checkpoint_usage(name.clone(), app);
+                // Pending destroys must leave the model before the snapshot
+                // replay, or the replay could raise dead windows.
+                self.remove_destroyed(self.destroys.lock().unwrap().flush_all());
+                // Replay the workspace's visibility snapshot first: it
+                // restores what arrange leaves alone — float positions,
+                // minimized state, stacking order. The arrange pass then
+                // re-asserts tiled frames, which also places windows that
+                // arrived while the workspace was inactive.
+                if let Err(err) = self.visibility.lock().unwrap().restore(name, &self.effects) {
+                    tracing::warn!(workspace = %name, %err, "visibility restore failed");
+                }
+                // The arrange here is the switch's visible work, so it runs
+                // under the switch budget.
+                let budget = self.config.lock().unwrap().config().budgets.switch_ms;
+                if let Err(err) = self.arrange_with_budget(name, budget_duration(budget)) {
+                    tracing::warn!(workspace = %name, %err, "arrange after switch failed");
+                }
+            }
+            Event::Window(WindowEvent::Created(_)) => self.arrange_active(),
+            Event::Window(WindowEvent::Destroyed(id)) => {
+                // A window that closes while cut can no longer be pasted,
+                // and can no longer be a focus-revert target.
+                self.clipboard.lock().unwrap().forget(*id);
+                self.focus_guard.lock().unwrap().forget(*id);
+                self.warper.lock().unwrap().forget(*id);
+                self.cosmetics.lock().unwrap().forget(*id);
+                self.visibility.lock().unwrap().forget_window(*id);
+                self.compliance.lock().unwrap().forget(*id);
+                self.relations.lock().unwrap().forget(*id);
+                self.creation_guard.lock().unwrap().record_destroyed(*id);
+                self.damper.lock().unwrap().forget(*id);
+                // Removal and the arrange are deferred to the destroy
+                // coalescer; the event loop arms the flush timer.
+                let bundle = self
+                    .windows
+                    .lock()
+                    .unwrap()
+                    .get(*id)
+                    .map(|w| w.app_bundle_id.clone());
+                if let Some(bundle) = bundle {
+                    if let Some(delay) = self.destroys.lock().unwrap().note_destroyed(&bundle, *id)
+                    {
+                        *self.destroy_timer.lock().unwrap() = Some(delay);
+                    }
+                }
+            }
+            Event::Window(WindowEvent::Moved { window, .. }) => {
+                // A manual move invalidates the applied-frame cache so the
+                // next arrange re-asserts the window's target.
+                self.windows.lock().unwrap().invalidate(*window);
+                // Each such re-assert is a re-tile to the damper: an app
+                // that keeps snapping its own frame back trips it and
+                // auto-floats for the cool-down instead of tugging.
+                let tripped = {
+                    let config = self.config.lock().unwrap().config().damping.clone();
+                    let tiled = self
+                        .windows
+                        .lock()
+                        .unwrap()
+                        .get(*window)
+                        .is_some_and(|w| !w.floating);
+                    tiled
+                        && self.damper.lock().unwrap().record_retile(
+                            *window,
+                            Instant::now(),
+                            &config,
+                        )
+                };
+                if tripped {
+                    {
+                        let mut windows = self.windows.lock().unwrap();
+                        if let Some(mut info) = windows.get(*window).cloned() {
+                            info.floating = true;
+                            windows.insert(info);
+                            windows.invalidate(*window);
+                        }
+                    }
+                    self.arrange_active();
+                }
+            }
+            // ClamshellChanged is the handler's own announcement, derived
+            // below from the accompanying attach/detach events.
+            Event::Display(crate::events::DisplayEvent::ClamshellChanged { .. }) => {}
+            Event::Display(_) => {
+                self.refresh_topology();
+                self.refresh_clamshell();
+            }
+            Event::Daemon(crate::events::DaemonEvent::ConfigReloaded) => {
+                // Group declarations come from config; rebuild them while
+                // keeping the active group when it still exists.
+                let declared = self.config.lock().unwrap().config().groups.clone();
+                {
+                    let mut groups = self.groups.lock().unwrap();
+                    let active = groups.active().map(|g| g.name.clone());
+                    let mut rebuilt = GroupRegistry::new(declared);
+                    if let Some(name) = active {
+                        let _ = rebuilt.switch(&name);
+                    }
+                    *groups = rebuilt;
+                }
+                // The keymap table is also config-derived; recompiling it
+                // in place retargets the live event tap.
+                let keybindings = self.config.lock().unwrap().config().keybindings.clone();
+                *self.keymap.lock().unwrap() =
+                    crate::keyboard::KeyboardMappingSet::compile(&keybindings);
+                self.arrange_active()
+            }
+            _ => {}
+        }
+    }
+
+    /// Re-derive the mirror topology after a display event. Mirroring
+    /// toggles at the worst times (plugging into a projector): when the
+    /// logical monitor set changed, workspace pins that now point at a
+    /// mirror or a detached display are reported — they fall back to the
+    /// main display through [`display_for`](Self::display_for), keeping
+    /// the pin for when the topology changes back — and the active
+    /// workspace re-arranges against the new set.
+    fn refresh_topology(&self) {
+        #[cfg(target_os = "macos")]
+        let displays = match crate::macos::list_displays() {
+            Ok(displays) => displays,
+            Err(_) => return,
+        };
+        #[cfg(not(target_os = "macos"))]
+        let displays: Vec<crate::models::display::DisplayInfo> = Vec::new();
+        #[cfg(target_os = "macos")]
+        let mirror_of = crate::macos::windows::mirror_primary;
+        #[cfg(not(target_os = "macos"))]
+        let mirror_of = |_| None;
+
+        let derived =
+            crate::workspace::topology::DisplayTopology::derive(displays.clone(), mirror_of);
+        let changed = {
+            let mut topology = self.topology.lock().unwrap();
+            let changed = derived.changed_from(&topology);
+            *topology = derived;
+            changed
+        };
+        if !changed {
+            return;
+        }
+        // Pins are stored by display name; resolve them to ids to ask the
+        // topology which assignments went stale.
+        let pinned: Vec<(String, crate::models::display::DisplayId)> = {
+            let workspaces = self.workspaces.lock().unwrap();
+            workspaces
+                .workspaces()
+                .iter()
+                .filter_map(|w| {
+                    let name = w.display.as_deref()?;
+                    let display = displays.iter().find(|d| d.name == name)?;
+                    Some((w.name.clone(), display.id))
+                })
+                .collect()
+        };
+        {
+            let topology = self.topology.lock().unwrap();
+            for workspace in crate::workspace::topology::stale_assignments(
+                &topology,
+                pinned.iter().map(|(w, d)| (w.as_str(), *d)),
+            ) {
+                tracing::info!(
+                    workspace,
+                    "pinned display is mirrored or detached; falling back to the main display"
+                );
+            }
+        }
+        // Work areas moved with the topology; floating windows with rule
+        // geometry re-resolve against their new display.
+        self.reapply_fixed_geometries();
+        self.arrange_active();
+    }
+
+    /// Re-resolve the rule geometry of every floating window; called when
+    /// the display topology changed and work areas may have moved.
+    fn reapply_fixed_geometries(&self) {
+        let rules = self.config.lock().unwrap().config().rules.clone();
+        let floating: Vec<crate::models::WindowInfo> = self
+            .windows
+            .lock()
+            .unwrap()
+            .windows()
+            .filter(|w| w.floating)
+            .cloned()
+            .collect();
+        for mut info in floating {
+            let Some(geometry) = rules
+                .iter()
+                .find(|r| r.enabled && r.matches(&info))
+                .and_then(|r| r.fixed_geometry.clone())
+            else {
+                continue;
+            };
+            self.apply_fixed_geometry(&mut info, &geometry);
+            self.windows.lock().unwrap().insert(info);
+        }
+    }
+
+    /// Resolve a floating rule's geometry against the display the
+    /// window's workspace arranges on and move the window there. Unknown
+    /// preset names were already flagged by `config check`; they are
+    /// skipped with a warning rather than failing the placement.
+    fn apply_fixed_geometry(
+        &self,
+        info: &mut crate::models::WindowInfo,
+        geometry: &crate::models::FloatGeometry,
+    ) {
+        let pin = self
+            .workspaces
+            .lock()
+            .unwrap()
+            .get(&info.workspace)
+            .and_then(|w| w.display.clone());
+        let Some(display) = self.display_for(pin.as_deref()) else {
+            return;
+        };
+        let Some(frame) = geometry.resolve(&display.work_area) else {
+            tracing::warn!(
+                window = info.id,
+                "fixed_geometry names an unknown preset; ignored"
+            );
+            return;
+        };
+        if let Err(err) = self.effects.set_window_frame(info.id, frame) {
+            tracing::warn!(window = info.id, %err, "fixed geometry could not be applied");
+            return;
+        }
+        info.frame = frame;
+    }
+
+    /// Feed the display list to the clamshell tracker and act on lid
+    /// transitions: a close migrates workspaces pinned to the built-in
+    /// display onto the externals, a reopen returns the parked ones to
+    /// their original pinning. Both publish `ClamshellChanged` so plugins
+    /// and hooks see the transition, and both re-arrange.
+    fn refresh_clamshell(&self) {
+        #[cfg(target_os = "macos")]
+        {
+            use crate::workspace::clamshell::ClamshellTransition;
+
+            let Ok(displays) = crate::macos::list_displays() else {
+                return;
+            };
+            let transition = self
+                .clamshell
+                .lock()
+                .unwrap()
+                .observe(&displays, crate::macos::windows::is_builtin);
+            let Some(transition) = transition else {
+                return;
+            };
+            // Snapshot the workspace list first: the plan methods lock the
+            // tracker, and nothing here may hold both locks at once.
+            let workspaces = self.workspaces.lock().unwrap().workspaces().to_vec();
+            let plan = match transition {
+                ClamshellTransition::Closed => {
+                    let externals: Vec<_> = displays
+                        .iter()
+                        .filter(|d| !crate::macos::windows::is_builtin(d.id))
+                        .cloned()
+                        .collect();
+                    self.clamshell
+                        .lock()
+                        .unwrap()
+                        .close_plan(&workspaces, &externals)
+                }
+                ClamshellTransition::Opened => self.clamshell.lock().unwrap().reopen_plan(|name| {
+                    workspaces
+                        .iter()
+                        .find(|w| w.name == name)
+                        .and_then(|w| w.display.clone())
+                }),
+            };
+            self.apply_repins(plan);
+            self.bus.publish(Event::Display(
+                crate::events::DisplayEvent::ClamshellChanged {
+                    closed: transition == ClamshellTransition::Closed,
+                },
+            ));
+            self.arrange_active();
+        }
+    }
+
+    /// Re-pin workspaces per a clamshell migration plan, keeping their
+    /// layout and quiet flag.
+    #[cfg(target_os = "macos")]
+    fn apply_repins(&self, plan: Vec<(String, String)>) {
+        let mut workspaces = self.workspaces.lock().unwrap();
+        for (name, display) in plan {
+            let Some(existing) = workspaces.get(&name).cloned() else {
+                continue;
+            };
+            if let Err(err) =
+                workspaces.ensure(&name, existing.layout, Some(display), existing.quiet)
+            {
+                tracing::warn!(workspace = %name, %err, "clamshell re-pin failed");
+            }
+        }
+    }
+
+    /// Remove a flushed destroy batch from the model. Clipboard and guard
+    /// state were already dropped when the events arrived; this is the
+    /// deferred half. Returns whether anything actually left the model.
+    fn remove_destroyed(&self, batches: Vec<crate::workspace::coalesce::DestroyBatch>) -> bool {
+        let mut removed_any = false;
+        for batch in batches {
+            for id in batch.windows {
+                let removed = self.windows.lock().unwrap().remove(id);
+                if let Some(removed) = removed {
+                    removed_any = true;
+                    // Closing a window is when manual ratios most often stop
+                    // making sense; opt-in auto-balance resets them here.
+                    if self.config.lock().unwrap().config().auto_balance_on_close {
+                        if let Err(err) = self
+                            .workspaces
+                            .lock()
+                            .unwrap()
+                            .balance_splits(&removed.workspace)
+                        {
+                            tracing::debug!(%err, "auto-balance after close skipped");
+                        }
+                    }
+                }
+            }
+        }
+        removed_any
+    }
+
+    /// Flush due destroy batches: one model update and one arrange for the
+    /// whole burst. The event loop's one-shot timer lands here.
+    pub fn flush_destroys(&self) {
+        let batches = self.destroys.lock().unwrap().flush_due();
+        if self.remove_destroyed(batches) {
+            self.arrange_active();
+        }
+    }
+
+    /// Flush every pending destroy batch immediately (daemon shutdown).
+    pub fn flush_all_destroys(&self) {
+        let batches = self.destroys.lock().unwrap().flush_all();
+        if self.remove_destroyed(batches) {
+            self.arrange_active();
+        }
+    }
+
+    /// The flush delay requested by the last destroy event, if it opened a
+    /// new batch; taking it arms the event loop's one-shot timer.
+    pub fn take_destroy_timer(&self) -> Option<std::time::Duration> {
+        self.destroy_timer.lock().unwrap().take()
+    }
+
+    /// Re-admit windows whose damping cool-down expired: they stop
+    /// auto-floating, and the next pass retries tiling them once. The tick
+    /// thread calls this every interval.
+    pub fn sweep_damping(&self) {
+        let config = self.config.lock().unwrap().config().damping.clone();
+        let expired = self.damper.lock().unwrap().expire(Instant::now(), &config);
+        if expired.is_empty() {
+            return;
+        }
+        {
+            let mut windows = self.windows.lock().unwrap();
+            for id in &expired {
+                if let Some(mut info) = windows.get(*id).cloned() {
+                    info.floating = false;
+                    windows.insert(info);
+                    windows.invalidate(*id);
+                }
+            }
+        }
+        tracing::info!(
+            windows = expired.len(),
+            "damping cool-down expired; retrying tiling"
+        );
+        self.arrange_active();
+    }
+
+    /// Scan for conflicting window managers: each tool is warned about
+    /// once, and with `auto_pause_on_conflict` set tiling suspends while
+    /// any conflict is alive and resumes when the last one quits. The
+    /// tick thread calls this periodically.
+    pub fn sweep_conflicts(&self) {
+        let (new, should_pause) = {
+            let mut monitor = self.conflicts.lock().unwrap();
+            (monitor.scan_new(), monitor.should_pause())
+        };
+        for conflict in &new {
+            tracing::warn!(
+                tool = conflict.name,
+                pid = conflict.pid,
+                "conflicting window manager detected; two tilers will fight over the same windows"
+            );
+        }
+        let was_paused = self
+            .conflict_paused
+            .swap(should_pause, std::sync::atomic::Ordering::Relaxed);
+        if should_pause && !was_paused {
+            tracing::warn!("auto-pausing tiling while a conflicting window manager runs");
+        } else if !should_pause && was_paused {
+            tracing::info!("conflicting window manager gone; resuming tiling");
+            self.arrange_active();
+        }
+    }
+
+    /// Pick up config edits made underneath us — a sync client replacing
+    /// the file, a dotfile switch — by soft reloading when any layer's
+    /// mtime moved. A load failure (including a sync conflict next to the
+    /// base file) leaves the running config in place. The tick thread
+    /// calls this periodically.
+    pub fn sweep_config_sync(&self) {
+        let mut config = self.config.lock().unwrap();
+        if !config.changed_on_disk() {
+            return;
+        }
+        tracing::info!("config changed on disk; soft reloading");
+        if let Err(err) = super::reload::soft_reload(&mut config, &self.bus) {
+            tracing::warn!(%err, "on-disk config change could not be reloaded");
+        }
+    }
+
+    /// The re-evaluation delay for the last held-back window, if any;
+    /// taking it arms the event loop's one-shot re-arrange timer.
+    pub fn take_creation_timer(&self) -> Option<std::time::Duration> {
+        self.creation_timer.lock().unwrap().take()
+    }
+
+    /// Whether the last budgeted pass deferred frames; taking it schedules
+    /// the event loop's follow-up pass that finishes them off the visible
+    /// path.
+    pub fn take_deferred_arrange(&self) -> bool {
+        self.deferred_arrange
+            .swap(false, std::sync::atomic::Ordering::AcqRel)
+    }
+
+    /// Arrange-pass counters for the metrics thread: total passes and
+    /// passes that exceeded their deadline budget.
+    pub fn arrange_stats(&self) -> (u64, u64) {
+        (
+            self.arrange_passes
+                .load(std::sync::atomic::Ordering::Relaxed),
+            self.degraded_passes
+                .load(std::sync::atomic::Ordering::Relaxed),
+        )
+    }
+
+    /// Merge a sync peer's workspace definitions into the model: last
+    /// writer wins per UUID, unknown definitions are adopted. Changed
+    /// workspaces converge through `ensure`, then the active arrangement
+    /// refreshes.
+    pub fn merge_synced_workspaces(&self, remote: Vec<crate::models::Workspace>) {
+        let changed = {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let mut local = workspaces.workspaces().to_vec();
+            let changed = crate::sync::SyncService::merge_workspaces(&mut local, remote);
+            for workspace in &changed {
+                if let Err(err) = workspaces.ensure(
+                    &workspace.name,
+                    workspace.layout,
+                    workspace.display.clone(),
+                    workspace.quiet,
+                ) {
+                    tracing::warn!(workspace = %workspace.name, %err, "sync merge skipped");
+                }
+            }
+            changed.len()
+        };
+        if changed > 0 {
+            tracing::info!(changed, "workspace definitions merged from sync peer");
+            self.arrange_active();
+        }
+    }
+
+    /// The current workspace definitions, for the sync announcement.
+    pub fn workspace_definitions(&self) -> Vec<crate::models::Workspace> {
+        self.workspaces.lock().unwrap().workspaces().to_vec()
+    }
+
+    /// Reconcile the model against a fresh system enumeration: adopt
+    /// untracked windows (routing them through rules and the catch-all),
+    /// drop vanished ones, and re-arrange when anything was repaired.
+    pub fn reconcile_now(&self) {
+        #[cfg(target_os = "macos")]
+        {
+            let actual = match crate::macos::list_windows() {
+                Ok(actual) => actual,
+                Err(err) => {
+                    tracing::warn!(%err, "window enumeration failed; reconcile skipped");
+                    return;
+                }
+            };
+            self.reconcile_with(&actual);
+            // AXFocusedWindowChanged funnels through here like every other
+            // notification, so this is where real focus changes land in
+            // the model's recency field.
+            if let Ok(id) = crate::macos::accessibility::focused_window() {
+                self.note_focused(id);
+            }
+        }
+    }
+
+    /// Record an observed focus change on the model, so recency queries
+    /// (`window stale`, focused-window target resolution) reflect actual
+    /// focus rather than enumeration time. Publishes `Focused` once per
+    /// change, not per reconcile pass.
+    pub fn note_focused(&self, window_id: WindowId) {
+        {
+            let mut last = self.last_focus.lock().unwrap();
+            if *last == Some(window_id) {
+                return;
+            }
+            *last = Some(window_id);
+        }
+        let focused = {
+            let mut windows = self.windows.lock().unwrap();
+            match windows.get(window_id).cloned() {
+                Some(mut info) => {
+                    info.last_focused_at = std::time::SystemTime::now();
+                    windows.insert(info.clone());
+                    Some(info)
+                }
+                None => None,
+            }
+        };
+        if let Some(info) = focused {
+            // An observed focus landing on an inactive workspace means the
+            // app raised itself — the focus-steal signature the journal's
+            // offender report keys on.
+            let active = self.workspaces.lock().unwrap().active().map(str::to_string);
+            let trigger = if active.as_deref() == Some(info.workspace.as_str()) {
+                crate::diagnostics::focus_journal::FocusTrigger::User
+            } else {
+                crate::diagnostics::focus_journal::FocusTrigger::AppInitiated
+            };
+            self.journal_focus(&info, trigger);
+            // The guard judges after the journal entry so reverted grabs
+            // still show up in the offender report.
+            let verdict = self.focus_guard.lock().unwrap().judge(
+                window_id,
+                &info.app_bundle_id,
+                trigger,
+                &self.profiles.lock().unwrap(),
+            );
+            if let crate::workspace::focus_guard::FocusVerdict::Revert { restore } = verdict {
+                if let Err(err) = self.effects.raise_window(restore) {
+                    tracing::warn!(window = restore, %err, "focus revert failed");
+                }
+                // The grab never becomes the usage baseline or a Focused
+                // event; the restore is observed like any focus change.
+                return;
+            }
+            self.checkpoint_usage(info.workspace, info.app_bundle_id);
+            self.bus
+                .publish(Event::Window(WindowEvent::Focused(window_id)));
+        }
+    }
+
+    /// Reconcile against the given enumeration (full or targeted, as the
+    /// polling fallback delivers).
+    pub fn reconcile_with(&self, actual: &[crate::models::WindowInfo]) {
+        let (report, adopted) = {
+            let mut windows = self.windows.lock().unwrap();
+            let known: Vec<WindowId> = windows.windows().map(|w| w.id).collect();
+            let report = windows.reconcile(actual);
+            let adopted: Vec<WindowId> = actual
+                .iter()
+                .map(|w| w.id)
+                .filter(|id| !known.contains(id))
+                .collect();
+            (report, adopted)
+        };
+        for id in adopted {
+            self.place_window(id);
+        }
+        if !report.is_clean() {
+            self.arrange_active();
+        }
+    }
+
+    /// Route a newly adopted window through the rules and the catch-all,
+    /// then announce it on the bus.
+    fn place_window(&self, window_id: WindowId) {
+        let Some(mut info) = self.windows.lock().unwrap().get(window_id).cloned() else {
+            return;
+        };
+        // A sheet or modal child attached to an existing window of the same
+        // app is recorded and left alone: no rules, no tiling — the OS
+        // moves it with its parent.
+        #[cfg(target_os = "macos")]
+        {
+            let parents: Vec<WindowId> = self
+                .windows
+                .lock()
+                .unwrap()
+                .windows()
+                .filter(|w| w.id != window_id && w.app_bundle_id == info.app_bundle_id)
+                .map(|w| w.id)
+                .collect();
+            for parent in parents {
+                let attached = crate::macos::accessibility::attached_children(parent)
+                    .map(|children| children.contains(&window_id))
+                    .unwrap_or(false);
+                if attached {
+                    tracing::debug!(
+                        window = window_id,
+                        parent,
+                        "attached child adopted; excluded from tiling"
+                    );
+                    self.relations.lock().unwrap().attach(parent, window_id);
+                    self.bus.publish(Event::Window(WindowEvent::Created(info)));
+                    return;
+                }
+            }
+        }
+        if self
+            .suspensions
+            .lock()
+            .unwrap()
+            .is_suspended(&info.app_bundle_id)
+        {
+            // Suspended apps are unmanaged: no rules run, the window floats
+            // where the app put it until the suspension lifts.
+            info.floating = true;
+            self.windows.lock().unwrap().insert(info.clone());
+            self.bus.publish(Event::Window(WindowEvent::Created(info)));
+            return;
+        }
+        let (rules, catch_all) = {
+            let config = self.config.lock().unwrap();
+            (
+                config.config().rules.clone(),
+                config.config().catch_all.clone(),
+            )
+        };
+        let active = self.workspaces.lock().unwrap().active().map(str::to_string);
+        let placement = crate::workspace::catch_all::place(&info, &rules, &catch_all);
+        let target = match &placement {
+            crate::workspace::catch_all::Placement::Rule { rule, workspace } => {
+                if let Some(rule) = rules.iter().find(|r| &r.name == rule) {
+                    info.floating = rule.floating;
+                    info.locked = rule.locked;
+                }
+                workspace.clone().or_else(|| active.clone())
+            }
+            crate::workspace::catch_all::Placement::CatchAll { workspace } => {
+                Some(workspace.clone())
+            }
+            crate::workspace::catch_all::Placement::Active => active.clone(),
+        };
+        if let Some(target) = target {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            if workspaces.get(&target).is_none() {
+                if let Err(err) = workspaces.create(crate::models::Workspace::new(target.clone())) {
+                    tracing::warn!(%err, "could not create placement workspace");
+                }
+            }
+            if workspaces.get(&target).is_some_and(|w| w.quiet) {
+                // Quiet workspace: the window is accepted but floats where
+                // the app put it; already-tiled windows stay managed.
+                info.floating = true;
+            }
+            info.workspace = target;
+        }
+        // A floating rule may pin its geometry: resolve the preset or
+        // percentages against the workspace's display before the window
+        // is announced.
+        if info.floating {
+            if let crate::workspace::catch_all::Placement::Rule { rule, .. } = &placement {
+                if let Some(geometry) = rules
+                    .iter()
+                    .find(|r| &r.name == rule)
+                    .and_then(|r| r.fixed_geometry.as_ref())
+                {
+                    self.apply_fixed_geometry(&mut info, geometry);
+                }
+            }
+        }
+        tracing::debug!(
+            window = window_id,
+            app = %info.app_bundle_id,
+            workspace = %info.workspace,
+            ?placement,
+            "window placed"
+        );
+        self.windows.lock().unwrap().insert(info.clone());
+        // The guard holds the newcomer out of tiling for its lifetime
+        // delay; the event loop arms a re-arrange for when it elapses, so
+        // a survivor claims its slot without polling.
+        let delay = self
+            .creation_guard
+            .lock()
+            .unwrap()
+            .record_created(window_id);
+        *self.creation_timer.lock().unwrap() = Some(delay);
+        self.bus.publish(Event::Window(WindowEvent::Created(info)));
+    }
+
+    /// The display a workspace arranges on: its pinned display when that
+    /// is attached and draws its own content, the first logical display
+    /// otherwise. Mirror secondaries are collapsed out here — tiling a
+    /// mirror would double-manage every window on its primary. `None`
+    /// means nothing to arrange on (headless, or a platform without
+    /// display enumeration).
+    fn display_for(&self, pinned: Option<&str>) -> Option<crate::models::display::DisplayInfo> {
+        #[cfg(target_os = "macos")]
+        {
+            let displays = crate::macos::list_displays().ok()?;
+            let topology = crate::workspace::topology::DisplayTopology::derive(
+                displays,
+                crate::macos::windows::mirror_primary,
+            );
+            let logical = topology.logical_displays();
+            pinned
+                .and_then(|name| logical.iter().find(|d| d.name == name).cloned())
+                .or_else(|| logical.first().cloned())
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = pinned;
+            None
+        }
+    }
+
+    /// Execute one action and return how to undo it, so sequences and
+    /// transactions can unwind completed steps when a later one fails.
+    /// Returns `Validation` for actions that have no IPC execution path
+    /// yet, so clients learn nothing happened.
+    fn execute(&self, action: &ActionType) -> Result<Rollback> {
+        match action {
+            ActionType::SwitchWorkspace { workspace } => {
+                let name = self.resolve_name(workspace)?;
+                self.activate_with_rollback(&name)
+            }
+            ActionType::SwitchWorkspaceIndex { index } => {
+                // Indices resolve inside the active group; without one they
+                // fall back to the global workspace order.
+                let target = {
+                    let groups = self.groups.lock().unwrap();
+                    let workspaces = self.workspaces.lock().unwrap();
+                    let order: Vec<String> = workspaces
+                        .workspaces()
+                        .iter()
+                        .map(|w| w.name.clone())
+                        .collect();
+                    groups.workspace_at(*index, &order).map(str::to_string)
+                };
+                match target {
+                    Some(name) => self.activate_with_rollback(&name),
+                    None => Err(TilleRSError::NotFound {
+                        kind: "workspace",
+                        name: format!("index {index}"),
+                    }),
+                }
+            }
+            ActionType::SwitchGroup { group } => {
+                let (prior, members) = {
+                    let mut groups = self.groups.lock().unwrap();
+                    let prior = groups.active().map(|g| g.name.clone());
+                    let members = groups.switch(group)?.workspaces.clone();
+                    (prior, members)
+                };
+                self.bus
+                    .publish(Event::Workspace(WorkspaceEvent::GroupActivated {
+                        name: group.clone(),
+                    }));
+                // Members pinned to their own displays arrange concurrently;
+                // arranging only the active workspace would leave the other
+                // monitors showing the previous group's frames.
+                self.arrange_group(&members);
+                let handle = Arc::clone(&self.groups);
+                let bus = self.bus.clone();
+                Ok(Some(Box::new(move || {
+                    let mut groups = handle.lock().unwrap();
+                    match prior {
+                        Some(name) => {
+                            groups.switch(&name)?;
+                            bus.publish(Event::Workspace(WorkspaceEvent::GroupActivated { name }));
+                        }
+                        None => groups.clear(),
+                    }
+                    Ok(())
+                })))
+            }
+            ActionType::EnsureWorkspace {
+                workspace,
+                layout,
+                display,
+                quiet,
+            } => {
+                let mut workspaces = self.workspaces.lock().unwrap();
+                let prior = workspaces.get(workspace).cloned();
+                workspaces.ensure(workspace, *layout, display.clone(), *quiet)?;
+                let handle = Arc::clone(&self.workspaces);
+                let name = workspace.clone();
+                Ok(Some(Box::new(move || {
+                    let mut workspaces = handle.lock().unwrap();
+                    match prior {
+                        // It existed: converge back to its previous shape.
+                        Some(prev) => {
+                            workspaces.ensure(&prev.name, prev.layout, prev.display, prev.quiet)
+                        }
+                        // It was created by this step: take it back out.
+                        None => workspaces.remove(&name).map(|_| ()),
+                    }
+                })))
+            }
+            ActionType::RemoveWorkspace { workspace } => {
+                let name = self.resolve_name(workspace)?;
+                let removed = self.workspaces.lock().unwrap().remove(&name)?;
+                self.temporary.lock().unwrap().forget(&name);
+                self.visibility.lock().unwrap().forget_workspace(&name);
+                let handle = Arc::clone(&self.workspaces);
+                Ok(Some(Box::new(move || {
+                    handle.lock().unwrap().create(removed)
+                })))
+            }
+            ActionType::CreateTemporaryWorkspace { workspace, minutes } => {
+                let existed = self.workspaces.lock().unwrap().get(workspace).is_some();
+                self.workspaces.lock().unwrap().ensure(
+                    workspace,
+                    crate::tiling::LayoutPattern::Tall,
+                    None,
+                    false,
+                )?;
+                self.temporary.lock().unwrap().register(
+                    workspace.clone(),
+                    std::time::Duration::from_secs(minutes * 60),
+                );
+                let workspaces = Arc::clone(&self.workspaces);
+                let temporary = Arc::clone(&self.temporary);
+                let name = workspace.clone();
+                Ok(Some(Box::new(move || {
+                    temporary.lock().unwrap().forget(&name);
+                    if !existed {
+                        workspaces.lock().unwrap().remove(&name)?;
+                    }
+                    Ok(())
+                })))
+            }
+            ActionType::PromoteTemporaryWorkspace { workspace } => {
+                let name = self.resolve_name(workspace)?;
+                if self.temporary.lock().unwrap().promote(&name) {
+                    // The original deadline is gone with the promotion;
+                    // there is nothing faithful to restore.
+                    Ok(None)
+                } else {
+                    Err(TilleRSError::NotFound {
+                        kind: "temporary workspace",
+                        name,
+                    })
+                }
+            }
+            ActionType::ToggleQuietMode { workspace } => {
+                let name = self.resolve_name(workspace)?;
+                self.workspaces.lock().unwrap().toggle_quiet(&name)?;
+                let handle = Arc::clone(&self.workspaces);
+                Ok(Some(Box::new(move || {
+                    handle.lock().unwrap().toggle_quiet(&name).map(|_| ())
+                })))
+            }
+            ActionType::MoveWindowToWorkspace {
+                window_id,
+                workspace,
+            } => self.move_window(*window_id, workspace),
+            ActionType::MoveToWorkspace { workspace } => {
+                let id = self.target_window(None)?;
+                self.move_window(id, workspace)
+            }
+            ActionType::CutWindows { window_ids } => {
+                let selection: Vec<WindowId> = if window_ids.is_empty() {
+                    vec![self.target_window(None)?]
+                } else {
+                    window_ids.clone()
+                };
+                // Replacing the clipboard restores the previous cut first,
+                // so windows can never be stranded hidden with no way back.
+                let previous = self.clipboard.lock().unwrap().paste();
+                Self::restore_cut(&self.windows, &self.effects, &previous);
+                let mut cut = Vec::with_capacity(selection.len());
+                {
+                    let mut windows = self.windows.lock().unwrap();
+                    for id in &selection {
+                        let mut info =
+                            windows
+                                .get(*id)
+                                .cloned()
+                                .ok_or_else(|| TilleRSError::NotFound {
+                                    kind: "window",
+                                    name: id.to_string(),
+                                })?;
+                        cut.push((info.id, info.workspace.clone()));
+                        // Cut windows stay assigned to their origin in the
+                        // model; the minimized flag keeps them out of the
+                        // layout, matching what hiding does on screen.
+                        info.minimized = true;
+                        windows.insert(info);
+                    }
+                }
+                for (id, _) in &cut {
+                    if let Err(err) = self.effects.hide_window(*id) {
+                        tracing::warn!(window = id, %err, "could not hide cut window");
+                    }
+                }
+                self.clipboard.lock().unwrap().cut(cut);
+                let clipboard = Arc::clone(&self.clipboard);
+                let windows = Arc::clone(&self.windows);
+                let effects = self.effects;
+                Ok(Some(Box::new(move || {
+                    let cut = clipboard.lock().unwrap().paste();
+                    Self::restore_cut(&windows, &effects, &cut);
+                    Ok(())
+                })))
+            }
+            ActionType::PasteWindows => {
+                let target = self
+                    .workspaces
+                    .lock()
+                    .unwrap()
+                    .active()
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        TilleRSError::Validation("no active workspace to paste into".into())
+                    })?;
+                let cut = self.clipboard.lock().unwrap().paste();
+                if cut.is_empty() {
+                    return Err(TilleRSError::Validation(
+                        "the window clipboard is empty; cut windows first".into(),
+                    ));
+                }
+                {
+                    let mut windows = self.windows.lock().unwrap();
+                    for entry in &cut {
+                        if let Some(mut info) = windows.get(entry.window).cloned() {
+                            info.workspace = target.clone();
+                            info.minimized = false;
+                            windows.insert(info);
+                            windows.invalidate(entry.window);
+                        }
+                    }
+                }
+                for entry in &cut {
+                    if let Err(err) = self.effects.set_window_minimized(entry.window, false) {
+                        tracing::warn!(window = entry.window, %err, "could not unhide pasted window");
+                    }
+                }
+                let clipboard = Arc::clone(&self.clipboard);
+                let windows = Arc::clone(&self.windows);
+                let effects = self.effects;
+                Ok(Some(Box::new(move || {
+                    // Re-cut: hide the windows again and put them back on
+                    // the clipboard under their original origins.
+                    {
+                        let mut windows = windows.lock().unwrap();
+                        for entry in &cut {
+                            if let Some(mut info) = windows.get(entry.window).cloned() {
+                                info.workspace = entry.origin_workspace.clone();
+                                info.minimized = true;
+                                windows.insert(info);
+                            }
+                        }
+                    }
+                    for entry in &cut {
+                        if let Err(err) = effects.hide_window(entry.window) {
+                            tracing::warn!(window = entry.window, %err, "could not re-hide window");
+                        }
+                    }
+                    clipboard
+                        .lock()
+                        .unwrap()
+                        .cut(cut.into_iter().map(|c| (c.window, c.origin_workspace)));
+                    Ok(())
+                })))
+            }
+            ActionType::CloseWindow { window_id } => {
+                self.effects.close_window(*window_id)?;
+                // Drop the model entry eagerly so the arrange pass that
+                // follows excludes it; AX confirms via the reconcile loop.
+                // A closed window cannot be reopened, so no rollback.
+                self.windows.lock().unwrap().remove(*window_id);
+                self.bus
+                    .publish(Event::Window(WindowEvent::Destroyed(*window_id)));
+                Ok(None)
+            }
+            ActionType::RaiseWindow { window_id } => {
+                let id = self.target_window(*window_id)?;
+                self.effects.raise_window(id)?;
+                Ok(None)
+            }
+            ActionType::LowerWindow { window_id } => {
+                let id = self.target_window(*window_id)?;
+                // AX has no send-to-back; raising every sibling above the
+                // window realizes the same order. Least recently focused
+                // first, so the window the user last saw ends up on top.
+                let mut siblings: Vec<crate::models::WindowInfo> = {
+                    let windows = self.windows.lock().unwrap();
+                    let workspace =
+                        windows
+                            .get(id)
+                            .map(|w| w.workspace.clone())
+                            .ok_or_else(|| TilleRSError::NotFound {
+                                kind: "window",
+                                name: id.to_string(),
+                            })?;
+                    windows
+                        .windows()
+                        .filter(|w| w.id != id && w.workspace == workspace && !w.minimized)
+                        .cloned()
+                        .collect()
+                };
+                siblings.sort_by_key(|w| w.last_focused_at);
+                for sibling in &siblings {
+                    self.effects.raise_window(sibling.id)?;
+                }
+                // The prior z-order is unknowable through AX; no rollback.
+                Ok(None)
+            }
+            ActionType::FocusWindow { window_id } => self.focus_window(*window_id),
+            ActionType::FocusLeft { warp } => self.focus_direction(FocusDirection::Left, *warp),
+            ActionType::FocusRight { warp } => self.focus_direction(FocusDirection::Right, *warp),
+            ActionType::FocusUp { warp } => self.focus_direction(FocusDirection::Up, *warp),
+            ActionType::FocusDown { warp } => self.focus_direction(FocusDirection::Down, *warp),
+            ActionType::Retile => {
+                // Invalidate applied frames so the arrange pass that follows
+                // every action re-asserts targets even for windows the user
+                // moved by hand. Nothing to undo.
+                let mut windows = self.windows.lock().unwrap();
+                let ids: Vec<WindowId> = windows.windows().map(|w| w.id).collect();
+                for id in ids {
+                    windows.invalidate(id);
+                }
+                Ok(None)
+            }
+            ActionType::PauseWorkspace { workspace } => {
+                let name = self.resolve_name(workspace)?;
+                if !self.paused.lock().unwrap().pause(&name) {
+                    return Ok(None);
+                }
+                let handle = Arc::clone(&self.paused);
+                Ok(Some(Box::new(move || {
+                    handle.lock().unwrap().resume(&name);
+                    Ok(())
+                })))
+            }
+            ActionType::ResumeWorkspace { workspace } => {
+                let name = self.resolve_name(workspace)?;
+                if !self.paused.lock().unwrap().resume(&name) {
+                    return Ok(None);
+                }
+                // Re-adopt: drop applied frames so the arrange pass after
+                // this action re-asserts targets for the freed windows.
+                {
+                    let mut windows = self.windows.lock().unwrap();
+                    let ids: Vec<WindowId> = windows
+                        .windows()
+                        .filter(|w| w.workspace == name)
+                        .map(|w| w.id)
+                        .collect();
+                    for id in ids {
+                        windows.invalidate(id);
+                    }
+                }
+                let handle = Arc::clone(&self.paused);
+                Ok(Some(Box::new(move || {
+                    handle.lock().unwrap().pause(&name);
+                    Ok(())
+                })))
+            }
+            ActionType::SuspendAppRules { bundle_id, minutes } => {
+                self.suspensions.lock().unwrap().suspend(
+                    bundle_id.clone(),
+                    std::time::Duration::from_secs(minutes * 60),
+                );
+                tracing::info!(app = %bundle_id, minutes, "app rules suspended");
+                let handle = Arc::clone(&self.suspensions);
+                let bundle = bundle_id.clone();
+                Ok(Some(Box::new(move || {
+                    handle.lock().unwrap().resume(&bundle);
+                    Ok(())
+                })))
+            }
+            ActionType::ResumeAppRules { bundle_id } => {
+                if !self.suspensions.lock().unwrap().resume(bundle_id) {
+                    return Err(TilleRSError::NotFound {
+                        kind: "suspension",
+                        name: bundle_id.clone(),
+                    });
+                }
+                // Re-adopt the app's windows on the next arrange pass. The
+                // original deadline is gone, so there is no rollback.
+                self.invalidate_app_windows(bundle_id);
+                Ok(None)
+            }
+            ActionType::StartFocusSession { minutes } => {
+                let workspace = self
+                    .workspaces
+                    .lock()
+                    .unwrap()
+                    .active()
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        TilleRSError::Validation(
+                            "no active workspace to start a focus session on".into(),
+                        )
+                    })?;
+                let event = self
+                    .focus_timer
+                    .lock()
+                    .unwrap()
+                    .start(workspace, std::time::Duration::from_secs(minutes * 60));
+                tracing::info!(?event, "focus session started");
+                let handle = Arc::clone(&self.focus_timer);
+                Ok(Some(Box::new(move || {
+                    handle.lock().unwrap().cancel();
+                    Ok(())
+                })))
+            }
+            ActionType::EndFocusSession => {
+                // The elapsed part of the session is gone either way, so
+                // there is no faithful rollback.
+                match self.focus_timer.lock().unwrap().cancel() {
+                    Some(event) => {
+                        tracing::info!(?event, "focus session overridden");
+                        Ok(None)
+                    }
+                    None => Err(TilleRSError::NotFound {
+                        kind: "focus session",
+                        name: "active".to_string(),
+                    }),
+                }
+            }
+            ActionType::BalanceLayout => {
+                let name = self
+                    .workspaces
+                    .lock()
+                    .unwrap()
+                    .active()
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        TilleRSError::Validation("no active workspace to balance".into())
+                    })?;
+                match self.workspaces.lock().unwrap().balance_splits(&name)? {
+                    None => Ok(None),
+                    Some(prior) => {
+                        let handle = Arc::clone(&self.workspaces);
+                        Ok(Some(Box::new(move || {
+                            handle.lock().unwrap().set_splits(&name, prior)
+                        })))
+                    }
+                }
+            }
+            ActionType::ToggleFloat => self.toggle_window_flag(|w| {
+                w.floating = !w.floating;
+            }),
+            ActionType::ToggleLock => {
+                let rollback = self.toggle_window_flag(|w| {
+                    w.locked = !w.locked;
+                })?;
+                // Runtime state lives on the model either way; a failed
+                // save only costs persistence across restarts.
+                if let Err(err) = self.persist_lock() {
+                    tracing::warn!(%err, "lock state could not be persisted to the config");
+                }
+                Ok(rollback)
+            }
+            ActionType::ToggleCosmetics => {
+                let enabled = self.cosmetics.lock().unwrap().toggle(&self.effects)?;
+                tracing::info!(enabled, "cosmetic rule effects toggled");
+                if enabled {
+                    // Re-enabling re-applies on the next pass; force one.
+                    self.arrange_active();
+                }
+                // The toggle is its own inverse; no rollback needed.
+                Ok(None)
+            }
+            ActionType::PinWorkspaceToDisplay { workspace, display } => {
+                let name = self.resolve_name(workspace)?;
+                let mut workspaces = self.workspaces.lock().unwrap();
+                let (layout, prior_display, quiet) = workspaces
+                    .get(&name)
+                    .map(|w| (w.layout, w.display.clone(), w.quiet))
+                    .ok_or_else(|| TilleRSError::NotFound {
+                        kind: "workspace",
+                        name: name.clone(),
+                    })?;
+                workspaces.ensure(&name, layout, Some(display.clone()), quiet)?;
+                let handle = Arc::clone(&self.workspaces);
+                Ok(Some(Box::new(move || {
+                    handle
+                        .lock()
+                        .unwrap()
+                        .ensure(&name, layout, prior_display, quiet)
+                })))
+            }
+            ActionType::SetHooksEnabled { enabled } => {
+                let prior = {
+                    let mut hooks = self.hooks.lock().unwrap();
+                    let prior = hooks.is_killed();
+                    hooks.set_killed(!enabled);
+                    prior
+                };
+                let handle = Arc::clone(&self.hooks);
+                Ok(Some(Box::new(move || {
+                    handle.lock().unwrap().set_killed(prior);
+                    Ok(())
+                })))
+            }
+            ActionType::SoftReload => {
+                let mut config = self.config.lock().unwrap();
+                super::reload::soft_reload(&mut config, &self.bus).map(|_| None)
+            }
+            ActionType::PluginAction { name } => {
+                // The lock is released before follow-ups run; a plugin
+                // action itself has no inverse, so it never participates
+                // in sequence rollback.
+                let follow_ups = self.plugins.lock().unwrap().handle_action(name)?;
+                for follow_up in &follow_ups {
+                    self.execute(follow_up)?;
+                }
+                Ok(None)
+            }
+            ActionType::Sequence(actions) => {
+                // Intra-sequence rollback happens inside the runner; once
+                // the whole sequence completed it is treated as committed,
+                // so a failing later sibling does not unwind it.
+                crate::workspace::sequence::run_sequence(actions, |a| self.execute(a))
+                    .map(|()| None)
+            }
+            ActionType::PullTabOut => {
+                let id = self.target_window(None)?;
+                #[cfg(target_os = "macos")]
+                {
+                    let pid = self.pid_for_window(id)?;
+                    // The menu acts on the key window; raise first.
+                    self.effects.raise_window(id)?;
+                    self.effects.pull_tab_out(pid)?;
+                    // The detached window arrives as a Created event via
+                    // the observers; a menu press has no faithful inverse.
+                    Ok(None)
+                }
+                #[cfg(not(target_os = "macos"))]
+                {
+                    let _ = id;
+                    Err(TilleRSError::Validation("native tabs require macOS".into()))
+                }
+            }
+            ActionType::MergeIntoTabs => {
+                let id = self.target_window(None)?;
+                #[cfg(target_os = "macos")]
+                {
+                    let pid = self.pid_for_window(id)?;
+                    self.effects.raise_window(id)?;
+                    self.effects.merge_into_tabs(pid)?;
+                    Ok(None)
+                }
+                #[cfg(not(target_os = "macos"))]
+                {
+                    let _ = id;
+                    Err(TilleRSError::Validation("native tabs require macOS".into()))
+                }
+            }
+            ActionType::ArchiveSweep => {
+                let moved = self.archive_sweep()?;
+                tracing::info!(moved, "archival sweep dispatched");
+                // The sweep's inverse is the explicit `archive_undo`
+                // action, backed by the kept report, not a rollback.
+                Ok(None)
+            }
+            ActionType::ArchiveUndo => {
+                let restored = self.archive_undo()?;
+                tracing::info!(restored, "archival sweep undone");
+                Ok(None)
+            }
+            ActionType::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                // Evaluate against the dispatch context: focused window,
+                // active workspace, lid state. The taken branch's rollback
+                // is returned unchanged; an untaken branch is a no-op.
+                let workspace = self
+                    .workspaces
+                    .lock()
+                    .unwrap()
+                    .active()
+                    .map(str::to_string)
+                    .unwrap_or_default();
+                let focused = self
+                    .target_window(None)
+                    .ok()
+                    .and_then(|id| self.windows.lock().unwrap().get(id).cloned());
+                let clamshell = self.clamshell.lock().unwrap().is_closed();
+                let branch = if condition.holds(focused.as_ref(), &workspace, clamshell) {
+                    Some(then.as_ref())
+                } else {
+                    otherwise.as_deref()
+                };
+                match branch {
+                    Some(action) => self.execute(action),
+                    None => Ok(None),
+                }
+            }
+        }
+    }
+
+    /// The pid owning a window's app, resolved through the running-
+    /// application list — the model tracks bundle ids, not pids.
+    #[cfg(target_os = "macos")]
+    fn pid_for_window(&self, window_id: WindowId) -> Result<i32> {
+        let bundle = self
+            .windows
+            .lock()
+            .unwrap()
+            .get(window_id)
+            .map(|w| w.app_bundle_id.clone())
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "window",
+                name: window_id.to_string(),
+            })?;
+        crate::macos::running_applications()
+            .into_iter()
+            .find(|(_, _, id)| id.as_deref() == Some(bundle.as_str()))
+            .map(|(pid, _, _)| pid)
+            .ok_or(TilleRSError::NotFound {
+                kind: "application",
+                name: bundle,
+            })
+    }
+
+    /// The effective raise policy for a window: its app profile's
+    /// override when one is set, else the global `raise_policy` setting.
+    #[cfg(target_os = "macos")]
+    fn raise_policy_for(&self, window_id: WindowId) -> crate::models::app_profile::RaisePolicy {
+        let bundle = self
+            .windows
+            .lock()
+            .unwrap()
+            .get(window_id)
+            .map(|w| w.app_bundle_id.clone());
+        bundle
+            .and_then(|bundle| {
+                self.profiles
+                    .lock()
+                    .unwrap()
+                    .get(&bundle)
+                    .and_then(|profile| profile.raise_policy)
+            })
+            .unwrap_or(self.config.lock().unwrap().config().raise_policy)
+    }
+
+    /// Resolve an optional window target: the given id, or the most
+    /// recently focused window on the active workspace — what bindings
+    /// without an explicit target operate on.
+    fn target_window(&self, window_id: Option<WindowId>) -> Result<WindowId> {
+        if let Some(id) = window_id {
+            return Ok(id);
+        }
+        let active = self.workspaces.lock().unwrap().active().map(str::to_string);
+        let windows = self.windows.lock().unwrap();
+        windows
+            .windows()
+            .filter(|w| Some(w.workspace.as_str()) == active.as_deref())
+            .max_by_key(|w| w.last_focused_at)
+            .map(|w| w.id)
+            .ok_or(TilleRSError::NotFound {
+                kind: "window",
+                name: "focused".to_string(),
+            })
+    }
+
+    /// Mirror the focused window's lock state into the config: locking
+    /// writes the generated persistence rule, unlocking removes whatever
+    /// generated rule matches the window (its name may carry a previous
+    /// session's window id, so it is matched by content, not name).
+    fn persist_lock(&self) -> Result<()> {
+        let id = self.target_window(None)?;
+        let Some(info) = self.windows.lock().unwrap().get(id).cloned() else {
+            return Ok(());
+        };
+        let mut config = self.config.lock().unwrap();
+        if info.locked {
+            config.add_rule(crate::workspace::locks::persistence_rule(&info))?;
+            config.save_with_reason(&format!("Lock window {id}"))
+        } else {
+            let stale: Vec<String> = config
+                .config()
+                .rules
+                .iter()
+                .filter(|r| {
+                    r.name
+                        .starts_with(crate::workspace::locks::LOCK_RULE_PREFIX)
+                        && r.matches(&info)
+                })
+                .map(|r| r.name.clone())
+                .collect();
+            if stale.is_empty() {
+                return Ok(());
+            }
+            for name in stale {
+                config.remove_rule(&name)?;
+            }
+            config.save_with_reason(&format!("Unlock window {id}"))
+        }
+    }
+
+    /// Flip a boolean flag on the focused window's model entry; the
+    /// rollback applies the same toggle again.
+    fn toggle_window_flag(&self, toggle: fn(&mut crate::models::WindowInfo)) -> Result<Rollback> {
+        let id = self.target_window(None)?;
+        {
+            let mut windows = self.windows.lock().unwrap();
+            let mut info = windows
+                .get(id)
+                .cloned()
+                .ok_or_else(|| TilleRSError::NotFound {
+                    kind: "window",
+                    name: id.to_string(),
+                })?;
+            toggle(&mut info);
+            windows.insert(info);
+            windows.invalidate(id);
+        }
+        let handle = Arc::clone(&self.windows);
+        Ok(Some(Box::new(move || {
+            let mut windows = handle.lock().unwrap();
+            if let Some(mut info) = windows.get(id).cloned() {
+                toggle(&mut info);
+                windows.insert(info);
+                windows.invalidate(id);
+            }
+            Ok(())
+        })))
+    }
+
+    /// Activate a workspace by resolved name; the rollback re-activates
+    /// whichever workspace was active before.
+    /// Snapshot the active workspace's visibility state before a switch,
+    /// so switching back replays float positions, minimized state, and
+    /// stacking order instead of losing them to a fresh layout pass.
+    fn snapshot_departing(&self) {
+        let Some(active) = self.workspaces.lock().unwrap().active().map(str::to_string) else {
+            return;
+        };
+        let windows = self.windows.lock().unwrap();
+        self.visibility
+            .lock()
+            .unwrap()
+            .capture(&active, windows.windows().filter(|w| w.workspace == active));
+    }
+
+    fn activate_with_rollback(&self, name: &str) -> Result<Rollback> {
+        self.ensure_switch_allowed(name)?;
+        self.snapshot_departing();
+        let prior = {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let prior = workspaces.active().map(str::to_string);
+            workspaces.activate(name)?;
+            prior
+        };
+        if prior.as_deref() == Some(name) {
+            return Ok(None);
+        }
+        let handle = Arc::clone(&self.workspaces);
+        Ok(
+            prior.map(|prior| -> Box<dyn FnOnce() -> Result<()> + Send> {
+                Box::new(move || handle.lock().unwrap().activate(&prior))
+            }),
+        )
+    }
+
+    /// Focus a window: switch to its workspace if needed, assert AX
+    /// keyboard focus with the effective raise policy (the app profile's
+    /// override, else the global setting), and record the focus in the
+    /// model. Focus history is not worth restoring, so no rollback.
+    fn focus_window(&self, window_id: WindowId) -> Result<Rollback> {
+        let workspace = self
+            .windows
+            .lock()
+            .unwrap()
+            .get(window_id)
+            .map(|w| w.workspace.clone())
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "window",
+                name: window_id.to_string(),
+            })?;
+        let needs_switch = self.workspaces.lock().unwrap().active() != Some(workspace.as_str());
+        if needs_switch {
+            self.ensure_switch_allowed(&workspace)?;
+            self.snapshot_departing();
+            self.workspaces.lock().unwrap().activate(&workspace)?;
+        }
+        #[cfg(target_os = "macos")]
+        {
+            let policy = self.raise_policy_for(window_id);
+            let pid = self.pid_for_window(window_id)?;
+            self.effects.focus_window(window_id, pid, policy)?;
+        }
+        // Without AX there is no keyboard focus to move; raising is the
+        // closest observable effect.
+        #[cfg(not(target_os = "macos"))]
+        self.effects.raise_window(window_id)?;
+        let focused = {
+            let mut windows = self.windows.lock().unwrap();
+            match windows.get(window_id).cloned() {
+                Some(mut info) => {
+                    info.last_focused_at = std::time::SystemTime::now();
+                    windows.insert(info.clone());
+                    Some(info)
+                }
+                None => None,
+            }
+        };
+        if let Some(info) = focused {
+            // Pre-empt the reconcile path's observation so this change is
+            // journaled once, attributed to us rather than to the app.
+            *self.last_focus.lock().unwrap() = Some(window_id);
+            self.journal_focus(
+                &info,
+                crate::diagnostics::focus_journal::FocusTrigger::Tillers,
+            );
+            self.checkpoint_usage(info.workspace, info.app_bundle_id);
+        }
+        self.bus
+            .publish(Event::Window(WindowEvent::Focused(window_id)));
+        Ok(None)
+    }
+
+    /// Focus the nearest window in a direction on the active workspace,
+    /// optionally warping the cursor to it. `warp` is the binding's
+    /// per-action override of the global warp setting. Focus moves are
+    /// not worth undoing, so no rollback.
+    fn focus_direction(&self, direction: FocusDirection, warp: Option<bool>) -> Result<Rollback> {
+        let origin = self.target_window(None)?;
+        let (origin_frame, candidates) = {
+            let active = self.workspaces.lock().unwrap().active().map(str::to_string);
+            let windows = self.windows.lock().unwrap();
+            let origin_frame =
+                windows
+                    .get(origin)
+                    .map(|w| w.frame)
+                    .ok_or_else(|| TilleRSError::NotFound {
+                        kind: "window",
+                        name: origin.to_string(),
+                    })?;
+            let candidates: Vec<(WindowId, crate::models::Rect)> = windows
+                .windows()
+                .filter(|w| {
+                    w.id != origin
+                        && Some(w.workspace.as_str()) == active.as_deref()
+                        && !w.minimized
+                })
+                .map(|w| (w.id, w.frame))
+                .collect();
+            (origin_frame, candidates)
+        };
+        let Some((target, frame)) = nearest_in_direction(&origin_frame, &candidates, direction)
+        else {
+            // Pressing into an edge is not an error; the binding simply
+            // has nowhere to go.
+            return Ok(None);
+        };
+        // Remember where the pointer sat inside the departing window, so
+        // a later warp back lands where the user left off.
+        #[cfg(target_os = "macos")]
+        if let Some((x, y)) = crate::macos::cursor_position() {
+            if x >= origin_frame.x
+                && x <= origin_frame.x + origin_frame.width
+                && y >= origin_frame.y
+                && y <= origin_frame.y + origin_frame.height
+            {
+                self.warper.lock().unwrap().record_position(origin, x, y);
+            }
+        }
+        self.focus_window(target)?;
+        let config = self.config.lock().unwrap().config().warp_cursor;
+        let destination = self
+            .warper
+            .lock()
+            .unwrap()
+            .destination(target, &frame, &config, warp);
+        if let Some((x, y)) = destination {
+            self.effects.warp_cursor(x, y)?;
+        }
+        Ok(None)
+    }
+
+    /// Put cut windows back where they came from: origin workspace,
+    /// unminimized, frames re-asserted on the next arrange pass. An
+    /// associated function so rollback closures can call it too.
+    fn restore_cut(
+        windows: &Mutex<WindowManager>,
+        effects: &Effects,
+        cut: &[crate::workspace::clipboard::CutWindow],
+    ) {
+        let mut windows = windows.lock().unwrap();
+        for entry in cut {
+            if let Some(mut info) = windows.get(entry.window).cloned() {
+                info.workspace = entry.origin_workspace.clone();
+                info.minimized = false;
+                windows.insert(info);
+                windows.invalidate(entry.window);
+            }
+            if let Err(err) = effects.set_window_minimized(entry.window, false) {
+                tracing::warn!(window = entry.window, %err, "could not unhide cut window");
+            }
+        }
+    }
+
+    /// Restore any still-cut windows to their origins. Called when the
+    /// event loop exits, so a shutdown never strands hidden windows.
+    pub fn restore_cut_windows(&self) {
+        let cut = self.clipboard.lock().unwrap().paste();
+        if !cut.is_empty() {
+            tracing::info!(windows = cut.len(), "restoring cut windows on shutdown");
+            Self::restore_cut(&self.windows, &self.effects, &cut);
+        }
+    }
+
+    /// Refuse a switch away from a workspace locked by a focus session.
+    /// The explicit way out is `end_focus_session`, which cancels the
+    /// session instead of bypassing this check.
+    fn ensure_switch_allowed(&self, target: &str) -> Result<()> {
+        let timer = self.focus_timer.lock().unwrap();
+        if timer.blocks_switch(target, false) {
+            let locked = timer
+                .active()
+                .map(|s| s.workspace.clone())
+                .unwrap_or_default();
+            return Err(TilleRSError::Validation(format!(
+                "a focus session is locking workspace '{locked}'; \
+                 end it with end_focus_session before switching"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Resolve a workspace reference string to its current name.
+    fn resolve_name(&self, reference: &str) -> Result<String> {
+        let reference = WorkspaceRef::from_str(reference)?;
+        let workspaces = self.workspaces.lock().unwrap();
+        workspaces.resolve(&reference).map(|w| w.name.clone())
+    }
+
+    /// Reassign a window in the model; the next arrange pass realizes it.
+    /// The rollback restores the previous assignment.
+    fn move_window(&self, window_id: u32, workspace: &str) -> Result<Rollback> {
+        let name = self.resolve_name(workspace)?;
+        let prior = {
+            let mut windows = self.windows.lock().unwrap();
+            let info = windows
+                .windows()
+                .find(|w| w.id == window_id)
+                .cloned()
+                .ok_or_else(|| TilleRSError::NotFound {
+                    kind: "window",
+                    name: window_id.to_string(),
+                })?;
+            let prior = info.workspace.clone();
+            let mut updated = info;
+            updated.workspace = name.clone();
+            windows.insert(updated);
+            windows.invalidate(window_id);
+            prior
+        };
+        if prior == name {
+            return Ok(None);
+        }
+        let handle = Arc::clone(&self.windows);
+        Ok(Some(Box::new(move || {
+            let mut windows = handle.lock().unwrap();
+            if let Some(mut info) = windows.get(window_id).cloned() {
+                info.workspace = prior;
+                windows.insert(info);
+                windows.invalidate(window_id);
+            }
+            Ok(())
+        })))
+    }
+
+    fn health(&self) -> HealthReport {
+        HealthReport {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime_secs: self.started.elapsed().as_secs(),
+            workspaces: self.workspaces.lock().unwrap().workspaces().len(),
+            windows: self.windows.lock().unwrap().windows().count(),
+            subscribers: self.bus.subscriber_count(),
+            lagged_events: self.bus.lagged_total(),
+            conflicts: self
+                .conflicts
+                .lock()
+                .unwrap()
+                .scan()
+                .into_iter()
+                .map(|c| c.name.to_string())
+                .collect(),
+            orchestrator: self.orchestrator.lock().unwrap().state().to_string(),
+        }
+    }
+}
+
+impl RequestHandler for DaemonHandler {
+    fn deck_state(&self, icons: bool) -> Option<crate::ipc::deck::DeckUpdate> {
+        let (names, active) = self.workspace_snapshot();
+        // Per-workspace window counts and dominant app, from one pass over
+        // the model.
+        let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        let mut apps: std::collections::HashMap<(String, String), usize> =
+            std::collections::HashMap::new();
+        for window in self.windows.lock().unwrap().windows() {
+            *counts.entry(window.workspace.clone()).or_default() += 1;
+            *apps
+                .entry((window.workspace.clone(), window.app_bundle_id.clone()))
+                .or_default() += 1;
+        }
+        let icon_for = move |workspace: &str| -> Option<crate::ipc::deck::DeckIcon> {
+            let (_, bundle) = apps
+                .iter()
+                .filter(|((ws, _), _)| ws == workspace)
+                .max_by_key(|(_, count)| **count)
+                .map(|((ws, bundle), _)| (ws.clone(), bundle.clone()))?;
+            // Cache-or-async-load: a miss kicks off a background load and
+            // this update ships without the icon; the deck's next poll
+            // finds it cached.
+            let icon = self.icons.lock().unwrap().get(&bundle)?;
+            Some(crate::ipc::deck::DeckIcon {
+                width: icon.width,
+                height: icon.height,
+                rgba: icon.rgba.clone(),
+            })
+        };
+        Some(crate::ipc::deck::deck_update(
+            &names,
+            active.as_deref(),
+            |workspace| counts.get(workspace).copied().unwrap_or(0),
+            icons.then_some(&icon_for as crate::ipc::deck::IconResolver<'_>),
+        ))
+    }
+
+    fn subscribe_events(&self) -> Option<crate::events::EventSubscriber> {
+        Some(self.bus.subscribe())
+    }
+
+    fn handle(&self, request: Request) -> Response {
+        self.sweep_temporaries();
+        self.sweep_suspensions();
+        self.sweep_focus_timer();
+        match request {
+            Request::Action { action } => {
+                if self.mode == OperationMode::Observe {
+                    tracing::info!(?action, "observer mode: action not executed");
+                    return observer_refusal();
+                }
+                match self.execute(&action) {
+                    Ok(_) => {
+                        self.arrange_active();
+                        Response::Ok
+                    }
+                    Err(err) => error_response(err),
+                }
+            }
+            Request::Transaction { actions } => {
+                if self.mode == OperationMode::Observe {
+                    tracing::info!(
+                        count = actions.len(),
+                        "observer mode: transaction not executed"
+                    );
+                    return observer_refusal();
+                }
+                match crate::workspace::sequence::run_sequence(&actions, |a| self.execute(a)) {
+                    // One arrange pass for the whole transaction, never
+                    // between steps.
+                    Ok(()) => {
+                        self.arrange_active();
+                        Response::Ok
+                    }
+                    Err(err) => error_response(err),
+                }
+            }
+            Request::QueryWindows => Response::Windows {
+                windows: self.windows.lock().unwrap().windows().cloned().collect(),
+            },
+            Request::QueryWorkspaces => {
+                let workspaces = self.workspaces.lock().unwrap();
+                Response::Workspaces {
+                    workspaces: workspaces.workspaces().to_vec(),
+                    active: workspaces.active().map(str::to_string),
+                }
+            }
+            Request::Health => Response::Health(self.health()),
+        }
+    }
+}
+
+fn error_response(err: TilleRSError) -> Response {
+    Response::Error {
+        code: err.code_name().to_string(),
+        message: err.to_string(),
+    }
+}
+
+fn observer_refusal() -> Response {
+    Response::Error {
+        code: "permission".into(),
+        message: "daemon is running in observer mode; actions are disabled".into(),
+    }
+}
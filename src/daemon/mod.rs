@@ -0,0 +1,254 @@
+//! The long-running daemon: owns the event loop and all side effects.
+
+pub mod effects;
+pub mod handler;
+pub mod reload;
+pub mod runtime;
+pub mod startup;
+
+pub use effects::Effects;
+pub use handler::DaemonHandler;
+pub use reload::{soft_reload, SoftReloadReport};
+pub use startup::StartupTimeline;
+
+use crate::config::ConfigManager;
+use crate::errors::Result;
+
+/// How the process was started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationMode {
+    /// Normal daemon: manages windows and grabs hotkeys.
+    Daemon,
+    /// Read-only observer: runs all detection and rule evaluation, logs
+    /// every action it *would* take, but never touches a window or
+    /// registers a hotkey. Useful for validating a config against a live
+    /// session.
+    Observe,
+}
+
+/// Daemon entry point.
+pub fn run(mode: OperationMode) -> Result<()> {
+    let mut timeline = StartupTimeline::new();
+
+    // Permission probes talk to TCC and can stall; run them alongside
+    // config loading instead of before it.
+    let permissions = std::thread::spawn(check_permissions);
+    let manager = timeline.time("config", ConfigManager::load_default)?;
+    let trusted = timeline.time("permissions", || permissions.join().unwrap_or(false));
+
+    if !trusted {
+        // The daemon can't prompt; point at the guided recovery flow
+        // instead of printing the pane-hunting instructions alone.
+        tracing::warn!(
+            "{} Run `tillers diagnostics permissions` for guided recovery.",
+            crate::diagnostics::permissions::MacPermission::Accessibility.instructions()
+        );
+    }
+
+    // Apps restore their windows for a while after login; the handler's
+    // construction runs the adoption pass, so the configured grace period
+    // and per-app readiness probes are waited out first. Every probe has a
+    // timeout, so this phase is bounded even with a hung app.
+    timeline.time("readiness", || {
+        wait_for_readiness(&manager.config().startup)
+    });
+
+    let effects = Effects::new(mode);
+    tracing::info!(
+        ?mode,
+        trusted,
+        rules = manager.config().rules.len(),
+        "tillers daemon starting"
+    );
+    if mode == OperationMode::Observe {
+        tracing::info!("observer mode: no windows will be moved and no hotkeys grabbed");
+    }
+
+    // The IPC server is what makes `tillers <subcommand>` operate on this
+    // process instead of a throwaway local model.
+    let auth = manager.config().ipc.clone();
+    #[cfg(target_os = "macos")]
+    let triggers = manager.config().triggers.clone();
+    let sync_config = manager.config().sync.clone();
+    // The quick-switch HUD watches the modifier the numeric workspace
+    // bindings share; without such bindings hold detection stays off.
+    let hold_modifier = crate::keyboard::workspace_modifier(&manager.config().keybindings);
+    let bus = crate::events::EventBus::new();
+    let events = bus.subscribe();
+    let handler = std::sync::Arc::new(DaemonHandler::new(mode, effects, manager, bus));
+    let _ipc = timeline.time("ipc", || {
+        let ipc_handler: std::sync::Arc<dyn crate::ipc::server::RequestHandler> =
+            std::sync::Arc::clone(&handler) as _;
+        crate::ipc::server::spawn(ipc_handler, auth)
+    })?;
+
+    // Hotkeys; observer mode never grabs keys (enforced in Effects). The
+    // tap shares the handler's keymap table, so a config reload retargets
+    // it without touching the registration.
+    timeline.time("hotkeys", || {
+        let dispatch = std::sync::Arc::clone(&handler);
+        let hold_bus = handler.bus().clone();
+        let result = effects.register_hotkeys(
+            handler.keymap(),
+            hold_modifier,
+            move |action, chord| {
+                dispatch.record_shortcut(chord, action);
+                dispatch.bus().publish(crate::events::Event::Keyboard(
+                    crate::events::KeyboardEvent::ShortcutDispatched {
+                        mapping: chord.to_string(),
+                    },
+                ));
+                dispatch_trigger(&dispatch, "hotkey", action);
+            },
+            // Hold state reaches the HUD through the bus: the panel is
+            // AppKit and must be shown from the event loop's main thread.
+            move |change| {
+                hold_bus.publish(crate::events::Event::Keyboard(
+                    crate::events::KeyboardEvent::WorkspaceModifierHeld {
+                        held: change == crate::keyboard::hold::HoldEvent::Show,
+                    },
+                ));
+            },
+        );
+        if let Err(err) = result {
+            tracing::warn!(%err, "hotkey registration failed; keybindings inactive");
+        }
+    });
+
+    // Time-driven work: temporary-workspace expiry, periodic reconcile.
+    runtime::spawn_tick(std::sync::Arc::clone(&handler));
+    // Window-server notifications; every AX event funnels into a
+    // reconcile pass against the handler's model.
+    #[cfg(target_os = "macos")]
+    runtime::spawn_ax_observers(std::sync::Arc::clone(&handler));
+
+    timeline.mark_ready();
+
+    // Nothing below is needed to tile the first workspace.
+    startup::spawn_deferred("tray", {
+        let handler = std::sync::Arc::clone(&handler);
+        move || {
+            runtime::spawn_tray(handler);
+            Ok(())
+        }
+    });
+    startup::spawn_deferred("metrics", {
+        let handler = std::sync::Arc::clone(&handler);
+        move || {
+            runtime::spawn_metrics(handler);
+            Ok(())
+        }
+    });
+    startup::spawn_deferred("touchbar", {
+        let handler = std::sync::Arc::clone(&handler);
+        move || {
+            runtime::spawn_touchbar(handler);
+            Ok(())
+        }
+    });
+    startup::spawn_deferred("hooks", {
+        let handler = std::sync::Arc::clone(&handler);
+        move || {
+            runtime::spawn_hooks(handler);
+            Ok(())
+        }
+    });
+    if sync_config.enabled {
+        startup::spawn_deferred("sync", {
+            let handler = std::sync::Arc::clone(&handler);
+            move || {
+                runtime::spawn_sync(handler, sync_config)?;
+                Ok(())
+            }
+        });
+    }
+    startup::spawn_deferred("plugins", {
+        let handler = std::sync::Arc::clone(&handler);
+        move || {
+            let dir = ConfigManager::default_path()
+                .parent()
+                .map(|config_dir| config_dir.join("plugins"))
+                .unwrap_or_default();
+            let loaded = handler.plugins().lock().unwrap().load_directory(dir)?;
+            if loaded > 0 {
+                tracing::info!(loaded, "plugins loaded");
+            }
+            Ok(())
+        }
+    });
+
+    // Hot corners poll on their own thread; swipes need an AppKit monitor
+    // installed from the main thread, kept alive for the daemon's
+    // lifetime. Both dispatch through the handler like any IPC action.
+    #[cfg(target_os = "macos")]
+    let _swipe_monitor = {
+        let dispatch = std::sync::Arc::clone(&handler);
+        crate::macos::gestures::spawn_hot_corner_monitor(triggers.clone(), move |action| {
+            dispatch_trigger(&*dispatch, "gesture", action);
+        });
+        let dispatch = std::sync::Arc::clone(&handler);
+        crate::macos::gestures::install_swipe_monitor(triggers, move |action| {
+            dispatch_trigger(&*dispatch, "gesture", action);
+        })
+    };
+
+    // The main thread is the event loop: it blocks on the bus and reacts
+    // to workspace switches, window lifecycle, and config reloads. It
+    // only returns when the bus closes, which keeps the process alive for
+    // the IPC server and upkeep threads.
+    runtime::run_event_loop(handler, events);
+    Ok(())
+}
+
+/// Route a trigger-fired action (hotkey, gesture, hot corner) through the
+/// handler, logging refusals the way an IPC client would see them.
+fn dispatch_trigger(
+    handler: &DaemonHandler,
+    source: &'static str,
+    action: &crate::models::ActionType,
+) {
+    use crate::ipc::protocol::{Request, Response};
+    use crate::ipc::server::RequestHandler;
+
+    let response = handler.handle(Request::Action {
+        action: action.clone(),
+    });
+    if let Response::Error { code, message } = response {
+        tracing::warn!(source, %code, %message, "triggered action failed");
+    }
+}
+
+/// Block until the startup grace period elapsed and every readiness probe
+/// passed or timed out, feeding the tracker fresh window snapshots.
+fn wait_for_readiness(config: &crate::workspace::readiness::StartupConfig) {
+    #[cfg(target_os = "macos")]
+    {
+        use std::time::Instant;
+
+        let mut tracker =
+            crate::workspace::readiness::ReadinessTracker::new(config.clone(), Instant::now());
+        loop {
+            let windows = crate::macos::list_windows().unwrap_or_default();
+            let now = Instant::now();
+            tracker.observe(&windows, now);
+            if tracker.ready(&windows, now) {
+                return;
+            }
+            std::thread::sleep(crate::workspace::readiness::READINESS_POLL_INTERVAL);
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = config;
+}
+
+/// Whether the process holds Accessibility trust.
+fn check_permissions() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::accessibility_trusted()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        false
+    }
+}
@@ -0,0 +1,49 @@
+//! Soft reload: re-read config and rebuild derived state in place.
+//!
+//! The middle ground between hot-reloading a single value and a full
+//! restart. The config is re-read and swapped in, then a
+//! [`DaemonEvent::ConfigReloaded`] goes out on the bus; each subscriber
+//! rebuilds what it derives from config — the keymap lookup table, AX
+//! observer registrations, the active arrangement — without touching its
+//! runtime state. Hidden windows stay in their visibility snapshots,
+//! focus history survives, and nothing is re-adopted.
+
+use crate::config::ConfigManager;
+use crate::errors::Result;
+use crate::events::{DaemonEvent, Event, EventBus};
+
+/// What a soft reload picked up, for the log and the CLI reply.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SoftReloadReport {
+    pub rules: usize,
+    pub keybindings: usize,
+    pub groups: usize,
+    pub hooks: usize,
+    /// Subscribers notified to rebuild their derived state.
+    pub subscribers: usize,
+}
+
+/// Re-read the config from disk, swap it in, and announce the reload.
+///
+/// A config that no longer parses aborts the reload and leaves the old
+/// config in place — a broken edit must never take down a running daemon.
+pub fn soft_reload(manager: &mut ConfigManager, bus: &EventBus) -> Result<SoftReloadReport> {
+    manager.reload()?;
+
+    let config = manager.config();
+    let report = SoftReloadReport {
+        rules: config.rules.len(),
+        keybindings: config.keybindings.len(),
+        groups: config.groups.len(),
+        hooks: config.hooks.hooks.len(),
+        subscribers: bus.subscriber_count(),
+    };
+    bus.publish(Event::Daemon(DaemonEvent::ConfigReloaded));
+    tracing::info!(
+        rules = report.rules,
+        keybindings = report.keybindings,
+        subscribers = report.subscribers,
+        "soft reload complete"
+    );
+    Ok(report)
+}
@@ -0,0 +1,526 @@
+//! The daemon's runtime: the event loop and the upkeep threads.
+//!
+//! [`daemon::run`](super::run) wires three long-lived pieces around the
+//! handler: the event loop (the main thread, blocking on the bus), a tick
+//! thread for time-driven work (temporary-workspace expiry, periodic
+//! reconciliation), and — on macOS — the AX observer thread that turns
+//! window-server notifications into reconcile passes.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::events::{DisplayEvent, Event, EventSubscriber, KeyboardEvent, WorkspaceEvent};
+
+use super::DaemonHandler;
+
+/// How often the tick thread wakes up for time-driven work.
+pub const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often recorded usage statistics are flushed to disk.
+pub const STATS_FLUSH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How often the archival policy sweeps for stale windows. Staleness is
+/// measured in days, so an hourly check is already generous.
+pub const ARCHIVAL_SWEEP_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// How often the process table is scanned for conflicting window
+/// managers.
+pub const CONFLICT_SCAN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Run the event loop until the bus closes. This is the daemon's main
+/// thread: every subsystem that reacts to state changes hangs off the bus,
+/// and the handler folds each event into the model and arranges as needed.
+/// Wallpaper switching lives here rather than on an upkeep thread because
+/// the desktop-image API must be called from the main thread.
+pub fn run_event_loop(handler: Arc<DaemonHandler>, mut events: EventSubscriber) {
+    let mut wallpaper = crate::integrations::wallpaper::WallpaperSwitcher::new();
+    #[cfg(target_os = "macos")]
+    let mut hud: Option<crate::macos::overlay::HudPanel> = None;
+    #[cfg(target_os = "macos")]
+    let mut thumbnails = crate::ui::thumbnails::ThumbnailService::new();
+    #[cfg(target_os = "macos")]
+    let status_item = match crate::macos::statusbar::StatusItem::install({
+        let handler = Arc::clone(&handler);
+        move || {
+            if let Err(err) = handler.open_settings() {
+                tracing::warn!(%err, "settings window failed");
+            }
+        }
+    }) {
+        Ok(item) => {
+            item.refresh(
+                &handler.tray_status().label(),
+                &tray_rows(&handler, &mut thumbnails),
+            );
+            Some(item)
+        }
+        Err(err) => {
+            tracing::warn!(%err, "status item unavailable; tray menu disabled");
+            None
+        }
+    };
+    while let Some(event) = events.blocking_recv() {
+        match &event {
+            Event::Workspace(WorkspaceEvent::Activated { name }) => {
+                handler.apply_wallpaper(&mut wallpaper, name);
+            }
+            Event::Display(DisplayEvent::Detached(id)) => wallpaper.forget_display(*id),
+            Event::Keyboard(KeyboardEvent::WorkspaceModifierHeld { held }) => {
+                // The quick-switch HUD is an AppKit panel, so like
+                // wallpapers it lives on this thread.
+                #[cfg(target_os = "macos")]
+                {
+                    if let Some(panel) = hud.take() {
+                        panel.dismiss();
+                    }
+                    if *held {
+                        let style =
+                            crate::ui::theme::Theme::new(handler.theme_spec()).overlay_style(0.85);
+                        match crate::macos::overlay::HudPanel::show(&handler.hud_entries(), style) {
+                            Ok(panel) => hud = Some(panel),
+                            Err(err) => tracing::warn!(%err, "quick-switch HUD failed"),
+                        }
+                    }
+                }
+                #[cfg(not(target_os = "macos"))]
+                let _ = held;
+            }
+            _ => {}
+        }
+        handler.on_event(&event);
+        // A destroy that opened a new batch wants a one-shot flush timer;
+        // it is armed here because the timer thread needs the `Arc`.
+        if let Some(delay) = handler.take_destroy_timer() {
+            let flusher = Arc::clone(&handler);
+            let spawned = std::thread::Builder::new()
+                .name("tillers-destroy-flush".into())
+                .spawn(move || {
+                    std::thread::sleep(delay);
+                    flusher.flush_destroys();
+                });
+            if let Err(err) = spawned {
+                tracing::warn!(%err, "destroy flush timer failed; flushing inline");
+                handler.flush_destroys();
+            }
+        }
+        // A held-back new window wants a re-arrange once its lifetime delay
+        // elapses, so a survivor claims its slot.
+        if let Some(delay) = handler.take_creation_timer() {
+            let arranger = Arc::clone(&handler);
+            let spawned = std::thread::Builder::new()
+                .name("tillers-creation-retile".into())
+                .spawn(move || {
+                    std::thread::sleep(delay);
+                    arranger.arrange_active();
+                });
+            if let Err(err) = spawned {
+                tracing::warn!(%err, "creation retile timer failed; arranging inline");
+                handler.arrange_active();
+            }
+        }
+        // A budgeted pass that deferred frames finishes them on a one-shot
+        // thread, off the visible path. If the follow-up loses the
+        // orchestrator race it is skipped; the cold cache entries make the
+        // next ordinary pass pick the frames up instead.
+        if handler.take_deferred_arrange() {
+            let finisher = Arc::clone(&handler);
+            let spawned = std::thread::Builder::new()
+                .name("tillers-arrange-finish".into())
+                .spawn(move || finisher.arrange_active());
+            if let Err(err) = spawned {
+                tracing::warn!(%err, "deferred arrange thread failed; finishing inline");
+                handler.arrange_active();
+            }
+        }
+        // Menu rebuilds happen here, after the handler folded the event in,
+        // so the rows always reflect the post-event model.
+        #[cfg(target_os = "macos")]
+        if let Some(item) = &status_item {
+            if let Event::Window(crate::events::WindowEvent::Destroyed(id)) = &event {
+                thumbnails.forget(*id);
+            }
+            if matches!(
+                &event,
+                Event::Window(_) | Event::Workspace(_) | Event::Daemon(_)
+            ) {
+                item.refresh(
+                    &handler.tray_status().label(),
+                    &tray_rows(&handler, &mut thumbnails),
+                );
+            }
+        }
+    }
+    handler.flush_all_destroys();
+    handler.restore_cut_windows();
+    handler.flush_stats();
+    tracing::info!("event bus closed; event loop exiting");
+}
+
+/// Spawn the tick thread: expires temporary workspaces and app-rule
+/// suspensions, and reconciles the window model against the system on
+/// [`RECONCILE_INTERVAL`].
+///
+/// [`RECONCILE_INTERVAL`]: crate::workspace::window_manager::RECONCILE_INTERVAL
+pub fn spawn_tick(handler: Arc<DaemonHandler>) -> std::thread::JoinHandle<()> {
+    std::thread::Builder::new()
+        .name("tillers-tick".into())
+        .spawn(move || {
+            let mut last_reconcile = std::time::Instant::now();
+            let mut last_stats_flush = std::time::Instant::now();
+            let mut last_archival = std::time::Instant::now();
+            let mut last_conflict_scan = std::time::Instant::now();
+            // A conflicting tool already running at startup should be
+            // warned about before the first periodic scan comes around.
+            handler.sweep_conflicts();
+            loop {
+                std::thread::sleep(TICK_INTERVAL);
+                handler.sweep_temporaries();
+                handler.sweep_suspensions();
+                handler.sweep_focus_timer();
+                handler.sweep_damping();
+                handler.sweep_config_sync();
+                if last_reconcile.elapsed() >= crate::workspace::window_manager::RECONCILE_INTERVAL
+                {
+                    last_reconcile = std::time::Instant::now();
+                    handler.reconcile_now();
+                }
+                if last_stats_flush.elapsed() >= STATS_FLUSH_INTERVAL {
+                    last_stats_flush = std::time::Instant::now();
+                    handler.flush_stats();
+                }
+                if last_archival.elapsed() >= ARCHIVAL_SWEEP_INTERVAL {
+                    last_archival = std::time::Instant::now();
+                    handler.sweep_archival();
+                }
+                if last_conflict_scan.elapsed() >= CONFLICT_SCAN_INTERVAL {
+                    last_conflict_scan = std::time::Instant::now();
+                    handler.sweep_conflicts();
+                }
+            }
+        })
+        .expect("spawn tick thread")
+}
+
+/// Menu rows for the tray: the active workspace's windows with their
+/// thumbnails, captured within the service's per-pass budget.
+#[cfg(target_os = "macos")]
+fn tray_rows(
+    handler: &DaemonHandler,
+    thumbnails: &mut crate::ui::thumbnails::ThumbnailService,
+) -> Vec<crate::macos::statusbar::MenuRow> {
+    let windows = handler.tray_windows();
+    thumbnails
+        .thumbnails(&windows)
+        .into_iter()
+        .zip(&windows)
+        .map(
+            |((_, thumbnail), window)| crate::macos::statusbar::MenuRow {
+                title: if window.title.is_empty() {
+                    window.app_bundle_id.clone()
+                } else {
+                    window.title.clone()
+                },
+                thumbnail,
+            },
+        )
+        .collect()
+}
+
+/// How often the metrics thread re-measures memory and object counts.
+pub const METRICS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Spawn the tray updater: re-derives the tray status from live state on
+/// every bus event and logs transitions. The NSStatusItem itself is driven
+/// from the event loop (AppKit is main-thread-only); this thread keeps the
+/// transition log that works on every platform.
+pub fn spawn_tray(handler: Arc<DaemonHandler>) -> std::thread::JoinHandle<()> {
+    let mut events = handler.bus().subscribe();
+    std::thread::Builder::new()
+        .name("tillers-tray".into())
+        .spawn(move || {
+            let mut current = handler.tray_status();
+            tracing::debug!(status = %current.label(), "tray status");
+            while events.blocking_recv().is_some() {
+                let status = handler.tray_status();
+                if status != current {
+                    tracing::info!(status = %status.label(), "tray status changed");
+                    current = status;
+                }
+            }
+        })
+        .expect("spawn tray thread")
+}
+
+/// Spawn the Touch Bar updater: folds workspace events into the strip
+/// model and re-renders on change. Like the tray, the model runs on every
+/// platform; only the AppKit render behind the `touchbar` feature touches
+/// NSTouchBar.
+pub fn spawn_touchbar(handler: Arc<DaemonHandler>) -> std::thread::JoinHandle<()> {
+    let mut events = handler.bus().subscribe();
+    std::thread::Builder::new()
+        .name("tillers-touchbar".into())
+        .spawn(move || {
+            let (names, active) = handler.workspace_snapshot();
+            let mut model = crate::ui::touchbar::TouchBarModel::new(names, active.as_deref());
+            #[cfg(all(target_os = "macos", feature = "touchbar"))]
+            crate::ui::touchbar::appkit::render(model.items());
+            while let Some(event) = events.blocking_recv() {
+                if let crate::events::Event::Workspace(workspace_event) = event {
+                    if model.apply(&workspace_event) {
+                        tracing::debug!(items = model.items().len(), "touch bar updated");
+                        #[cfg(all(target_os = "macos", feature = "touchbar"))]
+                        crate::ui::touchbar::appkit::render(model.items());
+                    }
+                }
+            }
+        })
+        .expect("spawn touch bar thread")
+}
+
+/// Spawn the hooks thread: maps bus events to configured hooks and
+/// launches them through the runner, which enforces the concurrency and
+/// rate limits and the kill switch.
+pub fn spawn_hooks(handler: Arc<DaemonHandler>) -> std::thread::JoinHandle<()> {
+    let mut events = handler.bus().subscribe();
+    std::thread::Builder::new()
+        .name("tillers-hooks".into())
+        .spawn(move || {
+            while let Some(event) = events.blocking_recv() {
+                let Some((name, env)) = crate::integrations::hooks::event_descriptor(&event) else {
+                    continue;
+                };
+                let configured = handler.hooks_for(name);
+                if configured.is_empty() {
+                    continue;
+                }
+                let mut runner = handler.hook_runner().lock().unwrap();
+                for hook in &configured {
+                    if let Err(err) = runner.launch(hook, &env) {
+                        tracing::warn!(hook = %hook.name, %err, "hook launch failed");
+                    }
+                }
+            }
+        })
+        .expect("spawn hooks thread")
+}
+
+/// Spawn the metrics thread: refreshes memory usage and per-subsystem
+/// object counts on [`METRICS_INTERVAL`], running the watchdog against
+/// each fresh reading.
+pub fn spawn_metrics(handler: Arc<DaemonHandler>) -> std::thread::JoinHandle<()> {
+    std::thread::Builder::new()
+        .name("tillers-metrics".into())
+        .spawn(move || {
+            let mut metrics = crate::metrics::PerformanceMetrics::new();
+            let mut watchdog = crate::metrics::MemoryWatchdog::default();
+            loop {
+                std::thread::sleep(METRICS_INTERVAL);
+                metrics.event_lag_total = handler.bus().lagged_total();
+                let (arrange_passes, degraded_passes) = handler.arrange_stats();
+                metrics.arrange_passes = arrange_passes;
+                metrics.degraded_passes = degraded_passes;
+                metrics.refresh_memory(handler.object_counts(), &mut watchdog);
+                tracing::debug!(
+                    memory_mb = metrics.memory_usage_mb,
+                    windows = metrics.objects.tracked_windows,
+                    ax_handles = metrics.objects.cached_ax_handles,
+                    "metrics refreshed"
+                );
+            }
+        })
+        .expect("spawn metrics thread")
+}
+
+/// Spawn the sync beacon thread: announces this machine and its workspace
+/// definitions on the multicast group every
+/// [`DISCOVERY_INTERVAL`](crate::sync::DISCOVERY_INTERVAL) and merges
+/// definitions received from authenticated peers. Construction fails fast
+/// on a bad `[sync]` config (enabled without a shared key) so the
+/// misconfiguration is loud instead of silently doing nothing.
+pub fn spawn_sync(
+    handler: Arc<DaemonHandler>,
+    config: crate::sync::SyncConfig,
+) -> crate::errors::Result<std::thread::JoinHandle<()>> {
+    let mut service = crate::sync::SyncService::new(config)?;
+    std::thread::Builder::new()
+        .name("tillers-sync".into())
+        .spawn(move || {
+            use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+
+            let socket = match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, crate::sync::SYNC_PORT)) {
+                Ok(socket) => socket,
+                Err(err) => {
+                    tracing::warn!(%err, "sync beacon socket failed; sync disabled");
+                    return;
+                }
+            };
+            if let Err(err) =
+                socket.join_multicast_v4(&crate::sync::MULTICAST_GROUP, &Ipv4Addr::UNSPECIFIED)
+            {
+                tracing::warn!(%err, "sync multicast join failed; sync disabled");
+                return;
+            }
+            let _ = socket.set_read_timeout(Some(TICK_INTERVAL));
+            let target = SocketAddr::from((crate::sync::MULTICAST_GROUP, crate::sync::SYNC_PORT));
+            let ours = service.machine_name();
+            tracing::info!(machine = %ours, "workspace sync active");
+
+            let mut last_announce: Option<std::time::Instant> = None;
+            let mut buf = vec![0u8; 64 * 1024];
+            loop {
+                if last_announce
+                    .map(|at| at.elapsed() >= crate::sync::DISCOVERY_INTERVAL)
+                    .unwrap_or(true)
+                {
+                    last_announce = Some(std::time::Instant::now());
+                    let definitions = handler.workspace_definitions();
+                    let envelopes = service.hello().and_then(|hello| {
+                        service
+                            .workspace_announcement(&definitions)
+                            .map(|workspaces| [hello, workspaces])
+                    });
+                    match envelopes {
+                        Ok(envelopes) => {
+                            for envelope in &envelopes {
+                                let datagram = match serde_json::to_vec(envelope) {
+                                    Ok(datagram) => datagram,
+                                    Err(_) => continue,
+                                };
+                                if let Err(err) = socket.send_to(&datagram, target) {
+                                    tracing::debug!(%err, "sync announcement failed");
+                                }
+                            }
+                        }
+                        Err(err) => tracing::warn!(%err, "sync announcement could not be built"),
+                    }
+                }
+                let (len, src) = match socket.recv_from(&mut buf) {
+                    Ok(received) => received,
+                    // Timeouts pace the announce check; anything else is
+                    // logged and retried.
+                    Err(err)
+                        if err.kind() == std::io::ErrorKind::WouldBlock
+                            || err.kind() == std::io::ErrorKind::TimedOut =>
+                    {
+                        continue
+                    }
+                    Err(err) => {
+                        tracing::debug!(%err, "sync receive failed");
+                        continue;
+                    }
+                };
+                let Ok(envelope) =
+                    serde_json::from_slice::<crate::sync::protocol::SyncEnvelope>(&buf[..len])
+                else {
+                    continue;
+                };
+                // Authentication happens here; tampered or foreign-key
+                // traffic is dropped before any state is touched.
+                let message = match service.open(&envelope) {
+                    Ok(message) => message,
+                    Err(err) => {
+                        tracing::debug!(%src, %err, "sync message rejected");
+                        continue;
+                    }
+                };
+                match message {
+                    crate::sync::protocol::SyncMessage::Hello {
+                        machine_name,
+                        protocol_version,
+                    } => {
+                        // Our own beacon loops back; ignore it.
+                        if machine_name != ours {
+                            service.peer_discovered(crate::sync::Peer {
+                                machine_name,
+                                address: src,
+                                protocol_version,
+                            });
+                        }
+                    }
+                    crate::sync::protocol::SyncMessage::Workspaces { workspaces } => {
+                        // Merging our own announcement is a no-op: same
+                        // UUIDs, same definitions.
+                        handler.merge_synced_workspaces(workspaces);
+                    }
+                    crate::sync::protocol::SyncMessage::LayoutSnapshot { .. } => {
+                        // Snapshots are defined in the protocol but not yet
+                        // applied; definitions sync is the experiment.
+                    }
+                }
+            }
+        })
+        .map_err(|e| crate::errors::TilleRSError::Validation(format!("spawn sync thread: {e}")))
+}
+
+/// Spawn the AX observer thread: registers window notifications for every
+/// running app (with the polling fallback for apps that refuse), then
+/// services the observers' run-loop sources, reconciling whenever a
+/// notification fired. Newly launched apps are picked up on the way.
+#[cfg(target_os = "macos")]
+pub fn spawn_ax_observers(handler: Arc<DaemonHandler>) -> std::thread::JoinHandle<()> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    use crate::macos::observers::ObserverRegistry;
+
+    /// Set from the AX callback; drained by the observer thread. The
+    /// callback runs inside the run loop and must stay trivial — the
+    /// actual diffing happens in a reconcile pass on this thread.
+    static AX_DIRTY: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn on_ax_notification(
+        _observer: accessibility_sys::AXObserverRef,
+        _element: accessibility_sys::AXUIElementRef,
+        _notification: core_foundation::string::CFStringRef,
+        _refcon: *mut std::ffi::c_void,
+    ) {
+        AX_DIRTY.store(true, Ordering::Release);
+    }
+
+    std::thread::Builder::new()
+        .name("tillers-ax".into())
+        .spawn(move || {
+            let registry = Arc::new(Mutex::new(ObserverRegistry::new()));
+            {
+                let mut registry = registry.lock().unwrap();
+                for (pid, _name, bundle_id) in crate::macos::running_applications() {
+                    registry.watch(pid, bundle_id.as_deref().unwrap_or(""), on_ax_notification);
+                }
+            }
+            // Apps that refused observers get the targeted polling loop;
+            // its enumerations flow through the same reconcile path.
+            let polled = Arc::clone(&handler);
+            crate::macos::observers::spawn_polling_loop(Arc::clone(&registry), move |windows| {
+                polled.reconcile_with(&windows);
+            });
+
+            let mut last_upgrade_check = std::time::Instant::now();
+            loop {
+                // Service observer run-loop sources for up to a tick, then
+                // handle whatever the callbacks flagged.
+                core_foundation::runloop::CFRunLoop::run_in_mode(
+                    unsafe { core_foundation::runloop::kCFRunLoopDefaultMode },
+                    TICK_INTERVAL,
+                    false,
+                );
+                if AX_DIRTY.swap(false, Ordering::Acquire) {
+                    handler.reconcile_now();
+                }
+                if last_upgrade_check.elapsed() >= TICK_INTERVAL * 5 {
+                    last_upgrade_check = std::time::Instant::now();
+                    let mut registry = registry.lock().unwrap();
+                    registry.retry_upgrades(std::time::Instant::now(), on_ax_notification);
+                    for (pid, _name, bundle_id) in crate::macos::running_applications() {
+                        if registry.mode(pid).is_none() {
+                            registry.watch(
+                                pid,
+                                bundle_id.as_deref().unwrap_or(""),
+                                on_ax_notification,
+                            );
+                        }
+                    }
+                }
+            }
+        })
+        .expect("spawn AX observer thread")
+}
@@ -0,0 +1,149 @@
+//! Startup phase timing and deferred subsystem initialization.
+//!
+//! The daemon should be ready to tile well under [`TARGET_READY_MS`].
+//! Everything the first arrange does not need — tray, metrics, hooks,
+//! scripting — initializes after readiness on background threads, and each
+//! startup writes a phase timeline that `tillers diagnostics
+//! startup-timings` reports on.
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+
+/// Budget from process start to ready-to-tile, in milliseconds.
+pub const TARGET_READY_MS: u64 = 200;
+
+/// One timed startup phase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupPhase {
+    pub name: String,
+    /// Milliseconds after process start when the phase began.
+    pub started_ms: u64,
+    pub duration_ms: u64,
+    /// Phases that ran after ready-to-tile do not count against the budget.
+    pub deferred: bool,
+}
+
+/// Timeline for the most recent daemon startup.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StartupReport {
+    pub phases: Vec<StartupPhase>,
+    /// Milliseconds from process start to ready-to-tile.
+    pub ready_ms: Option<u64>,
+    pub target_ms: u64,
+}
+
+impl StartupReport {
+    pub fn within_budget(&self) -> Option<bool> {
+        self.ready_ms.map(|ms| ms <= self.target_ms)
+    }
+}
+
+/// Records phases during startup and persists the report on readiness.
+#[derive(Debug)]
+pub struct StartupTimeline {
+    origin: Instant,
+    report: StartupReport,
+    ready: bool,
+}
+
+impl Default for StartupTimeline {
+    fn default() -> Self {
+        StartupTimeline {
+            origin: Instant::now(),
+            report: StartupReport {
+                target_ms: TARGET_READY_MS,
+                ..StartupReport::default()
+            },
+            ready: false,
+        }
+    }
+}
+
+impl StartupTimeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time a startup phase. Phases after [`mark_ready`](Self::mark_ready)
+    /// are recorded as deferred.
+    pub fn time<T>(&mut self, name: &str, phase: impl FnOnce() -> T) -> T {
+        let started = self.origin.elapsed();
+        let value = phase();
+        self.report.phases.push(StartupPhase {
+            name: name.to_string(),
+            started_ms: started.as_millis() as u64,
+            duration_ms: (self.origin.elapsed() - started).as_millis() as u64,
+            deferred: self.ready,
+        });
+        value
+    }
+
+    /// Record ready-to-tile and persist the report for the diagnostics CLI.
+    pub fn mark_ready(&mut self) {
+        self.ready = true;
+        let ready_ms = self.origin.elapsed().as_millis() as u64;
+        self.report.ready_ms = Some(ready_ms);
+        if ready_ms > TARGET_READY_MS {
+            tracing::warn!(ready_ms, target_ms = TARGET_READY_MS, "startup over budget");
+        } else {
+            tracing::debug!(ready_ms, "daemon ready to tile");
+        }
+        if let Err(err) = self.save() {
+            tracing::warn!(%err, "failed to persist startup timings");
+        }
+    }
+
+    /// Where the last startup's report is stored.
+    pub fn report_path() -> PathBuf {
+        let home = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        home.join(".local")
+            .join("share")
+            .join("tillers")
+            .join("startup-timings.json")
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::report_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(&self.report)?)?;
+        Ok(())
+    }
+
+    pub fn report(&self) -> &StartupReport {
+        &self.report
+    }
+}
+
+/// Load the report persisted by the last daemon startup.
+pub fn load_last_report() -> Result<StartupReport> {
+    let raw = std::fs::read_to_string(StartupTimeline::report_path())?;
+    serde_json::from_str(&raw)
+        .map_err(|e| crate::errors::TilleRSError::Serialization(e.to_string()))
+}
+
+/// Initialize an optional subsystem off the startup path. The closure runs
+/// on its own thread after ready-to-tile; failures are logged, never fatal.
+pub fn spawn_deferred(name: &'static str, init: impl FnOnce() -> Result<()> + Send + 'static) {
+    std::thread::Builder::new()
+        .name(format!("tillers-init-{name}"))
+        .spawn(move || {
+            let started = Instant::now();
+            match init() {
+                Ok(()) => tracing::debug!(
+                    name,
+                    elapsed_ms = started.elapsed().as_millis() as u64,
+                    "deferred subsystem initialized"
+                ),
+                Err(err) => tracing::warn!(name, %err, "deferred subsystem failed to initialize"),
+            }
+        })
+        .expect("spawn deferred init thread");
+}
@@ -0,0 +1,118 @@
+//! Detection of other window managers and snapping tools.
+//!
+//! Two tiling engines fighting over the same windows produces flicker and
+//! runaway re-arrange loops, so we warn loudly and can optionally pause
+//! our own tiling while a conflicting tool is running.
+
+use serde::Serialize;
+
+/// Tools known to move or resize windows behind our back.
+///
+/// Matched against the bundle id (GUI apps) or process name (daemons like
+/// yabai that have no bundle).
+pub const KNOWN_CONFLICTS: &[ConflictSignature] = &[
+    ConflictSignature {
+        name: "yabai",
+        bundle_id: None,
+        process_name: Some("yabai"),
+    },
+    ConflictSignature {
+        name: "Amethyst",
+        bundle_id: Some("com.amethyst.Amethyst"),
+        process_name: None,
+    },
+    ConflictSignature {
+        name: "Rectangle",
+        bundle_id: Some("com.knollsoft.Rectangle"),
+        process_name: None,
+    },
+    ConflictSignature {
+        name: "Magnet",
+        bundle_id: Some("com.crowdcafe.windowmagnet"),
+        process_name: None,
+    },
+];
+
+/// How to recognize one conflicting tool.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ConflictSignature {
+    pub name: &'static str,
+    pub bundle_id: Option<&'static str>,
+    pub process_name: Option<&'static str>,
+}
+
+/// A conflicting tool observed running right now.
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectedConflict {
+    pub name: &'static str,
+    pub pid: i32,
+}
+
+/// Watches for conflicting window managers at startup and on app launches.
+#[derive(Debug, Default)]
+pub struct ConflictMonitor {
+    /// Conflicts already reported, to avoid repeating notifications.
+    reported: Vec<&'static str>,
+    /// When set, tiling is auto-paused while any conflict is active.
+    pub auto_pause: bool,
+}
+
+impl ConflictMonitor {
+    pub fn new(auto_pause: bool) -> Self {
+        ConflictMonitor {
+            reported: Vec::new(),
+            auto_pause,
+        }
+    }
+
+    /// Scan the process table for known conflicting tools.
+    pub fn scan(&self) -> Vec<DetectedConflict> {
+        let running = running_processes();
+        KNOWN_CONFLICTS
+            .iter()
+            .filter_map(|sig| {
+                running
+                    .iter()
+                    .find(|(_, name, bundle)| {
+                        sig.process_name.is_some_and(|p| p == name)
+                            || (sig.bundle_id.is_some() && sig.bundle_id == bundle.as_deref())
+                    })
+                    .map(|(pid, _, _)| DetectedConflict {
+                        name: sig.name,
+                        pid: *pid,
+                    })
+            })
+            .collect()
+    }
+
+    /// Scan and return only conflicts that have not been reported before;
+    /// the caller turns these into notifications and health warnings.
+    pub fn scan_new(&mut self) -> Vec<DetectedConflict> {
+        let conflicts = self.scan();
+        let new: Vec<DetectedConflict> = conflicts
+            .into_iter()
+            .filter(|c| !self.reported.contains(&c.name))
+            .collect();
+        for c in &new {
+            self.reported.push(c.name);
+        }
+        new
+    }
+
+    /// Whether tiling should currently be paused because of a conflict.
+    pub fn should_pause(&self) -> bool {
+        self.auto_pause && !self.scan().is_empty()
+    }
+}
+
+/// (pid, process name, bundle id) for every running process we can see.
+fn running_processes() -> Vec<(i32, String, Option<String>)> {
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::running_applications()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Vec::new()
+    }
+}
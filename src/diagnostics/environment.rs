@@ -0,0 +1,151 @@
+//! Checks for macOS settings known to conflict with tiling.
+
+use std::process::Command;
+
+use serde::Serialize;
+
+/// Outcome of a single environment check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    /// The setting could not be read (e.g. `defaults` key absent).
+    Unknown,
+}
+
+/// One inspected setting with an actionable suggestion.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    /// A `defaults write` (or System Settings) remedy, when one exists.
+    pub suggestion: Option<String>,
+}
+
+/// Run every environment check and return the results in display order.
+pub fn run_checks() -> Vec<EnvironmentCheck> {
+    vec![
+        check_separate_spaces(),
+        check_auto_rearrange_spaces(),
+        check_dock_magnification(),
+        check_stage_manager(),
+    ]
+}
+
+/// "Displays have separate Spaces" must be ON for per-display workspaces.
+fn check_separate_spaces() -> EnvironmentCheck {
+    match read_defaults_bool("com.apple.spaces", "spans-displays") {
+        // spans-displays=true means separate Spaces is OFF.
+        Some(true) => EnvironmentCheck {
+            name: "displays-have-separate-spaces",
+            status: CheckStatus::Warning,
+            message: "Displays do not have separate Spaces; multi-monitor \
+                      workspace assignment will not work correctly."
+                .into(),
+            suggestion: Some(
+                "defaults write com.apple.spaces spans-displays -bool false \
+                 && killall SystemUIServer"
+                    .into(),
+            ),
+        },
+        Some(false) => ok(
+            "displays-have-separate-spaces",
+            "Displays have separate Spaces.",
+        ),
+        None => unknown("displays-have-separate-spaces"),
+    }
+}
+
+/// Automatic Space rearrangement reorders workspaces under us.
+fn check_auto_rearrange_spaces() -> EnvironmentCheck {
+    match read_defaults_bool("com.apple.dock", "mru-spaces") {
+        Some(true) => EnvironmentCheck {
+            name: "auto-rearrange-spaces",
+            status: CheckStatus::Warning,
+            message: "macOS automatically rearranges Spaces by recent use; \
+                      workspace indices will drift."
+                .into(),
+            suggestion: Some(
+                "defaults write com.apple.dock mru-spaces -bool false && killall Dock".into(),
+            ),
+        },
+        Some(false) => ok(
+            "auto-rearrange-spaces",
+            "Automatic Space rearrangement is off.",
+        ),
+        None => unknown("auto-rearrange-spaces"),
+    }
+}
+
+/// Dock magnification enlarges the Dock's reserved area unpredictably,
+/// which changes the usable work area mid-layout.
+fn check_dock_magnification() -> EnvironmentCheck {
+    match read_defaults_bool("com.apple.dock", "magnification") {
+        Some(true) => EnvironmentCheck {
+            name: "dock-magnification",
+            status: CheckStatus::Warning,
+            message: "Dock magnification changes the reserved screen area on \
+                      hover, causing layout jitter near the Dock."
+                .into(),
+            suggestion: Some(
+                "defaults write com.apple.dock magnification -bool false && killall Dock".into(),
+            ),
+        },
+        Some(false) => ok("dock-magnification", "Dock magnification is off."),
+        None => unknown("dock-magnification"),
+    }
+}
+
+/// Stage Manager fights external tiling for window placement.
+fn check_stage_manager() -> EnvironmentCheck {
+    match read_defaults_bool("com.apple.WindowManager", "GloballyEnabled") {
+        Some(true) => EnvironmentCheck {
+            name: "stage-manager",
+            status: CheckStatus::Warning,
+            message: "Stage Manager is enabled and will fight TilleRS for \
+                      window placement."
+                .into(),
+            suggestion: Some(
+                "defaults write com.apple.WindowManager GloballyEnabled -bool false".into(),
+            ),
+        },
+        Some(false) => ok("stage-manager", "Stage Manager is disabled."),
+        None => unknown("stage-manager"),
+    }
+}
+
+fn ok(name: &'static str, message: &str) -> EnvironmentCheck {
+    EnvironmentCheck {
+        name,
+        status: CheckStatus::Ok,
+        message: message.to_string(),
+        suggestion: None,
+    }
+}
+
+fn unknown(name: &'static str) -> EnvironmentCheck {
+    EnvironmentCheck {
+        name,
+        status: CheckStatus::Unknown,
+        message: "Setting could not be read on this system.".to_string(),
+        suggestion: None,
+    }
+}
+
+/// Read a boolean from the `defaults` database; `None` when unreadable.
+fn read_defaults_bool(domain: &str, key: &str) -> Option<bool> {
+    let output = Command::new("defaults")
+        .args(["read", domain, key])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "1" | "true" | "YES" => Some(true),
+        "0" | "false" | "NO" => Some(false),
+        _ => None,
+    }
+}
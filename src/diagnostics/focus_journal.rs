@@ -0,0 +1,140 @@
+//! Journal of focus transitions, for debugging focus-stealing apps.
+//!
+//! The daemon appends one line per focus change to a JSONL file in the
+//! data directory, tagged with what triggered it. `tillers diagnostics
+//! focus-history` reads the file directly, so the history is inspectable
+//! even when the daemon is wedged — which is exactly when focus debugging
+//! happens.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+
+/// Journal size cap; the file is truncated to the newest half when the
+/// entry count doubles this.
+const MAX_ENTRIES: usize = 2048;
+
+/// What caused a focus change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusTrigger {
+    /// The user clicked or keyboard-navigated.
+    User,
+    /// TilleRS focused it (rule, workspace switch, focus action).
+    Tillers,
+    /// The application raised itself without user input — the
+    /// focus-steal signature.
+    AppInitiated,
+    /// Could not be attributed.
+    Unknown,
+}
+
+/// One focus transition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusEntry {
+    pub window: u32,
+    pub app_bundle_id: String,
+    pub workspace: String,
+    pub at: SystemTime,
+    pub trigger: FocusTrigger,
+}
+
+/// Append-only journal backed by a JSONL file.
+#[derive(Debug)]
+pub struct FocusJournal {
+    path: PathBuf,
+    entries_written: usize,
+}
+
+impl FocusJournal {
+    /// Default location: `~/.local/share/tillers/focus-journal.jsonl`.
+    pub fn default_path() -> PathBuf {
+        let home = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        home.join(".local")
+            .join("share")
+            .join("tillers")
+            .join("focus-journal.jsonl")
+    }
+
+    pub fn open_default() -> Result<Self> {
+        Self::open(Self::default_path())
+    }
+
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let entries_written = load(&path)?.len();
+        Ok(FocusJournal {
+            path,
+            entries_written,
+        })
+    }
+
+    /// Append one transition, compacting the file when it outgrows the cap.
+    pub fn record(&mut self, entry: &FocusEntry) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        self.entries_written += 1;
+        if self.entries_written > MAX_ENTRIES * 2 {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    fn compact(&mut self) -> Result<()> {
+        let entries = load(&self.path)?;
+        let keep = &entries[entries.len().saturating_sub(MAX_ENTRIES)..];
+        let mut out = String::new();
+        for entry in keep {
+            out.push_str(&serde_json::to_string(entry)?);
+            out.push('\n');
+        }
+        std::fs::write(&self.path, out)?;
+        self.entries_written = keep.len();
+        Ok(())
+    }
+}
+
+/// The newest `limit` entries from the journal file, oldest first.
+pub fn recent(limit: usize) -> Result<Vec<FocusEntry>> {
+    let mut entries = load(&FocusJournal::default_path())?;
+    let skip = entries.len().saturating_sub(limit);
+    entries.drain(..skip);
+    Ok(entries)
+}
+
+/// Bundle ids ranked by app-initiated focus grabs, worst first.
+pub fn offenders(entries: &[FocusEntry]) -> Vec<(String, usize)> {
+    let mut counts = std::collections::HashMap::new();
+    for entry in entries {
+        if entry.trigger == FocusTrigger::AppInitiated {
+            *counts.entry(entry.app_bundle_id.clone()).or_insert(0) += 1;
+        }
+    }
+    let mut ranked: Vec<_> = counts.into_iter().collect();
+    ranked.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    ranked
+}
+
+fn load(path: &std::path::Path) -> Result<Vec<FocusEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(path)?;
+    // Tolerate a torn final line from a crash mid-append.
+    Ok(raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
@@ -0,0 +1,8 @@
+//! Diagnostics: environment checks, health reports, and debugging tools.
+
+pub mod conflicts;
+pub mod environment;
+pub mod focus_journal;
+pub mod permissions;
+pub mod replay;
+pub mod usage;
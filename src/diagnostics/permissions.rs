@@ -0,0 +1,183 @@
+//! Interactive permission recovery.
+//!
+//! Printing "open System Settings and enable TilleRS" and exiting leaves
+//! the user to find the right pane themselves and restart manually. The
+//! assistant opens the exact Privacy & Security pane, polls TCC for the
+//! grant, and continues automatically the moment it lands; the step it is
+//! stuck on is tracked so health status can show *where* onboarding
+//! stalled, not just that it did. Non-interactive contexts (no TTY, logs)
+//! still get the plain-text instructions.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::errors::{Result, TilleRSError};
+
+/// How often the grant is re-probed while waiting.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long to wait for a grant before giving up.
+pub const GRANT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The macOS permissions TilleRS depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MacPermission {
+    /// Required: window control via AX.
+    Accessibility,
+    /// Optional: window thumbnails in the tray and overview.
+    ScreenRecording,
+}
+
+impl MacPermission {
+    /// Deep link to the exact System Settings pane.
+    pub fn settings_url(&self) -> &'static str {
+        match self {
+            MacPermission::Accessibility => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility"
+            }
+            MacPermission::ScreenRecording => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+            }
+        }
+    }
+
+    /// Whether the permission is currently granted.
+    pub fn granted(&self) -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            match self {
+                MacPermission::Accessibility => crate::macos::accessibility_trusted(),
+                MacPermission::ScreenRecording => crate::macos::capture::screen_recording_granted(),
+            }
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            false
+        }
+    }
+
+    /// The plain-text instructions, for logs and non-interactive runs.
+    pub fn instructions(&self) -> String {
+        match self {
+            MacPermission::Accessibility => {
+                crate::i18n::t("permission-accessibility-required").to_string()
+            }
+            MacPermission::ScreenRecording => {
+                crate::i18n::t("permission-screen-recording-optional").to_string()
+            }
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MacPermission::Accessibility => "Accessibility",
+            MacPermission::ScreenRecording => "Screen Recording",
+        }
+    }
+}
+
+/// Where the user is in the recovery flow; surfaced in health status so a
+/// stalled onboarding is diagnosable ("stuck with the pane open for ten
+/// minutes" reads very differently from "never opened it").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryStep {
+    NotStarted,
+    /// The Settings pane was opened; waiting for the user to act.
+    SettingsOpened,
+    /// Polling TCC for the grant.
+    WaitingForGrant,
+    Granted,
+    /// The grant never arrived within the timeout.
+    TimedOut,
+    /// The pane could not be opened; fell back to printed instructions.
+    OpenFailed,
+}
+
+/// Walks one permission through prompt → open pane → poll → continue.
+#[derive(Debug)]
+pub struct RecoveryAssistant {
+    permission: MacPermission,
+    step: RecoveryStep,
+}
+
+impl RecoveryAssistant {
+    pub fn new(permission: MacPermission) -> Self {
+        RecoveryAssistant {
+            permission,
+            step: RecoveryStep::NotStarted,
+        }
+    }
+
+    /// The step the flow is currently on.
+    pub fn step(&self) -> RecoveryStep {
+        self.step
+    }
+
+    pub fn permission(&self) -> MacPermission {
+        self.permission
+    }
+
+    /// Run the interactive flow on the terminal: print what is missing,
+    /// open the right pane, poll until granted or `timeout`. Returns
+    /// whether the permission is granted when the flow ends.
+    pub fn run(&mut self, timeout: Duration) -> Result<bool> {
+        if self.permission.granted() {
+            self.step = RecoveryStep::Granted;
+            return Ok(true);
+        }
+        println!("{}", self.permission.instructions());
+
+        match open_settings_pane(self.permission.settings_url()) {
+            Ok(()) => {
+                self.step = RecoveryStep::SettingsOpened;
+                println!(
+                    "Opened System Settings > Privacy & Security > {}.",
+                    self.permission.label()
+                );
+            }
+            Err(err) => {
+                // Keep going: the user can navigate there by hand while
+                // we poll.
+                self.step = RecoveryStep::OpenFailed;
+                tracing::warn!(%err, "could not open System Settings");
+            }
+        }
+
+        println!(
+            "Waiting for the {} grant (up to {}s); enable TilleRS in the list...",
+            self.permission.label(),
+            timeout.as_secs()
+        );
+        self.step = RecoveryStep::WaitingForGrant;
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if self.permission.granted() {
+                self.step = RecoveryStep::Granted;
+                println!("{} granted — continuing.", self.permission.label());
+                return Ok(true);
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        self.step = RecoveryStep::TimedOut;
+        println!(
+            "Still not granted after {}s. Grant it and run `tillers diagnostics permissions` \
+             again.",
+            timeout.as_secs()
+        );
+        Ok(false)
+    }
+}
+
+/// Open a System Settings deep link via `open(1)`.
+fn open_settings_pane(url: &str) -> Result<()> {
+    let status = std::process::Command::new("open").arg(url).status()?;
+    if !status.success() {
+        return Err(TilleRSError::Validation(format!(
+            "`open {url}` exited with {status}"
+        )));
+    }
+    Ok(())
+}
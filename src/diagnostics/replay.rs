@@ -0,0 +1,260 @@
+//! Event-journal replay against the simulation backend.
+//!
+//! A bug report is most useful as the event sequence that produced it.
+//! Replay feeds a recorded journal (JSON lines, one event per line)
+//! through the orchestrator and workspace manager running on the headless
+//! [`SimulatedWindowSystem`], then reports the resulting state plus any
+//! invariant violations — the same failure reproduces deterministically
+//! on any machine, with no windows harmed.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+use crate::events::EventBus;
+use crate::models::display::DisplayInfo;
+use crate::models::{Rect, WindowId, WindowInfo, Workspace};
+use crate::tiling::TilingEngine;
+use crate::window_system::scene::Scene;
+use crate::window_system::simulation::SimulatedWindowSystem;
+use crate::window_system::WindowSystem;
+use crate::workspace::{WorkspaceManager, WorkspaceOrchestrator};
+
+/// One journaled event, the serialized mirror of the bus's event types.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum JournalEvent {
+    WindowCreated { window: WindowInfo },
+    WindowDestroyed { window: WindowId },
+    WindowMoved { window: WindowId, frame: Rect },
+    WorkspaceCreated { name: String },
+    WorkspaceActivated { name: String },
+    WorkspaceRemoved { name: String },
+}
+
+/// An invariant that did not hold after applying one event.
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    /// Zero-based index of the journal line that broke the invariant.
+    pub event_index: usize,
+    pub message: String,
+}
+
+/// What the replay produced.
+#[derive(Debug, Serialize)]
+pub struct ReplayReport {
+    /// Events applied from the journal.
+    pub events: usize,
+    /// Mutations the arrange passes issued against the backend.
+    pub operations: usize,
+    pub workspaces: Vec<String>,
+    pub active: Option<String>,
+    pub windows: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl ReplayReport {
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Load a journal file; torn final lines (a crash mid-write) are skipped,
+/// matching the focus journal's tolerance.
+pub fn load_journal(path: impl AsRef<Path>) -> Result<Vec<JournalEvent>> {
+    let raw = std::fs::read_to_string(path)?;
+    Ok(raw
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Replay `journal` against a fresh simulation seeded from `scene` (or a
+/// single synthetic 1920x1080 display when none is given).
+pub fn replay(journal: &[JournalEvent], scene: Option<Scene>) -> Result<ReplayReport> {
+    let scene =
+        scene.unwrap_or_else(|| Scene::new(vec![synthetic_display()], Vec::new(), Vec::new()));
+    let mut system = SimulatedWindowSystem::from_scene(scene);
+    let mut manager = WorkspaceManager::new(EventBus::new());
+    let mut orchestrator = WorkspaceOrchestrator::new();
+    let engine = TilingEngine::new();
+    let mut violations = Vec::new();
+
+    for (index, event) in journal.iter().enumerate() {
+        if let Err(message) = apply(event, &mut system, &mut manager, &mut orchestrator, &engine) {
+            violations.push(Violation {
+                event_index: index,
+                message,
+            });
+        }
+        check_invariants(index, &system, &manager, &orchestrator, &mut violations)?;
+    }
+
+    Ok(ReplayReport {
+        events: journal.len(),
+        operations: system.operations.len(),
+        workspaces: manager
+            .workspaces()
+            .iter()
+            .map(|w| w.name.clone())
+            .collect(),
+        active: manager.active().map(str::to_string),
+        windows: system.windows()?.len(),
+        violations,
+    })
+}
+
+/// Apply one event the way the daemon's event loop would; the error
+/// string becomes a violation rather than aborting the replay, so one bad
+/// event still shows everything downstream of it.
+fn apply(
+    event: &JournalEvent,
+    system: &mut SimulatedWindowSystem,
+    manager: &mut WorkspaceManager,
+    orchestrator: &mut WorkspaceOrchestrator,
+    engine: &TilingEngine,
+) -> std::result::Result<(), String> {
+    match event {
+        JournalEvent::WindowCreated { window } => {
+            system.add_window(window.clone());
+            arrange_active(system, manager, orchestrator, engine)
+        }
+        JournalEvent::WindowDestroyed { window } => {
+            system.remove_window(*window);
+            arrange_active(system, manager, orchestrator, engine)
+        }
+        JournalEvent::WindowMoved { window, frame } => system
+            .set_window_frame(*window, *frame)
+            .map_err(|e| e.to_string()),
+        JournalEvent::WorkspaceCreated { name } => manager
+            .create(Workspace::new(name.clone()))
+            .map_err(|e| e.to_string()),
+        JournalEvent::WorkspaceActivated { name } => {
+            manager.activate(name).map_err(|e| e.to_string())?;
+            arrange_active(system, manager, orchestrator, engine)
+        }
+        JournalEvent::WorkspaceRemoved { name } => {
+            manager.remove(name).map_err(|e| e.to_string())?;
+            Ok(())
+        }
+    }
+}
+
+/// Run one arrange pass for the active workspace through the orchestrator,
+/// exactly as the daemon would, so transition errors reproduce too.
+fn arrange_active(
+    system: &mut SimulatedWindowSystem,
+    manager: &WorkspaceManager,
+    orchestrator: &mut WorkspaceOrchestrator,
+    engine: &TilingEngine,
+) -> std::result::Result<(), String> {
+    let Some(active) = manager.active() else {
+        return Ok(());
+    };
+    let Some(workspace) = manager.get(active) else {
+        return Ok(());
+    };
+    let displays = system.displays().map_err(|e| e.to_string())?;
+    let Some(display) = displays.first() else {
+        return Ok(());
+    };
+    let tiled: Vec<WindowId> = system
+        .windows()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .filter(|w| w.workspace == workspace.name && !w.floating && !w.minimized)
+        .map(|w| w.id)
+        .collect();
+    if tiled.is_empty() {
+        return Ok(());
+    }
+
+    let token = orchestrator
+        .begin_arrange(&workspace.name)
+        .map_err(|e| e.to_string())?;
+    let frames = engine.compute_frames_with_splits(
+        workspace.layout,
+        display.work_area,
+        tiled.len(),
+        &workspace.splits,
+    );
+    let mut first_error = None;
+    for (window, frame) in tiled.iter().zip(frames) {
+        if token.is_cancelled() {
+            break;
+        }
+        if let Err(err) = system.set_window_frame(*window, frame) {
+            first_error.get_or_insert(err.to_string());
+        }
+    }
+    orchestrator.finish_arrange().map_err(|e| e.to_string())?;
+    match first_error {
+        Some(message) => Err(message),
+        None => Ok(()),
+    }
+}
+
+/// Invariants every event must leave intact.
+fn check_invariants(
+    index: usize,
+    system: &SimulatedWindowSystem,
+    manager: &WorkspaceManager,
+    orchestrator: &WorkspaceOrchestrator,
+    violations: &mut Vec<Violation>,
+) -> Result<()> {
+    let mut violate = |message: String| {
+        violations.push(Violation {
+            event_index: index,
+            message,
+        });
+    };
+
+    if !orchestrator.is_idle() {
+        violate(format!(
+            "orchestrator stuck in state '{}' between events",
+            orchestrator.state()
+        ));
+    }
+    if let Some(active) = manager.active() {
+        if manager.get(active).is_none() {
+            violate(format!("active workspace '{active}' does not exist"));
+        }
+    }
+    for window in system.windows()? {
+        if window.frame.width < 0.0 || window.frame.height < 0.0 {
+            violate(format!(
+                "window {} has a negative-sized frame {:?}",
+                window.id, window.frame
+            ));
+        }
+        if !window.workspace.is_empty() && manager.get(&window.workspace).is_none() {
+            violate(format!(
+                "window {} is assigned to unknown workspace '{}'",
+                window.id, window.workspace
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The stand-in display used when no scene accompanies the journal.
+fn synthetic_display() -> DisplayInfo {
+    DisplayInfo {
+        id: 1,
+        name: "main".into(),
+        frame: Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 1920.0,
+            height: 1080.0,
+        },
+        work_area: Rect {
+            x: 0.0,
+            y: 25.0,
+            width: 1920.0,
+            height: 1055.0,
+        },
+    }
+}
@@ -0,0 +1,75 @@
+//! Workspace usage reports from the statistics store.
+
+use serde::Serialize;
+
+use crate::stats::{StatisticsStore, WorkspaceUsage};
+
+/// Shade characters from empty to busiest bucket.
+const SHADES: &[char] = &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// JSON form of one workspace's report.
+#[derive(Debug, Serialize)]
+pub struct UsageReport<'a> {
+    pub workspace: &'a str,
+    pub total_seconds: u64,
+    /// Seconds per (weekday, hour).
+    pub seconds: &'a [Vec<u64>],
+    /// Top applications by focused seconds, descending.
+    pub top_apps: Vec<(&'a str, u64)>,
+}
+
+/// Build reports for every workspace, busiest first.
+pub fn build_reports(store: &StatisticsStore) -> Vec<UsageReport<'_>> {
+    let mut reports: Vec<UsageReport<'_>> = store
+        .workspaces()
+        .iter()
+        .map(|(name, usage)| {
+            let mut top_apps: Vec<(&str, u64)> = usage
+                .apps
+                .iter()
+                .map(|(app, secs)| (app.as_str(), *secs))
+                .collect();
+            top_apps.sort_by_key(|(_, secs)| std::cmp::Reverse(*secs));
+            top_apps.truncate(5);
+            UsageReport {
+                workspace: name,
+                total_seconds: usage.total_seconds(),
+                seconds: &usage.seconds,
+                top_apps,
+            }
+        })
+        .collect();
+    reports.sort_by_key(|report| std::cmp::Reverse(report.total_seconds));
+    reports
+}
+
+/// Render one workspace's weekday × hour heatmap as ASCII.
+pub fn render_heatmap(workspace: &str, usage: &WorkspaceUsage) -> String {
+    let max = usage
+        .seconds
+        .iter()
+        .flatten()
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{workspace} — {}h total\n",
+        usage.total_seconds() / 3600
+    ));
+    out.push_str("     0         6         12        18      23\n");
+    for (day, row) in usage.seconds.iter().enumerate() {
+        out.push_str(&format!("{} |", WEEKDAYS[day]));
+        for &secs in row {
+            let idx = ((secs * (SHADES.len() as u64 - 1)) / max) as usize;
+            out.push(SHADES[idx]);
+            out.push(' ');
+        }
+        out.push_str("|\n");
+    }
+    out
+}
@@ -0,0 +1,211 @@
+//! Crate-wide error type.
+//!
+//! Every fallible public API in TilleRS returns [`Result`] so callers only
+//! ever have to deal with a single error enum.
+
+use thiserror::Error;
+
+/// The AXError codes TilleRS reacts to, decoded from the raw `i32` at the
+/// call site so nothing downstream string-matches error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxErrorCode {
+    /// `kAXErrorCannotComplete` (-25204): the app is busy or not
+    /// responding; usually transient.
+    CannotComplete,
+    /// `kAXErrorNotImplemented` (-25208): the app does not implement this
+    /// operation at all; retrying is pointless.
+    NotImplemented,
+    /// `kAXErrorInvalidUIElement` (-25202): the element is stale — the
+    /// window closed or the app relaunched.
+    InvalidUIElement,
+    /// `kAXErrorAPIDisabled` (-25211): Accessibility trust was revoked.
+    ApiDisabled,
+    /// Any other code, kept verbatim for diagnostics.
+    Other(i32),
+}
+
+/// What a failed AX call should do next, derived from the code instead of
+/// guessed from message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxRecovery {
+    /// Transient; retry with backoff.
+    Retry,
+    /// The app will never support this operation; record it in the app's
+    /// capability profile and stop trying.
+    RecordIncapable,
+    /// The element is gone; drop it from the model and re-enumerate.
+    RefreshElement,
+    /// Permission lost; tiling must stop until it is re-granted.
+    PermissionLost,
+}
+
+impl AxErrorCode {
+    /// Decode a raw AXError return value.
+    pub fn from_raw(code: i32) -> Self {
+        match code {
+            -25204 => AxErrorCode::CannotComplete,
+            -25208 => AxErrorCode::NotImplemented,
+            -25202 => AxErrorCode::InvalidUIElement,
+            -25211 => AxErrorCode::ApiDisabled,
+            other => AxErrorCode::Other(other),
+        }
+    }
+
+    /// The recovery classification retry logic branches on.
+    pub fn recovery(&self) -> AxRecovery {
+        match self {
+            AxErrorCode::CannotComplete | AxErrorCode::Other(_) => AxRecovery::Retry,
+            AxErrorCode::NotImplemented => AxRecovery::RecordIncapable,
+            AxErrorCode::InvalidUIElement => AxRecovery::RefreshElement,
+            AxErrorCode::ApiDisabled => AxRecovery::PermissionLost,
+        }
+    }
+}
+
+impl std::fmt::Display for AxErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AxErrorCode::CannotComplete => f.write_str("cannotComplete"),
+            AxErrorCode::NotImplemented => f.write_str("notImplemented"),
+            AxErrorCode::InvalidUIElement => f.write_str("invalidUIElement"),
+            AxErrorCode::ApiDisabled => f.write_str("apiDisabled"),
+            AxErrorCode::Other(code) => write!(f, "AXError {code}"),
+        }
+    }
+}
+
+/// Unified error type for all TilleRS subsystems.
+#[derive(Debug, Error)]
+pub enum TilleRSError {
+    /// Configuration could not be read, parsed, or validated.
+    #[error("configuration error: {0}")]
+    Config(String),
+
+    /// Configuration failed to parse, with file/line/column detail.
+    #[error("{0}")]
+    ConfigParse(#[from] crate::config::ConfigParseError),
+
+    /// A required macOS permission (Accessibility, Screen Recording) is
+    /// missing or was revoked.
+    #[error("permission error: {0}")]
+    Permission(String),
+
+    /// A named entity (rule, workspace, window, ...) was not found.
+    #[error("{kind} not found: {name}")]
+    NotFound { kind: &'static str, name: String },
+
+    /// The daemon is not running or its socket is unreachable.
+    #[error("daemon unavailable: {0}")]
+    IpcUnavailable(String),
+
+    /// An Accessibility call failed with a concrete AXError code; the
+    /// code's [`recovery`](AxErrorCode::recovery) drives retry decisions.
+    #[error("AX {op} failed for window {window}: {code}")]
+    Ax {
+        op: &'static str,
+        window: u32,
+        code: AxErrorCode,
+    },
+
+    /// The daemon refused or failed a request the CLI sent over IPC.
+    /// `code` is the daemon-side [`code_name`](Self::code_name), so the
+    /// original error class survives the wire.
+    #[error("daemon: {message}")]
+    Remote { code: String, message: String },
+
+    /// User input failed validation before any side effect took place.
+    #[error("validation error: {0}")]
+    Validation(String),
+
+    /// Underlying I/O failure.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// (De)serialization failure (TOML or JSON).
+    #[error("serialization error: {0}")]
+    Serialization(String),
+}
+
+impl TilleRSError {
+    /// Stable process exit code for this error class. Scripts branch on
+    /// these, so changing an assignment is a breaking CLI change.
+    ///
+    /// - 10: permission missing or revoked
+    /// - 20: entity not found
+    /// - 30: invalid input or configuration
+    /// - 40: daemon unreachable
+    /// - 1: everything else (I/O, serialization, AX failures)
+    ///
+    /// An AX failure with `apiDisabled` counts as a permission error:
+    /// Accessibility trust was revoked mid-session.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            TilleRSError::Permission(_)
+            | TilleRSError::Ax {
+                code: AxErrorCode::ApiDisabled,
+                ..
+            } => 10,
+            TilleRSError::Ax { .. } => 1,
+            TilleRSError::NotFound { .. } => 20,
+            TilleRSError::Config(_)
+            | TilleRSError::ConfigParse(_)
+            | TilleRSError::Validation(_) => 30,
+            TilleRSError::IpcUnavailable(_) => 40,
+            // A remote error keeps the exit code of its original class.
+            TilleRSError::Remote { code, .. } => match code.as_str() {
+                "permission" => 10,
+                "not-found" => 20,
+                "validation" => 30,
+                _ => 1,
+            },
+            TilleRSError::Io(_) | TilleRSError::Serialization(_) => 1,
+        }
+    }
+
+    /// Machine-readable error class, carried as `code` in `--json` error
+    /// output alongside the numeric exit code.
+    pub fn code_name(&self) -> &'static str {
+        match self {
+            TilleRSError::Permission(_)
+            | TilleRSError::Ax {
+                code: AxErrorCode::ApiDisabled,
+                ..
+            } => "permission",
+            TilleRSError::Ax { .. } => "ax",
+            TilleRSError::NotFound { .. } => "not-found",
+            TilleRSError::Config(_)
+            | TilleRSError::ConfigParse(_)
+            | TilleRSError::Validation(_) => "validation",
+            TilleRSError::IpcUnavailable(_) => "ipc-unavailable",
+            TilleRSError::Remote { code, .. } => match code.as_str() {
+                "permission" => "permission",
+                "not-found" => "not-found",
+                "validation" => "validation",
+                _ => "remote",
+            },
+            TilleRSError::Io(_) => "io",
+            TilleRSError::Serialization(_) => "serialization",
+        }
+    }
+}
+
+/// Convenience alias used throughout the crate.
+pub type Result<T> = std::result::Result<T, TilleRSError>;
+
+impl From<toml::de::Error> for TilleRSError {
+    fn from(err: toml::de::Error) -> Self {
+        TilleRSError::Serialization(err.to_string())
+    }
+}
+
+impl From<toml::ser::Error> for TilleRSError {
+    fn from(err: toml::ser::Error) -> Self {
+        TilleRSError::Serialization(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for TilleRSError {
+    fn from(err: serde_json::Error) -> Self {
+        TilleRSError::Serialization(err.to_string())
+    }
+}
@@ -0,0 +1,186 @@
+//! Typed broadcast event bus.
+//!
+//! Subsystems used to register boxed closures on the managers, which made
+//! delivery order and backpressure opaque. Everything now flows through
+//! one broadcast bus with typed events; subscribers are independent, can
+//! lag without blocking publishers, and their lag is measurable.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+
+use crate::models::display::DisplayId;
+use crate::models::{Rect, WindowId, WindowInfo};
+
+/// Default bus capacity; slow subscribers older than this many events see
+/// lag instead of stalling the daemon.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone)]
+pub enum WorkspaceEvent {
+    Activated {
+        name: String,
+    },
+    Created {
+        name: String,
+    },
+    Removed {
+        name: String,
+    },
+    ArrangeCompleted {
+        name: String,
+        windows: usize,
+    },
+    /// A workspace group became the target of the numeric shortcuts.
+    GroupActivated {
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum WindowEvent {
+    Created(WindowInfo),
+    Destroyed(WindowId),
+    Focused(WindowId),
+    Moved { window: WindowId, frame: Rect },
+    TitleChanged { window: WindowId, title: String },
+}
+
+#[derive(Debug, Clone)]
+pub enum DisplayEvent {
+    Attached(DisplayId),
+    Detached(DisplayId),
+    TopologyChanged,
+    /// The laptop lid closed or reopened while externals stay attached;
+    /// always accompanied by the underlying attach/detach events.
+    ClamshellChanged {
+        closed: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum KeyboardEvent {
+    /// A bound shortcut fired.
+    ShortcutDispatched { mapping: String },
+    /// The bare workspace modifier crossed the hold threshold or was
+    /// released; the event loop shows or hides the quick-switch HUD.
+    WorkspaceModifierHeld { held: bool },
+}
+
+#[derive(Debug, Clone)]
+pub enum DaemonEvent {
+    /// The config was re-read and swapped in. Subscribers rebuild what
+    /// they derive from it — the keymap table, AX observer registrations,
+    /// the current arrangement — while keeping their runtime state
+    /// (window model, visibility snapshots, focus history).
+    ConfigReloaded,
+}
+
+/// Every event the daemon publishes.
+#[derive(Debug, Clone)]
+pub enum Event {
+    Workspace(WorkspaceEvent),
+    Window(WindowEvent),
+    Display(DisplayEvent),
+    Keyboard(KeyboardEvent),
+    Daemon(DaemonEvent),
+}
+
+/// The bus; cheap to clone, one per daemon.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Event>,
+    /// Total events dropped across all subscribers due to lag.
+    lagged_total: Arc<AtomicU64>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        EventBus {
+            sender,
+            lagged_total: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Publish an event to all current subscribers. Publishing never
+    /// blocks; with no subscribers the event is dropped.
+    pub fn publish(&self, event: Event) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> EventSubscriber {
+        EventSubscriber {
+            receiver: self.sender.subscribe(),
+            lagged: 0,
+            lagged_total: Arc::clone(&self.lagged_total),
+        }
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Events dropped bus-wide because subscribers fell behind.
+    pub fn lagged_total(&self) -> u64 {
+        self.lagged_total.load(Ordering::Relaxed)
+    }
+}
+
+/// One subscriber's view of the bus, with its own lag accounting.
+pub struct EventSubscriber {
+    receiver: broadcast::Receiver<Event>,
+    lagged: u64,
+    lagged_total: Arc<AtomicU64>,
+}
+
+impl EventSubscriber {
+    /// Receive the next event, transparently absorbing lag: skipped events
+    /// are counted, not surfaced as errors.
+    pub async fn recv(&mut self) -> Option<Event> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    self.lagged += skipped;
+                    self.lagged_total.fetch_add(skipped, Ordering::Relaxed);
+                    tracing::warn!(skipped, "event subscriber lagging");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Blocking variant of [`recv`](Self::recv) for dedicated threads
+    /// (the daemon's event loop, hook runner, deck pushers). Must not be
+    /// called from async context.
+    pub fn blocking_recv(&mut self) -> Option<Event> {
+        loop {
+            match self.receiver.blocking_recv() {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    self.lagged += skipped;
+                    self.lagged_total.fetch_add(skipped, Ordering::Relaxed);
+                    tracing::warn!(skipped, "event subscriber lagging");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Events this subscriber missed so far.
+    pub fn lagged(&self) -> u64 {
+        self.lagged
+    }
+}
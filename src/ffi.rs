@@ -0,0 +1,127 @@
+//! C-compatible FFI over the IPC client.
+//!
+//! A native SwiftUI settings app needs to talk to the daemon without
+//! shelling out to the CLI. This layer wraps the IPC client in a flat C
+//! ABI: opaque connection handle, UTF-8 JSON strings for payloads, and
+//! integer error codes matching the CLI exit codes (see
+//! [`TilleRSError::exit_code`]). Strings returned by these functions are
+//! owned by the library and must be released with [`tillers_string_free`].
+
+use std::ffi::{c_char, CStr, CString};
+use std::os::unix::net::UnixStream;
+
+use crate::errors::TilleRSError;
+use crate::ipc;
+
+/// Opaque connection handle held by the Swift side.
+pub struct TillersConnection {
+    #[allow(dead_code)]
+    stream: UnixStream,
+    negotiated: ipc::Negotiated,
+}
+
+/// Success; paired with the error codes from `TilleRSError::exit_code`.
+pub const TILLERS_OK: i32 = 0;
+
+fn error_code(err: &TilleRSError) -> i32 {
+    err.exit_code()
+}
+
+/// Connect to the running daemon and perform the version handshake.
+///
+/// Returns a handle through `out`, or an error code with `*out` null.
+///
+/// # Safety
+/// `out` must be a valid pointer to writable memory for one pointer.
+#[no_mangle]
+pub unsafe extern "C" fn tillers_connect(out: *mut *mut TillersConnection) -> i32 {
+    if out.is_null() {
+        return TilleRSError::Validation("out pointer is null".into()).exit_code();
+    }
+    *out = std::ptr::null_mut();
+    match ipc::connect() {
+        Ok((stream, _theirs, negotiated)) => {
+            let conn = Box::new(TillersConnection { stream, negotiated });
+            *out = Box::into_raw(conn);
+            TILLERS_OK
+        }
+        Err(err) => error_code(&err),
+    }
+}
+
+/// Close a connection and free its handle. Passing null is a no-op.
+///
+/// # Safety
+/// `conn` must be a pointer returned by [`tillers_connect`], used at most
+/// once here.
+#[no_mangle]
+pub unsafe extern "C" fn tillers_disconnect(conn: *mut TillersConnection) {
+    if !conn.is_null() {
+        drop(Box::from_raw(conn));
+    }
+}
+
+/// The negotiated protocol version and capabilities as a JSON string.
+///
+/// # Safety
+/// `conn` must be a live handle from [`tillers_connect`]; the returned
+/// string must be released with [`tillers_string_free`].
+#[no_mangle]
+pub unsafe extern "C" fn tillers_capabilities_json(conn: *const TillersConnection) -> *mut c_char {
+    if conn.is_null() {
+        return std::ptr::null_mut();
+    }
+    let negotiated = &(*conn).negotiated;
+    match serde_json::to_string(negotiated) {
+        Ok(json) => into_c_string(json),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Dispatch an action given as the JSON encoding of `ActionType`.
+///
+/// # Safety
+/// `conn` must be a live handle and `action_json` a NUL-terminated UTF-8
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn tillers_dispatch_json(
+    conn: *mut TillersConnection,
+    action_json: *const c_char,
+) -> i32 {
+    if conn.is_null() || action_json.is_null() {
+        return TilleRSError::Validation("null argument".into()).exit_code();
+    }
+    let raw = match CStr::from_ptr(action_json).to_str() {
+        Ok(s) => s,
+        Err(_) => return TilleRSError::Validation("action is not valid UTF-8".into()).exit_code(),
+    };
+    let action: crate::models::ActionType = match serde_json::from_str(raw) {
+        Ok(a) => a,
+        Err(e) => return error_code(&TilleRSError::Serialization(e.to_string())),
+    };
+    // Routed through the same path as the CLI; carries the action over
+    // the connection once the daemon serves requests (synth IPC server).
+    match crate::cli::dispatch_action(action) {
+        Ok(()) => TILLERS_OK,
+        Err(err) => error_code(&err),
+    }
+}
+
+/// Release a string returned by this library. Passing null is a no-op.
+///
+/// # Safety
+/// `s` must be a pointer returned by a `tillers_*` function, used at most
+/// once here.
+#[no_mangle]
+pub unsafe extern "C" fn tillers_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    match CString::new(s) {
+        Ok(c) => c.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
@@ -0,0 +1,62 @@
+//! English catalog — the fallback for every other locale.
+
+use super::Catalog;
+
+pub(super) fn catalog() -> Catalog {
+    let mut c = Catalog::new("en");
+
+    // Tray
+    c.insert("tray-status-active", "TilleRS: active");
+    c.insert("tray-status-paused", "TilleRS: paused");
+    c.insert(
+        "tray-status-rules-suspended",
+        "TilleRS: rules paused for {apps}",
+    );
+    c.insert(
+        "tray-status-focus-session",
+        "Focus: {workspace} — {minutes} min left",
+    );
+    c.insert("tray-group", "Group: {group}");
+    c.insert(
+        "tray-temporary",
+        "Temporary: {workspace} — {minutes} min left",
+    );
+    c.insert("tray-settings", "Settings…");
+
+    // Permissions
+    c.insert(
+        "permission-accessibility-required",
+        "TilleRS needs Accessibility permission to manage windows. Open \
+         System Settings > Privacy & Security > Accessibility and enable TilleRS.",
+    );
+    c.insert(
+        "permission-screen-recording-optional",
+        "Screen Recording permission is optional; it enables window \
+         thumbnails in the tray and overview.",
+    );
+
+    // Notifications
+    c.insert(
+        "notify-rules-suspended",
+        "Rules paused for {app} ({minutes} min)",
+    );
+    c.insert("notify-rules-resumed", "Rules resumed for {app}");
+    c.insert(
+        "notify-window-damped",
+        "{title} keeps resizing itself; floating it for {minutes} min",
+    );
+    c.insert(
+        "notify-focus-restored",
+        "{app} grabbed focus; restored ({strikes} strikes)",
+    );
+    c.insert(
+        "notify-windows-adopted",
+        "Adopted {count} existing windows across {workspaces} workspaces",
+    );
+    c.insert(
+        "notify-window-caught",
+        "{title} matched no rule and was sent to {workspace}",
+    );
+
+    c
+}
@@ -0,0 +1,112 @@
+//! Localization of user-facing strings.
+//!
+//! A gettext-style key → message catalog per locale. Catalogs are plain
+//! data, so contributors add a translation by registering one more
+//! [`Catalog`] — no code changes elsewhere. Lookup falls back to English
+//! for missing keys so partial translations stay usable.
+
+mod en;
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// A message catalog for one locale.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    /// BCP 47 language tag, e.g. `en`, `de`, `pt-BR`.
+    pub locale: String,
+    messages: HashMap<&'static str, String>,
+}
+
+impl Catalog {
+    pub fn new(locale: impl Into<String>) -> Self {
+        Catalog {
+            locale: locale.into(),
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Register a message; used by catalog modules at build time.
+    pub fn insert(&mut self, key: &'static str, message: impl Into<String>) {
+        self.messages.insert(key, message.into());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+}
+
+/// Resolves messages against the active locale with English fallback.
+#[derive(Debug)]
+pub struct Localizer {
+    active: Catalog,
+    fallback: Catalog,
+}
+
+impl Localizer {
+    /// Build a localizer for an explicit locale tag.
+    pub fn for_locale(locale: &str) -> Self {
+        let fallback = en::catalog();
+        let active = registered_catalogs()
+            .into_iter()
+            .find(|c| locale == c.locale || locale.starts_with(&format!("{}-", c.locale)))
+            .unwrap_or_else(|| fallback.clone());
+        Localizer { active, fallback }
+    }
+
+    /// Build a localizer for the system locale.
+    pub fn from_system() -> Self {
+        Self::for_locale(&detect_locale())
+    }
+
+    /// Look up a message key. Unknown keys return the key itself so a
+    /// missing translation is visible rather than a panic.
+    pub fn message<'a>(&'a self, key: &'a str) -> &'a str {
+        self.active
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .unwrap_or(key)
+    }
+}
+
+/// All catalogs compiled into this build. Translations register here.
+fn registered_catalogs() -> Vec<Catalog> {
+    vec![en::catalog()]
+}
+
+/// Detect the user's locale from the POSIX environment, defaulting to `en`.
+///
+/// On macOS the login shell inherits `LANG` from the user's Language &
+/// Region settings, which is sufficient for the daemon and CLI.
+pub fn detect_locale() -> String {
+    for var in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        if let Ok(value) = std::env::var(var) {
+            if let Some(tag) = value.split('.').next() {
+                if !tag.is_empty() && tag != "C" && tag != "POSIX" {
+                    return tag.replace('_', "-");
+                }
+            }
+        }
+    }
+    "en".to_string()
+}
+
+/// Process-wide localizer, initialized on first use.
+pub fn localizer() -> &'static Localizer {
+    static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+    LOCALIZER.get_or_init(Localizer::from_system)
+}
+
+/// Shorthand for `localizer().message(key)`.
+pub fn t(key: &'static str) -> &'static str {
+    localizer().message(key)
+}
+
+/// Look up a message and substitute `{name}` placeholders.
+pub fn t_args(key: &'static str, args: &[(&str, &str)]) -> String {
+    let mut message = t(key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), value);
+    }
+    message
+}
@@ -0,0 +1,294 @@
+//! Event hooks: user commands run on daemon events, under protective
+//! limits.
+//!
+//! A hook is a shell command bound to an event name ("workspace_activated",
+//! "display_attached", ...). Hooks run other people's code on our event
+//! loop's schedule, so every launch goes through quota checks — max
+//! concurrent, max per minute, a hard wall-clock limit — and a sanitized
+//! environment. Violations are recorded for health status, and a kill
+//! switch stops all launching without a daemon restart, so a buggy hook
+//! can degrade itself instead of the window manager.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+
+/// Violations kept for health reporting; older ones age out.
+const MAX_VIOLATIONS: usize = 256;
+
+/// Environment variables a hook inherits from the daemon. Everything else
+/// is dropped: hooks get a predictable, secret-free environment.
+const INHERITED_ENV: &[&str] = &["HOME", "PATH", "LANG", "TMPDIR", "USER"];
+
+/// One configured hook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookConfig {
+    pub name: String,
+    /// Event that fires the hook, e.g. `workspace_activated`.
+    pub event: String,
+    /// Command run via `/bin/sh -c`; event details arrive as
+    /// `TILLERS_*` environment variables.
+    pub command: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// The `[hooks.limits]` section.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HookLimits {
+    /// Hooks allowed to run at the same time, across all hooks.
+    pub max_concurrent: usize,
+    /// Launches allowed per hook per minute.
+    pub max_per_minute: usize,
+    /// Wall-clock limit per run; the process is killed on expiry.
+    pub timeout_secs: u64,
+}
+
+impl Default for HookLimits {
+    fn default() -> Self {
+        HookLimits {
+            max_concurrent: 4,
+            max_per_minute: 30,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// The `[hooks]` config section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    pub hooks: Vec<HookConfig>,
+    pub limits: HookLimits,
+}
+
+/// Why a launch was suppressed or a run killed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookViolationKind {
+    /// The concurrent-run ceiling was hit.
+    Concurrency,
+    /// The per-minute launch quota was hit.
+    RateLimit,
+    /// The run exceeded its wall-clock limit and was killed.
+    Timeout,
+    /// The kill switch is on.
+    Disabled,
+}
+
+/// One recorded limit violation, surfaced in health status.
+#[derive(Debug, Clone, Serialize)]
+pub struct HookViolation {
+    pub hook: String,
+    pub kind: HookViolationKind,
+    pub at: SystemTime,
+}
+
+/// Map a bus event to the hook event name and the `TILLERS_*` variables
+/// (without the prefix) its hooks receive. `None` for events hooks cannot
+/// bind to.
+pub fn event_descriptor(
+    event: &crate::events::Event,
+) -> Option<(&'static str, Vec<(String, String)>)> {
+    use crate::events::{DaemonEvent, DisplayEvent, Event, WindowEvent, WorkspaceEvent};
+
+    Some(match event {
+        Event::Workspace(WorkspaceEvent::Activated { name }) => (
+            "workspace_activated",
+            vec![("WORKSPACE".into(), name.clone())],
+        ),
+        Event::Workspace(WorkspaceEvent::Created { name }) => (
+            "workspace_created",
+            vec![("WORKSPACE".into(), name.clone())],
+        ),
+        Event::Workspace(WorkspaceEvent::Removed { name }) => (
+            "workspace_removed",
+            vec![("WORKSPACE".into(), name.clone())],
+        ),
+        Event::Window(WindowEvent::Created(info)) => (
+            "window_created",
+            vec![
+                ("WINDOW".into(), info.id.to_string()),
+                ("APP".into(), info.app_bundle_id.clone()),
+                ("WORKSPACE".into(), info.workspace.clone()),
+            ],
+        ),
+        Event::Window(WindowEvent::Destroyed(id)) => {
+            ("window_destroyed", vec![("WINDOW".into(), id.to_string())])
+        }
+        Event::Window(WindowEvent::Focused(id)) => {
+            ("window_focused", vec![("WINDOW".into(), id.to_string())])
+        }
+        Event::Display(DisplayEvent::Attached(id)) => {
+            ("display_attached", vec![("DISPLAY".into(), id.to_string())])
+        }
+        Event::Display(DisplayEvent::Detached(id)) => {
+            ("display_detached", vec![("DISPLAY".into(), id.to_string())])
+        }
+        Event::Daemon(DaemonEvent::ConfigReloaded) => ("config_reloaded", Vec::new()),
+        _ => return None,
+    })
+}
+
+/// Launches hooks, enforces the limits, and keeps the violation record.
+pub struct HookRunner {
+    limits: HookLimits,
+    /// Runs currently in flight; shared with the waiter threads that
+    /// decrement it.
+    running: Arc<AtomicUsize>,
+    /// Recent launch times per hook, pruned to the rate-limit horizon.
+    launches: HashMap<String, VecDeque<Instant>>,
+    violations: VecDeque<HookViolation>,
+    killed: bool,
+}
+
+impl HookRunner {
+    pub fn new(limits: HookLimits) -> Self {
+        HookRunner {
+            limits,
+            running: Arc::new(AtomicUsize::new(0)),
+            launches: HashMap::new(),
+            violations: VecDeque::new(),
+            killed: false,
+        }
+    }
+
+    /// The kill switch: stop (or resume) launching hooks entirely.
+    /// Dispatched as an action so it works from a keybinding while the
+    /// config file that caused the problem is still broken.
+    pub fn set_killed(&mut self, killed: bool) {
+        if killed != self.killed {
+            tracing::warn!(killed, "hook kill switch changed");
+        }
+        self.killed = killed;
+    }
+
+    pub fn is_killed(&self) -> bool {
+        self.killed
+    }
+
+    /// Violations recorded so far, newest last; health status reports
+    /// these alongside the kill-switch state.
+    pub fn violations(&self) -> impl Iterator<Item = &HookViolation> {
+        self.violations.iter()
+    }
+
+    /// Launch a hook if every limit allows it. Returns whether the hook
+    /// was actually started; a suppressed launch records a violation but
+    /// is not an error — the event loop must not care.
+    pub fn launch(&mut self, hook: &HookConfig, event_env: &[(String, String)]) -> Result<bool> {
+        if !hook.enabled {
+            return Ok(false);
+        }
+        if self.killed {
+            self.record(hook, HookViolationKind::Disabled);
+            return Ok(false);
+        }
+        if self.running.load(Ordering::Relaxed) >= self.limits.max_concurrent {
+            self.record(hook, HookViolationKind::Concurrency);
+            return Ok(false);
+        }
+        let now = Instant::now();
+        let recent = self.launches.entry(hook.name.clone()).or_default();
+        while recent
+            .front()
+            .is_some_and(|&at| now.duration_since(at) > Duration::from_secs(60))
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= self.limits.max_per_minute {
+            self.record(hook, HookViolationKind::RateLimit);
+            return Ok(false);
+        }
+        recent.push_back(now);
+
+        let mut command = std::process::Command::new("/bin/sh");
+        command.arg("-c").arg(&hook.command);
+        // Sanitized environment: a short inherited whitelist plus the
+        // event's TILLERS_* variables — no daemon secrets, no surprises.
+        command.env_clear();
+        for key in INHERITED_ENV {
+            if let Ok(value) = std::env::var(key) {
+                command.env(key, value);
+            }
+        }
+        for (key, value) in event_env {
+            command.env(format!("TILLERS_{key}"), value);
+        }
+        let mut child = command.spawn()?;
+
+        self.running.fetch_add(1, Ordering::Relaxed);
+        let running = Arc::clone(&self.running);
+        let name = hook.name.clone();
+        let timeout = Duration::from_secs(self.limits.timeout_secs);
+        std::thread::Builder::new()
+            .name(format!("tillers-hook-{name}"))
+            .spawn(move || {
+                let deadline = Instant::now() + timeout;
+                loop {
+                    match child.try_wait() {
+                        Ok(Some(status)) => {
+                            if !status.success() {
+                                tracing::warn!(hook = %name, %status, "hook exited nonzero");
+                            }
+                            break;
+                        }
+                        Ok(None) if Instant::now() >= deadline => {
+                            tracing::warn!(
+                                hook = %name,
+                                timeout_secs = timeout.as_secs(),
+                                "hook exceeded its time limit; killing"
+                            );
+                            let _ = child.kill();
+                            let _ = child.wait();
+                            break;
+                        }
+                        Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+                        Err(err) => {
+                            tracing::warn!(hook = %name, %err, "waiting on hook failed");
+                            break;
+                        }
+                    }
+                }
+                running.fetch_sub(1, Ordering::Relaxed);
+            })
+            .expect("spawn hook waiter thread");
+        Ok(true)
+    }
+
+    /// Record a timeout kill reported back by a waiter; also called
+    /// internally for suppressed launches.
+    pub fn record_timeout(&mut self, hook_name: &str) {
+        self.push_violation(HookViolation {
+            hook: hook_name.to_string(),
+            kind: HookViolationKind::Timeout,
+            at: SystemTime::now(),
+        });
+    }
+
+    fn record(&mut self, hook: &HookConfig, kind: HookViolationKind) {
+        tracing::warn!(hook = %hook.name, ?kind, "hook launch suppressed");
+        self.push_violation(HookViolation {
+            hook: hook.name.clone(),
+            kind,
+            at: SystemTime::now(),
+        });
+    }
+
+    fn push_violation(&mut self, violation: HookViolation) {
+        if self.violations.len() >= MAX_VIOLATIONS {
+            self.violations.pop_front();
+        }
+        self.violations.push_back(violation);
+    }
+}
@@ -0,0 +1,4 @@
+//! Optional integrations with the surrounding desktop environment.
+
+pub mod hooks;
+pub mod wallpaper;
@@ -0,0 +1,52 @@
+//! Per-workspace wallpaper switching.
+//!
+//! Workspaces can carry a wallpaper path; when one activates, the desktop
+//! image of each affected display is switched through NSWorkspace, via
+//! the [`Effects`] gateway so observer mode only logs. The last applied
+//! path per display is cached so rapid workspace flipping never re-sets
+//! an image that is already up.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::daemon::Effects;
+use crate::errors::Result;
+use crate::models::display::DisplayId;
+use crate::models::Workspace;
+
+/// Applies and caches desktop images per display.
+#[derive(Debug, Default)]
+pub struct WallpaperSwitcher {
+    /// Last image applied to each display.
+    applied: HashMap<DisplayId, PathBuf>,
+}
+
+impl WallpaperSwitcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called when `workspace` activates on `display`. No-op when the
+    /// workspace has no wallpaper or the display already shows it.
+    pub fn on_workspace_activated(
+        &mut self,
+        effects: &Effects,
+        workspace: &Workspace,
+        display: DisplayId,
+    ) -> Result<()> {
+        let Some(path) = &workspace.wallpaper else {
+            return Ok(());
+        };
+        if self.applied.get(&display) == Some(path) {
+            return Ok(());
+        }
+        effects.set_desktop_image(display, path)?;
+        self.applied.insert(display, path.clone());
+        Ok(())
+    }
+
+    /// Forget cached state for a display (e.g. it was unplugged).
+    pub fn forget_display(&mut self, display: DisplayId) {
+        self.applied.remove(&display);
+    }
+}
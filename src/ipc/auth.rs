@@ -0,0 +1,112 @@
+//! Authorization scopes for IPC clients.
+//!
+//! The socket is only reachable by the owning user, so by default every
+//! connection is trusted — the CLI keeps working with zero setup. For
+//! setups that run third-party clients (status bars, deck plugins,
+//! scripts from dotfiles), the config can lower the default to read-only
+//! and hand out tokens that grant full control or an explicit action
+//! list. The token travels in the handshake; the daemon resolves it to a
+//! scope once per connection and checks every subsequent request against
+//! it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, TilleRSError};
+
+/// What a connection may do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpcScope {
+    /// Everything, including actions and transactions.
+    Trusted,
+    /// Queries and event subscriptions only.
+    ReadOnly,
+    /// Read-only plus exactly these action tags (the serde `action`
+    /// names, e.g. `switch_workspace`).
+    Actions(Vec<String>),
+}
+
+impl IpcScope {
+    /// Whether this scope permits a request.
+    pub fn permits(&self, request: &RequestKind<'_>) -> bool {
+        match (self, request) {
+            (IpcScope::Trusted, _) => true,
+            (_, RequestKind::Query | RequestKind::Subscribe) => true,
+            (IpcScope::ReadOnly, _) => false,
+            (IpcScope::Actions(allowed), RequestKind::Action(tag)) => {
+                allowed.iter().any(|a| a == tag)
+            }
+            (IpcScope::Actions(allowed), RequestKind::Transaction(tags)) => {
+                tags.iter().all(|tag| allowed.iter().any(|a| a == tag))
+            }
+        }
+    }
+}
+
+/// Classification of one incoming request for the permission check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestKind<'a> {
+    Query,
+    Subscribe,
+    /// A single action, by its serde tag.
+    Action(&'a str),
+    /// A transaction; permitted only when every step is.
+    Transaction(Vec<&'a str>),
+}
+
+/// Scope granted to connections that present no token.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultScope {
+    /// Full control — the socket is per-user, so this is safe for setups
+    /// that only ever run the bundled CLI.
+    #[default]
+    Trusted,
+    /// Queries and subscriptions only; commands require a token.
+    ReadOnly,
+}
+
+/// One configured token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpcTokenConfig {
+    /// Label shown in logs and diagnostics; never the token itself.
+    pub name: String,
+    pub token: String,
+    /// Grants full control when true; otherwise the token is limited to
+    /// read-only plus `actions`.
+    #[serde(default)]
+    pub trusted: bool,
+    /// Action tags this token may dispatch (ignored when `trusted`).
+    #[serde(default)]
+    pub actions: Vec<String>,
+}
+
+/// The `[ipc]` section of the config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IpcAuthConfig {
+    pub default_scope: DefaultScope,
+    pub tokens: Vec<IpcTokenConfig>,
+}
+
+/// Resolve a connection's token (from its hello) to a scope. An unknown
+/// token is refused outright rather than downgraded, so a typo in a
+/// client config surfaces immediately.
+pub fn authorize(config: &IpcAuthConfig, token: Option<&str>) -> Result<IpcScope> {
+    let Some(token) = token else {
+        return Ok(match config.default_scope {
+            DefaultScope::Trusted => IpcScope::Trusted,
+            DefaultScope::ReadOnly => IpcScope::ReadOnly,
+        });
+    };
+    let entry = config
+        .tokens
+        .iter()
+        .find(|t| t.token == token)
+        .ok_or_else(|| TilleRSError::Permission("unknown IPC token".into()))?;
+    tracing::debug!(client = entry.name, "IPC token accepted");
+    Ok(if entry.trusted {
+        IpcScope::Trusted
+    } else {
+        IpcScope::Actions(entry.actions.clone())
+    })
+}
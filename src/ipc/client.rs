@@ -0,0 +1,93 @@
+//! The CLI-side IPC client.
+//!
+//! Wraps a connected, handshaken stream and exposes one method per
+//! request. Daemon-side failures come back as
+//! [`TilleRSError::Remote`] carrying the original error class, so the CLI
+//! exits with the same code the error would have produced in-process.
+
+use std::io::BufReader;
+use std::os::unix::net::UnixStream;
+
+use crate::errors::{Result, TilleRSError};
+use crate::ipc::protocol::{HealthReport, Request, Response};
+use crate::ipc::{read_message, write_message, Encoding};
+use crate::models::{ActionType, WindowInfo, Workspace};
+
+/// One connection to the daemon, post-handshake.
+pub struct IpcClient {
+    writer: UnixStream,
+    reader: BufReader<UnixStream>,
+    encoding: Encoding,
+}
+
+impl IpcClient {
+    /// Connect and handshake. Fails with `IpcUnavailable` (exit code 40)
+    /// when no daemon is listening.
+    pub fn connect() -> Result<Self> {
+        let (stream, _theirs, negotiated) = crate::ipc::connect()?;
+        Ok(IpcClient {
+            writer: stream.try_clone()?,
+            reader: BufReader::new(stream),
+            encoding: negotiated.encoding,
+        })
+    }
+
+    /// Send one request and read its response.
+    fn request(&mut self, request: &Request) -> Result<Response> {
+        write_message(&mut self.writer, self.encoding, request)?;
+        read_message(&mut self.reader, self.encoding)
+    }
+
+    /// Dispatch a single action.
+    pub fn action(&mut self, action: ActionType) -> Result<()> {
+        match self.request(&Request::Action { action })? {
+            Response::Ok => Ok(()),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Dispatch a transaction. The daemon executes the actions in order;
+    /// when one fails, completed steps are rolled back in reverse at the
+    /// model level (a closed window stays closed) and the step's error is
+    /// returned. A single arrange pass runs after the whole transaction,
+    /// never between steps.
+    pub fn transaction(&mut self, actions: Vec<ActionType>) -> Result<()> {
+        match self.request(&Request::Transaction { actions })? {
+            Response::Ok => Ok(()),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// The daemon's window model, focus history included.
+    pub fn query_windows(&mut self) -> Result<Vec<WindowInfo>> {
+        match self.request(&Request::QueryWindows)? {
+            Response::Windows { windows } => Ok(windows),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// The workspace list and the active workspace.
+    pub fn query_workspaces(&mut self) -> Result<(Vec<Workspace>, Option<String>)> {
+        match self.request(&Request::QueryWorkspaces)? {
+            Response::Workspaces { workspaces, active } => Ok((workspaces, active)),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Daemon liveness summary.
+    pub fn health(&mut self) -> Result<HealthReport> {
+        match self.request(&Request::Health)? {
+            Response::Health(report) => Ok(report),
+            other => Err(unexpected(other)),
+        }
+    }
+}
+
+/// Map a non-matching response: errors carry their class across the wire,
+/// anything else means the two ends disagree about the protocol.
+fn unexpected(response: Response) -> TilleRSError {
+    match response {
+        Response::Error { code, message } => TilleRSError::Remote { code, message },
+        other => TilleRSError::Serialization(format!("unexpected response from daemon: {other:?}")),
+    }
+}
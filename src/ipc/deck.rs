@@ -0,0 +1,147 @@
+//! Stream Deck (and similar macro-pad) integration endpoint.
+//!
+//! Deck plugins poll nothing: they connect once, send `subscribe`, and the
+//! daemon pushes a [`DeckUpdate`] immediately and again on every workspace
+//! change, so the buttons reflect the active workspace with no visible
+//! lag. The command set is deliberately tiny and stable — a plugin written
+//! against it should survive daemon upgrades via the capability handshake.
+//!
+//! Wire protocol, after the standard handshake (see the module docs of
+//! [`crate::ipc`]); the `stream-deck` capability marks support:
+//!
+//! ```text
+//! plugin -> daemon   {"cmd":"subscribe","icons":true}
+//! daemon -> plugin   {"buttons":[{"workspace":"web","active":true,"windows":3,...}]}
+//! plugin -> daemon   {"cmd":"switch","workspace":"code"}
+//! daemon -> plugin   {"buttons":[...]}                      (pushed, not polled)
+//! ```
+//!
+//! Prefer the MessagePack encoding when embedding icons; RGBA pixel data
+//! in JSON inflates roughly fourfold.
+
+use std::io::BufReader;
+use std::os::unix::net::UnixStream;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, TilleRSError};
+
+use super::{read_message, write_message, Encoding};
+
+/// Capability string a daemon with this endpoint announces.
+pub const DECK_CAPABILITY: &str = "stream-deck";
+
+/// Commands a deck plugin sends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum DeckCommand {
+    /// Start receiving pushed [`DeckUpdate`]s. With `icons`, each button
+    /// carries the dominant app's icon for rendering on the key.
+    Subscribe {
+        #[serde(default)]
+        icons: bool,
+    },
+    /// Switch to a workspace (the tap handler).
+    Switch { workspace: String },
+    /// One-shot state query without subscribing.
+    Query,
+}
+
+/// Key-sized RGBA icon, tightly packed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckIcon {
+    pub width: usize,
+    pub height: usize,
+    #[serde(with = "serde_bytes")]
+    pub rgba: Vec<u8>,
+}
+
+/// One deck button.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckButton {
+    pub workspace: String,
+    pub active: bool,
+    /// Window count, for a badge.
+    pub windows: usize,
+    /// Icon of the workspace's dominant app, when icons were requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<DeckIcon>,
+}
+
+/// The full strip state; pushed whole on every change so plugins never
+/// have to reconcile deltas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckUpdate {
+    pub buttons: Vec<DeckButton>,
+}
+
+/// Resolves a workspace to its dominant app's icon.
+pub type IconResolver<'a> = &'a dyn Fn(&str) -> Option<DeckIcon>;
+
+/// Build the update pushed to subscribers. `windows` counts per workspace;
+/// `icon_for` resolves a workspace to its dominant app's icon and is only
+/// consulted for subscribers that asked for icons.
+pub fn deck_update(
+    workspaces: &[String],
+    active: Option<&str>,
+    windows: impl Fn(&str) -> usize,
+    icon_for: Option<IconResolver<'_>>,
+) -> DeckUpdate {
+    DeckUpdate {
+        buttons: workspaces
+            .iter()
+            .map(|workspace| DeckButton {
+                workspace: workspace.clone(),
+                active: Some(workspace.as_str()) == active,
+                windows: windows(workspace),
+                icon: icon_for.and_then(|f| f(workspace)),
+            })
+            .collect(),
+    }
+}
+
+/// Example plugin-side client; the reference for third-party integrations
+/// and what the bundled Stream Deck plugin uses.
+pub struct DeckClient {
+    writer: UnixStream,
+    reader: BufReader<UnixStream>,
+    encoding: Encoding,
+}
+
+impl DeckClient {
+    /// Connect, handshake, and subscribe for pushed updates.
+    pub fn subscribe(icons: bool) -> Result<Self> {
+        let (stream, _theirs, negotiated) = super::connect()?;
+        if !negotiated.capabilities.iter().any(|c| c == DECK_CAPABILITY) {
+            return Err(TilleRSError::IpcUnavailable(
+                "daemon does not support the stream-deck endpoint".into(),
+            ));
+        }
+        // Pushes arrive whenever state changes; no read timeout.
+        stream.set_read_timeout(None)?;
+        let mut client = DeckClient {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            encoding: negotiated.encoding,
+        };
+        client.send(&DeckCommand::Subscribe { icons })?;
+        Ok(client)
+    }
+
+    /// Block until the daemon pushes the next state.
+    pub fn next_update(&mut self) -> Result<DeckUpdate> {
+        read_message(&mut self.reader, self.encoding)
+    }
+
+    /// Tap handler: switch to a workspace. The confirming state arrives
+    /// as a pushed update.
+    pub fn switch(&mut self, workspace: impl Into<String>) -> Result<()> {
+        self.send(&DeckCommand::Switch {
+            workspace: workspace.into(),
+        })
+    }
+
+    fn send(&mut self, command: &DeckCommand) -> Result<()> {
+        write_message(&mut self.writer, self.encoding, command)
+    }
+}
@@ -0,0 +1,288 @@
+//! IPC between the CLI and the daemon.
+//!
+//! Transport is a Unix socket carrying newline-delimited JSON. Every
+//! connection opens with a version handshake: both sides announce their
+//! protocol version and capability list, and each side restricts itself to
+//! the intersection — an older CLI talking to a newer daemon (or the
+//! reverse) degrades to the features both understand instead of failing.
+
+pub mod auth;
+pub mod client;
+pub mod deck;
+pub mod protocol;
+pub mod server;
+
+pub use client::IpcClient;
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, TilleRSError};
+
+/// Current protocol version. Bump on wire-incompatible changes; additive
+/// features ride on capabilities instead.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Capabilities this build speaks. Sent verbatim in the handshake;
+/// unknown strings from the peer are ignored, which is what makes the
+/// degradation graceful.
+pub const CAPABILITIES: &[&str] = &[
+    "actions",
+    "transactions",
+    "query-windows",
+    "diagnostics",
+    deck::DECK_CAPABILITY,
+];
+
+/// Socket the daemon listens on: `~/.local/share/tillers/daemon.sock`.
+pub fn socket_path() -> PathBuf {
+    let home = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    home.join(".local")
+        .join("share")
+        .join("tillers")
+        .join("daemon.sock")
+}
+
+/// Wire encodings for everything after the handshake. The handshake
+/// itself is always JSON so any version can read it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    /// Newline-delimited JSON; always supported.
+    #[default]
+    Json,
+    /// Length-prefixed MessagePack; cheaper to encode and parse, which
+    /// matters for status-bar subscribers polling the event stream at
+    /// high frequency.
+    MessagePack,
+}
+
+/// First message on every connection, in both directions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hello {
+    pub version: u32,
+    pub capabilities: Vec<String>,
+    /// Encodings this end can speak, preferred first. Missing (older
+    /// peers) means JSON only.
+    #[serde(default)]
+    pub encodings: Vec<Encoding>,
+    /// Authorization token, for clients running under a scoped config
+    /// (see [`auth`]). Absent means the configured default scope.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl Hello {
+    pub fn current() -> Self {
+        Hello {
+            version: PROTOCOL_VERSION,
+            capabilities: CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+            encodings: vec![Encoding::MessagePack, Encoding::Json],
+            token: std::env::var("TILLERS_IPC_TOKEN").ok(),
+        }
+    }
+}
+
+/// What both ends agreed on after the handshake.
+#[derive(Debug, Clone, Serialize)]
+pub struct Negotiated {
+    /// The lower of the two protocol versions; both sides speak it.
+    pub version: u32,
+    /// Capabilities common to both ends.
+    pub capabilities: Vec<String>,
+    /// What the peer offered but we do not understand (informational).
+    pub peer_only: Vec<String>,
+    /// Encoding used for everything after the handshake: the requester's
+    /// most preferred encoding both ends support, JSON as the floor.
+    pub encoding: Encoding,
+}
+
+/// Intersect our hello with the peer's. `we_initiated` says which end
+/// opened the connection: the initiator's preference order decides the
+/// encoding, so both sides resolve the same one even when their own
+/// preference lists are ordered differently.
+pub fn negotiate(ours: &Hello, theirs: &Hello, we_initiated: bool) -> Negotiated {
+    let capabilities = ours
+        .capabilities
+        .iter()
+        .filter(|c| theirs.capabilities.contains(c))
+        .cloned()
+        .collect();
+    let peer_only = theirs
+        .capabilities
+        .iter()
+        .filter(|c| !ours.capabilities.contains(c))
+        .cloned()
+        .collect();
+    let (preferred, supported) = if we_initiated {
+        (&ours.encodings, &theirs.encodings)
+    } else {
+        (&theirs.encodings, &ours.encodings)
+    };
+    let encoding = preferred
+        .iter()
+        .find(|e| supported.contains(e))
+        .copied()
+        .unwrap_or(Encoding::Json);
+    Negotiated {
+        version: ours.version.min(theirs.version),
+        capabilities,
+        peer_only,
+        encoding,
+    }
+}
+
+/// Write one post-handshake message in the negotiated encoding.
+pub fn write_message<T: Serialize>(
+    writer: &mut impl Write,
+    encoding: Encoding,
+    message: &T,
+) -> Result<()> {
+    match encoding {
+        Encoding::Json => {
+            writeln!(writer, "{}", serde_json::to_string(message)?)?;
+        }
+        Encoding::MessagePack => {
+            let payload = rmp_serde::to_vec_named(message)
+                .map_err(|e| TilleRSError::Serialization(e.to_string()))?;
+            writer.write_all(&(payload.len() as u32).to_be_bytes())?;
+            writer.write_all(&payload)?;
+        }
+    }
+    Ok(())
+}
+
+/// Read one post-handshake message in the negotiated encoding.
+pub fn read_message<T: serde::de::DeserializeOwned>(
+    reader: &mut impl BufRead,
+    encoding: Encoding,
+) -> Result<T> {
+    match encoding {
+        Encoding::Json => {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            Ok(serde_json::from_str(line.trim())?)
+        }
+        Encoding::MessagePack => {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len)?;
+            let mut payload = vec![0u8; u32::from_be_bytes(len) as usize];
+            reader.read_exact(&mut payload)?;
+            rmp_serde::from_slice(&payload).map_err(|e| TilleRSError::Serialization(e.to_string()))
+        }
+    }
+}
+
+/// Connect to the daemon and perform the handshake. Returns the daemon's
+/// hello alongside the negotiated intersection.
+pub fn connect() -> Result<(UnixStream, Hello, Negotiated)> {
+    let path = socket_path();
+    let stream = UnixStream::connect(&path).map_err(|e| {
+        TilleRSError::IpcUnavailable(format!(
+            "cannot connect to {} ({e}); is the daemon running?",
+            path.display()
+        ))
+    })?;
+    stream
+        .set_read_timeout(Some(Duration::from_secs(2)))
+        .and_then(|_| stream.set_write_timeout(Some(Duration::from_secs(2))))?;
+
+    let ours = Hello::current();
+    let mut writer = stream.try_clone()?;
+    writeln!(writer, "{}", serde_json::to_string(&ours)?)?;
+
+    let mut line = String::new();
+    BufReader::new(stream.try_clone()?).read_line(&mut line)?;
+    let theirs: Hello = serde_json::from_str(line.trim()).map_err(|e| {
+        TilleRSError::IpcUnavailable(format!("daemon sent a malformed handshake: {e}"))
+    })?;
+    let negotiated = negotiate(&ours, &theirs, true);
+    Ok((stream, theirs, negotiated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hello(encodings: Vec<Encoding>) -> Hello {
+        Hello {
+            version: PROTOCOL_VERSION,
+            capabilities: CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+            encodings,
+            token: None,
+        }
+    }
+
+    /// Both ends must resolve the same encoding even when their own
+    /// preference lists are ordered differently: the initiator's wins.
+    #[test]
+    fn negotiate_encoding_follows_initiator_preference() {
+        let client = hello(vec![Encoding::MessagePack, Encoding::Json]);
+        let server = hello(vec![Encoding::Json, Encoding::MessagePack]);
+
+        let at_client = negotiate(&client, &server, true);
+        let at_server = negotiate(&server, &client, false);
+        assert_eq!(at_client.encoding, Encoding::MessagePack);
+        assert_eq!(at_server.encoding, Encoding::MessagePack);
+    }
+
+    /// A peer without the encodings field (an older build) gets JSON.
+    #[test]
+    fn negotiate_falls_back_to_json() {
+        let client = hello(vec![Encoding::MessagePack, Encoding::Json]);
+        let old_server = hello(Vec::new());
+        assert_eq!(
+            negotiate(&client, &old_server, true).encoding,
+            Encoding::Json
+        );
+        assert_eq!(
+            negotiate(&old_server, &client, false).encoding,
+            Encoding::Json
+        );
+    }
+
+    #[test]
+    fn negotiate_intersects_capabilities_and_versions() {
+        let mut theirs = hello(vec![Encoding::Json]);
+        theirs.version = PROTOCOL_VERSION + 1;
+        theirs.capabilities = vec!["actions".into(), "from-the-future".into()];
+
+        let negotiated = negotiate(&Hello::current(), &theirs, true);
+        assert_eq!(negotiated.version, PROTOCOL_VERSION);
+        assert_eq!(negotiated.capabilities, vec!["actions".to_string()]);
+        assert_eq!(negotiated.peer_only, vec!["from-the-future".to_string()]);
+    }
+}
+
+/// Result of `tillers diagnostics api-check`.
+#[derive(Debug, Serialize)]
+pub struct ApiCheckReport {
+    pub socket: PathBuf,
+    pub cli_version: u32,
+    pub daemon_version: u32,
+    pub negotiated: Negotiated,
+    /// Round-trip time of the handshake, microseconds.
+    pub round_trip_us: u128,
+}
+
+/// Round-trip test against the running daemon: connect, handshake, and
+/// time it. Fails with `IpcUnavailable` (exit code 40) when no daemon is
+/// listening.
+pub fn api_check() -> Result<ApiCheckReport> {
+    let started = Instant::now();
+    let (_stream, theirs, negotiated) = connect()?;
+    let round_trip_us = started.elapsed().as_micros();
+    Ok(ApiCheckReport {
+        socket: socket_path(),
+        cli_version: PROTOCOL_VERSION,
+        daemon_version: theirs.version,
+        negotiated,
+        round_trip_us,
+    })
+}
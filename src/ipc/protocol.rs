@@ -0,0 +1,75 @@
+//! Post-handshake request and response messages.
+//!
+//! Requests carry the same [`ActionType`] values keybindings use, so the
+//! CLI, the batch file format, and IPC all speak one action vocabulary.
+//! Responses embed the daemon-side error class (`code`), letting the CLI
+//! exit with the code the error would have produced locally.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{ActionType, WindowInfo, Workspace};
+
+/// One request from a connected client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "request", rename_all = "snake_case")]
+pub enum Request {
+    /// Dispatch a single action.
+    Action { action: ActionType },
+    /// Dispatch several actions as one all-or-nothing transaction with a
+    /// single arrange pass at the end.
+    Transaction { actions: Vec<ActionType> },
+    /// The daemon's window model, including focus history.
+    QueryWindows,
+    /// The workspace list in its stable order, plus the active one.
+    QueryWorkspaces,
+    /// Liveness and load summary for `tillers diagnostics health`.
+    Health,
+}
+
+/// The daemon's reply to one request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "response", rename_all = "snake_case")]
+pub enum Response {
+    Ok,
+    /// The request failed; `code` is the daemon-side error class
+    /// ([`TilleRSError::code_name`](crate::errors::TilleRSError::code_name)).
+    Error {
+        code: String,
+        message: String,
+    },
+    Windows {
+        windows: Vec<WindowInfo>,
+    },
+    Workspaces {
+        workspaces: Vec<Workspace>,
+        active: Option<String>,
+    },
+    Health(HealthReport),
+}
+
+/// Daemon liveness summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub version: String,
+    pub uptime_secs: u64,
+    pub workspaces: usize,
+    pub windows: usize,
+    /// Current event-bus subscribers.
+    pub subscribers: usize,
+    /// Events dropped bus-wide because subscribers fell behind.
+    pub lagged_events: u64,
+    /// Conflicting window managers currently running.
+    pub conflicts: Vec<String>,
+    /// Current orchestrator state ("idle", "arranging <ws>", ...).
+    pub orchestrator: String,
+}
+
+/// The serde `action` tag of an action, used for scope checks; the same
+/// string that names it in config keybindings.
+pub fn action_tag(action: &ActionType) -> String {
+    serde_json::to_value(action)
+        .ok()
+        .and_then(|v| v.get("action").and_then(|t| t.as_str().map(str::to_string)))
+        // `Sequence` serializes without a tag; classify it as itself.
+        .unwrap_or_else(|| "sequence".to_string())
+}
@@ -0,0 +1,266 @@
+//! The daemon-side IPC listener.
+//!
+//! One accept loop on the Unix socket; each connection gets its own
+//! thread, a handshake, a scope resolved from its token, and then a
+//! request/response loop in the negotiated encoding. Request execution is
+//! behind [`RequestHandler`] so the server owns only transport and
+//! authorization — what an action *does* stays in the daemon.
+
+use std::io::{BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+
+use crate::errors::{Result, TilleRSError};
+use crate::events::Event;
+use crate::ipc::auth::{self, IpcAuthConfig, IpcScope, RequestKind};
+use crate::ipc::deck::{DeckCommand, DeckUpdate};
+use crate::ipc::protocol::{action_tag, Request, Response};
+use crate::ipc::{negotiate, read_message, write_message, Encoding, Hello};
+
+/// Executes one authorized request against live daemon state.
+pub trait RequestHandler: Send + Sync {
+    fn handle(&self, request: Request) -> Response;
+
+    /// The deck strip for the current state; `None` when the handler does
+    /// not serve the deck endpoint.
+    fn deck_state(&self, icons: bool) -> Option<DeckUpdate> {
+        let _ = icons;
+        None
+    }
+
+    /// Subscribe to daemon events, for connections that receive pushes.
+    /// `None` when the handler has no bus.
+    fn subscribe_events(&self) -> Option<crate::events::EventSubscriber> {
+        None
+    }
+}
+
+/// Anything a connected client can send after the handshake: a standard
+/// request (tagged `request`) or a deck command (tagged `cmd`).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Incoming {
+    Request(Request),
+    Deck(DeckCommand),
+}
+
+/// Bind the socket and spawn the accept loop.
+///
+/// A stale socket file from a crashed daemon is removed after confirming
+/// nothing answers on it; a live daemon on the socket is a hard error so
+/// two instances never fight over windows.
+pub fn spawn(
+    handler: Arc<dyn RequestHandler>,
+    auth: IpcAuthConfig,
+) -> Result<std::thread::JoinHandle<()>> {
+    let path = crate::ipc::socket_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            return Err(TilleRSError::Validation(format!(
+                "another daemon is already listening on {}",
+                path.display()
+            )));
+        }
+        std::fs::remove_file(&path)?;
+        tracing::info!(socket = %path.display(), "removed stale socket");
+    }
+    let listener = UnixListener::bind(&path)?;
+    tracing::info!(socket = %path.display(), "IPC server listening");
+
+    let handle = std::thread::Builder::new()
+        .name("tillers-ipc".into())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let handler = Arc::clone(&handler);
+                        let auth = auth.clone();
+                        let spawned = std::thread::Builder::new()
+                            .name("tillers-ipc-client".into())
+                            .spawn(move || {
+                                if let Err(err) = serve_connection(stream, handler, &auth) {
+                                    tracing::debug!(%err, "IPC connection ended with error");
+                                }
+                            });
+                        if let Err(err) = spawned {
+                            tracing::warn!(%err, "could not spawn IPC connection thread");
+                        }
+                    }
+                    Err(err) => tracing::warn!(%err, "IPC accept failed"),
+                }
+            }
+        })?;
+    Ok(handle)
+}
+
+/// Handshake, authorize, then serve requests until the client hangs up.
+fn serve_connection(
+    stream: UnixStream,
+    handler: Arc<dyn RequestHandler>,
+    auth: &IpcAuthConfig,
+) -> Result<()> {
+    let writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    // The handshake is always JSON so any version can read it.
+    let theirs: Hello = {
+        let mut line = String::new();
+        std::io::BufRead::read_line(&mut reader, &mut line)?;
+        serde_json::from_str(line.trim())
+            .map_err(|e| TilleRSError::Validation(format!("malformed client handshake: {e}")))?
+    };
+    let ours = Hello::current();
+    // Deck pushes come from their own thread once a subscription starts,
+    // so all writes go through one shared, locked stream.
+    let writer = Arc::new(Mutex::new(writer));
+    writeln!(writer.lock().unwrap(), "{}", serde_json::to_string(&ours)?)?;
+    // The client initiated, so its encoding preference order wins.
+    let negotiated = negotiate(&ours, &theirs, false);
+    let scope = auth::authorize(auth, theirs.token.as_deref())?;
+
+    loop {
+        let incoming: Incoming = match read_message(&mut reader, negotiated.encoding) {
+            Ok(incoming) => incoming,
+            // EOF or a torn message: the client is gone.
+            Err(_) => return Ok(()),
+        };
+        match incoming {
+            Incoming::Request(request) => {
+                let response = if permitted(&scope, &request) {
+                    handler.handle(request)
+                } else {
+                    Response::Error {
+                        code: "permission".into(),
+                        message: "this connection's scope does not permit that request".into(),
+                    }
+                };
+                write_message(&mut *writer.lock().unwrap(), negotiated.encoding, &response)?;
+            }
+            Incoming::Deck(command) => {
+                serve_deck_command(command, &handler, &scope, &writer, negotiated.encoding)?;
+            }
+        }
+    }
+}
+
+/// Handle one deck command. Deck clients expect [`DeckUpdate`] frames, not
+/// [`Response`]s, so authorization failures end the connection instead of
+/// answering with an error they cannot parse.
+fn serve_deck_command(
+    command: DeckCommand,
+    handler: &Arc<dyn RequestHandler>,
+    scope: &IpcScope,
+    writer: &Arc<Mutex<UnixStream>>,
+    encoding: Encoding,
+) -> Result<()> {
+    let push_state = |icons: bool| -> Result<()> {
+        let update = handler.deck_state(icons).ok_or_else(|| {
+            TilleRSError::IpcUnavailable("this daemon does not serve the deck endpoint".into())
+        })?;
+        write_message(&mut *writer.lock().unwrap(), encoding, &update)
+    };
+    match command {
+        DeckCommand::Query => {
+            if !scope.permits(&RequestKind::Query) {
+                return Err(scope_refusal("query"));
+            }
+            push_state(false)
+        }
+        DeckCommand::Switch { workspace } => {
+            let action = crate::models::ActionType::SwitchWorkspace { workspace };
+            let tag = action_tag(&action);
+            if !scope.permits(&RequestKind::Action(&tag)) {
+                return Err(scope_refusal("switch"));
+            }
+            if let Response::Error { code, message } = handler.handle(Request::Action { action }) {
+                tracing::debug!(%code, %message, "deck switch failed");
+            }
+            // The confirming state is pushed, subscribed or not.
+            push_state(false)
+        }
+        DeckCommand::Subscribe { icons } => {
+            if !scope.permits(&RequestKind::Subscribe) {
+                return Err(scope_refusal("subscribe"));
+            }
+            push_state(icons)?;
+            let Some(mut events) = handler.subscribe_events() else {
+                return Ok(());
+            };
+            let handler = Arc::clone(handler);
+            let writer = Arc::clone(writer);
+            std::thread::Builder::new()
+                .name("tillers-deck-push".into())
+                .spawn(move || {
+                    while let Some(event) = events.blocking_recv() {
+                        // Only state the buttons render warrants a push.
+                        if !matches!(event, Event::Workspace(_) | Event::Window(_)) {
+                            continue;
+                        }
+                        let Some(update) = handler.deck_state(icons) else {
+                            break;
+                        };
+                        let mut writer = writer.lock().unwrap();
+                        if write_message(&mut *writer, encoding, &update).is_err() {
+                            // The plugin hung up; the reader side notices too.
+                            break;
+                        }
+                    }
+                })?;
+            Ok(())
+        }
+    }
+}
+
+fn scope_refusal(what: &str) -> TilleRSError {
+    TilleRSError::Permission(format!(
+        "this connection's scope does not permit deck {what}"
+    ))
+}
+
+/// Check one request against the connection's scope.
+fn permitted(scope: &IpcScope, request: &Request) -> bool {
+    match request {
+        Request::Action { action } => {
+            let tag = action_tag(action);
+            scope.permits(&RequestKind::Action(&tag))
+        }
+        Request::Transaction { actions } => {
+            let tags: Vec<String> = actions.iter().map(action_tag).collect();
+            scope.permits(&RequestKind::Transaction(
+                tags.iter().map(String::as_str).collect(),
+            ))
+        }
+        Request::QueryWindows | Request::QueryWorkspaces | Request::Health => {
+            scope.permits(&RequestKind::Query)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn incoming_distinguishes_requests_from_deck_commands() {
+        let incoming: Incoming = serde_json::from_str(r#"{"request":"health"}"#).unwrap();
+        assert!(matches!(incoming, Incoming::Request(Request::Health)));
+
+        let incoming: Incoming =
+            serde_json::from_str(r#"{"cmd":"switch","workspace":"web"}"#).unwrap();
+        assert!(matches!(
+            incoming,
+            Incoming::Deck(DeckCommand::Switch { ref workspace }) if workspace == "web"
+        ));
+
+        let incoming: Incoming = serde_json::from_str(r#"{"cmd":"subscribe"}"#).unwrap();
+        assert!(matches!(
+            incoming,
+            Incoming::Deck(DeckCommand::Subscribe { icons: false })
+        ));
+    }
+}
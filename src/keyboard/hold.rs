@@ -0,0 +1,73 @@
+//! Press-and-hold detection for the workspace modifier.
+//!
+//! Tapping `opt+1` switches instantly and should never flash UI. Holding
+//! the bare modifier past the threshold means the user is still choosing a
+//! target, so the quick-switch HUD appears; it hides the moment the
+//! modifier is released. Detection runs off flags-changed events plus a
+//! timer tick — the event tap itself stays allocation-free.
+
+use std::time::{Duration, Instant};
+
+/// How long the bare modifier must be held before the HUD shows.
+pub const HOLD_THRESHOLD: Duration = Duration::from_millis(300);
+
+/// HUD visibility changes the detector asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoldEvent {
+    Show,
+    Hide,
+}
+
+/// Tracks whether the watched modifier is held down alone.
+#[derive(Debug)]
+pub struct ModifierHold {
+    /// Modifier bitmask to watch (see [`super::modifiers`]); typically the
+    /// workspace modifier from the `opt+1..9` bindings.
+    watched: u8,
+    held_since: Option<Instant>,
+    visible: bool,
+}
+
+impl ModifierHold {
+    pub fn new(watched: u8) -> Self {
+        ModifierHold {
+            watched,
+            held_since: None,
+            visible: false,
+        }
+    }
+
+    /// Feed every flags-changed event from the tap. Exactly the watched
+    /// modifier arms the timer; anything else — release, or additional
+    /// modifiers meaning a different chord — disarms it and hides a
+    /// visible HUD.
+    pub fn flags_changed(&mut self, modifiers: u8, now: Instant) -> Option<HoldEvent> {
+        if modifiers == self.watched {
+            self.held_since.get_or_insert(now);
+            return None;
+        }
+        self.held_since = None;
+        if self.visible {
+            self.visible = false;
+            return Some(HoldEvent::Hide);
+        }
+        None
+    }
+
+    /// Timer tick while armed: asks to show the HUD once the threshold
+    /// passes. Key presses while visible (switching with the HUD up) do
+    /// not hide it — only releasing the modifier does.
+    pub fn tick(&mut self, now: Instant) -> Option<HoldEvent> {
+        let since = self.held_since?;
+        if !self.visible && now.duration_since(since) >= HOLD_THRESHOLD {
+            self.visible = true;
+            return Some(HoldEvent::Show);
+        }
+        None
+    }
+
+    /// Whether the HUD is currently up.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
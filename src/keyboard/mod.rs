@@ -0,0 +1,302 @@
+//! Keyboard shortcut dispatch.
+//!
+//! The event-tap callback runs on every key press system-wide, so its hot
+//! path must not allocate. Configured mappings are precompiled into a
+//! [`KeyboardMappingSet`] — a flat table keyed by the packed
+//! (modifiers, keycode) pair — rebuilt only when the config changes;
+//! lookups are a single hash probe with no string formatting and no `Vec`.
+
+pub mod hold;
+pub mod triggers;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::ActionType;
+
+/// A second tap of the same chord within this window counts as a
+/// double-tap.
+pub const DOUBLE_TAP_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Modifier bitmask, normalized from `CGEventFlags` at tap time so
+/// left/right variants and caps lock never affect matching.
+pub mod modifiers {
+    pub const SHIFT: u8 = 1 << 0;
+    pub const CONTROL: u8 = 1 << 1;
+    pub const OPTION: u8 = 1 << 2;
+    pub const COMMAND: u8 = 1 << 3;
+}
+
+/// A single configured shortcut.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyboardMapping {
+    /// Normalized modifier bitmask, see [`modifiers`].
+    pub modifiers: u8,
+    /// Hardware keycode (`kVK_*`), layout-independent.
+    pub keycode: u16,
+    /// `1` fires on every press; `2` fires on the second press of the
+    /// chord within [`DOUBLE_TAP_TIMEOUT`]. When both tap counts are
+    /// bound, the single-tap action fires on the first press and the
+    /// double-tap action on the second — bindings should be designed for
+    /// that (e.g. switch-to on tap, toggle-back on double-tap).
+    #[serde(default = "default_taps")]
+    pub taps: u8,
+    pub action: ActionType,
+}
+
+fn default_taps() -> u8 {
+    1
+}
+
+/// Stable statistics key for a chord: `m<modifiers>-k<keycode>-t<taps>`.
+/// Shared by [`KeyboardMapping::stat_key`] and the event tap so recorded
+/// usage always matches configured bindings.
+pub fn chord_stat_key(modifiers: u8, keycode: u16, taps: u8) -> String {
+    format!("m{modifiers}-k{keycode}-t{taps}")
+}
+
+impl KeyboardMapping {
+    /// Stable key identifying this binding in the statistics store.
+    /// Derived from the chord, not the action, so rebinding a chord keeps
+    /// its usage history.
+    pub fn stat_key(&self) -> String {
+        chord_stat_key(self.modifiers, self.keycode, self.taps)
+    }
+
+    /// Human-readable chord, e.g. `cmd+opt+h (double-tap)`.
+    pub fn chord_label(&self) -> String {
+        let mut parts = Vec::new();
+        if self.modifiers & modifiers::COMMAND != 0 {
+            parts.push("cmd");
+        }
+        if self.modifiers & modifiers::OPTION != 0 {
+            parts.push("opt");
+        }
+        if self.modifiers & modifiers::CONTROL != 0 {
+            parts.push("ctrl");
+        }
+        if self.modifiers & modifiers::SHIFT != 0 {
+            parts.push("shift");
+        }
+        let keycode = format!("key{}", self.keycode);
+        parts.push(&keycode);
+        let chord = parts.join("+");
+        if self.taps >= 2 {
+            format!("{chord} (double-tap)")
+        } else {
+            chord
+        }
+    }
+}
+
+/// The modifier mask the quick-switch HUD watches: the chord shared by
+/// the `switch_workspace_index` bindings (ties broken toward the larger
+/// mask). `None` when no such bindings exist, which leaves hold detection
+/// off entirely.
+pub fn workspace_modifier(mappings: &[KeyboardMapping]) -> Option<u8> {
+    let mut counts: HashMap<u8, usize> = HashMap::new();
+    for mapping in mappings {
+        if mapping.modifiers != 0
+            && matches!(mapping.action, ActionType::SwitchWorkspaceIndex { .. })
+        {
+            *counts.entry(mapping.modifiers).or_insert(0) += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|&(mods, count)| (count, mods))
+        .map(|(mods, _)| mods)
+}
+
+/// Single- and double-tap slots for one chord.
+#[derive(Debug, Clone, Copy, Default)]
+struct TapSlots {
+    single: Option<usize>,
+    double: Option<usize>,
+}
+
+/// The active shortcuts, compiled for allocation-free lookup.
+///
+/// Actions live in one contiguous `Vec`; the index maps packed
+/// (modifiers, keycode) keys to per-tap-count positions in it. Later
+/// mappings for the same chord win, matching rule-file precedence
+/// elsewhere.
+#[derive(Debug, Default)]
+pub struct KeyboardMappingSet {
+    actions: Vec<ActionType>,
+    index: HashMap<u32, TapSlots>,
+    /// Chord and time of the previous press, for double-tap detection.
+    last_press: Option<(u32, Instant)>,
+}
+
+/// Pack a chord into the table key: modifiers in the high bits, keycode in
+/// the low 16.
+#[inline]
+fn pack(modifiers: u8, keycode: u16) -> u32 {
+    (u32::from(modifiers) << 16) | u32::from(keycode)
+}
+
+impl KeyboardMappingSet {
+    /// Compile `mappings` into a lookup table. Called on startup and on
+    /// every config reload — never from the event tap.
+    pub fn compile(mappings: &[KeyboardMapping]) -> Self {
+        let mut set = KeyboardMappingSet {
+            actions: Vec::with_capacity(mappings.len()),
+            index: HashMap::with_capacity(mappings.len()),
+            last_press: None,
+        };
+        for mapping in mappings {
+            let slot = set.actions.len();
+            set.actions.push(mapping.action.clone());
+            let slots = set
+                .index
+                .entry(pack(mapping.modifiers, mapping.keycode))
+                .or_default();
+            if mapping.taps >= 2 {
+                slots.double = Some(slot);
+            } else {
+                slots.single = Some(slot);
+            }
+        }
+        set
+    }
+
+    /// Resolve a chord to its action and the tap count that fired (for
+    /// the statistics key). This is the event-tap hot path: one hash
+    /// probe, no allocation, no formatting.
+    ///
+    /// Tracks tap timing internally: a repeat of the same chord within
+    /// [`DOUBLE_TAP_TIMEOUT`] resolves to the double-tap binding when one
+    /// exists, otherwise to the single-tap binding again.
+    #[inline]
+    pub fn lookup(&mut self, modifiers: u8, keycode: u16) -> Option<(&ActionType, u8)> {
+        let key = pack(modifiers, keycode);
+        let slots = *self.index.get(&key)?;
+        let now = Instant::now();
+        let is_double = matches!(
+            self.last_press,
+            Some((last_key, at)) if last_key == key && now.duration_since(at) <= DOUBLE_TAP_TIMEOUT
+        );
+        // A consumed double-tap resets the cycle so a triple press does
+        // not fire the double action twice.
+        self.last_press = if is_double && slots.double.is_some() {
+            None
+        } else {
+            Some((key, now))
+        };
+        let (slot, taps) = if is_double {
+            match slots.double {
+                Some(slot) => (slot, 2),
+                None => (slots.single?, 1),
+            }
+        } else {
+            (slots.single?, 1)
+        };
+        Some((&self.actions[slot], taps))
+    }
+
+    /// Whether a binding exists for exactly this chord, at any tap count.
+    /// The tap swallows bound chords even when the tap-count state means
+    /// no action fires, so half of a double-tap never leaks to the app.
+    #[inline]
+    pub fn is_bound(&self, modifiers: u8, keycode: u16) -> bool {
+        self.index.contains_key(&pack(modifiers, keycode))
+    }
+
+    /// Whether any mapping uses this keycode, regardless of modifiers.
+    /// Lets the tap pass through keys it can never match without probing
+    /// every modifier combination.
+    pub fn uses_keycode(&self, keycode: u16) -> bool {
+        self.index.keys().any(|&key| key as u16 == keycode)
+    }
+
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(taps: u8, action: ActionType) -> KeyboardMapping {
+        KeyboardMapping {
+            modifiers: modifiers::OPTION,
+            keycode: 4, // kVK_ANSI_H
+            taps,
+            action,
+        }
+    }
+
+    #[test]
+    fn double_tap_resolves_after_a_quick_repeat() {
+        let mut set = KeyboardMappingSet::compile(&[
+            mapping(1, ActionType::Retile),
+            mapping(2, ActionType::BalanceLayout),
+        ]);
+        // First press: the single-tap binding.
+        let (action, taps) = set.lookup(modifiers::OPTION, 4).unwrap();
+        assert!(matches!(action, ActionType::Retile));
+        assert_eq!(taps, 1);
+        // Second press inside the window: the double-tap binding.
+        let (action, taps) = set.lookup(modifiers::OPTION, 4).unwrap();
+        assert!(matches!(action, ActionType::BalanceLayout));
+        assert_eq!(taps, 2);
+        // A consumed double-tap resets the cycle: the third press is a
+        // first press again.
+        let (_, taps) = set.lookup(modifiers::OPTION, 4).unwrap();
+        assert_eq!(taps, 1);
+    }
+
+    #[test]
+    fn double_only_chord_is_bound_but_silent_on_the_first_press() {
+        let mut set = KeyboardMappingSet::compile(&[mapping(2, ActionType::BalanceLayout)]);
+        assert!(set.is_bound(modifiers::OPTION, 4));
+        assert!(set.lookup(modifiers::OPTION, 4).is_none());
+        let (action, taps) = set.lookup(modifiers::OPTION, 4).unwrap();
+        assert!(matches!(action, ActionType::BalanceLayout));
+        assert_eq!(taps, 2);
+    }
+
+    #[test]
+    fn a_different_chord_breaks_the_tap_chain() {
+        let mut set = KeyboardMappingSet::compile(&[
+            mapping(1, ActionType::Retile),
+            mapping(2, ActionType::BalanceLayout),
+            KeyboardMapping {
+                modifiers: modifiers::OPTION,
+                keycode: 38, // kVK_ANSI_J
+                taps: 1,
+                action: ActionType::Retile,
+            },
+        ]);
+        set.lookup(modifiers::OPTION, 4).unwrap();
+        set.lookup(modifiers::OPTION, 38).unwrap();
+        // The intervening press means this is a fresh single tap.
+        let (_, taps) = set.lookup(modifiers::OPTION, 4).unwrap();
+        assert_eq!(taps, 1);
+    }
+
+    #[test]
+    fn workspace_modifier_follows_the_index_bindings() {
+        assert_eq!(workspace_modifier(&[]), None);
+        let mappings: Vec<KeyboardMapping> = (1..=3)
+            .map(|index| KeyboardMapping {
+                modifiers: modifiers::OPTION,
+                keycode: 17 + index as u16,
+                taps: 1,
+                action: ActionType::SwitchWorkspaceIndex {
+                    index: index as usize,
+                },
+            })
+            .chain(std::iter::once(mapping(1, ActionType::Retile)))
+            .collect();
+        assert_eq!(workspace_modifier(&mappings), Some(modifiers::OPTION));
+    }
+}
@@ -0,0 +1,174 @@
+//! Hot-corner and trackpad-gesture triggers.
+//!
+//! Both compete with Mission Control for the same physical gestures, so
+//! every trigger carries its own enable flag and everything defaults to
+//! off; a user opts into exactly the corners and swipes the system is not
+//! already using. The macos layer feeds cursor positions and swipe events
+//! in; this module owns the config schema and the dwell state machine so
+//! it stays testable off-mac.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::display::DisplayInfo;
+use crate::models::ActionType;
+
+/// Cursor must be within this many points of the corner to count.
+pub const CORNER_SIZE: f64 = 4.0;
+
+/// One of the four corners of a display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// A hot-corner binding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CornerTrigger {
+    /// Off by default so a fresh config never shadows Mission Control's
+    /// own hot corners.
+    #[serde(default)]
+    pub enabled: bool,
+    pub corner: HotCorner,
+    /// The cursor must dwell in the corner this long before the action
+    /// fires, filtering out drive-by mouse moves.
+    #[serde(default = "default_dwell_ms")]
+    pub dwell_ms: u64,
+    pub action: ActionType,
+}
+
+fn default_dwell_ms() -> u64 {
+    250
+}
+
+/// Trackpad swipe direction, from the user's perspective.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// A trackpad-gesture binding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GestureTrigger {
+    /// Off by default; three- and four-finger swipes usually belong to
+    /// Mission Control.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Finger count the swipe must use.
+    #[serde(default = "default_fingers")]
+    pub fingers: u8,
+    pub direction: SwipeDirection,
+    pub action: ActionType,
+}
+
+fn default_fingers() -> u8 {
+    4
+}
+
+/// All configured triggers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TriggerConfig {
+    pub hot_corners: Vec<CornerTrigger>,
+    pub gestures: Vec<GestureTrigger>,
+}
+
+impl TriggerConfig {
+    /// The action bound to a swipe, if any enabled trigger matches.
+    pub fn swipe_action(&self, fingers: u8, direction: SwipeDirection) -> Option<&ActionType> {
+        self.gestures
+            .iter()
+            .find(|g| g.enabled && g.fingers == fingers && g.direction == direction)
+            .map(|g| &g.action)
+    }
+}
+
+/// Dwell state machine for hot corners.
+///
+/// Fed cursor positions by the macos event monitor; fires a trigger's
+/// action once when the cursor has sat in its corner for `dwell_ms`, then
+/// re-arms only after the cursor leaves the corner.
+#[derive(Debug, Default)]
+pub struct HotCornerTracker {
+    /// When the cursor entered the corner it is currently in, and whether
+    /// that visit already fired.
+    dwell: Option<(HotCorner, Instant, bool)>,
+    /// Corner regions recomputed on display changes.
+    regions: HashMap<HotCorner, (f64, f64)>,
+}
+
+impl HotCornerTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Recompute corner positions from the attached displays. Corners are
+    /// taken from the bounding box of all display frames, matching where
+    /// the cursor actually pins.
+    pub fn update_displays(&mut self, displays: &[DisplayInfo]) {
+        self.regions.clear();
+        let Some(first) = displays.first() else {
+            return;
+        };
+        let (mut min_x, mut min_y) = (first.frame.x, first.frame.y);
+        let (mut max_x, mut max_y) = (
+            first.frame.x + first.frame.width,
+            first.frame.y + first.frame.height,
+        );
+        for d in &displays[1..] {
+            min_x = min_x.min(d.frame.x);
+            min_y = min_y.min(d.frame.y);
+            max_x = max_x.max(d.frame.x + d.frame.width);
+            max_y = max_y.max(d.frame.y + d.frame.height);
+        }
+        self.regions.insert(HotCorner::TopLeft, (min_x, min_y));
+        self.regions.insert(HotCorner::TopRight, (max_x, min_y));
+        self.regions.insert(HotCorner::BottomLeft, (min_x, max_y));
+        self.regions.insert(HotCorner::BottomRight, (max_x, max_y));
+    }
+
+    /// Feed a cursor position; returns the action to dispatch when a
+    /// dwell completes.
+    pub fn observe<'a>(
+        &mut self,
+        x: f64,
+        y: f64,
+        now: Instant,
+        config: &'a TriggerConfig,
+    ) -> Option<&'a ActionType> {
+        let corner = self.regions.iter().find_map(|(&corner, &(cx, cy))| {
+            ((x - cx).abs() <= CORNER_SIZE && (y - cy).abs() <= CORNER_SIZE).then_some(corner)
+        });
+        let Some(corner) = corner else {
+            self.dwell = None;
+            return None;
+        };
+        let (entered, fired) = match self.dwell {
+            Some((c, entered, fired)) if c == corner => (entered, fired),
+            _ => (now, false),
+        };
+        self.dwell = Some((corner, entered, fired));
+        if fired {
+            return None;
+        }
+        let trigger = config
+            .hot_corners
+            .iter()
+            .find(|t| t.enabled && t.corner == corner)?;
+        if now.duration_since(entered) < Duration::from_millis(trigger.dwell_ms) {
+            return None;
+        }
+        self.dwell = Some((corner, entered, true));
+        Some(&trigger.action)
+    }
+}
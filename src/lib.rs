@@ -0,0 +1,27 @@
+//! TilleRS — a keyboard-driven tiling window manager for macOS.
+
+pub mod cli;
+pub mod config;
+pub mod core;
+pub mod daemon;
+pub mod diagnostics;
+pub mod errors;
+pub mod events;
+pub mod ffi;
+pub mod i18n;
+pub mod integrations;
+pub mod ipc;
+pub mod keyboard;
+#[cfg(target_os = "macos")]
+pub mod macos;
+pub mod metrics;
+pub mod models;
+pub mod plugins;
+pub mod stats;
+pub mod sync;
+pub mod tiling;
+pub mod ui;
+pub mod window_system;
+pub mod workspace;
+
+pub use errors::{Result, TilleRSError};
@@ -0,0 +1,390 @@
+//! Accessibility (AX) operations on windows.
+//!
+//! AX has no lookup from CGWindowID to AXUIElement, so we keep a cache of
+//! per-app window elements keyed by the `_AXUIElementGetWindow` private
+//! call (the same approach yabai uses).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use accessibility_sys::{
+    kAXErrorSuccess, kAXPositionAttribute, kAXSizeAttribute, kAXValueTypeCGPoint,
+    kAXValueTypeCGSize, AXUIElementCopyAttributeValue, AXUIElementRef,
+    AXUIElementSetAttributeValue, AXValueCreate, AXValueGetValue,
+};
+use core_foundation::base::{CFRelease, TCFType};
+use core_foundation::string::CFString;
+use core_graphics::geometry::{CGPoint, CGSize};
+
+use crate::errors::{AxErrorCode, AxRecovery, Result, TilleRSError};
+use crate::models::{Rect, WindowId};
+
+/// Cache of AX elements for known windows, maintained by the event layer.
+static ELEMENT_CACHE: Mutex<Option<HashMap<WindowId, usize>>> = Mutex::new(None);
+
+/// Register (or refresh) the AX element for a window. Called by the event
+/// layer when windows are discovered; the element is retained by the cache.
+pub(crate) fn cache_element(window: WindowId, element: AXUIElementRef) {
+    let mut cache = ELEMENT_CACHE.lock().unwrap();
+    cache
+        .get_or_insert_with(HashMap::new)
+        .insert(window, element as usize);
+}
+
+/// Number of AX element handles currently cached, for memory metrics.
+pub fn cached_element_count() -> usize {
+    ELEMENT_CACHE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(HashMap::len)
+        .unwrap_or(0)
+}
+
+fn element_for(window: WindowId) -> Result<AXUIElementRef> {
+    let cache = ELEMENT_CACHE.lock().unwrap();
+    cache
+        .as_ref()
+        .and_then(|c| c.get(&window))
+        .map(|&ptr| ptr as AXUIElementRef)
+        .ok_or_else(|| TilleRSError::NotFound {
+            kind: "window",
+            name: window.to_string(),
+        })
+}
+
+/// Move and resize a window via AX.
+pub fn set_window_frame(window: WindowId, frame: Rect) -> Result<()> {
+    let element = element_for(window)?;
+    let position = CGPoint::new(frame.x, frame.y);
+    let size = CGSize::new(frame.width, frame.height);
+
+    unsafe {
+        let pos_value = AXValueCreate(kAXValueTypeCGPoint, &position as *const _ as *const _);
+        let err = AXUIElementSetAttributeValue(
+            element,
+            CFString::from_static_string(kAXPositionAttribute).as_concrete_TypeRef(),
+            pos_value as _,
+        );
+        CFRelease(pos_value as _);
+        if err != kAXErrorSuccess {
+            return Err(ax_error("set position", window, err));
+        }
+
+        let size_value = AXValueCreate(kAXValueTypeCGSize, &size as *const _ as *const _);
+        let err = AXUIElementSetAttributeValue(
+            element,
+            CFString::from_static_string(kAXSizeAttribute).as_concrete_TypeRef(),
+            size_value as _,
+        );
+        CFRelease(size_value as _);
+        if err != kAXErrorSuccess {
+            return Err(ax_error("set size", window, err));
+        }
+    }
+    Ok(())
+}
+
+/// Read a window's current frame back via AX.
+pub fn window_frame(window: WindowId) -> Result<Rect> {
+    let element = element_for(window)?;
+    let mut position = CGPoint::new(0.0, 0.0);
+    let mut size = CGSize::new(0.0, 0.0);
+
+    unsafe {
+        let mut value: core_foundation::base::CFTypeRef = std::ptr::null();
+        let err = AXUIElementCopyAttributeValue(
+            element,
+            CFString::from_static_string(kAXPositionAttribute).as_concrete_TypeRef(),
+            &mut value,
+        );
+        if err != kAXErrorSuccess {
+            return Err(ax_error("read position", window, err));
+        }
+        AXValueGetValue(
+            value as _,
+            kAXValueTypeCGPoint,
+            &mut position as *mut _ as *mut _,
+        );
+        CFRelease(value);
+
+        let mut value: core_foundation::base::CFTypeRef = std::ptr::null();
+        let err = AXUIElementCopyAttributeValue(
+            element,
+            CFString::from_static_string(kAXSizeAttribute).as_concrete_TypeRef(),
+            &mut value,
+        );
+        if err != kAXErrorSuccess {
+            return Err(ax_error("read size", window, err));
+        }
+        AXValueGetValue(
+            value as _,
+            kAXValueTypeCGSize,
+            &mut size as *mut _ as *mut _,
+        );
+        CFRelease(value);
+    }
+    Ok(Rect {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// How many times a set-frame is re-issued before the window is declared
+/// non-compliant for this pass.
+pub const SET_FRAME_RETRIES: usize = 2;
+
+/// Frames within this many points per edge count as applied; apps are
+/// allowed to round to their own size increments (terminals, Java).
+pub const VERIFY_TOLERANCE: f64 = 4.0;
+
+/// Set a window's frame, then read it back and retry while the app
+/// silently ignores or partially applies the set.
+///
+/// Returns `Ok(true)` if the frame verified within tolerance, `Ok(false)`
+/// if the window accepted the AX calls but never settled on the target —
+/// the caller decides whether to reclassify it as floating.
+pub fn set_window_frame_verified(window: WindowId, frame: Rect) -> Result<bool> {
+    for attempt in 0..=SET_FRAME_RETRIES {
+        match set_window_frame(window, frame) {
+            Ok(()) => {}
+            // Transient failures (app busy) retry like an unverified set;
+            // anything else — stale element, unimplemented op, revoked
+            // trust — will not improve with repetition and propagates.
+            Err(TilleRSError::Ax { code, .. })
+                if code.recovery() == AxRecovery::Retry && attempt < SET_FRAME_RETRIES =>
+            {
+                tracing::debug!(window, attempt, %code, "set-frame failed transiently, retrying");
+                continue;
+            }
+            Err(err) => return Err(err),
+        }
+        let actual = window_frame(window)?;
+        if within_tolerance(&actual, &frame) {
+            return Ok(true);
+        }
+        tracing::debug!(
+            window,
+            attempt,
+            ?actual,
+            target = ?frame,
+            "set-frame did not stick, retrying"
+        );
+    }
+    Ok(false)
+}
+
+fn within_tolerance(actual: &Rect, target: &Rect) -> bool {
+    (actual.x - target.x).abs() <= VERIFY_TOLERANCE
+        && (actual.y - target.y).abs() <= VERIFY_TOLERANCE
+        && (actual.width - target.width).abs() <= VERIFY_TOLERANCE
+        && (actual.height - target.height).abs() <= VERIFY_TOLERANCE
+}
+
+/// Focus a window according to the raise policy.
+///
+/// - `Window`: raise just this window (AXRaise) and make it main.
+/// - `App`: activate the owning application, raising all its windows.
+/// - `None`: move AX focus without raising; the window keeps its z-order.
+pub fn focus_window(
+    window: WindowId,
+    pid: i32,
+    policy: crate::models::app_profile::RaisePolicy,
+) -> Result<()> {
+    use crate::models::app_profile::RaisePolicy;
+    use accessibility_sys::{AXUIElementCreateApplication, AXUIElementPerformAction};
+
+    let element = element_for(window)?;
+    unsafe {
+        match policy {
+            RaisePolicy::Window => {
+                let err = AXUIElementPerformAction(
+                    element,
+                    CFString::from_static_string("AXRaise").as_concrete_TypeRef(),
+                );
+                if err != kAXErrorSuccess {
+                    return Err(ax_error("raise", window, err));
+                }
+            }
+            RaisePolicy::App => {
+                let app =
+                    objc2_app_kit::NSRunningApplication::runningApplicationWithProcessIdentifier(
+                        pid,
+                    )
+                    .ok_or_else(|| TilleRSError::NotFound {
+                        kind: "application",
+                        name: pid.to_string(),
+                    })?;
+                #[allow(deprecated)]
+                app.activateWithOptions(
+                    objc2_app_kit::NSApplicationActivationOptions::ActivateAllWindows,
+                );
+            }
+            RaisePolicy::None => {}
+        }
+        // All policies move keyboard focus to the window.
+        let app_element = AXUIElementCreateApplication(pid);
+        let err = AXUIElementSetAttributeValue(
+            app_element,
+            CFString::from_static_string("AXFocusedWindow").as_concrete_TypeRef(),
+            element as _,
+        );
+        if err != kAXErrorSuccess {
+            return Err(ax_error("focus", window, err));
+        }
+    }
+    Ok(())
+}
+
+/// Hide a window by minimizing it via AX.
+pub fn hide_window(window: WindowId) -> Result<()> {
+    set_minimized(window, true)
+}
+
+/// Set or clear a window's minimized state via AX.
+pub fn set_minimized(window: WindowId, minimized: bool) -> Result<()> {
+    let element = element_for(window)?;
+    let value = if minimized {
+        core_foundation::boolean::CFBoolean::true_value()
+    } else {
+        core_foundation::boolean::CFBoolean::false_value()
+    };
+    unsafe {
+        let err = AXUIElementSetAttributeValue(
+            element,
+            CFString::from_static_string("AXMinimized").as_concrete_TypeRef(),
+            value.as_CFTypeRef(),
+        );
+        if err != kAXErrorSuccess {
+            return Err(ax_error("minimize", window, err));
+        }
+    }
+    Ok(())
+}
+
+/// Close a window by pressing its close button via AX — the same path as
+/// clicking the red traffic light, so apps keep their chance to show a
+/// save dialog instead of being killed.
+pub fn close_window(window: WindowId) -> Result<()> {
+    use accessibility_sys::AXUIElementPerformAction;
+
+    let element = element_for(window)?;
+    unsafe {
+        let mut button: core_foundation::base::CFTypeRef = std::ptr::null();
+        let err = AXUIElementCopyAttributeValue(
+            element,
+            CFString::from_static_string("AXCloseButton").as_concrete_TypeRef(),
+            &mut button,
+        );
+        if err != kAXErrorSuccess || button.is_null() {
+            return Err(ax_error("find close button", window, err));
+        }
+        let err = AXUIElementPerformAction(
+            button as AXUIElementRef,
+            CFString::from_static_string("AXPress").as_concrete_TypeRef(),
+        );
+        CFRelease(button);
+        if err != kAXErrorSuccess {
+            return Err(ax_error("close", window, err));
+        }
+    }
+    Ok(())
+}
+
+/// Raise a window to the front of its layer without changing focus
+/// ownership beyond what AXRaise implies.
+pub fn raise_window(window: WindowId) -> Result<()> {
+    use accessibility_sys::AXUIElementPerformAction;
+
+    let element = element_for(window)?;
+    unsafe {
+        let err = AXUIElementPerformAction(
+            element,
+            CFString::from_static_string("AXRaise").as_concrete_TypeRef(),
+        );
+        if err != kAXErrorSuccess {
+            return Err(ax_error("raise", window, err));
+        }
+    }
+    Ok(())
+}
+
+/// CGWindowIDs of sheets and modal children attached to a window.
+///
+/// Sheets live under `AXSheets`; app-modal dialogs surface as children
+/// with the `AXDialog` subrole. Either way they must move with the parent
+/// and never be tiled on their own.
+pub fn attached_children(window: WindowId) -> Result<Vec<WindowId>> {
+    extern "C" {
+        // Private but stable since 10.5; the only CGWindowID <-> AXUIElement
+        // bridge that exists. Same dependency as the element cache.
+        fn _AXUIElementGetWindow(element: AXUIElementRef, out: *mut u32) -> i32;
+    }
+
+    let element = element_for(window)?;
+    let mut children = Vec::new();
+    unsafe {
+        let mut value: core_foundation::base::CFTypeRef = std::ptr::null();
+        let err = AXUIElementCopyAttributeValue(
+            element,
+            CFString::from_static_string("AXSheets").as_concrete_TypeRef(),
+            &mut value,
+        );
+        if err == kAXErrorSuccess && !value.is_null() {
+            let sheets: core_foundation::array::CFArray =
+                core_foundation::array::CFArray::wrap_under_create_rule(value as _);
+            for sheet in sheets.iter() {
+                let mut id: u32 = 0;
+                if _AXUIElementGetWindow(*sheet as AXUIElementRef, &mut id) == kAXErrorSuccess {
+                    children.push(id);
+                }
+            }
+        }
+    }
+    Ok(children)
+}
+
+/// The CGWindowID of the window holding keyboard focus: the frontmost
+/// application's `AXFocusedWindow`. Uses the same private bridge as the
+/// element cache to get from the AX element back to a CGWindowID.
+pub fn focused_window() -> Result<WindowId> {
+    extern "C" {
+        fn _AXUIElementGetWindow(element: AXUIElementRef, out: *mut u32) -> i32;
+    }
+    use accessibility_sys::AXUIElementCreateApplication;
+
+    let pid = crate::macos::frontmost_application_pid().ok_or_else(|| TilleRSError::NotFound {
+        kind: "application",
+        name: "frontmost".to_string(),
+    })?;
+    unsafe {
+        let app = AXUIElementCreateApplication(pid);
+        let mut value: core_foundation::base::CFTypeRef = std::ptr::null();
+        let err = AXUIElementCopyAttributeValue(
+            app,
+            CFString::from_static_string("AXFocusedWindow").as_concrete_TypeRef(),
+            &mut value,
+        );
+        CFRelease(app as _);
+        if err != kAXErrorSuccess || value.is_null() {
+            return Err(ax_error("read focused window", 0, err));
+        }
+        let mut id: u32 = 0;
+        let bridged = _AXUIElementGetWindow(value as AXUIElementRef, &mut id);
+        CFRelease(value);
+        if bridged != kAXErrorSuccess {
+            return Err(ax_error("bridge focused window", 0, bridged));
+        }
+        Ok(id)
+    }
+}
+
+fn ax_error(op: &'static str, window: WindowId, code: i32) -> TilleRSError {
+    TilleRSError::Ax {
+        op,
+        window,
+        code: AxErrorCode::from_raw(code),
+    }
+}
@@ -0,0 +1,100 @@
+//! Window image capture for tray thumbnails.
+//!
+//! Capturing window contents requires the Screen Recording permission;
+//! without it CGWindowListCreateImage silently returns empty frames, so
+//! callers must check [`screen_recording_granted`] and fall back to app
+//! icons instead of showing blank thumbnails.
+
+use core_graphics::display::{
+    kCGWindowImageBoundsIgnoreFraming, kCGWindowListOptionIncludingWindow, CGWindowListCreateImage,
+};
+use core_graphics::geometry::CGRect;
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::WindowId;
+
+/// Whether the process may capture screen contents.
+pub fn screen_recording_granted() -> bool {
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+    }
+    unsafe { CGPreflightScreenCaptureAccess() }
+}
+
+/// Whether a window is marked content-protected (banking apps, password
+/// managers set `sharingType = .none`). Unknown windows report protected:
+/// if the sharing state cannot be read, nothing should be captured.
+pub fn content_protected(window: WindowId) -> bool {
+    use core_foundation::array::CFArray;
+    use core_foundation::base::TCFType;
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::number::CFNumber;
+    use core_foundation::string::CFString;
+    use core_graphics::display::CGWindowListCopyWindowInfo;
+
+    let raw = unsafe { CGWindowListCopyWindowInfo(kCGWindowListOptionIncludingWindow, window) };
+    if raw.is_null() {
+        return true;
+    }
+    let array: CFArray<CFDictionary<CFString, core_foundation::base::CFType>> =
+        unsafe { CFArray::wrap_under_create_rule(raw as _) };
+    let sharing = array.iter().next().and_then(|dict| {
+        dict.find(CFString::new("kCGWindowSharingState"))
+            .and_then(|v| v.downcast::<CFNumber>())
+            .and_then(|n| n.to_i64())
+    });
+    // kCGWindowSharingNone = 0; ReadOnly/ReadWrite may be captured.
+    !matches!(sharing, Some(state) if state != 0)
+}
+
+/// A captured window image, downscaled for tray display.
+#[derive(Debug, Clone)]
+pub struct WindowImage {
+    pub width: usize,
+    pub height: usize,
+    /// Tightly packed RGBA, row-major.
+    pub rgba: Vec<u8>,
+}
+
+/// Capture a window's contents. Fails when Screen Recording permission is
+/// missing, the window is content-protected, or it has gone away.
+pub fn capture_window(window: WindowId) -> Result<WindowImage> {
+    if !screen_recording_granted() {
+        return Err(TilleRSError::Validation(
+            "Screen Recording permission not granted".into(),
+        ));
+    }
+    // Enforced here, in the one place every capture path funnels through,
+    // so no caller can forget to honor content protection.
+    if content_protected(window) {
+        return Err(TilleRSError::Permission(format!(
+            "window {window} is content-protected"
+        )));
+    }
+    let image = unsafe {
+        CGWindowListCreateImage(
+            CGRect::new(
+                &core_graphics::geometry::CGPoint::new(0.0, 0.0),
+                &core_graphics::geometry::CGSize::new(0.0, 0.0),
+            ),
+            kCGWindowListOptionIncludingWindow,
+            window,
+            kCGWindowImageBoundsIgnoreFraming,
+        )
+    };
+    if image.is_null() {
+        return Err(TilleRSError::NotFound {
+            kind: "window",
+            name: window.to_string(),
+        });
+    }
+    let image = unsafe { core_graphics::image::CGImage::from_ptr(image) };
+    let width = image.width();
+    let height = image.height();
+    let data = image.data();
+    Ok(WindowImage {
+        width,
+        height,
+        rgba: data.bytes().to_vec(),
+    })
+}
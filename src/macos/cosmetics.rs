@@ -0,0 +1,66 @@
+//! Window alpha via the private window-server API.
+//!
+//! There is no public API to change another app's window transparency;
+//! the window server's SkyLight framework has one (`SLSSetWindowAlpha`),
+//! stable for a decade and what every tiling WM on macOS uses. It is
+//! resolved at runtime with `libloading` so a build never links it: if a
+//! macOS release drops or gates the symbol, opacity rules silently fall
+//! back to opaque windows instead of breaking the daemon.
+
+use std::sync::OnceLock;
+
+use crate::models::WindowId;
+
+/// `(SLSMainConnectionID, SLSSetWindowAlpha)`, resolved once. `None`
+/// when SkyLight is missing the symbols.
+type AlphaFns = (
+    unsafe extern "C" fn() -> i32,
+    unsafe extern "C" fn(cid: i32, wid: u32, alpha: f32) -> i32,
+);
+
+fn alpha_fns() -> Option<&'static AlphaFns> {
+    static FNS: OnceLock<Option<AlphaFns>> = OnceLock::new();
+    FNS.get_or_init(|| {
+        let library = unsafe {
+            libloading::Library::new(
+                "/System/Library/PrivateFrameworks/SkyLight.framework/SkyLight",
+            )
+        }
+        .ok()?;
+        let fns = unsafe {
+            let connection = *library.get(b"SLSMainConnectionID\0").ok()?;
+            let set_alpha = *library.get(b"SLSSetWindowAlpha\0").ok()?;
+            (connection, set_alpha)
+        };
+        // Leak the handle: the functions must stay valid for the process
+        // lifetime and SkyLight is loaded into every GUI process anyway.
+        std::mem::forget(library);
+        Some(fns)
+    })
+    .as_ref()
+}
+
+/// Whether window alpha can be changed on this system.
+pub fn alpha_supported() -> bool {
+    alpha_fns().is_some()
+}
+
+/// Set a window's alpha. Returns `Ok(false)` (after logging once per
+/// process) when the private API is unavailable — the permission-aware
+/// fallback path.
+pub fn set_window_alpha(window: WindowId, alpha: f64) -> crate::errors::Result<bool> {
+    let Some((connection, set_alpha)) = alpha_fns() else {
+        static WARNED: OnceLock<()> = OnceLock::new();
+        WARNED.get_or_init(|| {
+            tracing::warn!("SLSSetWindowAlpha unavailable; opacity rules are ignored");
+        });
+        return Ok(false);
+    };
+    let status = unsafe { set_alpha(connection(), window, alpha.clamp(0.0, 1.0) as f32) };
+    if status != 0 {
+        return Err(crate::errors::TilleRSError::Validation(format!(
+            "SLSSetWindowAlpha failed ({status}) for window {window}"
+        )));
+    }
+    Ok(true)
+}
@@ -0,0 +1,136 @@
+//! Hot-corner and trackpad-gesture event monitoring.
+//!
+//! Hot corners are detected by polling the global cursor position — an
+//! event tap would wake us on every mouse move, while a 50 ms poll is
+//! invisible both to the user and in the energy profile. Swipes come from
+//! an `NSEvent` global monitor; AppKit only delivers swipe events the
+//! system has not claimed for Mission Control, and does not report the
+//! finger count on them, so the configured count is advisory and the
+//! per-trigger enable flag is the real conflict guard.
+
+use std::time::{Duration, Instant};
+
+use crate::keyboard::triggers::{HotCornerTracker, SwipeDirection, TriggerConfig};
+use crate::models::ActionType;
+
+/// Cursor poll cadence for hot corners.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Display layout refresh cadence while polling.
+const DISPLAY_REFRESH: Duration = Duration::from_secs(5);
+
+/// Global cursor position without consuming an event.
+pub fn cursor_location() -> Option<(f64, f64)> {
+    #[repr(C)]
+    struct CGPoint {
+        x: f64,
+        y: f64,
+    }
+    extern "C" {
+        fn CGEventCreate(source: *const std::ffi::c_void) -> *mut std::ffi::c_void;
+        fn CGEventGetLocation(event: *mut std::ffi::c_void) -> CGPoint;
+        fn CFRelease(cf: *mut std::ffi::c_void);
+    }
+    unsafe {
+        let event = CGEventCreate(std::ptr::null());
+        if event.is_null() {
+            return None;
+        }
+        let point = CGEventGetLocation(event);
+        CFRelease(event);
+        Some((point.x, point.y))
+    }
+}
+
+/// Poll the cursor and fire hot-corner actions on a background thread.
+/// Does nothing (and spawns nothing) when no corner trigger is enabled.
+pub fn spawn_hot_corner_monitor(
+    config: TriggerConfig,
+    dispatch: impl Fn(&ActionType) + Send + 'static,
+) -> Option<std::thread::JoinHandle<()>> {
+    if !config.hot_corners.iter().any(|t| t.enabled) {
+        return None;
+    }
+    let handle = std::thread::Builder::new()
+        .name("tillers-hot-corners".into())
+        .spawn(move || {
+            let mut tracker = HotCornerTracker::new();
+            let mut displays_refreshed = Instant::now() - DISPLAY_REFRESH;
+            loop {
+                let now = Instant::now();
+                if now.duration_since(displays_refreshed) >= DISPLAY_REFRESH {
+                    if let Ok(displays) = super::list_displays() {
+                        tracker.update_displays(&displays);
+                    }
+                    displays_refreshed = now;
+                }
+                if let Some((x, y)) = cursor_location() {
+                    if let Some(action) = tracker.observe(x, y, now, &config) {
+                        tracing::debug!(?action, "hot corner fired");
+                        dispatch(action);
+                    }
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        })
+        .expect("spawn hot-corner monitor thread");
+    Some(handle)
+}
+
+/// Install a global `NSEvent` monitor for swipe gestures. The returned
+/// monitor object must be kept alive for as long as swipes should fire.
+/// Does nothing when no gesture trigger is enabled.
+pub fn install_swipe_monitor(
+    config: TriggerConfig,
+    dispatch: impl Fn(&ActionType) + 'static,
+) -> Option<objc2::rc::Retained<objc2::runtime::AnyObject>> {
+    use objc2::msg_send;
+    use objc2::runtime::AnyObject;
+    use objc2_app_kit::{NSEvent, NSEventMask};
+
+    if !config.gestures.iter().any(|g| g.enabled) {
+        return None;
+    }
+    let block = block2::RcBlock::new(move |event: std::ptr::NonNull<NSEvent>| {
+        let event = unsafe { event.as_ref() };
+        let (dx, dy) = unsafe { (event.deltaX(), event.deltaY()) };
+        let Some(direction) = swipe_direction(dx, dy) else {
+            return;
+        };
+        // AppKit does not expose the finger count on global swipe events;
+        // first enabled trigger for the direction wins.
+        if let Some(trigger) = config
+            .gestures
+            .iter()
+            .find(|g| g.enabled && g.direction == direction)
+        {
+            tracing::debug!(?direction, "swipe gesture fired");
+            dispatch(&trigger.action);
+        }
+    });
+    let monitor: Option<objc2::rc::Retained<AnyObject>> = unsafe {
+        msg_send![
+            NSEvent::class(),
+            addGlobalMonitorForEventsMatchingMask: NSEventMask::Swipe,
+            handler: &*block
+        ]
+    };
+    monitor
+}
+
+/// Classify a swipe's deltas. AppKit reports a positive `deltaX` for a
+/// physical left-to-right swipe.
+fn swipe_direction(dx: f64, dy: f64) -> Option<SwipeDirection> {
+    if dx.abs() >= dy.abs() {
+        if dx > 0.0 {
+            Some(SwipeDirection::Right)
+        } else if dx < 0.0 {
+            Some(SwipeDirection::Left)
+        } else {
+            None
+        }
+    } else if dy > 0.0 {
+        Some(SwipeDirection::Up)
+    } else {
+        Some(SwipeDirection::Down)
+    }
+}
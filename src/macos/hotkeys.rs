@@ -0,0 +1,255 @@
+//! The global hotkey event tap.
+//!
+//! A session-level CGEvent tap sees every key press before the focused
+//! application does. The callback does a single probe against the compiled
+//! [`KeyboardMappingSet`]; matched chords are swallowed and their actions
+//! handed to a dispatcher thread, everything else passes through
+//! untouched. Dispatch runs off the tap thread because the window server
+//! disables taps whose callback stalls — a slow arrange pass must cost one
+//! action's latency, never keyboard delivery.
+
+use std::ffi::c_void;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+use core_foundation::base::TCFType;
+use core_foundation::mach_port::{CFMachPort, CFMachPortRef};
+use core_foundation::runloop::{kCFRunLoopCommonModes, kCFRunLoopDefaultMode, CFRunLoop};
+
+use crate::errors::{Result, TilleRSError};
+use crate::keyboard::hold::{HoldEvent, ModifierHold};
+use crate::keyboard::{chord_stat_key, modifiers, KeyboardMappingSet};
+use crate::models::ActionType;
+
+/// `kCGSessionEventTap`: taps this login session, not raw HID.
+const SESSION_EVENT_TAP: u32 = 1;
+/// `kCGHeadInsertEventTap`: run before other taps so bound chords never
+/// reach the focused application.
+const HEAD_INSERT_EVENT_TAP: u32 = 0;
+/// `kCGEventTapOptionDefault`: an active tap that may swallow events.
+const TAP_OPTION_DEFAULT: u32 = 0;
+
+/// `kCGEventKeyDown`.
+const EVENT_KEY_DOWN: u32 = 10;
+/// `kCGEventFlagsChanged`: modifier presses and releases, which feed the
+/// press-and-hold detector for the quick-switch HUD.
+const EVENT_FLAGS_CHANGED: u32 = 12;
+
+/// `kCGKeyboardEventAutorepeat`.
+const FIELD_AUTOREPEAT: u32 = 8;
+/// `kCGKeyboardEventKeycode`.
+const FIELD_KEYCODE: u32 = 9;
+
+// CGEventFlags modifier masks.
+const FLAG_SHIFT: u64 = 1 << 17;
+const FLAG_CONTROL: u64 = 1 << 18;
+const FLAG_OPTION: u64 = 1 << 19;
+const FLAG_COMMAND: u64 = 1 << 20;
+
+/// Run-loop servicing slice between tap health checks; doubles as the
+/// hold-detection tick, so it must stay well under
+/// [`HOLD_THRESHOLD`](crate::keyboard::hold::HOLD_THRESHOLD).
+const TAP_TICK: Duration = Duration::from_millis(100);
+
+type CGEventRef = *mut c_void;
+
+extern "C" {
+    fn CGEventTapCreate(
+        tap: u32,
+        place: u32,
+        options: u32,
+        events_of_interest: u64,
+        callback: extern "C" fn(*mut c_void, u32, CGEventRef, *mut c_void) -> CGEventRef,
+        user_info: *mut c_void,
+    ) -> CFMachPortRef;
+    fn CGEventTapEnable(tap: CFMachPortRef, enable: bool);
+    fn CGEventTapIsEnabled(tap: CFMachPortRef) -> bool;
+    fn CGEventGetIntegerValueField(event: CGEventRef, field: u32) -> i64;
+    fn CGEventGetFlags(event: CGEventRef) -> u64;
+}
+
+/// What the tap hands the dispatcher thread.
+enum TapOutput {
+    /// A matched chord: its action and statistics key.
+    Action(ActionType, String),
+    /// The watched modifier's hold state changed.
+    Hold(HoldEvent),
+}
+
+/// State the tap callback reads through its `user_info` pointer; leaked
+/// once at registration because the tap lives for the daemon's lifetime.
+/// The mutexes are uncontended — callback and tick both run on the tap
+/// thread — but the callback only sees a shared reference.
+struct TapState {
+    keymap: Arc<Mutex<KeyboardMappingSet>>,
+    /// Press-and-hold detection for the workspace modifier; `None` when
+    /// the config has no numeric workspace bindings to watch.
+    hold: Option<Mutex<ModifierHold>>,
+    matched: mpsc::Sender<TapOutput>,
+}
+
+/// Collapse `CGEventFlags` to the normalized modifier bitmask: left/right
+/// variants fold together, caps lock and function flags are ignored.
+fn normalize_flags(flags: u64) -> u8 {
+    let mut mods = 0;
+    if flags & FLAG_SHIFT != 0 {
+        mods |= modifiers::SHIFT;
+    }
+    if flags & FLAG_CONTROL != 0 {
+        mods |= modifiers::CONTROL;
+    }
+    if flags & FLAG_OPTION != 0 {
+        mods |= modifiers::OPTION;
+    }
+    if flags & FLAG_COMMAND != 0 {
+        mods |= modifiers::COMMAND;
+    }
+    mods
+}
+
+/// The tap callback. Runs on the tap thread inside the window server's
+/// latency budget: one table probe, then either pass the event through
+/// (return it) or swallow it (return null) and queue the action.
+extern "C" fn on_key_event(
+    _proxy: *mut c_void,
+    event_type: u32,
+    event: CGEventRef,
+    user_info: *mut c_void,
+) -> CGEventRef {
+    let state = unsafe { &*(user_info as *const TapState) };
+    let mods = normalize_flags(unsafe { CGEventGetFlags(event) });
+    if event_type == EVENT_FLAGS_CHANGED {
+        // Modifier traffic is never consumed; it only drives the hold
+        // detector. Key presses while the HUD is up arrive as KeyDown.
+        if let Some(hold) = &state.hold {
+            if let Some(change) = hold
+                .lock()
+                .unwrap()
+                .flags_changed(mods, std::time::Instant::now())
+            {
+                let _ = state.matched.send(TapOutput::Hold(change));
+            }
+        }
+        return event;
+    }
+    if event_type != EVENT_KEY_DOWN {
+        // Includes the tap-disabled pseudo-events; re-enabling happens on
+        // the tap thread's tick, not here.
+        return event;
+    }
+    let keycode = unsafe { CGEventGetIntegerValueField(event, FIELD_KEYCODE) } as u16;
+    let mut keymap = state.keymap.lock().unwrap();
+    if !keymap.is_bound(mods, keycode) {
+        return event;
+    }
+    // A bound chord never reaches the application, but autorepeat fires
+    // the action only once per physical press.
+    if unsafe { CGEventGetIntegerValueField(event, FIELD_AUTOREPEAT) } == 0 {
+        if let Some((action, taps)) = keymap.lookup(mods, keycode) {
+            let chord = chord_stat_key(mods, keycode, taps);
+            let _ = state.matched.send(TapOutput::Action(action.clone(), chord));
+        }
+    }
+    std::ptr::null_mut()
+}
+
+/// Install the CGEvent tap that feeds global hotkeys and start its two
+/// threads: the tap thread servicing the run loop, and the dispatcher
+/// executing matched actions. `keymap` is shared — the handler recompiles
+/// it in place on config reload and the tap picks the new table up on the
+/// next press. When `hold_modifier` is set, its press-and-hold state is
+/// reported through `on_hold` for the quick-switch HUD. Returns once the
+/// tap is live, or with `Permission` when the window server refuses one
+/// (no Accessibility trust).
+pub fn register_event_tap(
+    keymap: Arc<Mutex<KeyboardMappingSet>>,
+    hold_modifier: Option<u8>,
+    dispatch: impl Fn(&ActionType, &str) + Send + 'static,
+    on_hold: impl Fn(HoldEvent) + Send + 'static,
+) -> Result<()> {
+    let (matched_tx, matched_rx) = mpsc::channel::<TapOutput>();
+    let (ready_tx, ready_rx) = mpsc::channel::<Result<()>>();
+
+    std::thread::Builder::new()
+        .name("tillers-hotkeys".into())
+        .spawn(move || {
+            let mut mask = 1u64 << EVENT_KEY_DOWN;
+            if hold_modifier.is_some() {
+                mask |= 1u64 << EVENT_FLAGS_CHANGED;
+            }
+            let state = Box::leak(Box::new(TapState {
+                keymap,
+                hold: hold_modifier.map(|watched| Mutex::new(ModifierHold::new(watched))),
+                matched: matched_tx,
+            }));
+            let port_ref = unsafe {
+                CGEventTapCreate(
+                    SESSION_EVENT_TAP,
+                    HEAD_INSERT_EVENT_TAP,
+                    TAP_OPTION_DEFAULT,
+                    mask,
+                    on_key_event,
+                    state as *mut TapState as *mut c_void,
+                )
+            };
+            if port_ref.is_null() {
+                let _ = ready_tx.send(Err(TilleRSError::Permission(
+                    "the window server refused an event tap; global hotkeys need \
+                     Accessibility permission"
+                        .into(),
+                )));
+                return;
+            }
+            let port = unsafe { CFMachPort::wrap_under_create_rule(port_ref) };
+            let source = match port.create_runloop_source(0) {
+                Ok(source) => source,
+                Err(()) => {
+                    let _ = ready_tx.send(Err(TilleRSError::Validation(
+                        "could not create a run-loop source for the event tap".into(),
+                    )));
+                    return;
+                }
+            };
+            CFRunLoop::get_current().add_source(&source, unsafe { kCFRunLoopCommonModes });
+            unsafe { CGEventTapEnable(port.as_concrete_TypeRef(), true) };
+            let _ = ready_tx.send(Ok(()));
+            loop {
+                CFRunLoop::run_in_mode(unsafe { kCFRunLoopDefaultMode }, TAP_TICK, false);
+                // Hold detection needs a timer tick: the threshold passes
+                // without any further flags-changed event arriving.
+                if let Some(hold) = &state.hold {
+                    if let Some(change) = hold.lock().unwrap().tick(std::time::Instant::now()) {
+                        let _ = state.matched.send(TapOutput::Hold(change));
+                    }
+                }
+                // The window server disables taps whose callback stalls;
+                // recover instead of going silently deaf.
+                if !unsafe { CGEventTapIsEnabled(port.as_concrete_TypeRef()) } {
+                    tracing::warn!("event tap was disabled; re-enabling");
+                    unsafe { CGEventTapEnable(port.as_concrete_TypeRef(), true) };
+                }
+            }
+        })
+        .map_err(|e| TilleRSError::Validation(format!("spawn hotkey tap thread: {e}")))?;
+
+    std::thread::Builder::new()
+        .name("tillers-hotkey-dispatch".into())
+        .spawn(move || {
+            while let Ok(output) = matched_rx.recv() {
+                match output {
+                    TapOutput::Action(action, chord) => {
+                        tracing::debug!(chord = %chord, "hotkey fired");
+                        dispatch(&action, &chord);
+                    }
+                    TapOutput::Hold(change) => on_hold(change),
+                }
+            }
+        })
+        .map_err(|e| TilleRSError::Validation(format!("spawn hotkey dispatch thread: {e}")))?;
+
+    ready_rx.recv().unwrap_or_else(|_| {
+        Err(TilleRSError::Validation(
+            "event tap thread exited during setup".into(),
+        ))
+    })
+}
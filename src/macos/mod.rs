@@ -0,0 +1,200 @@
+//! macOS platform bindings.
+//!
+//! Everything that touches Accessibility (AX), AppKit, or Core Graphics is
+//! kept behind this module so the rest of the crate stays testable off-mac.
+
+pub mod accessibility;
+pub mod capture;
+pub mod cosmetics;
+pub mod gestures;
+pub mod hotkeys;
+pub mod observers;
+pub mod overlay;
+pub mod probe;
+pub mod statusbar;
+pub mod tabs;
+pub mod windows;
+
+use objc2::msg_send;
+use objc2_app_kit::{NSScreen, NSWorkspace};
+use objc2_foundation::MainThreadMarker;
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::Rect;
+use crate::ui::theme::AccessibilitySettings;
+
+pub use accessibility::{
+    focus_window, hide_window, set_window_frame, set_window_frame_verified, window_frame,
+};
+pub use hotkeys::register_event_tap;
+pub use overlay::show_preview_rects;
+pub use windows::{list_displays, list_windows};
+
+/// Rasterize an application's icon to tightly packed RGBA at `size` px.
+/// Returns `None` when no app with that bundle id is installed.
+pub fn app_icon_rgba(bundle_id: &str, size: usize) -> Option<crate::ui::icons::Icon> {
+    use objc2_app_kit::NSBitmapImageRep;
+    use objc2_foundation::{NSRect, NSSize, NSString};
+
+    let workspace = NSWorkspace::sharedWorkspace();
+    let url =
+        unsafe { workspace.URLForApplicationWithBundleIdentifier(&NSString::from_str(bundle_id))? };
+    let path = unsafe { url.path()? };
+    let image = unsafe { workspace.iconForFile(&path) };
+    unsafe {
+        image.setSize(NSSize::new(size as f64, size as f64));
+        let rect = NSRect::new(
+            objc2_foundation::NSPoint::new(0.0, 0.0),
+            NSSize::new(size as f64, size as f64),
+        );
+        let cg_image: *mut std::ffi::c_void = msg_send![&*image, CGImageForProposedRect: &rect, context: std::ptr::null_mut::<objc2::runtime::AnyObject>(), hints: std::ptr::null_mut::<objc2::runtime::AnyObject>()];
+        if cg_image.is_null() {
+            return None;
+        }
+        let rep = NSBitmapImageRep::initWithCGImage(NSBitmapImageRep::alloc(), cg_image as _);
+        let data = rep.bitmapData();
+        if data.is_null() {
+            return None;
+        }
+        let len = (rep.bytesPerRow() as usize) * (rep.pixelsHigh() as usize);
+        let rgba = std::slice::from_raw_parts(data, len).to_vec();
+        Some(crate::ui::icons::Icon {
+            width: rep.pixelsWide() as usize,
+            height: rep.pixelsHigh() as usize,
+            rgba,
+        })
+    }
+}
+
+/// Teleport the mouse cursor to a point in global display coordinates.
+pub fn warp_cursor(x: f64, y: f64) -> Result<()> {
+    core_graphics::display::CGDisplay::warp_mouse_cursor_position(
+        core_graphics::geometry::CGPoint::new(x, y),
+    )
+    .map_err(|code| TilleRSError::Validation(format!("CGWarpMouseCursorPosition failed ({code})")))
+}
+
+/// Current mouse cursor position in global display coordinates, read
+/// through a throwaway CGEvent (matching the coordinate space window
+/// frames use).
+pub fn cursor_position() -> Option<(f64, f64)> {
+    use core_graphics::event::CGEvent;
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    let source = CGEventSource::new(CGEventSourceStateID::CombinedSessionState).ok()?;
+    let event = CGEvent::new(source).ok()?;
+    let point = event.location();
+    Some((point.x, point.y))
+}
+
+/// Whether this process is trusted for Accessibility control.
+pub fn accessibility_trusted() -> bool {
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+    }
+    unsafe { AXIsProcessTrusted() }
+}
+
+/// Set the desktop image of one display via NSWorkspace.
+pub fn set_desktop_image(
+    display: crate::models::display::DisplayId,
+    path: &std::path::Path,
+) -> Result<()> {
+    use objc2_foundation::{NSString, NSURL};
+
+    let mtm = MainThreadMarker::new().ok_or_else(|| {
+        TilleRSError::Validation("desktop image changes must run on the main thread".into())
+    })?;
+    let workspace = NSWorkspace::sharedWorkspace();
+    let url = unsafe { NSURL::fileURLWithPath(&NSString::from_str(&path.to_string_lossy())) };
+    for screen in NSScreen::screens(mtm).iter() {
+        if screen_display_id(&screen) != Some(display) {
+            continue;
+        }
+        unsafe {
+            workspace
+                .setDesktopImageURL_forScreen_options_error(
+                    &url,
+                    &screen,
+                    &objc2_foundation::NSDictionary::new(),
+                )
+                .map_err(|e| TilleRSError::Validation(format!("set desktop image: {e}")))?;
+        }
+        return Ok(());
+    }
+    Err(TilleRSError::NotFound {
+        kind: "display",
+        name: display.to_string(),
+    })
+}
+
+/// The CGDirectDisplayID behind an NSScreen.
+fn screen_display_id(screen: &NSScreen) -> Option<u32> {
+    use objc2_foundation::NSString;
+
+    let key = NSString::from_str("NSScreenNumber");
+    let number = screen.deviceDescription().objectForKey(&key)?;
+    let id: i64 = unsafe { msg_send![&*number, longLongValue] };
+    u32::try_from(id).ok()
+}
+
+/// Work area of the main display (the display with the focused window),
+/// excluding the menu bar and Dock.
+pub fn main_display_work_area() -> Result<Rect> {
+    let mtm = MainThreadMarker::new().ok_or_else(|| {
+        TilleRSError::Validation("display queries must run on the main thread".into())
+    })?;
+    let screen = NSScreen::mainScreen(mtm).ok_or_else(|| TilleRSError::NotFound {
+        kind: "display",
+        name: "main".into(),
+    })?;
+    let frame = screen.visibleFrame();
+    Ok(Rect {
+        x: frame.origin.x,
+        y: frame.origin.y,
+        width: frame.size.width,
+        height: frame.size.height,
+    })
+}
+
+/// Enumerate running applications as (pid, localized name, bundle id).
+///
+/// Daemons without a bundle (e.g. yabai) still appear with their process
+/// name and a `None` bundle id.
+pub fn running_applications() -> Vec<(i32, String, Option<String>)> {
+    let workspace = NSWorkspace::sharedWorkspace();
+    let apps = workspace.runningApplications();
+    apps.iter()
+        .map(|app| {
+            let pid = app.processIdentifier();
+            let name = app
+                .localizedName()
+                .map(|n| n.to_string())
+                .unwrap_or_default();
+            let bundle = app.bundleIdentifier().map(|b| b.to_string());
+            (pid, name, bundle)
+        })
+        .collect()
+}
+
+/// The pid of the frontmost (focused) application, if any.
+pub fn frontmost_application_pid() -> Option<i32> {
+    NSWorkspace::sharedWorkspace()
+        .frontmostApplication()
+        .map(|app| app.processIdentifier())
+}
+
+/// Read display-related accessibility preferences from NSWorkspace.
+pub fn accessibility_display_settings() -> AccessibilitySettings {
+    let workspace = NSWorkspace::sharedWorkspace();
+    unsafe {
+        AccessibilitySettings {
+            reduce_transparency: msg_send![
+                &*workspace,
+                accessibilityDisplayShouldReduceTransparency
+            ],
+            increase_contrast: msg_send![&*workspace, accessibilityDisplayShouldIncreaseContrast],
+            voiceover_running: msg_send![&*workspace, isVoiceOverEnabled],
+        }
+    }
+}
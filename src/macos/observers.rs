@@ -0,0 +1,230 @@
+//! AX observer registration with an adaptive polling fallback.
+//!
+//! Most apps deliver window events through an `AXObserver`. Sandboxed or
+//! broken apps refuse the registration, and losing them entirely would
+//! mean their windows drift unmanaged. Those apps fall back to a targeted
+//! low-frequency poll of just their windows — the rest of the system stays
+//! event-driven — and registration is retried in the background so an app
+//! that starts behaving is upgraded back to observers automatically.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use accessibility_sys::{
+    kAXErrorSuccess, AXObserverAddNotification, AXObserverCallback, AXObserverCreate,
+    AXObserverGetRunLoopSource, AXObserverRef, AXUIElementCreateApplication,
+};
+use core_foundation::base::TCFType;
+use core_foundation::string::CFString;
+
+use crate::errors::{AxErrorCode, Result, TilleRSError};
+
+/// How often polled apps' windows are re-read.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How often observer registration is retried for a polled app.
+pub const UPGRADE_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The notifications every watched app is registered for.
+const NOTIFICATIONS: &[&str] = &[
+    "AXWindowCreated",
+    "AXUIElementDestroyed",
+    "AXWindowMoved",
+    "AXWindowResized",
+    "AXFocusedWindowChanged",
+    "AXTitleChanged",
+];
+
+/// How one app's windows are currently watched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchMode {
+    /// Event-driven via AXObserver; the normal case.
+    Observed,
+    /// Observer registration failed; windows are polled instead.
+    Polling,
+}
+
+#[derive(Debug)]
+struct WatchState {
+    bundle_id: String,
+    mode: WatchMode,
+    /// When registration last failed, for retry pacing.
+    last_attempt: Instant,
+    /// Consecutive failed registrations, for diagnostics.
+    failures: u32,
+}
+
+/// Per-app watch state: who has observers, who is being polled, and when
+/// to retry upgrading the latter.
+#[derive(Debug, Default)]
+pub struct ObserverRegistry {
+    apps: HashMap<i32, WatchState>,
+}
+
+impl ObserverRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start watching an app: try observer registration, fall back to
+    /// polling when it fails. Returns the mode the app ended up in.
+    pub fn watch(&mut self, pid: i32, bundle_id: &str, callback: AXObserverCallback) -> WatchMode {
+        let mode = match register_observer(pid, callback) {
+            Ok(()) => WatchMode::Observed,
+            Err(err) => {
+                tracing::warn!(
+                    pid,
+                    bundle_id,
+                    %err,
+                    "AX observer registration failed; falling back to polling"
+                );
+                WatchMode::Polling
+            }
+        };
+        let failures = u32::from(mode == WatchMode::Polling);
+        self.apps.insert(
+            pid,
+            WatchState {
+                bundle_id: bundle_id.to_string(),
+                mode,
+                last_attempt: Instant::now(),
+                failures,
+            },
+        );
+        mode
+    }
+
+    /// The pids whose windows the polling loop must re-read.
+    pub fn polled_pids(&self) -> Vec<i32> {
+        self.apps
+            .iter()
+            .filter(|(_, state)| state.mode == WatchMode::Polling)
+            .map(|(&pid, _)| pid)
+            .collect()
+    }
+
+    /// Whether any app needs the polling loop at all; lets the loop park
+    /// itself when everything is event-driven.
+    pub fn any_polling(&self) -> bool {
+        self.apps.values().any(|s| s.mode == WatchMode::Polling)
+    }
+
+    /// Retry observer registration for polled apps whose retry interval
+    /// elapsed; successful ones upgrade back to event delivery. Returns
+    /// the pids that were upgraded.
+    pub fn retry_upgrades(&mut self, now: Instant, callback: AXObserverCallback) -> Vec<i32> {
+        let mut upgraded = Vec::new();
+        for (&pid, state) in &mut self.apps {
+            if state.mode != WatchMode::Polling
+                || now.duration_since(state.last_attempt) < UPGRADE_RETRY_INTERVAL
+            {
+                continue;
+            }
+            state.last_attempt = now;
+            match register_observer(pid, callback) {
+                Ok(()) => {
+                    tracing::info!(
+                        pid,
+                        bundle_id = %state.bundle_id,
+                        failures = state.failures,
+                        "AX observer registration succeeded; leaving polling mode"
+                    );
+                    state.mode = WatchMode::Observed;
+                    upgraded.push(pid);
+                }
+                Err(_) => state.failures += 1,
+            }
+        }
+        upgraded
+    }
+
+    /// Bundle ids of polled apps, for filtering window enumerations down
+    /// to the windows the poll actually owns.
+    pub fn polled_bundle_ids(&self) -> Vec<String> {
+        self.apps
+            .values()
+            .filter(|state| state.mode == WatchMode::Polling)
+            .map(|state| state.bundle_id.clone())
+            .collect()
+    }
+
+    /// The watch mode for an app, if it is watched at all.
+    pub fn mode(&self, pid: i32) -> Option<WatchMode> {
+        self.apps.get(&pid).map(|s| s.mode)
+    }
+
+    /// Stop tracking a terminated app.
+    pub fn forget(&mut self, pid: i32) {
+        self.apps.remove(&pid);
+    }
+}
+
+/// Spawn the targeted polling loop for apps without observers.
+///
+/// Each tick re-reads the window list filtered to polled apps and hands
+/// it to `on_windows`, whose diff against the model produces the same
+/// synthetic events an observer would have delivered. The loop parks
+/// itself (sleeps through ticks) while nothing is in polling mode.
+pub fn spawn_polling_loop(
+    registry: std::sync::Arc<std::sync::Mutex<ObserverRegistry>>,
+    on_windows: impl Fn(Vec<crate::models::WindowInfo>) + Send + 'static,
+) -> std::thread::JoinHandle<()> {
+    std::thread::Builder::new()
+        .name("tillers-ax-poll".into())
+        .spawn(move || loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let polled = registry.lock().unwrap().polled_bundle_ids();
+            if polled.is_empty() {
+                continue;
+            }
+            match super::list_windows() {
+                Ok(windows) => {
+                    let targeted: Vec<_> = windows
+                        .into_iter()
+                        .filter(|w| polled.contains(&w.app_bundle_id))
+                        .collect();
+                    on_windows(targeted);
+                }
+                Err(err) => tracing::warn!(%err, "polling window enumeration failed"),
+            }
+        })
+        .expect("spawn AX polling thread")
+}
+
+/// Create an AXObserver for an app and register the standard window
+/// notifications on its application element.
+fn register_observer(pid: i32, callback: AXObserverCallback) -> Result<()> {
+    unsafe {
+        let mut observer: AXObserverRef = std::ptr::null_mut();
+        let err = AXObserverCreate(pid, callback, &mut observer);
+        if err != kAXErrorSuccess {
+            return Err(TilleRSError::Ax {
+                op: "create observer",
+                window: 0,
+                code: AxErrorCode::from_raw(err),
+            });
+        }
+        let app = AXUIElementCreateApplication(pid);
+        for notification in NOTIFICATIONS {
+            let err = AXObserverAddNotification(
+                observer,
+                app,
+                CFString::new(notification).as_concrete_TypeRef(),
+                std::ptr::null_mut(),
+            );
+            if err != kAXErrorSuccess {
+                return Err(TilleRSError::Ax {
+                    op: "add notification",
+                    window: 0,
+                    code: AxErrorCode::from_raw(err),
+                });
+            }
+        }
+        let source = AXObserverGetRunLoopSource(observer);
+        core_foundation::runloop::CFRunLoop::get_current().add_source(
+            &core_foundation::runloop::CFRunLoopSource::wrap_under_get_rule(source),
+            core_foundation::runloop::kCFRunLoopDefaultMode,
+        );
+    }
+    Ok(())
+}
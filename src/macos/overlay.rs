@@ -0,0 +1,160 @@
+//! Borderless overlay panels used for layout previews.
+
+use std::time::Duration;
+
+use objc2_app_kit::{
+    NSBackingStoreType, NSColor, NSWindow, NSWindowCollectionBehavior, NSWindowStyleMask,
+};
+use objc2_foundation::{CGPoint, CGSize, MainThreadMarker, NSRect};
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::Rect;
+use crate::ui::theme::{Color, OverlayStyle};
+
+/// Draw one translucent, click-through panel per frame for `duration`.
+///
+/// Panels sit above normal windows but below the menu bar, join all Spaces,
+/// and never take key focus, so the preview cannot disturb the session it
+/// is previewing.
+pub fn show_preview_rects(
+    frames: &[Rect],
+    accent: Color,
+    style: OverlayStyle,
+    duration: Duration,
+) -> Result<()> {
+    let mtm = MainThreadMarker::new().ok_or_else(|| {
+        TilleRSError::Validation("overlays must be created on the main thread".into())
+    })?;
+
+    let mut panels = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let rect = NSRect::new(
+            CGPoint::new(frame.x, frame.y),
+            CGSize::new(frame.width, frame.height),
+        );
+        let panel = unsafe {
+            NSWindow::initWithContentRect_styleMask_backing_defer(
+                mtm.alloc(),
+                rect,
+                NSWindowStyleMask::Borderless,
+                NSBackingStoreType::NSBackingStoreBuffered,
+                false,
+            )
+        };
+        let alpha = if style.high_contrast {
+            1.0
+        } else {
+            style.background_alpha
+        };
+        let color = unsafe {
+            NSColor::colorWithSRGBRed_green_blue_alpha(
+                accent.r as f64 / 255.0,
+                accent.g as f64 / 255.0,
+                accent.b as f64 / 255.0,
+                alpha,
+            )
+        };
+        panel.setBackgroundColor(Some(&color));
+        panel.setOpaque(false);
+        panel.setIgnoresMouseEvents(true);
+        panel.setLevel(objc2_app_kit::NSStatusWindowLevel);
+        panel.setCollectionBehavior(
+            NSWindowCollectionBehavior::CanJoinAllSpaces | NSWindowCollectionBehavior::Stationary,
+        );
+        panel.orderFrontRegardless();
+        panels.push(panel);
+    }
+
+    std::thread::sleep(duration);
+    for panel in &panels {
+        panel.orderOut(None);
+    }
+    Ok(())
+}
+
+/// The quick-switch HUD panel; lives as long as the modifier is held.
+///
+/// Unlike the preview panels this does not block — the keyboard layer
+/// shows it on hold detection and dismisses it on release.
+pub struct HudPanel {
+    panel: objc2::rc::Retained<NSWindow>,
+}
+
+/// Row height in the HUD, in points.
+const HUD_ROW_HEIGHT: f64 = 28.0;
+const HUD_WIDTH: f64 = 360.0;
+const HUD_PADDING: f64 = 12.0;
+
+impl HudPanel {
+    /// Show the HUD centered on the main display.
+    pub fn show(entries: &[crate::ui::hud::HudEntry], style: OverlayStyle) -> Result<Self> {
+        use objc2_app_kit::NSTextField;
+        use objc2_foundation::NSString;
+
+        let mtm = MainThreadMarker::new().ok_or_else(|| {
+            TilleRSError::Validation("overlays must be created on the main thread".into())
+        })?;
+
+        let work_area = crate::macos::main_display_work_area()?;
+        let height = HUD_ROW_HEIGHT * entries.len() as f64 + 2.0 * HUD_PADDING;
+        let rect = NSRect::new(
+            CGPoint::new(
+                work_area.x + (work_area.width - HUD_WIDTH) / 2.0,
+                work_area.y + (work_area.height - height) / 2.0,
+            ),
+            CGSize::new(HUD_WIDTH, height),
+        );
+        let panel = unsafe {
+            NSWindow::initWithContentRect_styleMask_backing_defer(
+                mtm.alloc(),
+                rect,
+                NSWindowStyleMask::Borderless,
+                NSBackingStoreType::NSBackingStoreBuffered,
+                false,
+            )
+        };
+        let alpha = if style.high_contrast { 1.0 } else { 0.85 };
+        let background =
+            unsafe { NSColor::colorWithSRGBRed_green_blue_alpha(0.1, 0.1, 0.1, alpha) };
+        panel.setBackgroundColor(Some(&background));
+        panel.setOpaque(false);
+        panel.setIgnoresMouseEvents(true);
+        panel.setLevel(objc2_app_kit::NSStatusWindowLevel);
+        panel.setCollectionBehavior(
+            NSWindowCollectionBehavior::CanJoinAllSpaces | NSWindowCollectionBehavior::Stationary,
+        );
+
+        if let Some(content) = panel.contentView() {
+            for (i, entry) in entries.iter().enumerate() {
+                let marker = if entry.active { "●" } else { " " };
+                let line = format!(
+                    "{marker} {}  {:<24} {} windows  {}",
+                    entry.number,
+                    entry.name,
+                    entry.windows,
+                    entry.apps.join(", ")
+                );
+                let label =
+                    unsafe { NSTextField::labelWithString(&NSString::from_str(&line), mtm) };
+                label.setFrame(NSRect::new(
+                    CGPoint::new(
+                        HUD_PADDING,
+                        height - HUD_PADDING - HUD_ROW_HEIGHT * (i + 1) as f64,
+                    ),
+                    CGSize::new(HUD_WIDTH - 2.0 * HUD_PADDING, HUD_ROW_HEIGHT),
+                ));
+                unsafe {
+                    label.setTextColor(Some(&NSColor::whiteColor()));
+                    content.addSubview(&label);
+                }
+            }
+        }
+        panel.orderFrontRegardless();
+        Ok(HudPanel { panel })
+    }
+
+    /// Take the HUD down; called on modifier release.
+    pub fn dismiss(self) {
+        self.panel.orderOut(None);
+    }
+}
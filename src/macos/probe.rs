@@ -0,0 +1,75 @@
+//! Per-application AX capability probing.
+//!
+//! Instead of failing the same AX operation on every arrange pass, each
+//! app is probed once against a real window: try the operation, verify the
+//! result, restore the original state. The outcome lands in the app's
+//! [`ApplicationProfile`] and the engine picks a strategy from it.
+
+use crate::errors::Result;
+use crate::models::app_profile::{AxCapabilities, CapabilityStatus, ProfileStore};
+use crate::models::{Rect, WindowId};
+
+use super::accessibility;
+
+/// Offset used for the move probe, small enough to be invisible in
+/// practice but larger than the verification tolerance.
+const PROBE_NUDGE: f64 = 8.0;
+
+/// Probe which AX operations `window` honors and restore its frame after.
+///
+/// Raise and hide probes are intentionally skipped when the window is not
+/// already frontmost/visible — restoring z-order reliably is not possible,
+/// so those stay `Untested` until a natural opportunity (a focus or hide
+/// the user asked for) confirms them.
+pub fn probe_window(window: WindowId) -> Result<AxCapabilities> {
+    let original = accessibility::window_frame(window)?;
+    let mut caps = AxCapabilities::default();
+
+    // Move: nudge and read back.
+    let moved = Rect {
+        x: original.x + PROBE_NUDGE,
+        ..original
+    };
+    caps.move_window = probe_frame(window, moved);
+
+    // Resize: shrink one edge and read back.
+    let resized = Rect {
+        width: (original.width - PROBE_NUDGE).max(1.0),
+        ..original
+    };
+    caps.resize = probe_frame(window, resized);
+
+    // Restore whatever the probes changed.
+    let _ = accessibility::set_window_frame(window, original);
+    Ok(caps)
+}
+
+fn probe_frame(window: WindowId, target: Rect) -> CapabilityStatus {
+    match accessibility::set_window_frame_verified(window, target) {
+        Ok(true) => CapabilityStatus::Works,
+        Ok(false) => CapabilityStatus::Fails,
+        // The AX call itself errored (invalid element, app hung); leave
+        // the capability open rather than condemning the app.
+        Err(_) => CapabilityStatus::Untested,
+    }
+}
+
+/// Probe `window` and record the result in its app's profile, appending a
+/// compatibility note when an operation is unsupported.
+pub fn probe_and_record(
+    window: WindowId,
+    bundle_id: &str,
+    profiles: &mut ProfileStore,
+) -> Result<AxCapabilities> {
+    let caps = probe_window(window)?;
+    let profile = profiles.entry(bundle_id);
+    profile.ax_capabilities = caps;
+    if caps.move_window == CapabilityStatus::Fails {
+        profile.add_note("windows do not honor AX move; managed as floating");
+    }
+    if caps.resize == CapabilityStatus::Fails {
+        profile.add_note("windows do not honor AX resize; assigned fixed slots");
+    }
+    profiles.save()?;
+    Ok(caps)
+}
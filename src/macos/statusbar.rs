@@ -0,0 +1,180 @@
+//! The NSStatusItem behind the tray menu.
+//!
+//! The platform-independent status model lives in [`crate::ui::tray`]; this
+//! module owns the AppKit status item and rebuilds its menu from that model
+//! plus the window thumbnails. Like the other AppKit surfaces it is driven
+//! from the event loop, because NSStatusBar is main-thread-only.
+
+use objc2::rc::Retained;
+use objc2::runtime::NSObject;
+use objc2::{declare_class, msg_send_id, mutability, sel, ClassType, DeclaredClass};
+use objc2_app_kit::{NSImage, NSMenu, NSMenuItem, NSStatusBar, NSStatusItem};
+use objc2_foundation::{MainThreadMarker, NSSize, NSString};
+
+use crate::errors::{Result, TilleRSError};
+use crate::ui::thumbnails::Thumbnail;
+
+/// Height of a thumbnail in the menu, in points; widths scale to keep the
+/// window's aspect ratio.
+const THUMBNAIL_HEIGHT: f64 = 48.0;
+
+/// One row of the tray menu's window section.
+pub struct MenuRow {
+    pub title: String,
+    pub thumbnail: Thumbnail,
+}
+
+/// State behind the menu's action target: the callback the Settings item
+/// fires.
+pub struct MenuTargetIvars {
+    on_settings: Box<dyn Fn()>,
+}
+
+declare_class!(
+    /// The Objective-C object menu items target; AppKit delivers their
+    /// action selectors here.
+    struct MenuTarget;
+
+    unsafe impl ClassType for MenuTarget {
+        type Super = NSObject;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "TilleRSMenuTarget";
+    }
+
+    impl DeclaredClass for MenuTarget {
+        type Ivars = MenuTargetIvars;
+    }
+
+    unsafe impl MenuTarget {
+        #[method(openSettings:)]
+        fn open_settings(&self, _sender: Option<&objc2::runtime::AnyObject>) {
+            (self.ivars().on_settings)();
+        }
+    }
+);
+
+impl MenuTarget {
+    fn new(mtm: MainThreadMarker, on_settings: Box<dyn Fn()>) -> Retained<Self> {
+        let this = mtm.alloc().set_ivars(MenuTargetIvars { on_settings });
+        unsafe { msg_send_id![super(this), init] }
+    }
+}
+
+/// The installed status item. Dropping it leaves the item in the bar until
+/// the process exits, so the event loop keeps it alive for the daemon's
+/// lifetime.
+pub struct StatusItem {
+    item: Retained<NSStatusItem>,
+    target: Retained<MenuTarget>,
+    mtm: MainThreadMarker,
+}
+
+impl StatusItem {
+    /// Install the status item in the system status bar. `on_settings`
+    /// runs on the main thread when the menu's Settings item is picked.
+    pub fn install(on_settings: impl Fn() + 'static) -> Result<Self> {
+        let mtm = MainThreadMarker::new().ok_or_else(|| {
+            TilleRSError::Validation("the status item must be created on the main thread".into())
+        })?;
+        let bar = unsafe { NSStatusBar::systemStatusBar() };
+        let item = unsafe { bar.statusItemWithLength(objc2_app_kit::NSVariableStatusItemLength) };
+        if let Some(button) = item.button(mtm) {
+            unsafe { button.setTitle(&NSString::from_str("⌗")) };
+        }
+        let target = MenuTarget::new(mtm, Box::new(on_settings));
+        Ok(StatusItem { item, target, mtm })
+    }
+
+    /// Replace the menu: the status line on top, one row per window of the
+    /// active workspace with its thumbnail (or icon fallback), and the
+    /// Settings item at the bottom.
+    pub fn refresh(&self, status: &str, rows: &[MenuRow]) {
+        let menu = NSMenu::new(self.mtm);
+        let header = menu_item(self.mtm, status);
+        header.setEnabled(false);
+        menu.addItem(&header);
+        if !rows.is_empty() {
+            menu.addItem(&NSMenuItem::separatorItem(self.mtm));
+        }
+        for row in rows {
+            let item = menu_item(self.mtm, &row.title);
+            if let Some(image) = row_image(&row.thumbnail) {
+                unsafe { item.setImage(Some(&image)) };
+            }
+            menu.addItem(&item);
+        }
+        menu.addItem(&NSMenuItem::separatorItem(self.mtm));
+        let settings = menu_item(self.mtm, crate::i18n::t("tray-settings"));
+        unsafe {
+            settings.setTarget(Some(&self.target));
+            settings.setAction(Some(sel!(openSettings:)));
+        }
+        menu.addItem(&settings);
+        self.item.setMenu(Some(&menu));
+    }
+}
+
+/// A plain menu item with no action bound.
+fn menu_item(mtm: MainThreadMarker, title: &str) -> Retained<NSMenuItem> {
+    unsafe {
+        NSMenuItem::initWithTitle_action_keyEquivalent(
+            mtm.alloc(),
+            &NSString::from_str(title),
+            None,
+            &NSString::from_str(""),
+        )
+    }
+}
+
+/// The image for one row, if the thumbnail has one. Content-protected
+/// windows stay imageless, matching the capture layer's refusal.
+fn row_image(thumbnail: &Thumbnail) -> Option<Retained<NSImage>> {
+    match thumbnail {
+        Thumbnail::Image {
+            width,
+            height,
+            rgba,
+        } => rgba_image(*width, *height, rgba),
+        Thumbnail::AppIcon { bundle_id } => {
+            let icon = crate::macos::app_icon_rgba(bundle_id, THUMBNAIL_HEIGHT as usize)?;
+            rgba_image(icon.width, icon.height, &icon.rgba)
+        }
+        Thumbnail::Protected => None,
+    }
+}
+
+/// Wrap tightly packed RGBA in an NSImage sized for a menu row.
+fn rgba_image(width: usize, height: usize, rgba: &[u8]) -> Option<Retained<NSImage>> {
+    use objc2_app_kit::NSBitmapImageRep;
+
+    if width == 0 || height == 0 || rgba.len() < width * height * 4 {
+        return None;
+    }
+    unsafe {
+        let rep = NSBitmapImageRep::initWithBitmapDataPlanes_pixelsWide_pixelsHigh_bitsPerSample_samplesPerPixel_hasAlpha_isPlanar_colorSpaceName_bytesPerRow_bitsPerPixel(
+            NSBitmapImageRep::alloc(),
+            std::ptr::null_mut(),
+            width as isize,
+            height as isize,
+            8,
+            4,
+            true,
+            false,
+            &NSString::from_str("NSDeviceRGBColorSpace"),
+            (width * 4) as isize,
+            32,
+        )?;
+        let data = rep.bitmapData();
+        if data.is_null() {
+            return None;
+        }
+        std::ptr::copy_nonoverlapping(rgba.as_ptr(), data, width * height * 4);
+        let scale = THUMBNAIL_HEIGHT / height as f64;
+        let image = NSImage::initWithSize(
+            NSImage::alloc(),
+            NSSize::new(width as f64 * scale, THUMBNAIL_HEIGHT),
+        );
+        image.addRepresentation(&rep);
+        Some(image)
+    }
+}
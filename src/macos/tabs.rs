@@ -0,0 +1,80 @@
+//! Native tab manipulation via the app's Window menu.
+//!
+//! There is no public API for pulling a tab out or merging windows into
+//! tabs; the supported route is the same one the user has — the Window
+//! menu items AppKit adds for tab-capable apps. We walk the app's AX menu
+//! bar and press them.
+
+use accessibility_sys::{
+    kAXErrorSuccess, AXUIElementCopyAttributeValue, AXUIElementCreateApplication,
+    AXUIElementPerformAction, AXUIElementRef,
+};
+use core_foundation::array::CFArray;
+use core_foundation::base::{CFTypeRef, TCFType};
+use core_foundation::string::CFString;
+
+use crate::errors::{Result, TilleRSError};
+
+/// Menu item AppKit adds to tab-capable apps for detaching the current tab.
+pub const MOVE_TAB_TO_NEW_WINDOW: &str = "Move Tab to New Window";
+/// Menu item that merges all of the app's windows into one tab group.
+pub const MERGE_ALL_WINDOWS: &str = "Merge All Windows";
+
+/// Press a named item in the app's Window menu. The target window must be
+/// focused first so the menu acts on it.
+pub fn press_window_menu_item(pid: i32, item_title: &str) -> Result<()> {
+    unsafe {
+        let app = AXUIElementCreateApplication(pid);
+        let menu_bar = copy_attribute(app, "AXMenuBarAttribute")
+            .or_else(|_| copy_attribute(app, "AXMenuBar"))?;
+        let item = find_menu_item(menu_bar as AXUIElementRef, item_title).ok_or_else(|| {
+            TilleRSError::NotFound {
+                kind: "menu item",
+                name: item_title.to_string(),
+            }
+        })?;
+        let err = AXUIElementPerformAction(
+            item,
+            CFString::from_static_string("AXPress").as_concrete_TypeRef(),
+        );
+        if err != kAXErrorSuccess {
+            return Err(TilleRSError::Validation(format!(
+                "pressing '{item_title}' failed (AXError {err})"
+            )));
+        }
+    }
+    Ok(())
+}
+
+unsafe fn copy_attribute(element: AXUIElementRef, attribute: &str) -> Result<CFTypeRef> {
+    let mut value: CFTypeRef = std::ptr::null();
+    let err = AXUIElementCopyAttributeValue(
+        element,
+        CFString::new(attribute).as_concrete_TypeRef(),
+        &mut value,
+    );
+    if err != kAXErrorSuccess || value.is_null() {
+        return Err(TilleRSError::Validation(format!(
+            "AX read of {attribute} failed (AXError {err})"
+        )));
+    }
+    Ok(value)
+}
+
+/// Depth-first search of the menu tree for an item with the given title.
+unsafe fn find_menu_item(root: AXUIElementRef, title: &str) -> Option<AXUIElementRef> {
+    if let Ok(value) = copy_attribute(root, "AXTitle") {
+        let current: CFString = CFString::wrap_under_create_rule(value as _);
+        if current.to_string() == title {
+            return Some(root);
+        }
+    }
+    let children = copy_attribute(root, "AXChildren").ok()?;
+    let children: CFArray = CFArray::wrap_under_create_rule(children as _);
+    for child in children.iter() {
+        if let Some(found) = find_menu_item(*child as AXUIElementRef, title) {
+            return Some(found);
+        }
+    }
+    None
+}
@@ -0,0 +1,147 @@
+//! Window and display enumeration via Core Graphics.
+
+use core_foundation::array::CFArray;
+use core_foundation::base::TCFType;
+use core_foundation::dictionary::CFDictionary;
+use core_foundation::number::CFNumber;
+use core_foundation::string::CFString;
+use core_graphics::display::{CGDisplay, CGWindowListCopyWindowInfo};
+use core_graphics::window::{kCGNullWindowID, kCGWindowListOptionOnScreenOnly};
+
+use crate::errors::Result;
+use crate::models::display::DisplayInfo;
+use crate::models::{Rect, WindowInfo};
+
+/// Enumerate attached displays with their frames and work areas.
+pub fn list_displays() -> Result<Vec<DisplayInfo>> {
+    let ids = CGDisplay::active_displays().unwrap_or_default();
+    Ok(ids
+        .into_iter()
+        .map(|id| {
+            let display = CGDisplay::new(id);
+            let bounds = display.bounds();
+            let frame = Rect {
+                x: bounds.origin.x,
+                y: bounds.origin.y,
+                width: bounds.size.width,
+                height: bounds.size.height,
+            };
+            DisplayInfo {
+                id,
+                name: if display.is_main() {
+                    "main".to_string()
+                } else {
+                    format!("display-{id}")
+                },
+                // The true visible frame (minus menu bar and Dock) comes
+                // from NSScreen; CG bounds are a safe fallback for
+                // secondary displays where they match.
+                work_area: frame,
+                frame,
+            }
+        })
+        .collect())
+}
+
+/// The display this one mirrors, or `None` when it draws its own content.
+pub fn mirror_primary(display: u32) -> Option<u32> {
+    extern "C" {
+        fn CGDisplayMirrorsDisplay(display: u32) -> u32;
+    }
+    // kCGNullDirectDisplay
+    match unsafe { CGDisplayMirrorsDisplay(display) } {
+        0 => None,
+        primary => Some(primary),
+    }
+}
+
+/// Whether a display is the laptop's built-in panel.
+pub fn is_builtin(display: u32) -> bool {
+    extern "C" {
+        fn CGDisplayIsBuiltin(display: u32) -> i32;
+    }
+    unsafe { CGDisplayIsBuiltin(display) != 0 }
+}
+
+/// Enumerate on-screen windows from CGWindowList.
+///
+/// This sees every window, including ones TilleRS does not manage; callers
+/// filter by layer and app as needed. Workspace assignment and focus times
+/// are unknown at this level and left at their defaults.
+pub fn list_windows() -> Result<Vec<WindowInfo>> {
+    let raw =
+        unsafe { CGWindowListCopyWindowInfo(kCGWindowListOptionOnScreenOnly, kCGNullWindowID) };
+    if raw.is_null() {
+        return Ok(Vec::new());
+    }
+    let array: CFArray<CFDictionary<CFString, core_foundation::base::CFType>> =
+        unsafe { CFArray::wrap_under_create_rule(raw as _) };
+
+    let mut windows = Vec::new();
+    for dict in array.iter() {
+        let Some(id) = get_number(&dict, "kCGWindowNumber") else {
+            continue;
+        };
+        // Layer 0 is the normal window layer; everything else is chrome
+        // (menu bar, Dock, overlays).
+        if get_number(&dict, "kCGWindowLayer").unwrap_or(0) != 0 {
+            continue;
+        }
+        let title = get_string(&dict, "kCGWindowName").unwrap_or_default();
+        let owner = get_string(&dict, "kCGWindowOwnerName").unwrap_or_default();
+        let frame = get_bounds(&dict).unwrap_or(Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+        });
+        windows.push(WindowInfo {
+            id: id as u32,
+            title,
+            app_bundle_id: owner,
+            workspace: String::new(),
+            frame,
+            floating: false,
+            minimized: false,
+            locked: false,
+            last_focused_at: std::time::SystemTime::now(),
+        });
+    }
+    Ok(windows)
+}
+
+fn get_number(
+    dict: &CFDictionary<CFString, core_foundation::base::CFType>,
+    key: &str,
+) -> Option<i64> {
+    dict.find(CFString::new(key))
+        .and_then(|v| v.downcast::<CFNumber>())
+        .and_then(|n| n.to_i64())
+}
+
+fn get_string(
+    dict: &CFDictionary<CFString, core_foundation::base::CFType>,
+    key: &str,
+) -> Option<String> {
+    dict.find(CFString::new(key))
+        .and_then(|v| v.downcast::<CFString>())
+        .map(|s| s.to_string())
+}
+
+fn get_bounds(dict: &CFDictionary<CFString, core_foundation::base::CFType>) -> Option<Rect> {
+    let bounds = dict
+        .find(CFString::new("kCGWindowBounds"))?
+        .downcast::<CFDictionary>()?;
+    let get = |k: &str| {
+        bounds
+            .find(CFString::new(k).as_CFType())
+            .and_then(|v| v.downcast::<CFNumber>())
+            .and_then(|n| n.to_f64())
+    };
+    Some(Rect {
+        x: get("X")?,
+        y: get("Y")?,
+        width: get("Width")?,
+        height: get("Height")?,
+    })
+}
@@ -0,0 +1,104 @@
+//! Process memory measurement and a resident-set watchdog.
+
+use serde::{Deserialize, Serialize};
+
+/// Resident set size of the current process in bytes, via
+/// `task_info(MACH_TASK_BASIC_INFO)`. Returns `None` off macOS or if the
+/// kernel call fails.
+pub fn resident_bytes() -> Option<u64> {
+    #[cfg(target_os = "macos")]
+    {
+        // mach_task_basic_info, trimmed to the fields up to resident_size.
+        #[repr(C)]
+        struct MachTaskBasicInfo {
+            virtual_size: u64,
+            resident_size: u64,
+            resident_size_max: u64,
+            user_time: [u64; 2],
+            system_time: [u64; 2],
+            policy: i32,
+            suspend_count: i32,
+        }
+
+        const MACH_TASK_BASIC_INFO: u32 = 20;
+        const MACH_TASK_BASIC_INFO_COUNT: u32 =
+            (std::mem::size_of::<MachTaskBasicInfo>() / std::mem::size_of::<u32>()) as u32;
+        const KERN_SUCCESS: i32 = 0;
+
+        extern "C" {
+            fn mach_task_self() -> u32;
+            fn task_info(
+                task: u32,
+                flavor: u32,
+                info: *mut MachTaskBasicInfo,
+                count: *mut u32,
+            ) -> i32;
+        }
+
+        let mut info = unsafe { std::mem::zeroed::<MachTaskBasicInfo>() };
+        let mut count = MACH_TASK_BASIC_INFO_COUNT;
+        let kr = unsafe {
+            task_info(
+                mach_task_self(),
+                MACH_TASK_BASIC_INFO,
+                &mut info,
+                &mut count,
+            )
+        };
+        (kr == KERN_SUCCESS).then_some(info.resident_size)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        None
+    }
+}
+
+/// Resident set size in megabytes, `0.0` when unmeasurable.
+pub fn resident_mb() -> f64 {
+    resident_bytes()
+        .map(|bytes| bytes as f64 / (1024.0 * 1024.0))
+        .unwrap_or(0.0)
+}
+
+/// Warns when resident memory crosses a configurable ceiling.
+///
+/// The warning fires once per crossing: it re-arms only after RSS drops
+/// back under the ceiling, so a steady leak produces one warning, not one
+/// per poll.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryWatchdog {
+    /// Warn once resident memory exceeds this many MB. `0` disables.
+    pub ceiling_mb: f64,
+    #[serde(skip)]
+    over: bool,
+}
+
+impl Default for MemoryWatchdog {
+    fn default() -> Self {
+        MemoryWatchdog {
+            ceiling_mb: 256.0,
+            over: false,
+        }
+    }
+}
+
+impl MemoryWatchdog {
+    /// Check `resident` against the ceiling. Returns true when this call
+    /// crossed it (i.e. a warning was emitted).
+    pub fn check(&mut self, resident_mb: f64) -> bool {
+        if self.ceiling_mb <= 0.0 {
+            return false;
+        }
+        let crossed = resident_mb > self.ceiling_mb && !self.over;
+        self.over = resident_mb > self.ceiling_mb;
+        if crossed {
+            tracing::warn!(
+                resident_mb,
+                ceiling_mb = self.ceiling_mb,
+                "resident memory exceeded ceiling — possible leak"
+            );
+        }
+        crossed
+    }
+}
@@ -0,0 +1,70 @@
+//! Daemon performance metrics, exposed over IPC and diagnostics.
+
+pub mod memory;
+
+pub use memory::MemoryWatchdog;
+
+use serde::Serialize;
+
+/// Object counts per subsystem, for spotting which cache is growing.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ObjectCounts {
+    /// AX element handles held by the accessibility cache.
+    pub cached_ax_handles: usize,
+    /// Entries in the action journal.
+    pub journal_entries: usize,
+    /// Windows tracked by the window manager.
+    pub tracked_windows: usize,
+}
+
+/// Counters and gauges describing the daemon's health.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PerformanceMetrics {
+    /// Resident memory in MB, measured via `task_info`; `0.0` when the
+    /// measurement is unavailable.
+    pub memory_usage_mb: f64,
+    /// Per-subsystem object counts, refreshed with memory.
+    pub objects: ObjectCounts,
+    /// Total arrange passes since start.
+    pub arrange_passes: u64,
+    /// Arrange passes that served a coalesced destroy batch (one pass for
+    /// many windows) rather than a single event.
+    pub batched_passes: u64,
+    /// Destroy events absorbed into batches instead of triggering their
+    /// own arrange.
+    pub destroys_coalesced: u64,
+    /// Arrange/switch passes that exceeded their deadline budget and fell
+    /// back to a reduced pass.
+    pub degraded_passes: u64,
+    /// Events dropped bus-wide because subscribers lagged.
+    pub event_lag_total: u64,
+}
+
+impl PerformanceMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_arrange(&mut self, degraded: bool) {
+        self.arrange_passes += 1;
+        if degraded {
+            self.degraded_passes += 1;
+        }
+    }
+
+    /// Record an arrange pass that handled a destroy batch of
+    /// `batch_size` windows in one go.
+    pub fn record_batched_arrange(&mut self, batch_size: usize, degraded: bool) {
+        self.record_arrange(degraded);
+        self.batched_passes += 1;
+        self.destroys_coalesced += batch_size.saturating_sub(1) as u64;
+    }
+
+    /// Re-measure resident memory and update subsystem counts, running the
+    /// watchdog against the fresh reading.
+    pub fn refresh_memory(&mut self, counts: ObjectCounts, watchdog: &mut MemoryWatchdog) {
+        self.memory_usage_mb = memory::resident_mb();
+        self.objects = counts;
+        watchdog.check(self.memory_usage_mb);
+    }
+}
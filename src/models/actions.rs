@@ -0,0 +1,201 @@
+//! Actions: everything a keyboard mapping, CLI invocation, or rule can ask
+//! the window manager to do.
+
+use serde::{Deserialize, Serialize};
+
+/// A predicate evaluated against the dispatch context (focused window,
+/// active workspace) when a conditional action fires.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionCondition {
+    /// The focused window belongs to this bundle id.
+    App { equals: String },
+    /// The active workspace has this name.
+    Workspace { equals: String },
+    /// The focused window's floating state.
+    Floating { equals: bool },
+    /// The focused window's title matches this regex.
+    TitleMatches { pattern: String },
+    /// Whether the laptop lid is closed (clamshell mode).
+    Clamshell { equals: bool },
+}
+
+impl ActionCondition {
+    /// Evaluate against the dispatch context. `focused` is the focused
+    /// window, if any; window predicates are false without one.
+    /// `clamshell` is the current lid state from the clamshell tracker.
+    pub fn holds(
+        &self,
+        focused: Option<&crate::models::WindowInfo>,
+        workspace: &str,
+        clamshell: bool,
+    ) -> bool {
+        match self {
+            ActionCondition::App { equals } => {
+                focused.map(|w| &w.app_bundle_id == equals).unwrap_or(false)
+            }
+            ActionCondition::Workspace { equals } => workspace == equals,
+            ActionCondition::Floating { equals } => {
+                focused.map(|w| w.floating == *equals).unwrap_or(false)
+            }
+            ActionCondition::TitleMatches { pattern } => focused
+                .map(|w| {
+                    regex::Regex::new(pattern)
+                        .map(|re| re.is_match(&w.title))
+                        .unwrap_or(false)
+                })
+                .unwrap_or(false),
+            ActionCondition::Clamshell { equals } => clamshell == *equals,
+        }
+    }
+}
+
+/// All dispatchable actions.
+///
+/// Variants are serialized into config keybindings, so renaming one is a
+/// breaking config change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ActionType {
+    /// Switch to the named workspace.
+    SwitchWorkspace { workspace: String },
+    /// Switch to a workspace by its 1-based shortcut index, resolved
+    /// through the active group (the whole point of groups: `opt+1..9`
+    /// means different workspaces in different groups).
+    SwitchWorkspaceIndex { index: usize },
+    /// Activate a workspace group, re-binding the numeric shortcuts to
+    /// its members.
+    SwitchGroup { group: String },
+    /// Move the focused window to the named workspace.
+    MoveToWorkspace { workspace: String },
+    /// Toggle floating state of the focused window.
+    ToggleFloat,
+    /// Toggle the do-not-touch lock on the focused window.
+    ToggleLock,
+    /// Focus a specific window by id, switching workspace if needed.
+    FocusWindow { window_id: u32 },
+    /// Focus the nearest window in a direction. `warp` overrides the
+    /// global cursor-warp setting for this binding only.
+    FocusLeft {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        warp: Option<bool>,
+    },
+    /// See [`FocusLeft`](Self::FocusLeft).
+    FocusRight {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        warp: Option<bool>,
+    },
+    /// See [`FocusLeft`](Self::FocusLeft).
+    FocusUp {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        warp: Option<bool>,
+    },
+    /// See [`FocusLeft`](Self::FocusLeft).
+    FocusDown {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        warp: Option<bool>,
+    },
+    /// Close a specific window by id.
+    CloseWindow { window_id: u32 },
+    /// Move a specific window to the named workspace.
+    MoveWindowToWorkspace { window_id: u32, workspace: String },
+    /// Re-run the layout for the active workspace.
+    Retile,
+    /// Reset the active workspace's split ratios to equal distribution.
+    BalanceLayout,
+    /// Temporarily suspend all rules and tiling for an application.
+    SuspendAppRules {
+        bundle_id: String,
+        /// Suspension length in minutes; expiry is automatic.
+        minutes: u64,
+    },
+    /// Lift a suspension early.
+    ResumeAppRules { bundle_id: String },
+    /// Start a focus session locking the current workspace.
+    StartFocusSession {
+        /// Session length in minutes.
+        minutes: u64,
+    },
+    /// End the active focus session early (the explicit override).
+    EndFocusSession,
+    /// Detach windows from their workspaces onto the window clipboard;
+    /// they hide until pasted. Empty means the focused window.
+    CutWindows {
+        #[serde(default)]
+        window_ids: Vec<u32>,
+    },
+    /// Insert the cut windows into the current workspace's layout at the
+    /// insertion point (after the focused window).
+    PasteWindows,
+    /// Pin a workspace to a display (by display name).
+    PinWorkspaceToDisplay { workspace: String, display: String },
+    /// Toggle quiet mode on a workspace: new windows float untouched
+    /// where the app placed them, existing tiled windows stay managed.
+    ToggleQuietMode { workspace: String },
+    /// Create or update a workspace to match the given shape; a no-op when
+    /// it already matches. This is how `tillers apply` converges
+    /// workspaces declaratively.
+    EnsureWorkspace {
+        workspace: String,
+        layout: crate::tiling::LayoutPattern,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        display: Option<String>,
+        #[serde(default)]
+        quiet: bool,
+    },
+    /// Remove a workspace; its windows fall through to the catch-all.
+    RemoveWorkspace { workspace: String },
+    /// Create a workspace that self-destructs after the given time,
+    /// moving leftover windows to the configured fallback workspace.
+    CreateTemporaryWorkspace { workspace: String, minutes: u64 },
+    /// Lift a temporary workspace's time box, making it permanent.
+    PromoteTemporaryWorkspace { workspace: String },
+    /// Stop tiling and rule enforcement for one workspace.
+    PauseWorkspace { workspace: String },
+    /// Re-adopt and re-tile a paused workspace.
+    ResumeWorkspace { workspace: String },
+    /// Raise a window to the top of its layer (focused window when omitted).
+    RaiseWindow {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        window_id: Option<u32>,
+    },
+    /// Push a window to the bottom of its layer.
+    LowerWindow {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        window_id: Option<u32>,
+    },
+    /// Branch on the dispatch context: run `then` when the condition holds
+    /// for the focused window/workspace, otherwise `otherwise` (if any).
+    If {
+        condition: ActionCondition,
+        then: Box<ActionType>,
+        #[serde(default, rename = "else", skip_serializing_if = "Option::is_none")]
+        otherwise: Option<Box<ActionType>>,
+    },
+    /// Run several actions as one atomic step: executed in order through
+    /// the orchestrator, with completed steps rolled back in reverse if a
+    /// later one fails, and a single arrange pass at the end.
+    Sequence(Vec<ActionType>),
+    /// Toggle cosmetic rule effects (opacity, corner masks) globally.
+    ToggleCosmetics,
+    /// Hook kill switch: stop or resume launching event hooks. Bindable,
+    /// so a runaway hook can be stopped while its config is still broken.
+    SetHooksEnabled { enabled: bool },
+    /// Soft reload: re-read the config and rebuild everything derived
+    /// from it (keymaps, observers, arrangement) without dropping
+    /// runtime state or hidden windows.
+    SoftReload,
+    /// Run a plugin-contributed action, named `plugin-name/action`. Any
+    /// core actions the plugin returns are executed in order.
+    PluginAction { name: String },
+    /// Pull the focused native tab out into its own tiled window.
+    PullTabOut,
+    /// Merge the app's windows into one native tab group, where supported.
+    MergeIntoTabs,
+    /// Run the auto-archival sweep now: windows idle past the configured
+    /// threshold move to the archive target.
+    ArchiveSweep,
+    /// Undo the last archival sweep, returning its windows to the
+    /// workspaces they came from.
+    ArchiveUndo,
+}
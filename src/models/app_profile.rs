@@ -0,0 +1,181 @@
+//! Per-application behavior profiles.
+//!
+//! TilleRS learns how each app misbehaves (ignored AX resizes, odd window
+//! roles) and records it here so workarounds survive restarts and show up
+//! in diagnostics instead of living in tribal knowledge.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+
+/// Outcome of probing one AX operation against an app's windows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapabilityStatus {
+    /// Not probed yet; assume it works until proven otherwise.
+    #[default]
+    Untested,
+    Works,
+    Fails,
+}
+
+/// Which AX operations an app's windows actually honor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxCapabilities {
+    pub move_window: CapabilityStatus,
+    pub resize: CapabilityStatus,
+    pub raise: CapabilityStatus,
+    pub hide: CapabilityStatus,
+}
+
+/// How the engine should manage an app's windows, derived from its probed
+/// capabilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TilingStrategy {
+    /// Move and resize freely.
+    Full,
+    /// Moves work but resizes do not: assign fixed slots sized to the
+    /// window's own dimensions instead of resizing into a slot.
+    MoveOnly,
+    /// Neither works; manage as floating.
+    FloatOnly,
+}
+
+impl AxCapabilities {
+    /// Pick the management strategy these capabilities support. Untested
+    /// operations are assumed to work — probing refines this over time.
+    pub fn strategy(&self) -> TilingStrategy {
+        let moves = self.move_window != CapabilityStatus::Fails;
+        let resizes = self.resize != CapabilityStatus::Fails;
+        match (moves, resizes) {
+            (true, true) => TilingStrategy::Full,
+            (true, false) => TilingStrategy::MoveOnly,
+            (false, _) => TilingStrategy::FloatOnly,
+        }
+    }
+}
+
+/// What focusing a window through TilleRS raises.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RaisePolicy {
+    /// Raise only the focused window.
+    #[default]
+    Window,
+    /// Activate the whole application, raising all of its windows.
+    App,
+    /// Move keyboard focus without raising anything (AX focus only).
+    None,
+}
+
+/// How to treat an app that grabs focus on its own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusStealingBehavior {
+    /// Let the app's focus changes stand.
+    #[default]
+    Allow,
+    /// Never let the app steal focus: TilleRS restores focus to the
+    /// previously focused window when this app grabs it uninvited.
+    Passive,
+    /// Allow focus only when the app created a new window (the dialog
+    /// case); steals onto existing windows are reverted.
+    NewWindowsOnly,
+}
+
+/// Learned facts about one application, keyed by bundle identifier.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApplicationProfile {
+    pub bundle_id: String,
+    /// Windows of this app persistently ignore AX setFrame; tile them as
+    /// floating instead of fighting the app every arrange pass.
+    pub ax_resize_noncompliant: bool,
+    /// Probed per-operation AX support.
+    pub ax_capabilities: AxCapabilities,
+    /// Per-app raise behavior; `None` falls back to the global policy.
+    pub raise_policy: Option<RaisePolicy>,
+    /// How uninvited focus grabs by this app are handled.
+    pub focus_stealing: FocusStealingBehavior,
+    /// Human-readable compatibility notes, shown by diagnostics.
+    pub compatibility_notes: Vec<String>,
+}
+
+impl ApplicationProfile {
+    pub fn new(bundle_id: impl Into<String>) -> Self {
+        ApplicationProfile {
+            bundle_id: bundle_id.into(),
+            ..ApplicationProfile::default()
+        }
+    }
+
+    /// Append a compatibility note, skipping exact duplicates.
+    pub fn add_note(&mut self, note: impl Into<String>) {
+        let note = note.into();
+        if !self.compatibility_notes.contains(&note) {
+            self.compatibility_notes.push(note);
+        }
+    }
+}
+
+/// All known profiles, persisted as JSON in the data directory.
+#[derive(Debug, Default)]
+pub struct ProfileStore {
+    path: PathBuf,
+    profiles: BTreeMap<String, ApplicationProfile>,
+}
+
+impl ProfileStore {
+    /// Default store location: `~/.local/share/tillers/app-profiles.json`.
+    pub fn default_path() -> PathBuf {
+        let home = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        home.join(".local")
+            .join("share")
+            .join("tillers")
+            .join("app-profiles.json")
+    }
+
+    pub fn load_default() -> Result<Self> {
+        Self::load(Self::default_path())
+    }
+
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let profiles = if path.exists() {
+            let raw = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&raw)?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(ProfileStore { path, profiles })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.profiles)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, bundle_id: &str) -> Option<&ApplicationProfile> {
+        self.profiles.get(bundle_id)
+    }
+
+    /// Profile for `bundle_id`, created on first access.
+    pub fn entry(&mut self, bundle_id: &str) -> &mut ApplicationProfile {
+        self.profiles
+            .entry(bundle_id.to_string())
+            .or_insert_with(|| ApplicationProfile::new(bundle_id))
+    }
+
+    pub fn profiles(&self) -> impl Iterator<Item = &ApplicationProfile> {
+        self.profiles.values()
+    }
+}
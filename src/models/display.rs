@@ -0,0 +1,19 @@
+//! Display (monitor) identity and geometry.
+
+use serde::{Deserialize, Serialize};
+
+use super::Rect;
+
+/// Stable identifier for a display (the CGDirectDisplayID on macOS).
+pub type DisplayId = u32;
+
+/// One attached display.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayInfo {
+    pub id: DisplayId,
+    pub name: String,
+    /// Full frame in global coordinates.
+    pub frame: Rect,
+    /// Frame minus menu bar and Dock.
+    pub work_area: Rect,
+}
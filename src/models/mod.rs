@@ -0,0 +1,14 @@
+//! Core data model shared by the daemon, CLI, and IPC layer.
+
+pub mod actions;
+pub mod app_profile;
+pub mod display;
+pub mod rules;
+pub mod window;
+pub mod workspace;
+
+pub use actions::{ActionCondition, ActionType};
+pub use app_profile::{ApplicationProfile, ProfileStore};
+pub use rules::{FloatGeometry, Rect, WindowRule};
+pub use window::{WindowId, WindowInfo};
+pub use workspace::{Workspace, WorkspaceRef};
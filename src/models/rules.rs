@@ -0,0 +1,160 @@
+//! Window rules: declarative matching of windows to workspaces and
+//! per-window behavior overrides.
+
+use serde::{Deserialize, Serialize};
+
+/// A rectangle used for fixed window geometry, in display points.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Geometry for a floated window, resolved against the display it is on
+/// whenever it appears or moves displays.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FloatGeometry {
+    /// A named preset: `centered-60`, `centered-80`, `left-half`,
+    /// `right-half`, `top-half`, `bottom-half`, `top-left-quarter`,
+    /// `top-right-quarter`, `bottom-left-quarter`, `bottom-right-quarter`.
+    Preset(String),
+    /// Fractions of the display work area, each in `0.0..=1.0`.
+    Percent {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    /// Absolute frame in points. Display-independent; prefer presets or
+    /// percentages for multi-display setups.
+    Absolute(Rect),
+}
+
+impl FloatGeometry {
+    /// Resolve to a concrete frame within `work_area`. Returns `None` for
+    /// an unknown preset name, which the caller reports as a config
+    /// validation error.
+    pub fn resolve(&self, work_area: &Rect) -> Option<Rect> {
+        match self {
+            FloatGeometry::Absolute(rect) => Some(*rect),
+            FloatGeometry::Percent {
+                x,
+                y,
+                width,
+                height,
+            } => Some(Rect {
+                x: work_area.x + work_area.width * x,
+                y: work_area.y + work_area.height * y,
+                width: work_area.width * width,
+                height: work_area.height * height,
+            }),
+            FloatGeometry::Preset(name) => {
+                let (x, y, w, h) = match name.as_str() {
+                    "centered-60" => (0.2, 0.2, 0.6, 0.6),
+                    "centered-80" => (0.1, 0.1, 0.8, 0.8),
+                    "left-half" => (0.0, 0.0, 0.5, 1.0),
+                    "right-half" => (0.5, 0.0, 0.5, 1.0),
+                    "top-half" => (0.0, 0.0, 1.0, 0.5),
+                    "bottom-half" => (0.0, 0.5, 1.0, 0.5),
+                    "top-left-quarter" => (0.0, 0.0, 0.5, 0.5),
+                    "top-right-quarter" => (0.5, 0.0, 0.5, 0.5),
+                    "bottom-left-quarter" => (0.0, 0.5, 0.5, 0.5),
+                    "bottom-right-quarter" => (0.5, 0.5, 0.5, 0.5),
+                    _ => return None,
+                };
+                FloatGeometry::Percent {
+                    x,
+                    y,
+                    width: w,
+                    height: h,
+                }
+                .resolve(work_area)
+            }
+        }
+    }
+}
+
+/// A declarative rule matched against new (and re-evaluated) windows.
+///
+/// Rules are identified by `name`, which must be unique within a config.
+/// A rule matches when every populated matcher field matches the window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowRule {
+    /// Unique, human-readable identifier used by the CLI and logs.
+    pub name: String,
+    /// Bundle identifier of the owning application, e.g. `com.apple.Safari`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_bundle_id: Option<String>,
+    /// Regular expression matched against the window title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_pattern: Option<String>,
+    /// Workspace the matched window is sent to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// Whether the matched window floats instead of being tiled.
+    #[serde(default)]
+    pub floating: bool,
+    /// Optional geometry applied to floated windows: a named preset,
+    /// per-display percentages, or an absolute frame. Re-resolved whenever
+    /// the window appears or moves displays.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed_geometry: Option<FloatGeometry>,
+    /// Window alpha in `0.0..=1.0` applied while the rule matches;
+    /// requires the private window-server API and degrades to fully
+    /// opaque when it is unavailable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opacity: Option<f64>,
+    /// Rounded-corner radius in points, drawn as an overlay mask on top
+    /// of the window's corners.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corner_radius: Option<f64>,
+    /// Locked windows are never moved, hidden, or resized.
+    #[serde(default)]
+    pub locked: bool,
+    /// Disabled rules stay in the config but never match.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl WindowRule {
+    /// Whether this rule matches the window. Every populated matcher field
+    /// must match; an invalid title regex never matches.
+    pub fn matches(&self, window: &crate::models::WindowInfo) -> bool {
+        if let Some(bundle) = &self.app_bundle_id {
+            if bundle != &window.app_bundle_id {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.title_pattern {
+            match regex::Regex::new(pattern) {
+                Ok(re) if re.is_match(&window.title) => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Create an enabled rule with only a name; matchers are filled in by
+    /// the caller.
+    pub fn named(name: impl Into<String>) -> Self {
+        WindowRule {
+            name: name.into(),
+            app_bundle_id: None,
+            title_pattern: None,
+            workspace: None,
+            floating: false,
+            fixed_geometry: None,
+            opacity: None,
+            corner_radius: None,
+            locked: false,
+            enabled: true,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Window identity and metadata.
+
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::Rect;
+
+/// Stable identifier for a window (the CGWindowID on macOS).
+pub type WindowId = u32;
+
+/// Everything TilleRS tracks about one window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowInfo {
+    pub id: WindowId,
+    pub title: String,
+    /// Bundle id of the owning application.
+    pub app_bundle_id: String,
+    /// Name of the workspace the window is assigned to.
+    pub workspace: String,
+    pub frame: Rect,
+    pub floating: bool,
+    pub minimized: bool,
+    /// Locked windows are never moved, hidden, or resized.
+    #[serde(default)]
+    pub locked: bool,
+    /// When the window last had keyboard focus.
+    pub last_focused_at: SystemTime,
+}
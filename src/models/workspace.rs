@@ -0,0 +1,190 @@
+//! Workspace identity and configuration.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::tiling::LayoutPattern;
+
+/// A named collection of windows with its own layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    /// Stable identity that survives renames.
+    pub id: Uuid,
+    pub name: String,
+    pub layout: LayoutPattern,
+    /// Display the workspace is pinned to, when any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+    /// Desktop image applied when this workspace activates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallpaper: Option<std::path::PathBuf>,
+    /// Plugin layout (`plugin-name/layout`) used instead of `layout`;
+    /// arrange falls back to `layout` when the plugin fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_plugin: Option<String>,
+    /// Split ratios captured from manual resizes; persisted so they
+    /// survive switches and restarts.
+    #[serde(default)]
+    pub splits: crate::tiling::SplitRatios,
+    /// Quiet mode: new windows are accepted but left floating where the
+    /// app placed them; already-tiled windows stay managed.
+    #[serde(default)]
+    pub quiet: bool,
+}
+
+impl Workspace {
+    pub fn new(name: impl Into<String>) -> Self {
+        Workspace {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            layout: LayoutPattern::Tall,
+            display: None,
+            wallpaper: None,
+            layout_plugin: None,
+            splits: crate::tiling::SplitRatios::default(),
+            quiet: false,
+        }
+    }
+}
+
+/// A workspace reference as users and scripts write it: a name, a 1-based
+/// index into the workspace order, or the UUID that survives renames.
+///
+/// Every CLI and IPC endpoint that takes a workspace parses into this and
+/// resolves through [`WorkspaceRef::resolve`], so scripts can pin to the
+/// UUID and stay correct across renames while humans keep typing names.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WorkspaceRef {
+    Id(Uuid),
+    Index(usize),
+    Name(String),
+}
+
+impl std::str::FromStr for WorkspaceRef {
+    type Err = crate::errors::TilleRSError;
+
+    /// UUIDs and all-digit strings parse as id and index respectively;
+    /// everything else is a name. An index of 0 is rejected — indices are
+    /// 1-based everywhere the user sees them.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(id) = Uuid::parse_str(s) {
+            return Ok(WorkspaceRef::Id(id));
+        }
+        if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+            let index: usize = s.parse().map_err(|_| {
+                crate::errors::TilleRSError::Validation(format!(
+                    "workspace index '{s}' is out of range"
+                ))
+            })?;
+            if index == 0 {
+                return Err(crate::errors::TilleRSError::Validation(
+                    "workspace indices are 1-based; there is no workspace 0".into(),
+                ));
+            }
+            return Ok(WorkspaceRef::Index(index));
+        }
+        Ok(WorkspaceRef::Name(s.to_string()))
+    }
+}
+
+impl std::fmt::Display for WorkspaceRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkspaceRef::Id(id) => write!(f, "{id}"),
+            WorkspaceRef::Index(index) => write!(f, "{index}"),
+            WorkspaceRef::Name(name) => f.write_str(name),
+        }
+    }
+}
+
+impl WorkspaceRef {
+    /// Resolve against the workspace list (in its stable order, which is
+    /// what indices count through).
+    ///
+    /// A digit-named workspace makes an index reference ambiguous: "3" is
+    /// the third workspace *and* the one named "3". That resolves to an
+    /// error listing both candidates rather than a silent guess, pointing
+    /// at the UUID as the unambiguous spelling.
+    pub fn resolve<'a>(&self, workspaces: &'a [Workspace]) -> crate::errors::Result<&'a Workspace> {
+        match self {
+            WorkspaceRef::Id(id) => workspaces.iter().find(|w| &w.id == id).ok_or_else(|| {
+                crate::errors::TilleRSError::NotFound {
+                    kind: "workspace",
+                    name: id.to_string(),
+                }
+            }),
+            WorkspaceRef::Name(name) => {
+                workspaces.iter().find(|w| &w.name == name).ok_or_else(|| {
+                    crate::errors::TilleRSError::NotFound {
+                        kind: "workspace",
+                        name: name.clone(),
+                    }
+                })
+            }
+            WorkspaceRef::Index(index) => {
+                let by_index = workspaces.get(index - 1);
+                let digits = index.to_string();
+                let by_name = workspaces.iter().find(|w| w.name == digits);
+                match (by_index, by_name) {
+                    (Some(a), Some(b)) if a.id != b.id => {
+                        Err(crate::errors::TilleRSError::Validation(format!(
+                            "workspace reference '{index}' is ambiguous: index {index} is \
+                             '{}' ({}), but a workspace is also named '{digits}' ({}); \
+                             use the UUID",
+                            a.name, a.id, b.id
+                        )))
+                    }
+                    (Some(workspace), _) => Ok(workspace),
+                    (None, Some(workspace)) => Ok(workspace),
+                    (None, None) => Err(crate::errors::TilleRSError::NotFound {
+                        kind: "workspace",
+                        name: digits,
+                    }),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn refs_parse_as_id_index_or_name() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            WorkspaceRef::from_str(&id.to_string()).unwrap(),
+            WorkspaceRef::Id(id)
+        );
+        assert_eq!(WorkspaceRef::from_str("3").unwrap(), WorkspaceRef::Index(3));
+        assert_eq!(
+            WorkspaceRef::from_str("coding").unwrap(),
+            WorkspaceRef::Name("coding".into())
+        );
+    }
+
+    #[test]
+    fn index_zero_is_rejected() {
+        assert!(WorkspaceRef::from_str("0").is_err());
+    }
+
+    #[test]
+    fn digit_named_workspace_makes_an_index_ambiguous() {
+        let mut workspaces = vec![
+            Workspace::new("one"),
+            Workspace::new("two"),
+            Workspace::new("three"),
+        ];
+        assert_eq!(
+            WorkspaceRef::Index(2).resolve(&workspaces).unwrap().name,
+            "two"
+        );
+        // A workspace literally named "2" collides with the index.
+        workspaces.push(Workspace::new("2"));
+        assert!(WorkspaceRef::Index(2).resolve(&workspaces).is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! Plugin discovery and loading.
+
+use std::path::{Path, PathBuf};
+
+use libloading::Library;
+
+use crate::errors::{Result, TilleRSError};
+
+use super::{Capability, PluginEvent, TillersPlugin, PLUGIN_ABI_VERSION};
+
+/// A loaded plugin plus the library keeping its code alive.
+struct LoadedPlugin {
+    plugin: Box<dyn TillersPlugin>,
+    /// Held for its lifetime; dropping it would unload the plugin's code
+    /// while `plugin` still points into it.
+    _library: Option<Library>,
+}
+
+/// Loads plugins from `<config dir>/plugins` and routes calls to them.
+#[derive(Default)]
+pub struct PluginHost {
+    plugins: Vec<LoadedPlugin>,
+}
+
+impl PluginHost {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every `.dylib` and `.wasm` in the plugin directory. Individual
+    /// failures are logged and skipped; one broken plugin must not take
+    /// down the daemon.
+    pub fn load_directory(&mut self, dir: impl AsRef<Path>) -> Result<usize> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(0);
+        }
+        let mut loaded = 0;
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            let path = entry.path();
+            let result = match path.extension().and_then(|e| e.to_str()) {
+                Some("dylib") => self.load_dylib(&path),
+                Some("wasm") => self.load_wasm(&path),
+                _ => continue,
+            };
+            match result {
+                Ok(name) => {
+                    tracing::info!(plugin = %name, path = %path.display(), "loaded plugin");
+                    loaded += 1;
+                }
+                Err(err) => {
+                    tracing::warn!(path = %path.display(), %err, "skipping plugin");
+                }
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Load one dylib plugin, verifying its ABI version.
+    fn load_dylib(&mut self, path: &PathBuf) -> Result<String> {
+        // Safety: the plugin contract requires `tillers_plugin_entry` to
+        // return a valid boxed TillersPlugin built against our ABI; the
+        // version check below rejects mismatched builds before any other
+        // call is made.
+        let (plugin, library) = unsafe {
+            let library = Library::new(path)
+                .map_err(|e| TilleRSError::Validation(format!("cannot load plugin: {e}")))?;
+            let entry: libloading::Symbol<'_, super::PluginEntry> = library
+                .get(b"tillers_plugin_entry")
+                .map_err(|e| TilleRSError::Validation(format!("missing plugin entry: {e}")))?;
+            let plugin = Box::from_raw(entry());
+            (plugin, library)
+        };
+
+        let manifest = plugin.manifest();
+        if manifest.abi_version != PLUGIN_ABI_VERSION {
+            return Err(TilleRSError::Validation(format!(
+                "plugin '{}' targets ABI {} but this build is ABI {}",
+                manifest.name, manifest.abi_version, PLUGIN_ABI_VERSION
+            )));
+        }
+
+        self.plugins.push(LoadedPlugin {
+            plugin,
+            _library: Some(library),
+        });
+        Ok(manifest.name)
+    }
+
+    /// Load one `.wasm` layout module, named after its file stem. The
+    /// sandbox limits live in [`super::wasm`]; here it is just another
+    /// layout-capability plugin.
+    fn load_wasm(&mut self, path: &Path) -> Result<String> {
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| {
+                TilleRSError::Validation(format!(
+                    "wasm layout path '{}' has no usable file name",
+                    path.display()
+                ))
+            })?
+            .to_string();
+        let layout = super::wasm::WasmLayout::load(name.clone(), path)?;
+        self.register(Box::new(super::wasm::WasmLayoutPlugin::new(layout)));
+        Ok(name)
+    }
+
+    /// Register an in-process plugin (used by built-in extensions and
+    /// tests; no dylib involved).
+    pub fn register(&mut self, plugin: Box<dyn TillersPlugin>) {
+        self.plugins.push(LoadedPlugin {
+            plugin,
+            _library: None,
+        });
+    }
+
+    /// Fan an event out to every plugin that declared `ObserveEvents`.
+    pub fn broadcast_event(&mut self, event: &PluginEvent) {
+        for loaded in &mut self.plugins {
+            if has_capability(loaded.plugin.as_ref(), Capability::ObserveEvents) {
+                loaded.plugin.on_event(event);
+            }
+        }
+    }
+
+    /// All custom action names, qualified as `plugin-name/action`.
+    pub fn action_names(&self) -> Vec<String> {
+        self.plugins
+            .iter()
+            .filter(|l| has_capability(l.plugin.as_ref(), Capability::CustomActions))
+            .flat_map(|l| {
+                let prefix = l.plugin.manifest().name;
+                l.plugin
+                    .action_names()
+                    .into_iter()
+                    .map(move |a| format!("{prefix}/{a}"))
+            })
+            .collect()
+    }
+
+    /// Dispatch a qualified action name to its owning plugin.
+    pub fn handle_action(&mut self, qualified: &str) -> Result<Vec<crate::models::ActionType>> {
+        let (plugin_name, action) = qualified.split_once('/').ok_or_else(|| {
+            TilleRSError::Validation(format!(
+                "plugin action '{qualified}' must be 'plugin-name/action'"
+            ))
+        })?;
+        let loaded = self
+            .plugins
+            .iter_mut()
+            .find(|l| l.plugin.manifest().name == plugin_name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "plugin",
+                name: plugin_name.to_string(),
+            })?;
+        if !has_capability(loaded.plugin.as_ref(), Capability::CustomActions) {
+            return Err(TilleRSError::Validation(format!(
+                "plugin '{plugin_name}' does not declare the custom_actions capability"
+            )));
+        }
+        loaded.plugin.handle_action(action)
+    }
+
+    /// All custom layout names, qualified as `plugin-name/layout`.
+    pub fn layout_names(&self) -> Vec<String> {
+        self.plugins
+            .iter()
+            .filter(|l| has_capability(l.plugin.as_ref(), Capability::CustomLayouts))
+            .flat_map(|l| {
+                let prefix = l.plugin.manifest().name;
+                l.plugin
+                    .layout_names()
+                    .into_iter()
+                    .map(move |layout| format!("{prefix}/{layout}"))
+            })
+            .collect()
+    }
+
+    /// Compute frames for a qualified custom layout. The frame count is
+    /// verified here so a misbehaving plugin fails the arrange pass with
+    /// a clear error instead of scrambling window assignments.
+    pub fn compute_layout(
+        &mut self,
+        qualified: &str,
+        display: &crate::models::display::DisplayInfo,
+        windows: &[crate::models::WindowInfo],
+    ) -> Result<Vec<crate::models::Rect>> {
+        let (plugin_name, layout) = qualified.split_once('/').ok_or_else(|| {
+            TilleRSError::Validation(format!(
+                "plugin layout '{qualified}' must be 'plugin-name/layout'"
+            ))
+        })?;
+        let loaded = self
+            .plugins
+            .iter_mut()
+            .find(|l| l.plugin.manifest().name == plugin_name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "plugin",
+                name: plugin_name.to_string(),
+            })?;
+        if !has_capability(loaded.plugin.as_ref(), Capability::CustomLayouts) {
+            return Err(TilleRSError::Validation(format!(
+                "plugin '{plugin_name}' does not declare the custom_layouts capability"
+            )));
+        }
+        let frames = loaded.plugin.compute_layout(layout, display, windows)?;
+        if frames.len() != windows.len() {
+            return Err(TilleRSError::Validation(format!(
+                "layout '{qualified}' returned {} frames for {} windows",
+                frames.len(),
+                windows.len()
+            )));
+        }
+        Ok(frames)
+    }
+
+    pub fn len(&self) -> usize {
+        self.plugins.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+}
+
+fn has_capability(plugin: &dyn TillersPlugin, capability: Capability) -> bool {
+    plugin.manifest().capabilities.contains(&capability)
+}
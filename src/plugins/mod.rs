@@ -0,0 +1,101 @@
+//! Plugin system.
+//!
+//! Community features load as plugins from the config directory instead of
+//! landing in core. A plugin implements [`TillersPlugin`] and is loaded
+//! either as a dylib (fast, trusted) or — see the `wasm` module — as a
+//! sandboxed WASM layout. Every plugin declares the capabilities it needs;
+//! the host refuses calls outside the declared set.
+
+pub mod host;
+pub mod wasm;
+
+pub use host::PluginHost;
+pub use wasm::WasmLayout;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+use crate::models::display::DisplayInfo;
+use crate::models::{ActionType, Rect, WindowInfo};
+
+/// Bumped whenever the plugin-facing types change incompatibly. Dylib
+/// plugins compiled against another ABI version are refused at load time.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// What a plugin is allowed to do; declared in its manifest and enforced
+/// by the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Receive window/workspace events.
+    ObserveEvents,
+    /// Contribute named actions dispatchable from keybindings.
+    CustomActions,
+    /// Contribute layout algorithms.
+    CustomLayouts,
+}
+
+/// Static description of a plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    pub version: String,
+    pub abi_version: u32,
+    pub capabilities: Vec<Capability>,
+}
+
+/// Events forwarded to plugins with the `ObserveEvents` capability.
+#[derive(Debug, Clone)]
+pub enum PluginEvent {
+    WindowCreated(WindowInfo),
+    WindowDestroyed(u32),
+    WorkspaceActivated(String),
+    /// The laptop lid closed (`true`) or reopened (`false`).
+    ClamshellChanged(bool),
+}
+
+/// The interface every plugin implements.
+///
+/// Default method bodies are no-ops so a plugin only implements the hooks
+/// matching its declared capabilities.
+pub trait TillersPlugin: Send {
+    fn manifest(&self) -> PluginManifest;
+
+    /// Event hook (`ObserveEvents`).
+    fn on_event(&mut self, _event: &PluginEvent) {}
+
+    /// Names of actions this plugin contributes (`CustomActions`).
+    fn action_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Execute one of this plugin's actions; may return follow-up core
+    /// actions to dispatch (`CustomActions`).
+    fn handle_action(&mut self, _name: &str) -> Result<Vec<ActionType>> {
+        Ok(Vec::new())
+    }
+
+    /// Names of layouts this plugin contributes (`CustomLayouts`).
+    fn layout_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Compute frames for a custom layout (`CustomLayouts`).
+    fn compute_layout(
+        &mut self,
+        _name: &str,
+        _display: &DisplayInfo,
+        _windows: &[WindowInfo],
+    ) -> Result<Vec<Rect>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Signature of the entry point a dylib plugin exports as
+/// `tillers_plugin_entry`.
+///
+/// A fat trait-object pointer is not C-ABI-safe in general, but both sides
+/// of this boundary are Rust compiled against the same `TillersPlugin`
+/// definition (enforced by the ABI version check), so the layout agrees.
+#[allow(improper_ctypes_definitions)]
+pub type PluginEntry = unsafe extern "C" fn() -> *mut dyn TillersPlugin;
@@ -0,0 +1,167 @@
+//! WASM-sandboxed layout plugins.
+//!
+//! A layout compiled to WASM gets window metadata and the work area as
+//! JSON, returns frames as JSON, and runs under strict fuel (CPU) and
+//! memory limits — a misbehaving layout can only fail its own arrange
+//! pass, never wedge the daemon. Any language that compiles to WASM works.
+//!
+//! Module contract:
+//! - export `memory`, `alloc(len: i32) -> i32`
+//! - export `compute_layout(ptr: i32, len: i32) -> i64` where the result
+//!   packs (ptr << 32 | len) of a JSON `LayoutResponse` in guest memory.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::{Rect, WindowInfo};
+
+/// CPU budget per layout call, in wasmtime fuel units (~instructions).
+const FUEL_PER_CALL: u64 = 50_000_000;
+
+/// Memory ceiling per layout instance.
+const MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Input handed to the guest.
+#[derive(Debug, Serialize)]
+pub struct LayoutRequest<'a> {
+    pub work_area: Rect,
+    pub windows: &'a [WindowInfo],
+}
+
+/// Output expected from the guest.
+#[derive(Debug, Deserialize)]
+pub struct LayoutResponse {
+    /// One frame per input window, in order.
+    pub frames: Vec<Rect>,
+}
+
+/// A compiled, reusable WASM layout.
+pub struct WasmLayout {
+    pub name: String,
+    engine: Engine,
+    module: Module,
+}
+
+impl WasmLayout {
+    /// Compile a layout module from disk. Compilation happens once; each
+    /// call gets a fresh store so guest state cannot leak between passes.
+    pub fn load(name: impl Into<String>, path: impl AsRef<Path>) -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)
+            .map_err(|e| TilleRSError::Validation(format!("wasm engine: {e}")))?;
+        let module = Module::from_file(&engine, path)
+            .map_err(|e| TilleRSError::Validation(format!("wasm module: {e}")))?;
+        Ok(WasmLayout {
+            name: name.into(),
+            engine,
+            module,
+        })
+    }
+
+    /// Run the layout under fuel and memory limits.
+    pub fn compute(&self, work_area: Rect, windows: &[WindowInfo]) -> Result<Vec<Rect>> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .build();
+        let mut store: Store<StoreLimits> = Store::new(&self.engine, limits);
+        store.limiter(|l| l);
+        store
+            .set_fuel(FUEL_PER_CALL)
+            .map_err(|e| wasm_err("fuel", &e))?;
+
+        let instance = Instance::new(&mut store, &self.module, &[])
+            .map_err(|e| wasm_err("instantiate", &e))?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| TilleRSError::Validation("wasm layout exports no memory".into()))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .map_err(|e| wasm_err("alloc export", &e))?;
+        let compute = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, "compute_layout")
+            .map_err(|e| wasm_err("compute_layout export", &e))?;
+
+        let request = serde_json::to_vec(&LayoutRequest { work_area, windows })?;
+        let ptr = alloc
+            .call(&mut store, request.len() as i32)
+            .map_err(|e| wasm_err("alloc", &e))?;
+        memory
+            .write(&mut store, ptr as usize, &request)
+            .map_err(|e| wasm_err("memory write", &e))?;
+
+        // A fuel exhaustion trap surfaces here as an error — the time
+        // limit working as intended.
+        let packed = compute
+            .call(&mut store, (ptr, request.len() as i32))
+            .map_err(|e| wasm_err("compute_layout", &e))?;
+        let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+
+        let mut out = vec![0u8; out_len];
+        memory
+            .read(&store, out_ptr, &mut out)
+            .map_err(|e| wasm_err("memory read", &e))?;
+        let response: LayoutResponse = serde_json::from_slice(&out)?;
+
+        if response.frames.len() != windows.len() {
+            return Err(TilleRSError::Validation(format!(
+                "wasm layout '{}' returned {} frames for {} windows",
+                self.name,
+                response.frames.len(),
+                windows.len()
+            )));
+        }
+        Ok(response.frames)
+    }
+}
+
+fn wasm_err(stage: &str, err: &dyn std::fmt::Display) -> TilleRSError {
+    TilleRSError::Validation(format!("wasm layout {stage} failed: {err}"))
+}
+
+/// Adapter exposing a [`WasmLayout`] as a layout-capability plugin, so
+/// `.wasm` files in the plugin directory load through the same host and
+/// naming scheme as dylib plugins.
+pub struct WasmLayoutPlugin {
+    layout: WasmLayout,
+}
+
+impl WasmLayoutPlugin {
+    pub fn new(layout: WasmLayout) -> Self {
+        WasmLayoutPlugin { layout }
+    }
+}
+
+impl super::TillersPlugin for WasmLayoutPlugin {
+    fn manifest(&self) -> super::PluginManifest {
+        super::PluginManifest {
+            name: self.layout.name.clone(),
+            // A raw module carries no version metadata of its own.
+            version: "unversioned".into(),
+            abi_version: super::PLUGIN_ABI_VERSION,
+            capabilities: vec![super::Capability::CustomLayouts],
+        }
+    }
+
+    fn layout_names(&self) -> Vec<String> {
+        vec![self.layout.name.clone()]
+    }
+
+    fn compute_layout(
+        &mut self,
+        name: &str,
+        display: &crate::models::display::DisplayInfo,
+        windows: &[WindowInfo],
+    ) -> Result<Vec<Rect>> {
+        if name != self.layout.name {
+            return Err(TilleRSError::NotFound {
+                kind: "layout",
+                name: name.to_string(),
+            });
+        }
+        self.layout.compute(display.work_area, windows)
+    }
+}
@@ -0,0 +1,9 @@
+//! Local usage statistics.
+//!
+//! The daemon records where time is spent (workspace and app focus) into a
+//! small JSON store under the user's data directory. Everything stays on
+//! disk locally; diagnostics commands read it back for reports.
+
+pub mod store;
+
+pub use store::{ShortcutUsage, StatisticsStore, WorkspaceUsage};
@@ -0,0 +1,172 @@
+//! Persistent store for usage statistics.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+
+/// Per-workspace usage, bucketed by weekday and hour.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceUsage {
+    /// Focused seconds per (weekday 0=Mon..6=Sun, hour 0..23).
+    pub seconds: Vec<Vec<u64>>,
+    /// Focused seconds attributed per application bundle id.
+    pub apps: HashMap<String, u64>,
+}
+
+impl Default for WorkspaceUsage {
+    fn default() -> Self {
+        WorkspaceUsage {
+            seconds: vec![vec![0; 24]; 7],
+            apps: HashMap::new(),
+        }
+    }
+}
+
+impl WorkspaceUsage {
+    /// Total focused seconds across all buckets.
+    pub fn total_seconds(&self) -> u64 {
+        self.seconds.iter().flatten().sum()
+    }
+}
+
+/// Usage of one keyboard binding, keyed by the mapping's stat key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutUsage {
+    pub count: u64,
+    /// Serialized action tag, for the most-frequent-actions report.
+    pub action: String,
+    pub last_used: SystemTime,
+}
+
+/// On-disk shape of the store.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct StatsData {
+    workspaces: HashMap<String, WorkspaceUsage>,
+    shortcuts: HashMap<String, ShortcutUsage>,
+}
+
+/// The statistics store; loaded at daemon start, flushed periodically.
+#[derive(Debug)]
+pub struct StatisticsStore {
+    path: PathBuf,
+    data: StatsData,
+}
+
+impl StatisticsStore {
+    /// Default store location: `~/.local/share/tillers/stats.json`.
+    pub fn default_path() -> PathBuf {
+        let home = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        home.join(".local/share/tillers/stats.json")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let data = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&path)?)?
+        } else {
+            StatsData::default()
+        };
+        Ok(StatisticsStore { path, data })
+    }
+
+    pub fn load_default() -> Result<Self> {
+        Self::load(Self::default_path())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string(&self.data)?)?;
+        Ok(())
+    }
+
+    /// Attribute a focus interval ending now to a workspace and app.
+    ///
+    /// The whole interval is bucketed at its end time; focus intervals are
+    /// short enough (seconds to minutes) that smearing across bucket
+    /// boundaries is not worth the complexity.
+    pub fn record_focus(
+        &mut self,
+        workspace: &str,
+        app_bundle_id: &str,
+        duration: Duration,
+        at: SystemTime,
+    ) {
+        let (weekday, hour) = weekday_hour(at);
+        let usage = self
+            .data
+            .workspaces
+            .entry(workspace.to_string())
+            .or_default();
+        usage.seconds[weekday][hour] += duration.as_secs();
+        *usage.apps.entry(app_bundle_id.to_string()).or_insert(0) += duration.as_secs();
+    }
+
+    /// Usage for all workspaces, for reports.
+    pub fn workspaces(&self) -> &HashMap<String, WorkspaceUsage> {
+        &self.data.workspaces
+    }
+
+    /// Count one invocation of a keyboard binding.
+    pub fn record_shortcut(&mut self, key: &str, action: &str, at: SystemTime) {
+        let usage = self
+            .data
+            .shortcuts
+            .entry(key.to_string())
+            .or_insert(ShortcutUsage {
+                count: 0,
+                action: action.to_string(),
+                last_used: at,
+            });
+        usage.count += 1;
+        usage.action = action.to_string();
+        usage.last_used = at;
+    }
+
+    /// Recorded shortcut usage, for the dead-binding report.
+    pub fn shortcuts(&self) -> &HashMap<String, ShortcutUsage> {
+        &self.data.shortcuts
+    }
+}
+
+/// (weekday 0=Mon, hour) in local time for a timestamp.
+fn weekday_hour(at: SystemTime) -> (usize, usize) {
+    let secs = at
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    let offset = local_utc_offset_seconds();
+    let local = secs + offset;
+    let days = local.div_euclid(86_400);
+    // 1970-01-01 was a Thursday (weekday index 3 with Monday = 0).
+    let weekday = (days + 3).rem_euclid(7) as usize;
+    let hour = (local.rem_euclid(86_400) / 3600) as usize;
+    (weekday, hour)
+}
+
+/// Current UTC offset, read once per call from the C library.
+fn local_utc_offset_seconds() -> i64 {
+    // `date +%z` is portable across macOS and Linux and avoids pulling in
+    // a timezone database crate for one offset.
+    std::process::Command::new("date")
+        .arg("+%z")
+        .output()
+        .ok()
+        .and_then(|o| {
+            let s = String::from_utf8_lossy(&o.stdout);
+            let s = s.trim();
+            let sign = if s.starts_with('-') { -1 } else { 1 };
+            let hours: i64 = s.get(1..3)?.parse().ok()?;
+            let minutes: i64 = s.get(3..5)?.parse().ok()?;
+            Some(sign * (hours * 3600 + minutes * 60))
+        })
+        .unwrap_or(0)
+}
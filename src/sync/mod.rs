@@ -0,0 +1,173 @@
+//! Experimental: workspace definition sync between Macs.
+//!
+//! For people running two Macs side by side (often with Universal Control),
+//! this keeps workspace *structure* — names, layouts, ordering — identical
+//! on both machines. Window contents are never synced; only definitions and
+//! layout snapshots travel.
+//!
+//! Peers currently find each other — and exchange state — over a single
+//! pre-shared-key authenticated multicast beacon; the planned split into
+//! mDNS discovery (`_tillers-sync._tcp.local.`) plus a TCP channel keeps
+//! the same envelope format. The whole subsystem is off unless
+//! `[sync] enabled = true` is set in the config.
+
+pub mod protocol;
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::Workspace;
+use protocol::{SyncEnvelope, SyncMessage, PROTOCOL_VERSION};
+
+/// mDNS service type peers advertise and browse for.
+pub const SERVICE_TYPE: &str = "_tillers-sync._tcp.local.";
+
+/// Multicast group the interim beacon rides on.
+pub const MULTICAST_GROUP: std::net::Ipv4Addr = std::net::Ipv4Addr::new(239, 84, 76, 83);
+
+/// UDP port for the beacon.
+pub const SYNC_PORT: u16 = 47365;
+
+/// How often we re-announce and re-browse.
+pub const DISCOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// `[sync]` config table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Master switch; everything below is ignored when false.
+    pub enabled: bool,
+    /// Pre-shared key both machines must agree on. Messages are
+    /// authenticated with an HMAC over this key; unauthenticated peers are
+    /// dropped before any state is exchanged.
+    pub shared_key: String,
+    /// Human-readable name announced to peers; defaults to the hostname.
+    pub machine_name: Option<String>,
+}
+
+/// A peer discovered on the local network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Peer {
+    pub machine_name: String,
+    pub address: std::net::SocketAddr,
+    pub protocol_version: u32,
+}
+
+/// Drives discovery and state exchange with peers.
+///
+/// Sync is last-writer-wins per workspace, keyed by the workspace UUID, so
+/// renaming on one machine propagates without duplicating the workspace.
+pub struct SyncService {
+    config: SyncConfig,
+    peers: Vec<Peer>,
+}
+
+impl SyncService {
+    pub fn new(config: SyncConfig) -> Result<Self> {
+        if config.enabled && config.shared_key.is_empty() {
+            return Err(TilleRSError::Config(
+                "[sync] enabled requires a non-empty shared_key".into(),
+            ));
+        }
+        Ok(SyncService {
+            config,
+            peers: Vec::new(),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// The name announced to peers, falling back to the hostname.
+    pub fn machine_name(&self) -> String {
+        self.config
+            .machine_name
+            .clone()
+            .unwrap_or_else(local_hostname)
+    }
+
+    /// Build the authenticated hello announcing this machine.
+    pub fn hello(&self) -> Result<SyncEnvelope> {
+        SyncEnvelope::seal(
+            &self.config.shared_key,
+            SyncMessage::Hello {
+                machine_name: self.machine_name(),
+                protocol_version: PROTOCOL_VERSION,
+            },
+        )
+    }
+
+    pub fn peers(&self) -> &[Peer] {
+        &self.peers
+    }
+
+    /// Record a peer found by the mDNS browser, replacing any stale entry
+    /// for the same machine.
+    pub fn peer_discovered(&mut self, peer: Peer) {
+        if peer.protocol_version != PROTOCOL_VERSION {
+            tracing::warn!(
+                machine = %peer.machine_name,
+                theirs = peer.protocol_version,
+                ours = PROTOCOL_VERSION,
+                "ignoring sync peer with incompatible protocol version"
+            );
+            return;
+        }
+        self.peers.retain(|p| p.machine_name != peer.machine_name);
+        self.peers.push(peer);
+    }
+
+    pub fn peer_lost(&mut self, machine_name: &str) {
+        self.peers.retain(|p| p.machine_name != machine_name);
+    }
+
+    /// Build the authenticated envelope announcing our workspace set.
+    pub fn workspace_announcement(&self, workspaces: &[Workspace]) -> Result<SyncEnvelope> {
+        SyncEnvelope::seal(
+            &self.config.shared_key,
+            SyncMessage::Workspaces {
+                workspaces: workspaces.to_vec(),
+            },
+        )
+    }
+
+    /// Verify and open an incoming envelope; tampered or foreign-key
+    /// messages are rejected.
+    pub fn open(&self, envelope: &SyncEnvelope) -> Result<SyncMessage> {
+        envelope.open(&self.config.shared_key)
+    }
+
+    /// Merge a peer's workspace set into ours: last writer wins per UUID,
+    /// unknown UUIDs are adopted. Returns workspaces that changed.
+    pub fn merge_workspaces(local: &mut Vec<Workspace>, remote: Vec<Workspace>) -> Vec<Workspace> {
+        let mut changed = Vec::new();
+        for remote_ws in remote {
+            match local.iter_mut().find(|w| w.id == remote_ws.id) {
+                Some(existing) => {
+                    if existing.name != remote_ws.name || existing.layout != remote_ws.layout {
+                        *existing = remote_ws.clone();
+                        changed.push(remote_ws);
+                    }
+                }
+                None => {
+                    local.push(remote_ws.clone());
+                    changed.push(remote_ws);
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// This machine's hostname, for the default announcement name.
+fn local_hostname() -> String {
+    std::process::Command::new("hostname")
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unnamed-mac".to_string())
+}
@@ -0,0 +1,79 @@
+//! Wire protocol for the experimental machine sync.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::{Rect, Workspace};
+
+/// Bumped on any incompatible change; mismatched peers are ignored.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Messages exchanged between peers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SyncMessage {
+    /// First message on every connection.
+    Hello {
+        machine_name: String,
+        protocol_version: u32,
+    },
+    /// Full workspace definition set of the sender.
+    Workspaces { workspaces: Vec<Workspace> },
+    /// Layout snapshot for one workspace: pattern state and frames, so the
+    /// receiving machine can reproduce the same structure.
+    LayoutSnapshot {
+        workspace_name: String,
+        frames: Vec<Rect>,
+    },
+}
+
+/// An authenticated message: payload plus a keyed digest.
+///
+/// This is integrity/authenticity only — traffic stays on the local
+/// network and carries no window contents, so confidentiality is out of
+/// scope for the experiment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncEnvelope {
+    payload: String,
+    digest: String,
+}
+
+impl SyncEnvelope {
+    /// Serialize and authenticate `message` with the shared key.
+    pub fn seal(shared_key: &str, message: SyncMessage) -> Result<Self> {
+        let payload = serde_json::to_string(&message)?;
+        Ok(SyncEnvelope {
+            digest: keyed_digest(shared_key, &payload),
+            payload,
+        })
+    }
+
+    /// Verify the digest and deserialize the payload.
+    pub fn open(&self, shared_key: &str) -> Result<SyncMessage> {
+        let expected = keyed_digest(shared_key, &self.payload);
+        if !constant_time_eq(expected.as_bytes(), self.digest.as_bytes()) {
+            return Err(TilleRSError::Validation(
+                "sync message failed authentication; check that both \
+                 machines use the same shared_key"
+                    .into(),
+            ));
+        }
+        Ok(serde_json::from_str(&self.payload)?)
+    }
+}
+
+fn keyed_digest(key: &str, payload: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(payload.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,443 @@
+//! The tiling engine: layout patterns and frame computation.
+//!
+//! The engine is pure geometry — it maps a work area and a window count to
+//! target frames. Applying frames to real windows happens in the macOS
+//! layer, which keeps every layout algorithm testable headlessly.
+
+pub mod patterns;
+pub mod splits;
+pub mod zorder;
+
+pub use patterns::LayoutPattern;
+pub use splits::SplitRatios;
+pub use zorder::{WindowLayout, ZOrderConfig};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::display::DisplayInfo;
+use crate::models::Rect;
+
+/// Gap configuration applied around and between tiled windows, in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gaps {
+    /// Gap between adjacent windows.
+    pub inner: f64,
+    /// Margin between windows and the work-area edge.
+    pub outer: f64,
+}
+
+impl Default for Gaps {
+    fn default() -> Self {
+        Gaps {
+            inner: 8.0,
+            outer: 8.0,
+        }
+    }
+}
+
+/// One gap dimension as configured: absolute points, or a percentage of
+/// the display's smaller dimension so the same config scales from a 14"
+/// laptop to an ultrawide.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GapValue {
+    /// `inner = 8.0`
+    Points(f64),
+    /// `inner = { percent = 0.8 }`
+    Percent { percent: f64 },
+}
+
+impl GapValue {
+    /// Resolve to points on a display.
+    pub fn resolve(&self, display: &DisplayInfo) -> f64 {
+        match self {
+            GapValue::Points(points) => *points,
+            GapValue::Percent { percent } => {
+                display.frame.width.min(display.frame.height) * percent / 100.0
+            }
+        }
+    }
+}
+
+/// Per-display gap override; unset fields fall back to the global value.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GapsOverride {
+    pub inner: Option<GapValue>,
+    pub outer: Option<GapValue>,
+}
+
+/// The `[gaps]` config section, resolved to concrete [`Gaps`] per display
+/// before each arrange pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GapsConfig {
+    pub inner: GapValue,
+    pub outer: GapValue,
+    /// Overrides keyed by display name.
+    pub displays: std::collections::BTreeMap<String, GapsOverride>,
+}
+
+impl Default for GapsConfig {
+    fn default() -> Self {
+        GapsConfig {
+            inner: GapValue::Points(8.0),
+            outer: GapValue::Points(8.0),
+            displays: std::collections::BTreeMap::new(),
+        }
+    }
+}
+
+impl GapsConfig {
+    /// Concrete gaps for one display: its override where set, the global
+    /// values otherwise, percentages scaled by the display.
+    pub fn resolve(&self, display: &DisplayInfo) -> Gaps {
+        let over = self
+            .displays
+            .get(&display.name)
+            .copied()
+            .unwrap_or_default();
+        Gaps {
+            inner: over.inner.unwrap_or(self.inner).resolve(display),
+            outer: over.outer.unwrap_or(self.outer).resolve(display),
+        }
+    }
+}
+
+/// The `[center_master]` config section: master share and auto-selection
+/// on ultrawide displays.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CenterMasterConfig {
+    /// Master column share of the work area; the side columns split the
+    /// rest evenly. Overridden per workspace by its persisted splits.
+    pub master_ratio: f64,
+    /// Automatically use center-master on displays whose aspect ratio
+    /// exceeds `aspect_threshold`.
+    pub auto_select: bool,
+    /// Width-to-height ratio above which a display counts as ultrawide
+    /// (2.0 ≈ anything wider than 16:9).
+    pub aspect_threshold: f64,
+}
+
+impl Default for CenterMasterConfig {
+    fn default() -> Self {
+        CenterMasterConfig {
+            master_ratio: 0.5,
+            auto_select: false,
+            aspect_threshold: 2.0,
+        }
+    }
+}
+
+impl CenterMasterConfig {
+    /// The pattern to actually use on a display: `configured`, unless
+    /// auto-selection applies and the display is ultrawide.
+    pub fn effective_pattern(
+        &self,
+        configured: LayoutPattern,
+        display: &DisplayInfo,
+    ) -> LayoutPattern {
+        let ultrawide = display.frame.height > 0.0
+            && display.frame.width / display.frame.height > self.aspect_threshold;
+        // Monocle is a deliberate choice; never override it.
+        if self.auto_select && ultrawide && configured != LayoutPattern::Monocle {
+            LayoutPattern::CenterMaster
+        } else {
+            configured
+        }
+    }
+}
+
+/// Computes window frames for layout patterns.
+#[derive(Debug, Clone, Default)]
+pub struct TilingEngine {
+    pub gaps: Gaps,
+    /// Fraction of the work area given to the main window, where the
+    /// pattern has a main/stack split.
+    pub main_area_ratio: f64,
+}
+
+impl TilingEngine {
+    pub fn new() -> Self {
+        TilingEngine {
+            gaps: Gaps::default(),
+            main_area_ratio: 0.6,
+        }
+    }
+
+    /// Compute target frames for `count` windows in `work_area`, using the
+    /// engine's global ratio and an even stack.
+    ///
+    /// Frames are returned in window order: index 0 is the main window.
+    pub fn compute_frames(
+        &self,
+        pattern: LayoutPattern,
+        work_area: Rect,
+        count: usize,
+    ) -> Vec<Rect> {
+        let splits = SplitRatios {
+            main: self.main_area_ratio,
+            stack: Vec::new(),
+        };
+        self.compute_frames_with_splits(pattern, work_area, count, &splits)
+    }
+
+    /// Like [`compute_frames`](Self::compute_frames), but honoring a
+    /// workspace's persisted split ratios.
+    pub fn compute_frames_with_splits(
+        &self,
+        pattern: LayoutPattern,
+        work_area: Rect,
+        count: usize,
+        splits: &SplitRatios,
+    ) -> Vec<Rect> {
+        if count == 0 {
+            return Vec::new();
+        }
+        let area = self.inset(work_area, self.gaps.outer);
+        match pattern {
+            LayoutPattern::Monocle => vec![area; count],
+            LayoutPattern::Tall => self.main_and_stack(area, count, false, splits),
+            LayoutPattern::Wide => self.main_and_stack(area, count, true, splits),
+            LayoutPattern::Grid => self.grid(area, count),
+            LayoutPattern::CenterMaster => self.center_master(area, count, splits),
+        }
+    }
+
+    /// Three columns for ultrawides: the master centered, stack windows
+    /// alternating onto the right and left columns. `splits.main` sets
+    /// the master column's share; with a single stack window the unused
+    /// side column is given back to the master.
+    fn center_master(&self, area: Rect, count: usize, splits: &SplitRatios) -> Vec<Rect> {
+        if count == 1 {
+            return vec![area];
+        }
+        let gap = self.gaps.inner;
+        let ratio = splits.main.clamp(0.1, 0.9);
+        let right_count = count / 2; // stack: right gets the extra window
+        let left_count = count - 1 - right_count;
+        let side_columns = 1 + usize::from(left_count > 0);
+        let master_w = area.width * ratio - gap * side_columns as f64 / 2.0;
+        let side_w = (area.width - master_w - gap * side_columns as f64) / side_columns as f64;
+
+        let column = |x: f64, w: f64, rows: usize| -> Vec<Rect> {
+            let usable_h = area.height - gap * (rows.saturating_sub(1)) as f64;
+            let h = usable_h / rows.max(1) as f64;
+            (0..rows)
+                .map(|row| Rect {
+                    x,
+                    y: area.y + row as f64 * (h + gap),
+                    width: w,
+                    height: h,
+                })
+                .collect()
+        };
+
+        let master_x = if left_count > 0 {
+            area.x + side_w + gap
+        } else {
+            area.x
+        };
+        let mut frames = vec![Rect {
+            x: master_x,
+            y: area.y,
+            width: master_w,
+            height: area.height,
+        }];
+        let mut right = column(master_x + master_w + gap, side_w, right_count).into_iter();
+        let mut left = column(area.x, side_w, left_count).into_iter();
+        // Window order: main first, then stack windows alternating
+        // right/left so new windows fill both sides evenly.
+        for i in 0..count - 1 {
+            let frame = if i % 2 == 0 {
+                right.next()
+            } else {
+                left.next()
+            };
+            frames.push(frame.expect("column sizes cover the stack"));
+        }
+        frames
+    }
+
+    fn inset(&self, r: Rect, by: f64) -> Rect {
+        Rect {
+            x: r.x + by,
+            y: r.y + by,
+            width: (r.width - 2.0 * by).max(0.0),
+            height: (r.height - 2.0 * by).max(0.0),
+        }
+    }
+
+    /// One main window plus a stack; `horizontal` stacks below instead of
+    /// beside the main window. Stack windows are sized by their persisted
+    /// weights so a manually resized stack entry keeps its share.
+    fn main_and_stack(
+        &self,
+        area: Rect,
+        count: usize,
+        horizontal: bool,
+        splits: &SplitRatios,
+    ) -> Vec<Rect> {
+        if count == 1 {
+            return vec![area];
+        }
+        let ratio = splits.main.clamp(0.1, 0.9);
+        let gap = self.gaps.inner;
+        let mut frames = Vec::with_capacity(count);
+        let stack_count = count - 1;
+        let total_weight = splits.stack_total(stack_count);
+
+        if horizontal {
+            let main_h = area.height * ratio - gap / 2.0;
+            let stack_h = area.height - main_h - gap;
+            frames.push(Rect {
+                x: area.x,
+                y: area.y,
+                width: area.width,
+                height: main_h,
+            });
+            let usable_w = area.width - gap * (stack_count as f64 - 1.0);
+            let mut x = area.x;
+            for i in 0..stack_count {
+                let w = usable_w * splits.stack_weight(i) / total_weight;
+                frames.push(Rect {
+                    x,
+                    y: area.y + main_h + gap,
+                    width: w,
+                    height: stack_h,
+                });
+                x += w + gap;
+            }
+        } else {
+            let main_w = area.width * ratio - gap / 2.0;
+            let stack_w = area.width - main_w - gap;
+            frames.push(Rect {
+                x: area.x,
+                y: area.y,
+                width: main_w,
+                height: area.height,
+            });
+            let usable_h = area.height - gap * (stack_count as f64 - 1.0);
+            let mut y = area.y;
+            for i in 0..stack_count {
+                let h = usable_h * splits.stack_weight(i) / total_weight;
+                frames.push(Rect {
+                    x: area.x + main_w + gap,
+                    y,
+                    width: stack_w,
+                    height: h,
+                });
+                y += h + gap;
+            }
+        }
+        frames
+    }
+
+    /// Near-square grid, filled row-major.
+    fn grid(&self, area: Rect, count: usize) -> Vec<Rect> {
+        let cols = (count as f64).sqrt().ceil() as usize;
+        let rows = count.div_ceil(cols);
+        let gap = self.gaps.inner;
+        let cell_w = (area.width - gap * (cols as f64 - 1.0)) / cols as f64;
+        let cell_h = (area.height - gap * (rows as f64 - 1.0)) / rows as f64;
+        (0..count)
+            .map(|i| {
+                let (row, col) = (i / cols, i % cols);
+                Rect {
+                    x: area.x + col as f64 * (cell_w + gap),
+                    y: area.y + row as f64 * (cell_h + gap),
+                    width: cell_w,
+                    height: cell_h,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Gapless engine so the expected frames are exact.
+    fn engine() -> TilingEngine {
+        TilingEngine {
+            gaps: Gaps {
+                inner: 0.0,
+                outer: 0.0,
+            },
+            main_area_ratio: 0.6,
+        }
+    }
+
+    #[test]
+    fn zero_windows_compute_no_frames() {
+        let frames = engine().compute_frames(LayoutPattern::Tall, rect(0.0, 0.0, 1000.0, 600.0), 0);
+        assert!(frames.is_empty());
+    }
+
+    #[test]
+    fn monocle_gives_every_window_the_full_area() {
+        let area = rect(0.0, 0.0, 1000.0, 600.0);
+        let frames = engine().compute_frames(LayoutPattern::Monocle, area, 3);
+        assert_eq!(frames, vec![area; 3]);
+    }
+
+    #[test]
+    fn tall_splits_main_beside_an_even_stack() {
+        let frames = engine().compute_frames(LayoutPattern::Tall, rect(0.0, 0.0, 1000.0, 600.0), 3);
+        assert_eq!(frames[0], rect(0.0, 0.0, 600.0, 600.0));
+        assert_eq!(frames[1], rect(600.0, 0.0, 400.0, 300.0));
+        assert_eq!(frames[2], rect(600.0, 300.0, 400.0, 300.0));
+    }
+
+    #[test]
+    fn stack_weights_shift_the_split() {
+        let splits = SplitRatios {
+            main: 0.6,
+            stack: vec![3.0, 1.0],
+        };
+        let frames = engine().compute_frames_with_splits(
+            LayoutPattern::Tall,
+            rect(0.0, 0.0, 1000.0, 600.0),
+            3,
+            &splits,
+        );
+        // Weighted 3:1, the first stack window takes three quarters.
+        assert_eq!(frames[1].height, 450.0);
+        assert_eq!(frames[2].height, 150.0);
+        assert_eq!(frames[2].y, 450.0);
+    }
+
+    #[test]
+    fn grid_fills_row_major() {
+        let frames = engine().compute_frames(LayoutPattern::Grid, rect(0.0, 0.0, 1000.0, 600.0), 4);
+        assert_eq!(frames[0], rect(0.0, 0.0, 500.0, 300.0));
+        assert_eq!(frames[1], rect(500.0, 0.0, 500.0, 300.0));
+        assert_eq!(frames[2], rect(0.0, 300.0, 500.0, 300.0));
+        assert_eq!(frames[3], rect(500.0, 300.0, 500.0, 300.0));
+    }
+
+    #[test]
+    fn outer_gap_insets_the_work_area() {
+        let engine = TilingEngine {
+            gaps: Gaps {
+                inner: 0.0,
+                outer: 10.0,
+            },
+            main_area_ratio: 0.6,
+        };
+        let frames =
+            engine.compute_frames(LayoutPattern::Monocle, rect(0.0, 0.0, 1000.0, 600.0), 1);
+        assert_eq!(frames[0], rect(10.0, 10.0, 980.0, 580.0));
+    }
+}
@@ -0,0 +1,56 @@
+//! Built-in layout patterns.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::TilleRSError;
+
+/// The built-in layout families.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutPattern {
+    /// Main window on the left, stack on the right.
+    Tall,
+    /// Main window on top, stack along the bottom.
+    Wide,
+    /// Near-square grid.
+    Grid,
+    /// Every window gets the full work area.
+    Monocle,
+    /// Ultrawide three-column layout: a wide master column in the center,
+    /// stack columns on both sides.
+    CenterMaster,
+}
+
+impl fmt::Display for LayoutPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LayoutPattern::Tall => "tall",
+            LayoutPattern::Wide => "wide",
+            LayoutPattern::Grid => "grid",
+            LayoutPattern::Monocle => "monocle",
+            LayoutPattern::CenterMaster => "center-master",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for LayoutPattern {
+    type Err = TilleRSError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tall" => Ok(LayoutPattern::Tall),
+            "wide" => Ok(LayoutPattern::Wide),
+            "grid" => Ok(LayoutPattern::Grid),
+            "monocle" => Ok(LayoutPattern::Monocle),
+            "center-master" | "center_master" => Ok(LayoutPattern::CenterMaster),
+            other => Err(TilleRSError::Validation(format!(
+                "unknown layout pattern '{other}' (expected tall, wide, grid, monocle, \
+                 or center-master)"
+            ))),
+        }
+    }
+}
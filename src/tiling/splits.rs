@@ -0,0 +1,136 @@
+//! Per-workspace split ratios.
+//!
+//! The engine's `main_area_ratio` is a global default. When the user drags
+//! a tiled window's edge, the resulting ratios are captured here and
+//! persisted with the workspace, so manual adjustments survive workspace
+//! switches and daemon restarts instead of snapping back on the next
+//! arrange.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::Rect;
+
+/// Split ratios for one workspace's layout tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SplitRatios {
+    /// Fraction of the work area given to the main window.
+    pub main: f64,
+    /// Relative weights of the stack windows, top to bottom. Windows
+    /// beyond the recorded weights default to `1.0`; an empty list means
+    /// an even stack.
+    pub stack: Vec<f64>,
+}
+
+impl Default for SplitRatios {
+    fn default() -> Self {
+        SplitRatios {
+            main: 0.6,
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl SplitRatios {
+    /// Reset to equal distribution: default main ratio, even stack. This
+    /// is the `balance_layout` action, and runs on window close when
+    /// auto-balance is enabled.
+    pub fn balance(&mut self) {
+        *self = SplitRatios::default();
+    }
+
+    /// Whether all ratios are already at their balanced defaults.
+    pub fn is_balanced(&self) -> bool {
+        *self == SplitRatios::default()
+    }
+
+    /// Weight of the stack window at `index`.
+    pub fn stack_weight(&self, index: usize) -> f64 {
+        self.stack.get(index).copied().unwrap_or(1.0).max(0.1)
+    }
+
+    /// Total stack weight for `count` stack windows.
+    pub fn stack_total(&self, count: usize) -> f64 {
+        (0..count).map(|i| self.stack_weight(i)).sum()
+    }
+
+    /// Update the main ratio from a manual resize of the main window
+    /// within `work_area`. `vertical_split` is true for Tall (main beside
+    /// stack), false for Wide (main above stack).
+    pub fn record_main_resize(&mut self, work_area: &Rect, new_frame: &Rect, vertical_split: bool) {
+        let ratio = if vertical_split {
+            new_frame.width / work_area.width.max(1.0)
+        } else {
+            new_frame.height / work_area.height.max(1.0)
+        };
+        self.main = ratio.clamp(0.1, 0.9);
+    }
+
+    /// Update a stack window's weight from a manual resize. The weight is
+    /// taken relative to an even share so untouched siblings keep `1.0`.
+    pub fn record_stack_resize(
+        &mut self,
+        index: usize,
+        stack_extent: f64,
+        new_extent: f64,
+        stack_count: usize,
+    ) {
+        if stack_count == 0 || stack_extent <= 0.0 {
+            return;
+        }
+        let even_share = stack_extent / stack_count as f64;
+        let weight = (new_extent / even_share).clamp(0.1, 10.0);
+        if self.stack.len() < stack_count {
+            self.stack.resize(stack_count, 1.0);
+        }
+        self.stack[index] = weight;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balance_resets_to_defaults() {
+        let mut splits = SplitRatios {
+            main: 0.8,
+            stack: vec![2.0, 0.5],
+        };
+        assert!(!splits.is_balanced());
+        splits.balance();
+        assert!(splits.is_balanced());
+        assert_eq!(splits.main, 0.6);
+        assert!(splits.stack.is_empty());
+    }
+
+    #[test]
+    fn unrecorded_stack_windows_weigh_one() {
+        let splits = SplitRatios {
+            main: 0.6,
+            stack: vec![2.0],
+        };
+        assert_eq!(splits.stack_weight(0), 2.0);
+        assert_eq!(splits.stack_weight(5), 1.0);
+        assert_eq!(splits.stack_total(3), 4.0);
+    }
+
+    #[test]
+    fn main_resize_is_clamped() {
+        let work_area = Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 1000.0,
+            height: 600.0,
+        };
+        let mut splits = SplitRatios::default();
+        let frame = Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 990.0,
+            height: 600.0,
+        };
+        splits.record_main_resize(&work_area, &frame, true);
+        assert_eq!(splits.main, 0.9);
+    }
+}
@@ -0,0 +1,81 @@
+//! Z-order policy applied after frame placement.
+//!
+//! Frames say where windows go; z-order says what wins when they overlap —
+//! which matters for main-and-stack patterns with negative gaps and for
+//! floating windows over the tiled plane. The policy turns a layout into a
+//! raise sequence executed back-to-front after the frames land.
+
+use serde::{Deserialize, Serialize};
+
+use crate::daemon::Effects;
+use crate::errors::Result;
+use crate::models::{Rect, WindowId};
+
+/// Stacking rules for one layout pattern.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ZOrderConfig {
+    /// Keep the main window above the stack.
+    pub master_above_stack: bool,
+    /// Keep floating windows above everything tiled.
+    pub floating_above_tiled: bool,
+}
+
+impl Default for ZOrderConfig {
+    fn default() -> Self {
+        ZOrderConfig {
+            master_above_stack: true,
+            floating_above_tiled: true,
+        }
+    }
+}
+
+/// A computed layout: tiled frame assignments plus the floating windows
+/// sharing the workspace, with the raise sequence that realizes the
+/// z-order policy.
+#[derive(Debug, Clone)]
+pub struct WindowLayout {
+    /// Tiled windows in layout order; index 0 is the main window.
+    pub tiled: Vec<(WindowId, Rect)>,
+    /// Floating windows, frontmost first.
+    pub floating: Vec<WindowId>,
+    /// Raise operations, back-to-front; applying them in order leaves the
+    /// last entry on top.
+    pub raise_order: Vec<WindowId>,
+}
+
+impl WindowLayout {
+    /// Combine frame assignments with the z-order policy.
+    pub fn new(
+        tiled: Vec<(WindowId, Rect)>,
+        floating: Vec<WindowId>,
+        config: ZOrderConfig,
+    ) -> Self {
+        let mut raise_order = Vec::with_capacity(tiled.len() + floating.len());
+        if config.master_above_stack {
+            // Stack first, main window last among the tiled plane.
+            raise_order.extend(tiled.iter().skip(1).map(|(w, _)| *w));
+            raise_order.extend(tiled.first().map(|(w, _)| *w));
+        } else {
+            raise_order.extend(tiled.iter().map(|(w, _)| *w));
+        }
+        if config.floating_above_tiled {
+            // Floating windows keep their own relative order on top.
+            raise_order.extend(floating.iter().rev());
+        }
+        WindowLayout {
+            tiled,
+            floating,
+            raise_order,
+        }
+    }
+
+    /// Execute the raise sequence. Called after every frame was applied so
+    /// raises never fight in-flight moves.
+    pub fn apply_z_order(&self, effects: &Effects) -> Result<()> {
+        for &window in &self.raise_order {
+            effects.raise_window(window)?;
+        }
+        Ok(())
+    }
+}
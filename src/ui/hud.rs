@@ -0,0 +1,89 @@
+//! Quick-switch HUD: the workspace list shown while the workspace
+//! modifier is held.
+//!
+//! One row per numeric slot — number, workspace name, window count, and
+//! the apps living there — so the user sees targets before committing to
+//! a digit. The model here is platform-independent; rendering goes
+//! through the same overlay panels as the layout preview, with icons
+//! resolved lazily via [`IconService`](crate::ui::icons::IconService).
+
+use crate::models::WindowInfo;
+
+/// Numeric slots shown; matches the `opt+1..9` bindings.
+pub const MAX_ENTRIES: usize = 9;
+
+/// App icons shown per row before truncating to a count.
+pub const MAX_APPS_PER_ENTRY: usize = 5;
+
+/// One HUD row.
+#[derive(Debug, Clone)]
+pub struct HudEntry {
+    /// The digit that switches here (1-based).
+    pub number: usize,
+    pub name: String,
+    pub windows: usize,
+    /// Distinct bundle ids on the workspace, first-seen order, capped at
+    /// [`MAX_APPS_PER_ENTRY`]; the renderer resolves these to icons.
+    pub apps: Vec<String>,
+    pub active: bool,
+}
+
+/// Build the HUD rows for the current numeric binding order.
+///
+/// `order` is what `opt+N` resolves to right now — the active group's
+/// members or the global workspace order — so the HUD always shows what
+/// the digits actually do.
+pub fn build(order: &[String], active: &str, windows: &[WindowInfo]) -> Vec<HudEntry> {
+    order
+        .iter()
+        .take(MAX_ENTRIES)
+        .enumerate()
+        .map(|(i, name)| {
+            let mut apps: Vec<String> = Vec::new();
+            let mut count = 0;
+            for w in windows.iter().filter(|w| &w.workspace == name) {
+                count += 1;
+                if apps.len() < MAX_APPS_PER_ENTRY && !apps.contains(&w.app_bundle_id) {
+                    apps.push(w.app_bundle_id.clone());
+                }
+            }
+            HudEntry {
+                number: i + 1,
+                name: name.clone(),
+                windows: count,
+                apps,
+                active: name == active,
+            }
+        })
+        .collect()
+}
+
+/// HUD visibility state, driven by the keyboard layer's
+/// [`ModifierHold`](crate::keyboard::hold::ModifierHold) and re-built on
+/// every show so rows never go stale.
+#[derive(Debug, Default)]
+pub struct WorkspaceHud {
+    entries: Vec<HudEntry>,
+    visible: bool,
+}
+
+impl WorkspaceHud {
+    /// Show with fresh rows.
+    pub fn show(&mut self, entries: Vec<HudEntry>) {
+        self.entries = entries;
+        self.visible = true;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.entries.clear();
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn entries(&self) -> &[HudEntry] {
+        &self.entries
+    }
+}
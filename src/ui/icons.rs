@@ -0,0 +1,165 @@
+//! Application icon service.
+//!
+//! The tray, overview, and command palette all want app icons by bundle
+//! id. Resolving one costs an NSWorkspace round-trip and a rasterization,
+//! so icons are cached in memory (bounded, LRU-evicted) and on disk as
+//! pre-scaled PNGs; loads happen off the caller's thread and the caller
+//! polls the cache, never blocks on it.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Icons are rasterized at this edge length (points).
+pub const ICON_SIZE: usize = 64;
+
+/// In-memory cache bound; oldest-used entries evict beyond it.
+pub const MEMORY_CAPACITY: usize = 128;
+
+/// A rasterized app icon.
+#[derive(Debug, Clone)]
+pub struct Icon {
+    pub width: usize,
+    pub height: usize,
+    /// Tightly packed RGBA, row-major.
+    pub rgba: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    icon: Arc<Icon>,
+    last_used: Instant,
+}
+
+/// Resolves, caches, and asynchronously loads app icons by bundle id.
+pub struct IconService {
+    memory: Mutex<HashMap<String, CacheEntry>>,
+    /// Bundle ids currently being loaded, so repeated polls don't spawn
+    /// duplicate loads.
+    loading: Mutex<Vec<String>>,
+    completed: mpsc::Receiver<(String, Option<Icon>)>,
+    sender: mpsc::Sender<(String, Option<Icon>)>,
+}
+
+impl Default for IconService {
+    fn default() -> Self {
+        let (sender, completed) = mpsc::channel();
+        IconService {
+            memory: Mutex::new(HashMap::new()),
+            loading: Mutex::new(Vec::new()),
+            completed,
+            sender,
+        }
+    }
+}
+
+impl IconService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Disk cache location: `~/.cache/tillers/icons/`.
+    pub fn cache_dir() -> PathBuf {
+        let home = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        home.join(".cache").join("tillers").join("icons")
+    }
+
+    /// The icon for `bundle_id` if already cached; otherwise kicks off an
+    /// async load and returns `None`. Callers re-poll on their next draw.
+    pub fn get(&self, bundle_id: &str) -> Option<Arc<Icon>> {
+        self.drain_completed();
+        {
+            let mut memory = self.memory.lock().unwrap();
+            if let Some(entry) = memory.get_mut(bundle_id) {
+                entry.last_used = Instant::now();
+                return Some(Arc::clone(&entry.icon));
+            }
+        }
+        self.spawn_load(bundle_id);
+        None
+    }
+
+    /// Move finished loads from the worker channel into the cache.
+    fn drain_completed(&self) {
+        while let Ok((bundle_id, icon)) = self.completed.try_recv() {
+            self.loading.lock().unwrap().retain(|b| b != &bundle_id);
+            if let Some(icon) = icon {
+                self.insert(bundle_id, icon);
+            }
+        }
+    }
+
+    fn insert(&self, bundle_id: String, icon: Icon) {
+        let mut memory = self.memory.lock().unwrap();
+        if memory.len() >= MEMORY_CAPACITY {
+            // Evict the least recently used entry.
+            if let Some(oldest) = memory
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone())
+            {
+                memory.remove(&oldest);
+            }
+        }
+        memory.insert(
+            bundle_id,
+            CacheEntry {
+                icon: Arc::new(icon),
+                last_used: Instant::now(),
+            },
+        );
+    }
+
+    fn spawn_load(&self, bundle_id: &str) {
+        {
+            let mut loading = self.loading.lock().unwrap();
+            if loading.iter().any(|b| b == bundle_id) {
+                return;
+            }
+            loading.push(bundle_id.to_string());
+        }
+        let bundle_id = bundle_id.to_string();
+        let sender = self.sender.clone();
+        std::thread::Builder::new()
+            .name("tillers-icon-load".into())
+            .spawn(move || {
+                let icon = load_icon(&bundle_id);
+                let _ = sender.send((bundle_id, icon));
+            })
+            .expect("spawn icon load thread");
+    }
+}
+
+/// Load an icon from the disk cache, or resolve it via NSWorkspace and
+/// cache it for next time.
+fn load_icon(bundle_id: &str) -> Option<Icon> {
+    let cached = IconService::cache_dir().join(format!("{bundle_id}.rgba"));
+    if let Ok(raw) = std::fs::read(&cached) {
+        if raw.len() == ICON_SIZE * ICON_SIZE * 4 {
+            return Some(Icon {
+                width: ICON_SIZE,
+                height: ICON_SIZE,
+                rgba: raw,
+            });
+        }
+    }
+    let icon = resolve_icon(bundle_id)?;
+    if std::fs::create_dir_all(IconService::cache_dir()).is_ok() {
+        let _ = std::fs::write(&cached, &icon.rgba);
+    }
+    Some(icon)
+}
+
+#[cfg(target_os = "macos")]
+fn resolve_icon(bundle_id: &str) -> Option<Icon> {
+    crate::macos::app_icon_rgba(bundle_id, ICON_SIZE)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn resolve_icon(_bundle_id: &str) -> Option<Icon> {
+    None
+}
@@ -0,0 +1,11 @@
+//! User-facing surfaces: tray, overlays, and on-screen displays.
+
+pub mod hud;
+pub mod icons;
+pub mod palette;
+pub mod preview;
+pub mod settings;
+pub mod theme;
+pub mod thumbnails;
+pub mod touchbar;
+pub mod tray;
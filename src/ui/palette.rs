@@ -0,0 +1,137 @@
+//! Command palette: a hotkey-triggered overlay with fuzzy search over all
+//! actions, workspaces, and windows.
+//!
+//! The palette only *selects* — execution goes through the same action
+//! dispatch path as keyboard mappings, so palette-triggered actions behave
+//! identically to bound shortcuts.
+
+use crate::models::{ActionType, WindowInfo, Workspace};
+
+/// Where a palette entry came from, for grouping and icons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Action,
+    Workspace,
+    Window,
+}
+
+/// One selectable row in the palette.
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub kind: EntryKind,
+    /// Text shown and matched against, e.g. "Switch to workspace: coding".
+    pub label: String,
+    /// Action executed when the entry is chosen.
+    pub action: ActionType,
+}
+
+/// A matched entry with its fuzzy score, higher is better.
+#[derive(Debug, Clone)]
+pub struct RankedEntry<'a> {
+    pub entry: &'a PaletteEntry,
+    pub score: i64,
+}
+
+/// The searchable index rebuilt whenever workspaces or windows change.
+#[derive(Debug, Default)]
+pub struct PaletteIndex {
+    entries: Vec<PaletteEntry>,
+}
+
+impl PaletteIndex {
+    /// Build the index from current state. Static actions are always
+    /// present; workspaces and windows contribute one entry each.
+    pub fn build(workspaces: &[Workspace], windows: &[WindowInfo]) -> Self {
+        let mut entries = vec![
+            PaletteEntry {
+                kind: EntryKind::Action,
+                label: "Toggle floating for focused window".into(),
+                action: ActionType::ToggleFloat,
+            },
+            PaletteEntry {
+                kind: EntryKind::Action,
+                label: "Re-tile active workspace".into(),
+                action: ActionType::Retile,
+            },
+        ];
+        for ws in workspaces {
+            entries.push(PaletteEntry {
+                kind: EntryKind::Workspace,
+                label: format!("Switch to workspace: {}", ws.name),
+                action: ActionType::SwitchWorkspace {
+                    workspace: ws.name.clone(),
+                },
+            });
+        }
+        for w in windows {
+            entries.push(PaletteEntry {
+                kind: EntryKind::Window,
+                label: format!("Focus window: {} — {}", w.app_bundle_id, w.title),
+                action: ActionType::FocusWindow { window_id: w.id },
+            });
+        }
+        PaletteIndex { entries }
+    }
+
+    /// Rank entries against `query`, best match first. An empty query
+    /// returns everything in index order.
+    pub fn search(&self, query: &str) -> Vec<RankedEntry<'_>> {
+        let mut ranked: Vec<RankedEntry<'_>> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                fuzzy_score(query, &entry.label).map(|score| RankedEntry { entry, score })
+            })
+            .collect();
+        ranked.sort_by_key(|ranked| std::cmp::Reverse(ranked.score));
+        ranked
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Case-insensitive subsequence match.
+///
+/// Every query character must appear in order; consecutive matches and
+/// word-boundary matches score higher, earlier matches break ties.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let candidate_lower = candidate.to_lowercase();
+    let mut score: i64 = 0;
+    let mut last_match: Option<usize> = None;
+    let mut search_from = 0;
+
+    for qc in query.to_lowercase().chars() {
+        let found = candidate_lower[search_from..]
+            .char_indices()
+            .find(|(_, c)| *c == qc)
+            .map(|(i, c)| (search_from + i, c))?;
+        let (idx, _) = found;
+
+        score += 1;
+        if last_match == Some(idx.saturating_sub(1)) {
+            score += 2; // consecutive run
+        }
+        let at_word_boundary = idx == 0
+            || matches!(
+                candidate_lower.as_bytes().get(idx - 1),
+                Some(b' ') | Some(b'-') | Some(b'_') | Some(b':') | Some(b'.')
+            );
+        if at_word_boundary {
+            score += 3;
+        }
+        last_match = Some(idx);
+        search_from = idx + qc.len_utf8();
+    }
+    // Prefer shorter candidates when the matched characters are equal.
+    score -= (candidate.len() / 16) as i64;
+    Some(score)
+}
@@ -0,0 +1,28 @@
+//! Dry-run layout preview: translucent rectangles showing where windows
+//! would land, without moving anything.
+
+use std::time::Duration;
+
+use crate::errors::Result;
+use crate::models::Rect;
+use crate::ui::theme::Theme;
+
+/// Duration a preview stays on screen.
+pub const PREVIEW_DURATION: Duration = Duration::from_secs(3);
+
+/// Show numbered, translucent rectangles for `frames`, then fade out.
+///
+/// Blocks for the preview duration; callers run it from the CLI path, not
+/// the daemon's event loop.
+pub fn show_layout_preview(theme: &Theme, frames: &[Rect]) -> Result<()> {
+    let style = theme.overlay_style(0.35);
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::show_preview_rects(frames, theme.palette().accent, style, PREVIEW_DURATION)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (frames, style);
+        Ok(())
+    }
+}
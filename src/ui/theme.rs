@@ -0,0 +1,261 @@
+//! Central theming and accessibility settings for all overlay surfaces.
+//!
+//! Overlays (OSD, overview, cheat sheet) never read system accessibility
+//! state themselves; they ask [`Theme`] so reduced transparency and high
+//! contrast are applied uniformly.
+
+use serde::{Deserialize, Serialize};
+
+/// System accessibility preferences relevant to overlay rendering.
+///
+/// Detected at startup and refreshed when the system posts an
+/// accessibility-settings-changed notification.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessibilitySettings {
+    /// "Reduce transparency" in System Settings > Accessibility > Display.
+    pub reduce_transparency: bool,
+    /// "Increase contrast" in System Settings > Accessibility > Display.
+    pub increase_contrast: bool,
+    /// Whether VoiceOver is currently running.
+    pub voiceover_running: bool,
+}
+
+impl AccessibilitySettings {
+    /// Read the current system accessibility settings.
+    ///
+    /// Backed by `NSWorkspace.accessibilityDisplayShouldReduceTransparency`
+    /// and friends on macOS; returns defaults on the headless backend.
+    pub fn detect() -> Self {
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::accessibility_display_settings()
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            Self::default()
+        }
+    }
+}
+
+/// An sRGB color with alpha, serialized as `#rrggbb` or `#rrggbbaa`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 0xff }
+    }
+
+    /// Parse `#rrggbb` / `#rrggbbaa`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let hex = s.strip_prefix('#')?;
+        let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        match hex.len() {
+            6 => Some(Color {
+                r: byte(0)?,
+                g: byte(2)?,
+                b: byte(4)?,
+                a: 0xff,
+            }),
+            8 => Some(Color {
+                r: byte(0)?,
+                g: byte(2)?,
+                b: byte(4)?,
+                a: byte(6)?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.a == 0xff {
+            serializer.serialize_str(&format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b))
+        } else {
+            serializer.serialize_str(&format!(
+                "#{:02x}{:02x}{:02x}{:02x}",
+                self.r, self.g, self.b, self.a
+            ))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Color::parse(&s).ok_or_else(|| {
+            serde::de::Error::custom(format!("invalid color '{s}', expected #rrggbb"))
+        })
+    }
+}
+
+/// Colors shared by the tray icon states, focus border, and OSD.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    pub accent: Color,
+    pub background: Color,
+    pub foreground: Color,
+    /// Focus border drawn around the focused tiled window.
+    pub focus_border: Color,
+    /// Border for windows marked urgent or in a degraded state.
+    pub warning: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        ThemeSpec::preset("dark").unwrap().palette
+    }
+}
+
+/// Declarative theme loaded from the `[theme]` TOML table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSpec {
+    /// Named preset the rest of the values are layered on.
+    pub preset: String,
+    pub palette: Palette,
+    /// Font family used by the OSD and overlays.
+    pub font_family: String,
+    pub font_size: f64,
+    /// Corner radius for overlay panels and the focus border, in points.
+    pub corner_radius: f64,
+    /// Focus border thickness in points.
+    pub border_width: f64,
+}
+
+impl Default for ThemeSpec {
+    fn default() -> Self {
+        Self::preset("dark").unwrap()
+    }
+}
+
+impl ThemeSpec {
+    /// Built-in presets: `dark` and `light`.
+    pub fn preset(name: &str) -> Option<Self> {
+        let palette = match name {
+            "dark" => Palette {
+                accent: Color::rgb(0x7a, 0xa2, 0xf7),
+                background: Color {
+                    r: 0x1a,
+                    g: 0x1b,
+                    b: 0x26,
+                    a: 0xe6,
+                },
+                foreground: Color::rgb(0xc0, 0xca, 0xf5),
+                focus_border: Color::rgb(0x7a, 0xa2, 0xf7),
+                warning: Color::rgb(0xe0, 0xaf, 0x68),
+            },
+            "light" => Palette {
+                accent: Color::rgb(0x34, 0x54, 0x8a),
+                background: Color {
+                    r: 0xe1,
+                    g: 0xe2,
+                    b: 0xe7,
+                    a: 0xe6,
+                },
+                foreground: Color::rgb(0x34, 0x3b, 0x58),
+                focus_border: Color::rgb(0x34, 0x54, 0x8a),
+                warning: Color::rgb(0x8f, 0x5e, 0x15),
+            },
+            _ => return None,
+        };
+        Some(ThemeSpec {
+            preset: name.to_string(),
+            palette,
+            font_family: "SF Pro".to_string(),
+            font_size: 13.0,
+            corner_radius: 8.0,
+            border_width: 2.0,
+        })
+    }
+}
+
+/// Resolved visual parameters for an overlay surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlayStyle {
+    /// Background alpha; forced to 1.0 under reduced transparency.
+    pub background_alpha: f64,
+    /// Whether to use the high-contrast palette variant.
+    pub high_contrast: bool,
+}
+
+/// A label attached to an overlay element for VoiceOver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessibilityLabel {
+    /// Short name read first, e.g. "Workspace 3: coding".
+    pub label: String,
+    /// Optional longer hint, e.g. "Press return to switch".
+    pub hint: Option<String>,
+}
+
+/// Shared theme state consulted by every overlay.
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    spec: ThemeSpec,
+    accessibility: AccessibilitySettings,
+}
+
+impl Theme {
+    /// Build a theme from a config spec plus freshly detected system
+    /// settings.
+    pub fn new(spec: ThemeSpec) -> Self {
+        Theme {
+            spec,
+            accessibility: AccessibilitySettings::detect(),
+        }
+    }
+
+    /// Build a theme from freshly detected system settings.
+    pub fn from_system() -> Self {
+        Self::new(ThemeSpec::default())
+    }
+
+    /// Swap in a new spec, e.g. after a config hot-reload. Accessibility
+    /// state is re-detected at the same time.
+    pub fn apply_spec(&mut self, spec: ThemeSpec) {
+        self.spec = spec;
+        self.refresh();
+    }
+
+    /// Re-read system settings; call on accessibility change notifications.
+    pub fn refresh(&mut self) {
+        self.accessibility = AccessibilitySettings::detect();
+    }
+
+    pub fn spec(&self) -> &ThemeSpec {
+        &self.spec
+    }
+
+    pub fn palette(&self) -> &Palette {
+        &self.spec.palette
+    }
+
+    pub fn accessibility(&self) -> AccessibilitySettings {
+        self.accessibility
+    }
+
+    /// Style for an overlay that would normally render with `alpha`.
+    pub fn overlay_style(&self, alpha: f64) -> OverlayStyle {
+        OverlayStyle {
+            background_alpha: if self.accessibility.reduce_transparency {
+                1.0
+            } else {
+                alpha
+            },
+            high_contrast: self.accessibility.increase_contrast,
+        }
+    }
+
+    /// Whether overlays should publish VoiceOver labels at all; skipping
+    /// the accessibility tree when VoiceOver is off avoids needless AX work.
+    pub fn announce_labels(&self) -> bool {
+        self.accessibility.voiceover_running
+    }
+}
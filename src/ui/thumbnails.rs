@@ -0,0 +1,143 @@
+//! Cached window thumbnails for the tray menu.
+//!
+//! Thumbnails are captured lazily — only when the tray menu is rebuilt —
+//! and cached with a per-window refresh interval plus a per-pass capture
+//! budget, so a burst of rebuilds never turns into a capture storm.
+//! Without Screen Recording permission the service degrades to app icons.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::models::{WindowId, WindowInfo};
+
+/// A cached thumbnail is reused this long before being recaptured.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// At most this many windows are captured per menu rebuild; the rest serve
+/// stale thumbnails or icons until the next one.
+pub const CAPTURES_PER_PASS: usize = 8;
+
+/// What the tray shows for one window.
+#[derive(Debug, Clone)]
+pub enum Thumbnail {
+    /// Captured window contents (RGBA, row-major).
+    Image {
+        width: usize,
+        height: usize,
+        rgba: Vec<u8>,
+    },
+    /// Screen Recording unavailable or capture budget exhausted; show the
+    /// app's icon instead.
+    AppIcon { bundle_id: String },
+    /// The window is content-protected; show a neutral placeholder and
+    /// never its contents.
+    Protected,
+}
+
+#[derive(Debug)]
+struct CachedImage {
+    taken: Instant,
+    width: usize,
+    height: usize,
+    rgba: Vec<u8>,
+}
+
+/// Lazily captures and caches window thumbnails.
+#[derive(Debug, Default)]
+pub struct ThumbnailService {
+    cache: HashMap<WindowId, CachedImage>,
+}
+
+impl ThumbnailService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Thumbnails for the given windows, in order. Call when the tray
+    /// menu is rebuilt; stale entries are recaptured within the pass budget.
+    pub fn thumbnails(&mut self, windows: &[WindowInfo]) -> Vec<(WindowId, Thumbnail)> {
+        let granted = self.capture_available();
+        let mut captures_left = CAPTURES_PER_PASS;
+        let mut result = Vec::with_capacity(windows.len());
+        for window in windows {
+            let thumbnail = if granted {
+                self.thumbnail_for(window, &mut captures_left)
+            } else {
+                Thumbnail::AppIcon {
+                    bundle_id: window.app_bundle_id.clone(),
+                }
+            };
+            result.push((window.id, thumbnail));
+        }
+        result
+    }
+
+    /// Drop the cached image of a closed window.
+    pub fn forget(&mut self, window: WindowId) {
+        self.cache.remove(&window);
+    }
+
+    fn thumbnail_for(&mut self, window: &WindowInfo, captures_left: &mut usize) -> Thumbnail {
+        let fresh = self
+            .cache
+            .get(&window.id)
+            .map(|c| c.taken.elapsed() < REFRESH_INTERVAL)
+            .unwrap_or(false);
+        if !fresh && *captures_left > 0 {
+            *captures_left -= 1;
+            match self.capture(window.id) {
+                Ok(image) => {
+                    self.cache.insert(window.id, image);
+                }
+                // The capture layer refuses content-protected windows;
+                // drop any stale image and show the placeholder.
+                Err(crate::errors::TilleRSError::Permission(_)) => {
+                    self.cache.remove(&window.id);
+                    return Thumbnail::Protected;
+                }
+                Err(err) => {
+                    tracing::debug!(window = window.id, %err, "thumbnail capture failed");
+                }
+            }
+        }
+        match self.cache.get(&window.id) {
+            Some(cached) => Thumbnail::Image {
+                width: cached.width,
+                height: cached.height,
+                rgba: cached.rgba.clone(),
+            },
+            None => Thumbnail::AppIcon {
+                bundle_id: window.app_bundle_id.clone(),
+            },
+        }
+    }
+
+    fn capture_available(&self) -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            crate::macos::capture::screen_recording_granted()
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            false
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn capture(&self, window: WindowId) -> crate::errors::Result<CachedImage> {
+        let image = crate::macos::capture::capture_window(window)?;
+        Ok(CachedImage {
+            taken: Instant::now(),
+            width: image.width,
+            height: image.height,
+            rgba: image.rgba,
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn capture(&self, _window: WindowId) -> crate::errors::Result<CachedImage> {
+        Err(crate::errors::TilleRSError::Validation(
+            "window capture requires macOS".into(),
+        ))
+    }
+}
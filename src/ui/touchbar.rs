@@ -0,0 +1,129 @@
+//! Touch Bar workspace switcher.
+//!
+//! One button per workspace, active workspace highlighted, tap to switch.
+//! Only compiled in with the `touchbar` feature — NSTouchBar is gone from
+//! current hardware, so the linkage is opt-in — and a no-op on Macs
+//! without the strip. As with the tray, the platform-independent model
+//! lives here and stays in sync by consuming workspace events off the
+//! bus; the AppKit rendering is behind the feature gate.
+
+use crate::events::{Event, EventSubscriber, WorkspaceEvent};
+use crate::models::ActionType;
+
+/// One Touch Bar button.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TouchBarItem {
+    pub workspace: String,
+    pub active: bool,
+}
+
+/// The strip's current contents, derived from workspace state.
+#[derive(Debug, Default)]
+pub struct TouchBarModel {
+    items: Vec<TouchBarItem>,
+}
+
+impl TouchBarModel {
+    pub fn new(workspaces: impl IntoIterator<Item = String>, active: Option<&str>) -> Self {
+        TouchBarModel {
+            items: workspaces
+                .into_iter()
+                .map(|workspace| {
+                    let active = Some(workspace.as_str()) == active;
+                    TouchBarItem { workspace, active }
+                })
+                .collect(),
+        }
+    }
+
+    pub fn items(&self) -> &[TouchBarItem] {
+        &self.items
+    }
+
+    /// The action a tap on button `index` dispatches.
+    pub fn tap(&self, index: usize) -> Option<ActionType> {
+        self.items
+            .get(index)
+            .map(|item| ActionType::SwitchWorkspace {
+                workspace: item.workspace.clone(),
+            })
+    }
+
+    /// Fold a workspace event into the model; returns whether the strip
+    /// needs re-rendering.
+    pub fn apply(&mut self, event: &WorkspaceEvent) -> bool {
+        match event {
+            WorkspaceEvent::Activated { name } => {
+                let mut changed = false;
+                for item in &mut self.items {
+                    let active = &item.workspace == name;
+                    changed |= item.active != active;
+                    item.active = active;
+                }
+                changed
+            }
+            WorkspaceEvent::Created { name } => {
+                self.items.push(TouchBarItem {
+                    workspace: name.clone(),
+                    active: false,
+                });
+                true
+            }
+            WorkspaceEvent::Removed { name } => {
+                let before = self.items.len();
+                self.items.retain(|item| &item.workspace != name);
+                self.items.len() != before
+            }
+            WorkspaceEvent::ArrangeCompleted { .. } | WorkspaceEvent::GroupActivated { .. } => {
+                false
+            }
+        }
+    }
+
+    /// Drive the model from the event bus until it closes, invoking
+    /// `render` after every change.
+    pub async fn run(
+        &mut self,
+        mut events: EventSubscriber,
+        mut render: impl FnMut(&[TouchBarItem]),
+    ) {
+        while let Some(event) = events.recv().await {
+            if let Event::Workspace(workspace_event) = event {
+                if self.apply(&workspace_event) {
+                    render(self.items());
+                }
+            }
+        }
+    }
+}
+
+/// AppKit rendering: an NSTouchBar with one NSButton per workspace in a
+/// scrubber, rebuilt whenever the model changes.
+#[cfg(all(target_os = "macos", feature = "touchbar"))]
+pub mod appkit {
+    use objc2::msg_send;
+    use objc2::runtime::AnyObject;
+    use objc2_foundation::NSString;
+
+    use super::TouchBarItem;
+
+    /// Replace the app's principal touch bar with buttons for `items`.
+    /// Safe to call on every model change; NSTouchBar diffs identifiers.
+    pub fn render(items: &[TouchBarItem]) {
+        unsafe {
+            let app: *mut AnyObject = msg_send![objc2::class!(NSApplication), sharedApplication];
+            let touch_bar: *mut AnyObject = msg_send![objc2::class!(NSTouchBar), new];
+            let identifiers: Vec<_> = items
+                .iter()
+                .map(|item| NSString::from_str(&format!("tillers.workspace.{}", item.workspace)))
+                .collect();
+            let array: *mut AnyObject = msg_send![
+                objc2::class!(NSArray),
+                arrayWithObjects: identifiers.as_ptr(),
+                count: identifiers.len()
+            ];
+            let _: () = msg_send![touch_bar, setDefaultItemIdentifiers: array];
+            let _: () = msg_send![app, setTouchBar: touch_bar];
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! System tray status model.
+//!
+//! The tray rendering itself lives behind macOS AppKit bindings; this module
+//! holds the platform-independent state the icon and menu are derived from.
+
+/// High-level daemon state reflected by the tray icon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayStatus {
+    /// Tiling active, nothing unusual.
+    Active,
+    /// One or more applications have rules temporarily suspended.
+    RulesSuspended { apps: Vec<String> },
+    /// Tiling globally paused.
+    Paused,
+    /// A focus session is running; the workspace is locked.
+    FocusSession {
+        workspace: String,
+        remaining_minutes: u64,
+    },
+}
+
+impl TrayStatus {
+    /// Short label shown in the tray menu next to the status icon.
+    pub fn label(&self) -> String {
+        match self {
+            TrayStatus::Active => crate::i18n::t("tray-status-active").to_string(),
+            TrayStatus::RulesSuspended { apps } => {
+                crate::i18n::t_args("tray-status-rules-suspended", &[("apps", &apps.join(", "))])
+            }
+            TrayStatus::Paused => crate::i18n::t("tray-status-paused").to_string(),
+            TrayStatus::FocusSession {
+                workspace,
+                remaining_minutes,
+            } => crate::i18n::t_args(
+                "tray-status-focus-session",
+                &[
+                    ("workspace", workspace),
+                    ("minutes", &remaining_minutes.to_string()),
+                ],
+            ),
+        }
+    }
+}
+
+/// Menu line naming the active workspace group, shown above the status
+/// when group mode is on.
+pub fn group_label(group: &str) -> String {
+    crate::i18n::t_args("tray-group", &[("group", group)])
+}
+
+/// Countdown line for a temporary workspace, one per running time box.
+pub fn temporary_label(workspace: &str, remaining: std::time::Duration) -> String {
+    let minutes = remaining.as_secs().div_ceil(60);
+    crate::i18n::t_args(
+        "tray-temporary",
+        &[("workspace", workspace), ("minutes", &minutes.to_string())],
+    )
+}
@@ -0,0 +1,35 @@
+//! The `WindowSystem` abstraction.
+//!
+//! All window/display access goes through this trait so the orchestration
+//! and tiling logic can run against the real macOS backend or the headless
+//! simulation, which replays captured scenes deterministically in tests
+//! and CI.
+
+pub mod scene;
+pub mod simulation;
+
+pub use scene::Scene;
+pub use simulation::SimulatedWindowSystem;
+
+use crate::errors::Result;
+use crate::models::display::DisplayInfo;
+use crate::models::{Rect, WindowId, WindowInfo};
+
+/// Read and mutate the window/display state of a machine (real or
+/// simulated).
+pub trait WindowSystem {
+    /// All attached displays.
+    fn displays(&self) -> Result<Vec<DisplayInfo>>;
+
+    /// All known windows, managed or not.
+    fn windows(&self) -> Result<Vec<WindowInfo>>;
+
+    /// Move/resize a window.
+    fn set_window_frame(&mut self, window: WindowId, frame: Rect) -> Result<()>;
+
+    /// Minimize/hide a window.
+    fn hide_window(&mut self, window: WindowId) -> Result<()>;
+
+    /// Give a window keyboard focus.
+    fn focus_window(&mut self, window: WindowId) -> Result<()>;
+}
@@ -0,0 +1,66 @@
+//! Scene files: a serialized snapshot of displays, apps, and windows.
+//!
+//! `tillers diagnostics capture-scene` writes one from a live machine;
+//! the simulation backend loads it, so a bug seen on a user's setup can be
+//! replayed deterministically.
+
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+use crate::models::display::DisplayInfo;
+use crate::models::WindowInfo;
+
+/// Version of the scene file format, bumped on breaking changes.
+pub const SCENE_FORMAT_VERSION: u32 = 1;
+
+/// An application present in a captured scene.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneApp {
+    pub bundle_id: String,
+    pub name: String,
+    pub pid: i32,
+}
+
+/// A full snapshot of the window environment at one instant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scene {
+    pub format_version: u32,
+    pub captured_at: SystemTime,
+    pub displays: Vec<DisplayInfo>,
+    pub apps: Vec<SceneApp>,
+    pub windows: Vec<WindowInfo>,
+}
+
+impl Scene {
+    pub fn new(displays: Vec<DisplayInfo>, apps: Vec<SceneApp>, windows: Vec<WindowInfo>) -> Self {
+        Scene {
+            format_version: SCENE_FORMAT_VERSION,
+            captured_at: SystemTime::now(),
+            displays,
+            apps,
+            windows,
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let raw = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, raw)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)?;
+        let scene: Scene = serde_json::from_str(&raw)?;
+        if scene.format_version > SCENE_FORMAT_VERSION {
+            tracing::warn!(
+                file_version = scene.format_version,
+                supported = SCENE_FORMAT_VERSION,
+                "scene file is newer than this build; fields may be ignored"
+            );
+        }
+        Ok(scene)
+    }
+}
@@ -0,0 +1,155 @@
+//! Headless simulation backend.
+//!
+//! Implements [`WindowSystem`] over plain data, usually loaded from a
+//! captured [`Scene`]. All mutations are recorded so tests can assert on
+//! exactly which operations an arrange pass issued.
+
+use std::path::Path;
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::display::DisplayInfo;
+use crate::models::{Rect, WindowId, WindowInfo};
+
+use super::scene::Scene;
+use super::WindowSystem;
+
+/// One recorded mutation, in issue order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimOperation {
+    SetFrame { window: WindowId, frame: Rect },
+    Hide { window: WindowId },
+    Focus { window: WindowId },
+}
+
+/// In-memory window system for tests and `--headless` verification.
+#[derive(Debug, Default)]
+pub struct SimulatedWindowSystem {
+    displays: Vec<DisplayInfo>,
+    windows: Vec<WindowInfo>,
+    /// Every mutation issued against the backend, in order.
+    pub operations: Vec<SimOperation>,
+}
+
+impl SimulatedWindowSystem {
+    /// Build from a scene captured on a real machine.
+    pub fn from_scene(scene: Scene) -> Self {
+        SimulatedWindowSystem {
+            displays: scene.displays,
+            windows: scene.windows,
+            operations: Vec::new(),
+        }
+    }
+
+    /// Load a scene file and build the backend from it.
+    pub fn from_scene_file(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::from_scene(Scene::load(path)?))
+    }
+
+    /// Add a window mid-run, as a replayed creation event would.
+    pub fn add_window(&mut self, window: WindowInfo) {
+        self.windows.push(window);
+    }
+
+    /// Remove a window mid-run; unknown ids are ignored, matching how a
+    /// destroy event for an unmanaged window is a no-op.
+    pub fn remove_window(&mut self, window: WindowId) {
+        self.windows.retain(|w| w.id != window);
+    }
+
+    fn window_mut(&mut self, window: WindowId) -> Result<&mut WindowInfo> {
+        self.windows
+            .iter_mut()
+            .find(|w| w.id == window)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "window",
+                name: window.to_string(),
+            })
+    }
+}
+
+impl WindowSystem for SimulatedWindowSystem {
+    fn displays(&self) -> Result<Vec<DisplayInfo>> {
+        Ok(self.displays.clone())
+    }
+
+    fn windows(&self) -> Result<Vec<WindowInfo>> {
+        Ok(self.windows.clone())
+    }
+
+    fn set_window_frame(&mut self, window: WindowId, frame: Rect) -> Result<()> {
+        self.window_mut(window)?.frame = frame;
+        self.operations
+            .push(SimOperation::SetFrame { window, frame });
+        Ok(())
+    }
+
+    fn hide_window(&mut self, window: WindowId) -> Result<()> {
+        self.window_mut(window)?.minimized = true;
+        self.operations.push(SimOperation::Hide { window });
+        Ok(())
+    }
+
+    fn focus_window(&mut self, window: WindowId) -> Result<()> {
+        self.window_mut(window)?.last_focused_at = std::time::SystemTime::now();
+        self.operations.push(SimOperation::Focus { window });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(id: WindowId) -> WindowInfo {
+        WindowInfo {
+            id,
+            title: format!("window {id}"),
+            app_bundle_id: "com.example.app".into(),
+            workspace: "main".into(),
+            frame: Rect {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            floating: false,
+            minimized: false,
+            locked: false,
+            last_focused_at: std::time::SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn mutations_update_state_and_are_recorded_in_order() {
+        let mut sim = SimulatedWindowSystem::default();
+        sim.add_window(window(1));
+        sim.add_window(window(2));
+
+        let frame = Rect {
+            x: 10.0,
+            y: 10.0,
+            width: 50.0,
+            height: 50.0,
+        };
+        sim.set_window_frame(1, frame).unwrap();
+        sim.hide_window(2).unwrap();
+
+        let windows = sim.windows().unwrap();
+        assert_eq!(windows[0].frame, frame);
+        assert!(windows[1].minimized);
+        assert_eq!(
+            sim.operations,
+            vec![
+                SimOperation::SetFrame { window: 1, frame },
+                SimOperation::Hide { window: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn operations_on_unknown_windows_fail_without_recording() {
+        let mut sim = SimulatedWindowSystem::default();
+        assert!(sim.hide_window(7).is_err());
+        assert!(sim.operations.is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Adoption of windows that existed before the daemon started.
+//!
+//! Managing only windows created after startup leaves the session half
+//! tiled after every restart. Instead the daemon scans every existing
+//! window once, routes each through the same rule evaluation new windows
+//! get, and logs a summary of where everything went.
+
+use std::collections::BTreeMap;
+
+use crate::models::{WindowId, WindowInfo, WindowRule};
+
+use super::catch_all::{self, CatchAllConfig, Placement};
+
+/// One adopted window and where it was routed.
+#[derive(Debug, Clone)]
+pub struct Adoption {
+    pub window: WindowId,
+    pub workspace: String,
+    /// The rule that matched, or `None` for catch-all/active placement.
+    pub rule: Option<String>,
+}
+
+/// Outcome of the startup scan.
+#[derive(Debug, Default)]
+pub struct AdoptionSummary {
+    pub adoptions: Vec<Adoption>,
+    /// Windows left on the active workspace (no rule, no catch-all).
+    pub unplaced: usize,
+}
+
+impl AdoptionSummary {
+    /// Adoption counts per workspace, for the summary log/notification.
+    pub fn per_workspace(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for adoption in &self.adoptions {
+            *counts.entry(adoption.workspace.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+/// Route every existing window through rule evaluation, as if it had just
+/// been created. `active_workspace` receives the leftovers when no
+/// catch-all is configured.
+pub fn adopt_existing(
+    windows: &[WindowInfo],
+    rules: &[WindowRule],
+    catch_all: &CatchAllConfig,
+    active_workspace: &str,
+) -> AdoptionSummary {
+    let mut summary = AdoptionSummary::default();
+    for window in windows {
+        match catch_all::place(window, rules, catch_all) {
+            Placement::Rule { rule, workspace } => summary.adoptions.push(Adoption {
+                window: window.id,
+                workspace: workspace.unwrap_or_else(|| active_workspace.to_string()),
+                rule: Some(rule),
+            }),
+            Placement::CatchAll { workspace } => summary.adoptions.push(Adoption {
+                window: window.id,
+                workspace,
+                rule: None,
+            }),
+            Placement::Active => {
+                summary.adoptions.push(Adoption {
+                    window: window.id,
+                    workspace: active_workspace.to_string(),
+                    rule: None,
+                });
+                summary.unplaced += 1;
+            }
+        }
+    }
+
+    let per_workspace: Vec<String> = summary
+        .per_workspace()
+        .into_iter()
+        .map(|(workspace, count)| format!("{workspace}: {count}"))
+        .collect();
+    tracing::info!(
+        adopted = summary.adoptions.len(),
+        unmatched = summary.unplaced,
+        placement = %per_workspace.join(", "),
+        "adopted pre-existing windows"
+    );
+    summary
+}
@@ -0,0 +1,129 @@
+//! Auto-archival of stale windows.
+//!
+//! Long-running workspaces accumulate windows nobody has touched in weeks.
+//! The archival policy periodically sweeps them into a dedicated Archive
+//! workspace (or minimizes them), keeps a report of what moved, and can
+//! undo the last sweep in one command.
+
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::window::{WindowId, WindowInfo};
+
+/// What happens to a stale window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveTarget {
+    /// Move into the named workspace (created on demand).
+    Workspace(String),
+    /// Minimize in place.
+    Minimize,
+}
+
+/// Configurable policy; lives in the `[archival]` config table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchivalPolicy {
+    pub enabled: bool,
+    /// Windows unfocused for this many days are considered stale.
+    pub max_idle_days: u64,
+    pub target: ArchiveTarget,
+}
+
+impl Default for ArchivalPolicy {
+    fn default() -> Self {
+        ArchivalPolicy {
+            enabled: false,
+            max_idle_days: 14,
+            target: ArchiveTarget::Workspace("Archive".to_string()),
+        }
+    }
+}
+
+/// One archived window, recorded so the sweep can be undone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedWindow {
+    pub window_id: WindowId,
+    pub title: String,
+    pub app_bundle_id: String,
+    /// Workspace the window came from; undo sends it back here.
+    pub original_workspace: String,
+}
+
+/// Report of one archival sweep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivalReport {
+    pub swept_at: SystemTime,
+    pub target: ArchiveTarget,
+    pub moved: Vec<ArchivedWindow>,
+}
+
+/// Runs the policy and remembers the last sweep for undo.
+#[derive(Debug, Default)]
+pub struct Archiver {
+    policy: ArchivalPolicy,
+    last_report: Option<ArchivalReport>,
+}
+
+impl Archiver {
+    pub fn new(policy: ArchivalPolicy) -> Self {
+        Archiver {
+            policy,
+            last_report: None,
+        }
+    }
+
+    /// Select the windows a sweep would move, without side effects.
+    ///
+    /// Floating and minimized windows are left alone; so are windows
+    /// already in the archive workspace.
+    pub fn select_stale<'a>(
+        &self,
+        windows: &'a [WindowInfo],
+        now: SystemTime,
+    ) -> Vec<&'a WindowInfo> {
+        if !self.policy.enabled {
+            return Vec::new();
+        }
+        let max_idle = Duration::from_secs(self.policy.max_idle_days * 24 * 60 * 60);
+        windows
+            .iter()
+            .filter(|w| !w.floating && !w.minimized)
+            .filter(|w| match &self.policy.target {
+                ArchiveTarget::Workspace(name) => w.workspace != *name,
+                ArchiveTarget::Minimize => true,
+            })
+            .filter(|w| {
+                now.duration_since(w.last_focused_at)
+                    .map(|idle| idle >= max_idle)
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Record a completed sweep so it can be undone. The caller performs
+    /// the actual moves; this keeps the archiver free of AX dependencies.
+    pub fn record_sweep(&mut self, moved: Vec<ArchivedWindow>, now: SystemTime) -> &ArchivalReport {
+        self.last_report = Some(ArchivalReport {
+            swept_at: now,
+            target: self.policy.target.clone(),
+            moved,
+        });
+        self.last_report.as_ref().unwrap()
+    }
+
+    /// Take the last report for undo; windows in it should be moved back to
+    /// their `original_workspace`.
+    pub fn take_undo(&mut self) -> Option<ArchivalReport> {
+        self.last_report.take()
+    }
+
+    pub fn last_report(&self) -> Option<&ArchivalReport> {
+        self.last_report.as_ref()
+    }
+
+    pub fn policy(&self) -> &ArchivalPolicy {
+        &self.policy
+    }
+}
@@ -0,0 +1,78 @@
+//! Catch-all workspace for windows matching no rule.
+//!
+//! With a strict per-app workspace setup, an unmatched window landing on
+//! whatever workspace happens to be active is the worst outcome — it
+//! disrupts the layout the user is looking at. When enabled, unmatched
+//! windows go to a designated catch-all workspace instead, with a
+//! notification carrying a quick action to re-route them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{ActionType, WindowInfo, WindowRule};
+
+/// Configuration for the catch-all workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatchAllConfig {
+    /// Route unmatched windows to [`workspace`](Self::workspace) instead
+    /// of the active workspace.
+    pub enabled: bool,
+    /// Name of the catch-all workspace; created on demand.
+    pub workspace: String,
+    /// Post a notification when a window is routed there.
+    pub notify: bool,
+}
+
+impl Default for CatchAllConfig {
+    fn default() -> Self {
+        CatchAllConfig {
+            enabled: false,
+            workspace: "overflow".to_string(),
+            notify: true,
+        }
+    }
+}
+
+/// Where a new window was routed and why.
+#[derive(Debug, Clone)]
+pub enum Placement {
+    /// A rule matched; use its workspace (or the active one if the rule
+    /// names none).
+    Rule {
+        rule: String,
+        workspace: Option<String>,
+    },
+    /// No rule matched; routed to the catch-all workspace.
+    CatchAll { workspace: String },
+    /// No rule matched and no catch-all configured; active workspace.
+    Active,
+}
+
+/// Decide the workspace for a newly created window.
+pub fn place(window: &WindowInfo, rules: &[WindowRule], catch_all: &CatchAllConfig) -> Placement {
+    if let Some(rule) = rules
+        .iter()
+        .filter(|r| r.enabled)
+        .find(|r| r.matches(window))
+    {
+        return Placement::Rule {
+            rule: rule.name.clone(),
+            workspace: rule.workspace.clone(),
+        };
+    }
+    if catch_all.enabled {
+        return Placement::CatchAll {
+            workspace: catch_all.workspace.clone(),
+        };
+    }
+    Placement::Active
+}
+
+/// Quick action offered on the catch-all notification: move the window to
+/// the workspace the user picks.
+pub fn reroute_action(window: &WindowInfo, workspace: &str) -> ActionType {
+    ActionType::MoveWindowToWorkspace {
+        window_id: window.id,
+        workspace: workspace.to_string(),
+    }
+}
@@ -0,0 +1,122 @@
+//! Clamshell (lid-closed) transitions.
+//!
+//! Closing a MacBook's lid while external displays stay attached removes
+//! the built-in display from the topology; reopening brings it back. Both
+//! look like ordinary display events, but treating them as such strands
+//! every workspace pinned to the internal display on the fallback monitor
+//! and forgets the pinning. The tracker recognizes the transition,
+//! migrates those workspaces to an external display, and remembers their
+//! original pinnings so they return when the lid reopens.
+
+use crate::models::display::{DisplayId, DisplayInfo};
+use crate::models::Workspace;
+
+/// A recognized lid transition, derived from display attach/detach events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClamshellTransition {
+    /// The built-in display disappeared while externals remain.
+    Closed,
+    /// The built-in display came back.
+    Opened,
+}
+
+/// Watches the display list for the built-in display and plans workspace
+/// migration around lid transitions.
+#[derive(Debug, Default)]
+pub struct ClamshellTracker {
+    /// Name of the built-in display, remembered from when it was last
+    /// attached so pinnings can be matched while it is gone.
+    internal: Option<String>,
+    closed: bool,
+    /// Workspaces parked on externals, as (workspace, original pinning,
+    /// external parked on); restored in order on reopen.
+    parked: Vec<(String, String, String)>,
+}
+
+impl ClamshellTracker {
+    /// Feed the current display list after any display event. `is_builtin`
+    /// reports whether a display is the laptop panel
+    /// (`macos::windows::is_builtin` on macOS).
+    ///
+    /// Every display disappearing at once is sleep or a GPU reset, not a
+    /// lid close, and reports no transition.
+    pub fn observe(
+        &mut self,
+        displays: &[DisplayInfo],
+        is_builtin: impl Fn(DisplayId) -> bool,
+    ) -> Option<ClamshellTransition> {
+        let builtin = displays.iter().find(|d| is_builtin(d.id));
+        match builtin {
+            Some(display) => {
+                self.internal = Some(display.name.clone());
+                if self.closed {
+                    self.closed = false;
+                    return Some(ClamshellTransition::Opened);
+                }
+                None
+            }
+            None => {
+                let known_internal = self.internal.is_some();
+                if !self.closed && known_internal && !displays.is_empty() {
+                    self.closed = true;
+                    return Some(ClamshellTransition::Closed);
+                }
+                None
+            }
+        }
+    }
+
+    /// Whether the lid is currently closed; feeds the `clamshell` action
+    /// condition.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Plan the lid-close migration: every workspace pinned to the
+    /// internal display is re-pinned to an external, round-robin so a
+    /// multi-monitor desk spreads them instead of piling onto one screen.
+    /// Original pinnings are parked for [`reopen_plan`](Self::reopen_plan).
+    pub fn close_plan(
+        &mut self,
+        workspaces: &[Workspace],
+        externals: &[DisplayInfo],
+    ) -> Vec<(String, String)> {
+        let Some(internal) = self.internal.clone() else {
+            return Vec::new();
+        };
+        if externals.is_empty() {
+            return Vec::new();
+        }
+        let mut plan = Vec::new();
+        for (i, workspace) in workspaces
+            .iter()
+            .filter(|w| w.display.as_deref() == Some(internal.as_str()))
+            .enumerate()
+        {
+            let target = &externals[i % externals.len()];
+            self.parked.push((
+                workspace.name.clone(),
+                internal.clone(),
+                target.name.clone(),
+            ));
+            plan.push((workspace.name.clone(), target.name.clone()));
+        }
+        plan
+    }
+
+    /// Plan the reopen: return every parked workspace to its original
+    /// pinning. `current` reports a workspace's present pinning; one the
+    /// user re-pinned while the lid was closed (its pin no longer matches
+    /// the external it was parked on) keeps the user's choice and is
+    /// dropped from the plan.
+    pub fn reopen_plan(
+        &mut self,
+        current: impl Fn(&str) -> Option<String>,
+    ) -> Vec<(String, String)> {
+        std::mem::take(&mut self.parked)
+            .into_iter()
+            .filter(|(name, _, parked_on)| current(name).as_deref() == Some(parked_on))
+            .map(|(name, original, _)| (name, original))
+            .collect()
+    }
+}
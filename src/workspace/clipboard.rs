@@ -0,0 +1,62 @@
+//! Cut/paste of window sets between workspaces.
+//!
+//! Reorganizing a project one window at a time is tedious. `CutWindows`
+//! detaches a selection from its workspace — the windows hide and leave
+//! the layout — and `PasteWindows` inserts them into the current
+//! workspace at the insertion point. The clipboard survives workspace
+//! switches but not daemon restarts; windows still on it at shutdown are
+//! restored to their origin.
+
+use crate::models::WindowId;
+
+/// One cut window and where it came from, for cancel/restore.
+#[derive(Debug, Clone)]
+pub struct CutWindow {
+    pub window: WindowId,
+    pub origin_workspace: String,
+}
+
+/// The daemon's window clipboard.
+#[derive(Debug, Default)]
+pub struct WindowClipboard {
+    cut: Vec<CutWindow>,
+}
+
+impl WindowClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cut a selection: replaces any previous clipboard contents (the
+    /// previous cut is restored by the caller first).
+    pub fn cut(&mut self, windows: impl IntoIterator<Item = (WindowId, String)>) {
+        self.cut = windows
+            .into_iter()
+            .map(|(window, origin_workspace)| CutWindow {
+                window,
+                origin_workspace,
+            })
+            .collect();
+    }
+
+    /// Take the clipboard contents for pasting, in cut order. The caller
+    /// inserts them into the active workspace's layout at the insertion
+    /// point (after the focused window).
+    pub fn paste(&mut self) -> Vec<CutWindow> {
+        std::mem::take(&mut self.cut)
+    }
+
+    /// Windows currently cut, without consuming them (tray display).
+    pub fn pending(&self) -> &[CutWindow] {
+        &self.cut
+    }
+
+    /// Drop a window that closed while cut.
+    pub fn forget(&mut self, window: WindowId) {
+        self.cut.retain(|c| c.window != window);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cut.is_empty()
+    }
+}
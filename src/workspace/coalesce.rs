@@ -0,0 +1,99 @@
+//! Coalescing of window-destroy events.
+//!
+//! A quitting app tears down all of its windows within milliseconds; ten
+//! sequential destroy events would otherwise mean ten arrange passes, each
+//! reflowing the survivors. Destroys are instead held per app for a short
+//! window, removed from the model in one batch, and answered with a single
+//! arrange.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::models::WindowId;
+
+/// How long a destroy waits for siblings from the same app before the
+/// batch flushes. Long enough to catch an app quit, short enough to be
+/// invisible on a single close.
+pub const COALESCE_WINDOW: Duration = Duration::from_millis(80);
+
+/// A flushed batch: every window the app lost during the window.
+#[derive(Debug, Clone)]
+pub struct DestroyBatch {
+    pub bundle_id: String,
+    pub windows: Vec<WindowId>,
+}
+
+#[derive(Debug)]
+struct Pending {
+    windows: Vec<WindowId>,
+    first_seen: Instant,
+}
+
+/// Buffers destroy events per app and releases them in batches.
+#[derive(Debug, Default)]
+pub struct DestroyCoalescer {
+    pending: HashMap<String, Pending>,
+}
+
+impl DestroyCoalescer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Buffer a destroy event. Returns the delay after which the caller
+    /// should call [`flush_due`](Self::flush_due) — only for the first
+    /// event of a batch, so one timer serves the whole burst.
+    pub fn note_destroyed(&mut self, bundle_id: &str, window: WindowId) -> Option<Duration> {
+        match self.pending.get_mut(bundle_id) {
+            Some(pending) => {
+                pending.windows.push(window);
+                None
+            }
+            None => {
+                self.pending.insert(
+                    bundle_id.to_string(),
+                    Pending {
+                        windows: vec![window],
+                        first_seen: Instant::now(),
+                    },
+                );
+                Some(COALESCE_WINDOW)
+            }
+        }
+    }
+
+    /// Batches whose coalescing window has elapsed. The caller removes
+    /// every window in a batch from the model, then runs one arrange.
+    pub fn flush_due(&mut self) -> Vec<DestroyBatch> {
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| p.first_seen.elapsed() >= COALESCE_WINDOW)
+            .map(|(bundle, _)| bundle.clone())
+            .collect();
+        due.into_iter()
+            .map(|bundle_id| {
+                let pending = self.pending.remove(&bundle_id).expect("batch exists");
+                DestroyBatch {
+                    bundle_id,
+                    windows: pending.windows,
+                }
+            })
+            .collect()
+    }
+
+    /// Flush everything immediately (daemon shutdown, workspace switch).
+    pub fn flush_all(&mut self) -> Vec<DestroyBatch> {
+        self.pending
+            .drain()
+            .map(|(bundle_id, pending)| DestroyBatch {
+                bundle_id,
+                windows: pending.windows,
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
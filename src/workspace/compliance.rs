@@ -0,0 +1,66 @@
+//! Reclassification of windows that ignore AX resize.
+//!
+//! Java, Electron, and some older games accept the AX calls but never
+//! apply the frame. After enough consecutive failed, verified set-frame
+//! attempts the window is treated as floating and the finding is written
+//! back into the app's [`ApplicationProfile`].
+
+use std::collections::HashMap;
+
+use crate::models::app_profile::ProfileStore;
+use crate::models::WindowId;
+
+/// Consecutive failed arrange passes before a window floats.
+pub const NONCOMPLIANCE_THRESHOLD: u32 = 3;
+
+/// Tracks per-window verification failures across arrange passes.
+#[derive(Debug, Default)]
+pub struct ResizeComplianceTracker {
+    strikes: HashMap<WindowId, u32>,
+}
+
+impl ResizeComplianceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a verified, successful set-frame; the window is compliant.
+    pub fn record_success(&mut self, window: WindowId) {
+        self.strikes.remove(&window);
+    }
+
+    /// Record a set-frame that never verified. Returns true when the
+    /// window just crossed the threshold and should be reclassified as
+    /// floating; the compatibility note is written to the app's profile.
+    pub fn record_failure(
+        &mut self,
+        window: WindowId,
+        bundle_id: &str,
+        profiles: &mut ProfileStore,
+    ) -> bool {
+        let strikes = self.strikes.entry(window).or_insert(0);
+        *strikes += 1;
+        if *strikes != NONCOMPLIANCE_THRESHOLD {
+            return false;
+        }
+        tracing::info!(
+            window,
+            bundle_id,
+            strikes = NONCOMPLIANCE_THRESHOLD,
+            "window ignores AX resize; reclassifying as floating"
+        );
+        let profile = profiles.entry(bundle_id);
+        profile.ax_resize_noncompliant = true;
+        profile
+            .add_note("windows ignore AX setFrame after verification retries; managed as floating");
+        if let Err(err) = profiles.save() {
+            tracing::warn!(%err, "failed to persist application profile");
+        }
+        true
+    }
+
+    /// Forget a closed window.
+    pub fn forget(&mut self, window: WindowId) {
+        self.strikes.remove(&window);
+    }
+}
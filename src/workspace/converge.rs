@@ -0,0 +1,159 @@
+//! Declarative state convergence for `tillers apply`.
+//!
+//! A state file declares the workspaces and rules that should exist; the
+//! planner diffs it against what does exist and produces the minimal step
+//! list that converges the two. Applying the same file twice yields an
+//! empty plan, so state files can live in git and be re-applied blindly.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigParseError;
+use crate::errors::Result;
+use crate::models::{WindowRule, Workspace};
+use crate::tiling::LayoutPattern;
+
+/// One workspace as the state file declares it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesiredWorkspace {
+    pub name: String,
+    pub layout: LayoutPattern,
+    /// Display the workspace is pinned to, when any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+    pub quiet: bool,
+}
+
+impl Default for DesiredWorkspace {
+    fn default() -> Self {
+        DesiredWorkspace {
+            name: String::new(),
+            layout: LayoutPattern::Tall,
+            display: None,
+            quiet: false,
+        }
+    }
+}
+
+/// The full desired state: everything listed should exist, anything
+/// TilleRS manages that is not listed should be removed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesiredState {
+    pub workspaces: Vec<DesiredWorkspace>,
+    pub rules: Vec<WindowRule>,
+}
+
+impl DesiredState {
+    /// Parse a state file, reporting file/line/column on failure like the
+    /// config loader does.
+    pub fn load(path: &std::path::Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)?;
+        let document =
+            toml_edit::ImDocument::parse(raw.clone()).map_err(|e: toml_edit::TomlError| {
+                ConfigParseError::from_toml(path.to_path_buf(), &raw, &e)
+            })?;
+        toml_edit::de::from_document(document).map_err(|e: toml_edit::de::Error| {
+            ConfigParseError::from_toml(path.to_path_buf(), &raw, &e.clone().into()).into()
+        })
+    }
+}
+
+/// One converging step. `Remove*` steps are destructive and gated behind
+/// confirmation by the CLI; everything else is safe to apply blindly.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanStep {
+    /// The workspace does not exist yet.
+    CreateWorkspace(DesiredWorkspace),
+    /// The workspace exists but differs (layout, display, or quiet mode).
+    UpdateWorkspace(DesiredWorkspace),
+    /// The workspace exists and is not in the desired state.
+    RemoveWorkspace { name: String },
+    /// No rule with this name exists yet.
+    AddRule(WindowRule),
+    /// A rule with this name exists but differs in any field.
+    UpdateRule(WindowRule),
+    /// The rule exists and is not in the desired state.
+    RemoveRule { name: String },
+}
+
+impl PlanStep {
+    /// Whether applying this step deletes something that exists.
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            PlanStep::RemoveWorkspace { .. } | PlanStep::RemoveRule { .. }
+        )
+    }
+}
+
+/// The computed difference between current and desired state, in apply
+/// order: creations and updates first, removals last.
+#[derive(Debug, Default)]
+pub struct Plan {
+    pub steps: Vec<PlanStep>,
+}
+
+impl Plan {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn destructive_steps(&self) -> impl Iterator<Item = &PlanStep> {
+        self.steps.iter().filter(|s| s.is_destructive())
+    }
+}
+
+/// Diff desired against current state.
+///
+/// Workspaces and rules are matched by name. A current workspace that
+/// matches its desired entry field-for-field produces no step, which is
+/// what makes apply idempotent.
+pub fn plan(
+    current_workspaces: &[Workspace],
+    current_rules: &[WindowRule],
+    desired: &DesiredState,
+) -> Plan {
+    let mut steps = Vec::new();
+
+    for want in &desired.workspaces {
+        match current_workspaces.iter().find(|w| w.name == want.name) {
+            None => steps.push(PlanStep::CreateWorkspace(want.clone())),
+            Some(have)
+                if have.layout != want.layout
+                    || have.display != want.display
+                    || have.quiet != want.quiet =>
+            {
+                steps.push(PlanStep::UpdateWorkspace(want.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+
+    for want in &desired.rules {
+        match current_rules.iter().find(|r| r.name == want.name) {
+            None => steps.push(PlanStep::AddRule(want.clone())),
+            Some(have) if have != want => steps.push(PlanStep::UpdateRule(want.clone())),
+            Some(_) => {}
+        }
+    }
+
+    // Removals last, so a rename (delete old + create new) never leaves a
+    // moment with neither workspace existing.
+    for have in current_workspaces {
+        if !desired.workspaces.iter().any(|w| w.name == have.name) {
+            steps.push(PlanStep::RemoveWorkspace {
+                name: have.name.clone(),
+            });
+        }
+    }
+    for have in current_rules {
+        if !desired.rules.iter().any(|r| r.name == have.name) {
+            steps.push(PlanStep::RemoveRule {
+                name: have.name.clone(),
+            });
+        }
+    }
+
+    Plan { steps }
+}
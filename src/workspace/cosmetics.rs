@@ -0,0 +1,114 @@
+//! Per-window cosmetic state from rules: opacity and corner masks.
+//!
+//! Cosmetics are the only rule effects that must be *undone* when a rule
+//! stops matching (a window moved out of the workspace, the rule was
+//! disabled, the user toggled cosmetics off), so this registry remembers
+//! what it applied. Opacity goes through [`Effects`] and degrades to
+//! opaque when the private API is missing; corner radii are collected
+//! into a mask list the overlay layer renders on top of the windows.
+
+use std::collections::HashMap;
+
+use crate::daemon::Effects;
+use crate::errors::Result;
+use crate::models::{Rect, WindowId, WindowInfo, WindowRule};
+
+/// A rounded-corner mask for the overlay renderer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CornerMask {
+    pub window: WindowId,
+    pub frame: Rect,
+    pub radius: f64,
+}
+
+/// Applied cosmetic state, keyed by window.
+#[derive(Debug, Default)]
+pub struct CosmeticsRegistry {
+    /// Runtime kill switch (the `ToggleCosmetics` action).
+    enabled: bool,
+    /// Alphas currently applied, so toggling off can restore 1.0.
+    alphas: HashMap<WindowId, f64>,
+    masks: HashMap<WindowId, CornerMask>,
+}
+
+impl CosmeticsRegistry {
+    pub fn new() -> Self {
+        CosmeticsRegistry {
+            enabled: true,
+            ..CosmeticsRegistry::default()
+        }
+    }
+
+    /// Apply (or re-apply after a move) the cosmetics of the first
+    /// matching enabled rule; clears them when no rule wants any.
+    pub fn sync(
+        &mut self,
+        window: &WindowInfo,
+        rules: &[WindowRule],
+        effects: &Effects,
+    ) -> Result<()> {
+        let rule = rules
+            .iter()
+            .find(|r| r.enabled && r.matches(window))
+            .filter(|_| self.enabled);
+
+        match rule.and_then(|r| r.opacity) {
+            Some(alpha) => {
+                if self.alphas.get(&window.id) != Some(&alpha)
+                    && effects.set_window_alpha(window.id, alpha)?
+                {
+                    self.alphas.insert(window.id, alpha);
+                }
+            }
+            None => self.clear_alpha(window.id, effects)?,
+        }
+
+        match rule.and_then(|r| r.corner_radius) {
+            Some(radius) => {
+                self.masks.insert(
+                    window.id,
+                    CornerMask {
+                        window: window.id,
+                        frame: window.frame,
+                        radius,
+                    },
+                );
+            }
+            None => {
+                self.masks.remove(&window.id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Runtime toggle; disabling restores every window to opaque and
+    /// drops all masks.
+    pub fn toggle(&mut self, effects: &Effects) -> Result<bool> {
+        self.enabled = !self.enabled;
+        if !self.enabled {
+            for (window, _) in std::mem::take(&mut self.alphas) {
+                let _ = effects.set_window_alpha(window, 1.0);
+            }
+            self.masks.clear();
+        }
+        Ok(self.enabled)
+    }
+
+    /// Masks the overlay layer should currently draw.
+    pub fn masks(&self) -> impl Iterator<Item = &CornerMask> {
+        self.masks.values()
+    }
+
+    /// Forget a closed window, restoring nothing (it is gone).
+    pub fn forget(&mut self, window: WindowId) {
+        self.alphas.remove(&window);
+        self.masks.remove(&window);
+    }
+
+    fn clear_alpha(&mut self, window: WindowId, effects: &Effects) -> Result<()> {
+        if self.alphas.remove(&window).is_some() {
+            effects.set_window_alpha(window, 1.0)?;
+        }
+        Ok(())
+    }
+}
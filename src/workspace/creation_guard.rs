@@ -0,0 +1,82 @@
+//! Guard against tiling transient windows.
+//!
+//! IDE splash screens, autocomplete popups, and floating tool palettes are
+//! created and destroyed in bursts; tiling them (or even counting them in
+//! the layout) makes startup churn through several arrange passes. New
+//! windows are held back until they look like real windows: big enough,
+//! and alive past a short delay.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{WindowId, WindowInfo};
+
+/// Thresholds for admitting a new window into tiling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreationGuardConfig {
+    /// Windows narrower than this (points) are never tiled.
+    pub min_width: f64,
+    /// Windows shorter than this (points) are never tiled.
+    pub min_height: f64,
+    /// A new window must survive this many milliseconds before the layout
+    /// engine counts it; splash screens rarely do.
+    pub lifetime_delay_ms: u64,
+}
+
+impl Default for CreationGuardConfig {
+    fn default() -> Self {
+        CreationGuardConfig {
+            min_width: 200.0,
+            min_height: 150.0,
+            lifetime_delay_ms: 300,
+        }
+    }
+}
+
+/// Decides when a newly created window becomes eligible for tiling.
+#[derive(Debug, Default)]
+pub struct CreationGuard {
+    config: CreationGuardConfig,
+    first_seen: HashMap<WindowId, Instant>,
+}
+
+impl CreationGuard {
+    pub fn new(config: CreationGuardConfig) -> Self {
+        CreationGuard {
+            config,
+            first_seen: HashMap::new(),
+        }
+    }
+
+    /// Record a window creation. Returns the delay after which the caller
+    /// should re-evaluate, so a deferred arrange can be scheduled.
+    pub fn record_created(&mut self, window: WindowId) -> Duration {
+        self.first_seen.entry(window).or_insert_with(Instant::now);
+        Duration::from_millis(self.config.lifetime_delay_ms)
+    }
+
+    /// Forget a destroyed window.
+    pub fn record_destroyed(&mut self, window: WindowId) {
+        self.first_seen.remove(&window);
+    }
+
+    /// Whether this window should be tiled and counted by the layout.
+    ///
+    /// Undersized windows are rejected permanently; young windows are
+    /// rejected until their lifetime delay elapses.
+    pub fn admits(&self, window: &WindowInfo) -> bool {
+        if window.frame.width < self.config.min_width
+            || window.frame.height < self.config.min_height
+        {
+            return false;
+        }
+        match self.first_seen.get(&window.id) {
+            Some(seen) => seen.elapsed() >= Duration::from_millis(self.config.lifetime_delay_ms),
+            // Never observed being created — it predates the daemon.
+            None => true,
+        }
+    }
+}
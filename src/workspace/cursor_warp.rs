@@ -0,0 +1,159 @@
+//! Cursor warping on keyboard-driven focus changes.
+//!
+//! Focus-follows-keyboard leaves the pointer stranded on the old window;
+//! scroll events then go to the wrong place. Optionally the cursor warps
+//! to the newly focused window — its center, or wherever the pointer last
+//! was inside it. Individual bindings can override the global setting
+//! (`FocusLeft { warp = false }`).
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Rect, WindowId};
+
+/// Where the cursor lands inside the focused window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarpTarget {
+    /// Center of the window.
+    #[default]
+    Center,
+    /// The pointer's last recorded position inside this window, falling
+    /// back to the center when it was never there.
+    LastPosition,
+}
+
+/// Global cursor-warp configuration.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WarpCursorConfig {
+    /// Warp on keyboard-driven focus changes. Mouse-driven focus never
+    /// warps — the pointer is already where the user wants it.
+    pub enabled: bool,
+    pub target: WarpTarget,
+}
+
+/// Remembers the pointer's last position inside each window and computes
+/// warp destinations.
+#[derive(Debug, Default)]
+pub struct CursorWarper {
+    last_position: HashMap<WindowId, (f64, f64)>,
+}
+
+impl CursorWarper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the pointer position while it is inside `window`; fed from
+    /// mouse-moved events.
+    pub fn record_position(&mut self, window: WindowId, x: f64, y: f64) {
+        self.last_position.insert(window, (x, y));
+    }
+
+    /// Forget a closed window.
+    pub fn forget(&mut self, window: WindowId) {
+        self.last_position.remove(&window);
+    }
+
+    /// The point to warp to when `window` gains focus, or `None` when
+    /// warping is off for this action. `action_override` is the binding's
+    /// per-action `warp` flag.
+    pub fn destination(
+        &self,
+        window: WindowId,
+        frame: &Rect,
+        config: &WarpCursorConfig,
+        action_override: Option<bool>,
+    ) -> Option<(f64, f64)> {
+        if !action_override.unwrap_or(config.enabled) {
+            return None;
+        }
+        let center = (frame.x + frame.width / 2.0, frame.y + frame.height / 2.0);
+        match config.target {
+            WarpTarget::Center => Some(center),
+            WarpTarget::LastPosition => {
+                // A stale position outside the current frame means the
+                // window moved since; the center is the honest fallback.
+                match self.last_position.get(&window) {
+                    Some(&(x, y))
+                        if x >= frame.x
+                            && x <= frame.x + frame.width
+                            && y >= frame.y
+                            && y <= frame.y + frame.height =>
+                    {
+                        Some((x, y))
+                    }
+                    _ => Some(center),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Rect {
+        Rect {
+            x: 100.0,
+            y: 100.0,
+            width: 200.0,
+            height: 100.0,
+        }
+    }
+
+    #[test]
+    fn per_action_override_beats_the_global_setting() {
+        let warper = CursorWarper::new();
+        let off = WarpCursorConfig {
+            enabled: false,
+            target: WarpTarget::Center,
+        };
+        assert!(warper.destination(1, &frame(), &off, None).is_none());
+        assert_eq!(
+            warper.destination(1, &frame(), &off, Some(true)),
+            Some((200.0, 150.0))
+        );
+        let on = WarpCursorConfig {
+            enabled: true,
+            ..off
+        };
+        assert!(warper.destination(1, &frame(), &on, Some(false)).is_none());
+    }
+
+    #[test]
+    fn last_position_is_used_only_while_still_inside_the_frame() {
+        let mut warper = CursorWarper::new();
+        let config = WarpCursorConfig {
+            enabled: true,
+            target: WarpTarget::LastPosition,
+        };
+        // Never seen: center fallback.
+        assert_eq!(
+            warper.destination(1, &frame(), &config, None),
+            Some((200.0, 150.0))
+        );
+        warper.record_position(1, 120.0, 130.0);
+        assert_eq!(
+            warper.destination(1, &frame(), &config, None),
+            Some((120.0, 130.0))
+        );
+        // The window moved away from the recorded point: back to center.
+        let moved = Rect {
+            x: 500.0,
+            ..frame()
+        };
+        assert_eq!(
+            warper.destination(1, &moved, &config, None),
+            Some((600.0, 150.0))
+        );
+        warper.forget(1);
+        assert_eq!(
+            warper.destination(1, &frame(), &config, None),
+            Some((200.0, 150.0))
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Adaptive damping for windows that fight the tiler.
+//!
+//! Some apps re-assert their own frame after every arrange — video
+//! players snapping to aspect ratio, terminals resizing to cell
+//! boundaries, electron apps being electron apps. Without damping the
+//! result is a visible tug-of-war at several frames per second. The
+//! damper counts re-tiles per window; past the threshold within the
+//! window, the window is marked auto-floating with a notification and
+//! left alone for a cool-down, after which tiling is retried once.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::WindowId;
+
+/// Thresholds for the re-tile damper.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DampingConfig {
+    pub enabled: bool,
+    /// Re-tiles within `window_secs` that trip the damper.
+    pub max_retiles: u32,
+    /// Length of the counting window, seconds.
+    pub window_secs: u64,
+    /// How long a tripped window stays auto-floating, seconds.
+    pub cooldown_secs: u64,
+}
+
+impl Default for DampingConfig {
+    fn default() -> Self {
+        DampingConfig {
+            enabled: true,
+            max_retiles: 6,
+            window_secs: 60,
+            cooldown_secs: 300,
+        }
+    }
+}
+
+/// Per-window re-tile counting and cool-down state.
+#[derive(Debug, Default)]
+pub struct RetileDamper {
+    /// Recent re-tile timestamps per window, pruned to the counting window.
+    retiles: HashMap<WindowId, VecDeque<Instant>>,
+    /// Tripped windows and when their cool-down started.
+    cooling: HashMap<WindowId, Instant>,
+}
+
+impl RetileDamper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a window had to be re-tiled (its frame drifted from
+    /// the assigned slot). Returns `true` when this re-tile trips the
+    /// damper — the caller floats the window and notifies.
+    pub fn record_retile(
+        &mut self,
+        window: WindowId,
+        now: Instant,
+        config: &DampingConfig,
+    ) -> bool {
+        if !config.enabled || self.cooling.contains_key(&window) {
+            return false;
+        }
+        let events = self.retiles.entry(window).or_default();
+        events.push_back(now);
+        let horizon = Duration::from_secs(config.window_secs);
+        while events
+            .front()
+            .is_some_and(|&oldest| now.duration_since(oldest) > horizon)
+        {
+            events.pop_front();
+        }
+        if events.len() as u32 > config.max_retiles {
+            self.retiles.remove(&window);
+            self.cooling.insert(window, now);
+            tracing::info!(window, "damper tripped; window auto-floats for cool-down");
+            return true;
+        }
+        false
+    }
+
+    /// Whether a window is currently damped (auto-floating, not re-tiled).
+    pub fn is_damped(&self, window: WindowId) -> bool {
+        self.cooling.contains_key(&window)
+    }
+
+    /// Windows whose cool-down has expired; the caller re-tiles them and
+    /// the damper starts counting afresh.
+    pub fn expire(&mut self, now: Instant, config: &DampingConfig) -> Vec<WindowId> {
+        let cooldown = Duration::from_secs(config.cooldown_secs);
+        let expired: Vec<WindowId> = self
+            .cooling
+            .iter()
+            .filter(|(_, &since)| now.duration_since(since) >= cooldown)
+            .map(|(&window, _)| window)
+            .collect();
+        for window in &expired {
+            self.cooling.remove(window);
+        }
+        expired
+    }
+
+    /// Drop all state for a closed window.
+    pub fn forget(&mut self, window: WindowId) {
+        self.retiles.remove(&window);
+        self.cooling.remove(&window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> DampingConfig {
+        DampingConfig {
+            enabled: true,
+            max_retiles: 2,
+            window_secs: 60,
+            cooldown_secs: 300,
+        }
+    }
+
+    #[test]
+    fn trips_only_past_the_threshold_within_the_window() {
+        let mut damper = RetileDamper::new();
+        let config = config();
+        let start = Instant::now();
+        assert!(!damper.record_retile(1, start, &config));
+        assert!(!damper.record_retile(1, start + Duration::from_secs(1), &config));
+        assert!(damper.record_retile(1, start + Duration::from_secs(2), &config));
+        assert!(damper.is_damped(1));
+        // Further re-tiles during the cool-down never re-trip.
+        assert!(!damper.record_retile(1, start + Duration::from_secs(3), &config));
+    }
+
+    #[test]
+    fn old_retiles_age_out_of_the_counting_window() {
+        let mut damper = RetileDamper::new();
+        let config = config();
+        let start = Instant::now();
+        assert!(!damper.record_retile(1, start, &config));
+        assert!(!damper.record_retile(1, start + Duration::from_secs(1), &config));
+        // The first two fall outside the 60s window by now.
+        assert!(!damper.record_retile(1, start + Duration::from_secs(120), &config));
+        assert!(!damper.is_damped(1));
+    }
+
+    #[test]
+    fn expire_releases_windows_after_the_cooldown() {
+        let mut damper = RetileDamper::new();
+        let config = config();
+        let start = Instant::now();
+        for i in 0..3 {
+            damper.record_retile(1, start + Duration::from_secs(i), &config);
+        }
+        assert!(damper.is_damped(1));
+        assert!(damper
+            .expire(start + Duration::from_secs(60), &config)
+            .is_empty());
+        assert_eq!(
+            damper.expire(start + Duration::from_secs(400), &config),
+            [1]
+        );
+        assert!(!damper.is_damped(1));
+    }
+}
@@ -0,0 +1,90 @@
+//! Per-operation deadline budgets.
+//!
+//! Visible work for an operation (say, a workspace switch) must complete
+//! within its budget. When the budget is exhausted mid-pass, the pass
+//! degrades gracefully: the most important items (visible/topmost windows)
+//! have already been handled because callers order items by priority, and
+//! the remainder is returned for asynchronous completion. Degradations are
+//! counted in metrics rather than hidden.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// `[budgets]` config table, all in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeadlineBudgets {
+    /// Visible work for a workspace switch.
+    pub switch_ms: u64,
+    /// One arrange pass.
+    pub arrange_ms: u64,
+}
+
+impl Default for DeadlineBudgets {
+    fn default() -> Self {
+        DeadlineBudgets {
+            switch_ms: 150,
+            arrange_ms: 100,
+        }
+    }
+}
+
+/// Result of a budgeted pass over a list of items.
+#[derive(Debug)]
+pub struct BudgetOutcome<T> {
+    /// Items handled within the budget.
+    pub completed: usize,
+    /// Items left over; the caller finishes these asynchronously.
+    pub deferred: Vec<T>,
+    pub elapsed: Duration,
+}
+
+impl<T> BudgetOutcome<T> {
+    /// Whether the pass had to degrade.
+    pub fn degraded(&self) -> bool {
+        !self.deferred.is_empty()
+    }
+}
+
+/// Run `f` over `items` (already ordered most-important-first) until the
+/// budget runs out; the remainder is returned instead of processed.
+///
+/// The budget is checked between items, so one slow AX call can overshoot
+/// by its own duration — budgets bound scheduling, not individual calls.
+pub fn run_with_budget<T>(
+    budget: Duration,
+    items: Vec<T>,
+    mut f: impl FnMut(&T),
+) -> BudgetOutcome<T> {
+    let start = Instant::now();
+    let mut completed = 0;
+    let mut iter = items.into_iter();
+    let mut deferred = Vec::new();
+
+    for item in iter.by_ref() {
+        if start.elapsed() >= budget {
+            deferred.push(item);
+            break;
+        }
+        f(&item);
+        completed += 1;
+    }
+    deferred.extend(iter);
+
+    let outcome = BudgetOutcome {
+        completed,
+        deferred,
+        elapsed: start.elapsed(),
+    };
+    if outcome.degraded() {
+        tracing::warn!(
+            completed,
+            deferred = outcome.deferred.len(),
+            elapsed_ms = outcome.elapsed.as_millis() as u64,
+            budget_ms = budget.as_millis() as u64,
+            "operation exceeded its deadline budget; finishing reduced pass asynchronously"
+        );
+    }
+    outcome
+}
@@ -0,0 +1,111 @@
+//! Enforcement of per-app focus-stealing behavior.
+//!
+//! Some apps activate themselves whenever they feel like it — updaters,
+//! chat clients, anything with a badge. When such an app carries a
+//! `Passive` or `NewWindowsOnly` override in its profile, the guard
+//! reverts the uninvited grab by restoring focus to the previously
+//! focused window, counts a strike, and leaves a journal entry. The guard
+//! itself is pure (decide, don't act) so the revert goes through
+//! [`Effects`](crate::daemon::Effects) at the call site and the logic is
+//! testable off-mac.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::diagnostics::focus_journal::FocusTrigger;
+use crate::models::app_profile::{FocusStealingBehavior, ProfileStore};
+use crate::models::WindowId;
+
+/// What to do about one focus change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusVerdict {
+    /// Let it stand.
+    Allow,
+    /// Restore focus to this window and notify.
+    Revert { restore: WindowId },
+}
+
+/// Tracks the focus baseline and per-app strikes.
+#[derive(Debug, Default)]
+pub struct FocusGuard {
+    /// The window the user actually chose last.
+    previous: Option<WindowId>,
+    /// Windows seen before, to distinguish new-window grabs.
+    known: HashSet<WindowId>,
+    /// Reverted grabs per bundle id since daemon start.
+    strikes: HashMap<String, u32>,
+}
+
+impl FocusGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Judge a focus change. `trigger` comes from the focus journal
+    /// attribution; only app-initiated changes are ever reverted.
+    pub fn judge(
+        &mut self,
+        window: WindowId,
+        bundle_id: &str,
+        trigger: FocusTrigger,
+        profiles: &ProfileStore,
+    ) -> FocusVerdict {
+        let is_new_window = self.known.insert(window);
+        if trigger != FocusTrigger::AppInitiated {
+            self.previous = Some(window);
+            return FocusVerdict::Allow;
+        }
+        let behavior = profiles
+            .get(bundle_id)
+            .map(|p| p.focus_stealing)
+            .unwrap_or_default();
+        let revert = match behavior {
+            FocusStealingBehavior::Allow => false,
+            FocusStealingBehavior::Passive => true,
+            FocusStealingBehavior::NewWindowsOnly => !is_new_window,
+        };
+        match (revert, self.previous) {
+            (true, Some(restore)) if restore != window => {
+                let strikes = self.strikes.entry(bundle_id.to_string()).or_insert(0);
+                *strikes += 1;
+                tracing::info!(
+                    bundle_id,
+                    window,
+                    restore,
+                    strikes = *strikes,
+                    "reverted uninvited focus grab"
+                );
+                FocusVerdict::Revert { restore }
+            }
+            _ => {
+                // Allowed grab (or nothing to restore to); it becomes the
+                // new baseline like any other focus change.
+                self.previous = Some(window);
+                FocusVerdict::Allow
+            }
+        }
+    }
+
+    /// A window closed; it can no longer be the restore target.
+    pub fn forget(&mut self, window: WindowId) {
+        self.known.remove(&window);
+        if self.previous == Some(window) {
+            self.previous = None;
+        }
+    }
+
+    /// Reverted grabs for one app since daemon start.
+    pub fn strikes(&self, bundle_id: &str) -> u32 {
+        self.strikes.get(bundle_id).copied().unwrap_or(0)
+    }
+
+    /// All apps with strikes, worst first, for diagnostics.
+    pub fn strike_report(&self) -> Vec<(String, u32)> {
+        let mut report: Vec<_> = self
+            .strikes
+            .iter()
+            .map(|(app, strikes)| (app.clone(), *strikes))
+            .collect();
+        report.sort_by_key(|(_, strikes)| std::cmp::Reverse(*strikes));
+        report
+    }
+}
@@ -0,0 +1,96 @@
+//! Focus sessions (pomodoro-style timers) that lock a workspace.
+//!
+//! Starting a session pins the current workspace: switch actions are
+//! rejected until the timer expires or the user explicitly overrides.
+//! The tray shows the remaining time and hook events fire on start,
+//! expiry, and override so external tooling can react.
+
+use std::time::{Duration, Instant};
+
+/// An active focus session.
+#[derive(Debug, Clone)]
+pub struct FocusSession {
+    /// Workspace locked for the duration of the session.
+    pub workspace: String,
+    pub started_at: Instant,
+    pub duration: Duration,
+}
+
+impl FocusSession {
+    pub fn remaining(&self) -> Duration {
+        (self.started_at + self.duration).saturating_duration_since(Instant::now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+}
+
+/// Events emitted for the tray, notifications, and hooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FocusTimerEvent {
+    Started { workspace: String, minutes: u64 },
+    Completed { workspace: String },
+    Overridden { workspace: String },
+}
+
+/// Owns the (at most one) active focus session.
+#[derive(Debug, Default)]
+pub struct FocusTimer {
+    session: Option<FocusSession>,
+}
+
+impl FocusTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a session locking `workspace`. Replaces any running session.
+    pub fn start(&mut self, workspace: impl Into<String>, duration: Duration) -> FocusTimerEvent {
+        let workspace = workspace.into();
+        self.session = Some(FocusSession {
+            workspace: workspace.clone(),
+            started_at: Instant::now(),
+            duration,
+        });
+        FocusTimerEvent::Started {
+            minutes: duration.as_secs() / 60,
+            workspace,
+        }
+    }
+
+    /// End the session early by user override.
+    pub fn cancel(&mut self) -> Option<FocusTimerEvent> {
+        self.session.take().map(|s| FocusTimerEvent::Overridden {
+            workspace: s.workspace,
+        })
+    }
+
+    /// Advance the timer; returns the completion event once when the
+    /// session expires. Called from the daemon's periodic tick.
+    pub fn tick(&mut self) -> Option<FocusTimerEvent> {
+        if self.session.as_ref().is_some_and(FocusSession::is_expired) {
+            let session = self.session.take().unwrap();
+            return Some(FocusTimerEvent::Completed {
+                workspace: session.workspace,
+            });
+        }
+        None
+    }
+
+    /// Whether a switch away from the locked workspace is currently
+    /// blocked. `via_override` is set for the explicit override action,
+    /// which is always allowed.
+    pub fn blocks_switch(&self, target_workspace: &str, via_override: bool) -> bool {
+        if via_override {
+            return false;
+        }
+        self.session
+            .as_ref()
+            .is_some_and(|s| !s.is_expired() && s.workspace != target_workspace)
+    }
+
+    pub fn active(&self) -> Option<&FocusSession> {
+        self.session.as_ref().filter(|s| !s.is_expired())
+    }
+}
@@ -0,0 +1,110 @@
+//! Workspace groups: a second level of organization.
+//!
+//! A group names a set of workspaces that belong together — "ClientA"
+//! holding Code/Comms/Docs. Switching groups re-binds the numeric
+//! workspace shortcuts (`opt+1..9`): index actions resolve inside the
+//! active group, so the same muscle memory addresses whichever context is
+//! current instead of a global workspace list that stops fitting in nine
+//! slots.
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, TilleRSError};
+
+/// One named group, declared in config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceGroup {
+    pub name: String,
+    /// Member workspaces in shortcut order: the first is `opt+1`.
+    pub workspaces: Vec<String>,
+}
+
+/// Runtime group state: which group the numeric shortcuts address.
+#[derive(Debug, Default)]
+pub struct GroupRegistry {
+    groups: Vec<WorkspaceGroup>,
+    active: Option<String>,
+}
+
+impl GroupRegistry {
+    pub fn new(groups: Vec<WorkspaceGroup>) -> Self {
+        GroupRegistry {
+            groups,
+            active: None,
+        }
+    }
+
+    /// Activate a group; numeric shortcuts now resolve inside it.
+    pub fn switch(&mut self, name: &str) -> Result<&WorkspaceGroup> {
+        let group =
+            self.groups
+                .iter()
+                .find(|g| g.name == name)
+                .ok_or_else(|| TilleRSError::NotFound {
+                    kind: "group",
+                    name: name.to_string(),
+                })?;
+        self.active = Some(group.name.clone());
+        Ok(group)
+    }
+
+    /// Leave group mode; shortcuts fall back to the global order.
+    pub fn clear(&mut self) {
+        self.active = None;
+    }
+
+    /// The active group, if any.
+    pub fn active(&self) -> Option<&WorkspaceGroup> {
+        let name = self.active.as_deref()?;
+        self.groups.iter().find(|g| g.name == name)
+    }
+
+    /// Resolve a numeric shortcut (1-based, as bound) to a workspace name.
+    /// Inside a group only its members are addressable; without one the
+    /// index falls back to `global_order`.
+    pub fn workspace_at<'a>(&'a self, index: usize, global_order: &'a [String]) -> Option<&'a str> {
+        let slot = index.checked_sub(1)?;
+        match self.active() {
+            Some(group) => group.workspaces.get(slot).map(String::as_str),
+            None => global_order.get(slot).map(String::as_str),
+        }
+    }
+
+    /// All declared group names, in config order.
+    pub fn names(&self) -> Vec<&str> {
+        self.groups.iter().map(|g| g.name.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> GroupRegistry {
+        GroupRegistry::new(vec![WorkspaceGroup {
+            name: "client".into(),
+            workspaces: vec!["code".into(), "comms".into()],
+        }])
+    }
+
+    #[test]
+    fn indices_resolve_inside_the_active_group() {
+        let mut registry = registry();
+        let global = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        // Without a group, indices count through the global order.
+        assert_eq!(registry.workspace_at(3, &global), Some("c"));
+        registry.switch("client").unwrap();
+        assert_eq!(registry.workspace_at(1, &global), Some("code"));
+        // Only the group's members are addressable while it is active.
+        assert_eq!(registry.workspace_at(3, &global), None);
+        registry.clear();
+        assert_eq!(registry.workspace_at(3, &global), Some("c"));
+    }
+
+    #[test]
+    fn switching_to_an_unknown_group_is_an_error() {
+        let mut registry = registry();
+        assert!(registry.switch("nope").is_err());
+        assert!(registry.active().is_none());
+    }
+}
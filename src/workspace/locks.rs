@@ -0,0 +1,86 @@
+//! Persistence for do-not-touch window locks.
+//!
+//! The lock itself lives on the window model (`WindowInfo::locked`): the
+//! arrange path never tiles a locked window and `ToggleLock` flips the
+//! flag. What survives a restart is a generated rule — matched by app and
+//! exact title, locked and floating — added to the config when a window
+//! is locked and removed again when it is unlocked.
+
+use crate::models::{WindowInfo, WindowRule};
+
+/// Name prefix of generated lock-persistence rules, so unlocking can tell
+/// them apart from rules the user wrote.
+pub const LOCK_RULE_PREFIX: &str = "locked:";
+
+/// Build the rule that persists a window's lock across restarts: matched
+/// by app and exact title, locked and floating so the window is
+/// re-adopted untouched.
+pub fn persistence_rule(window: &WindowInfo) -> WindowRule {
+    let mut rule = WindowRule::named(format!("{LOCK_RULE_PREFIX}{}", window.id));
+    rule.app_bundle_id = Some(window.app_bundle_id.clone());
+    rule.title_pattern = Some(regex_escape(&window.title));
+    rule.locked = true;
+    rule.floating = true;
+    rule
+}
+
+/// Escape a literal string for use as a title regex.
+fn regex_escape(literal: &str) -> String {
+    let mut escaped = String::with_capacity(literal.len());
+    for c in literal.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Rect;
+
+    fn window_titled(title: &str) -> WindowInfo {
+        WindowInfo {
+            id: 7,
+            title: title.into(),
+            app_bundle_id: "com.example.editor".into(),
+            workspace: "main".into(),
+            frame: Rect {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            floating: false,
+            minimized: false,
+            locked: true,
+            last_focused_at: std::time::SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn persistence_rule_matches_its_window_and_carries_the_lock() {
+        let window = window_titled("notes (draft) [v2]");
+        let rule = persistence_rule(&window);
+        assert!(rule.name.starts_with(LOCK_RULE_PREFIX));
+        assert!(rule.locked);
+        assert!(rule.floating);
+        // Regex metacharacters in the title are escaped, so the generated
+        // pattern matches the literal title and nothing looser.
+        assert!(rule.matches(&window));
+        // Without escaping "(draft)" would group and "[v2]" would be a
+        // character class, letting this title through.
+        assert!(!rule.matches(&window_titled("notes draft v")));
+    }
+
+    #[test]
+    fn persistence_rule_does_not_match_other_apps() {
+        let locked = window_titled("scratch");
+        let rule = persistence_rule(&locked);
+        let mut other = window_titled("scratch");
+        other.app_bundle_id = "com.example.terminal".into();
+        assert!(!rule.matches(&other));
+    }
+}
@@ -0,0 +1,175 @@
+//! The workspace manager: owns workspace definitions and the active set.
+
+use crate::errors::{Result, TilleRSError};
+use crate::events::{Event, EventBus, WorkspaceEvent};
+use crate::models::Workspace;
+
+/// Owns workspaces and publishes lifecycle events on the bus.
+///
+/// Listeners subscribe to the [`EventBus`] rather than registering
+/// closures here, so delivery order and backpressure are the bus's
+/// concern, uniformly for every subscriber.
+pub struct WorkspaceManager {
+    workspaces: Vec<Workspace>,
+    active: Option<String>,
+    bus: EventBus,
+}
+
+impl WorkspaceManager {
+    pub fn new(bus: EventBus) -> Self {
+        WorkspaceManager {
+            workspaces: Vec::new(),
+            active: None,
+            bus,
+        }
+    }
+
+    pub fn workspaces(&self) -> &[Workspace] {
+        &self.workspaces
+    }
+
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Workspace> {
+        self.workspaces.iter().find(|w| w.name == name)
+    }
+
+    /// Resolve a user-supplied workspace reference (name, 1-based index,
+    /// or UUID) against the current workspace order. This is the single
+    /// resolution point every CLI and IPC endpoint funnels through.
+    pub fn resolve(&self, reference: &crate::models::WorkspaceRef) -> Result<&Workspace> {
+        reference.resolve(&self.workspaces)
+    }
+
+    /// Subscribe to all daemon events; see [`EventBus::subscribe`].
+    pub fn subscribe(&self) -> crate::events::EventSubscriber {
+        self.bus.subscribe()
+    }
+
+    pub fn create(&mut self, workspace: Workspace) -> Result<()> {
+        if self.get(&workspace.name).is_some() {
+            return Err(TilleRSError::Validation(format!(
+                "a workspace named '{}' already exists",
+                workspace.name
+            )));
+        }
+        let name = workspace.name.clone();
+        self.workspaces.push(workspace);
+        self.bus
+            .publish(Event::Workspace(WorkspaceEvent::Created { name }));
+        Ok(())
+    }
+
+    /// Create the workspace when missing, otherwise update it in place to
+    /// the given shape. The idempotent primitive behind `tillers apply`.
+    pub fn ensure(
+        &mut self,
+        name: &str,
+        layout: crate::tiling::LayoutPattern,
+        display: Option<String>,
+        quiet: bool,
+    ) -> Result<()> {
+        if let Some(workspace) = self.workspaces.iter_mut().find(|w| w.name == name) {
+            workspace.layout = layout;
+            workspace.display = display;
+            workspace.quiet = quiet;
+            return Ok(());
+        }
+        let mut workspace = Workspace::new(name);
+        workspace.layout = layout;
+        workspace.display = display;
+        workspace.quiet = quiet;
+        self.create(workspace)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<Workspace> {
+        let idx = self
+            .workspaces
+            .iter()
+            .position(|w| w.name == name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "workspace",
+                name: name.to_string(),
+            })?;
+        let removed = self.workspaces.remove(idx);
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+        self.bus.publish(Event::Workspace(WorkspaceEvent::Removed {
+            name: name.to_string(),
+        }));
+        Ok(removed)
+    }
+
+    /// Mark a workspace active and announce it. The orchestrator drives
+    /// the actual hide/show/arrange around this.
+    pub fn activate(&mut self, name: &str) -> Result<()> {
+        if self.get(name).is_none() {
+            return Err(TilleRSError::NotFound {
+                kind: "workspace",
+                name: name.to_string(),
+            });
+        }
+        self.active = Some(name.to_string());
+        self.bus
+            .publish(Event::Workspace(WorkspaceEvent::Activated {
+                name: name.to_string(),
+            }));
+        Ok(())
+    }
+
+    /// Toggle quiet mode; returns the new state. Quiet workspaces accept
+    /// new windows but never auto-arrange them.
+    pub fn toggle_quiet(&mut self, name: &str) -> Result<bool> {
+        let workspace = self
+            .workspaces
+            .iter_mut()
+            .find(|w| w.name == name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "workspace",
+                name: name.to_string(),
+            })?;
+        workspace.quiet = !workspace.quiet;
+        Ok(workspace.quiet)
+    }
+
+    /// Reset a workspace's split ratios to the balanced defaults. Returns
+    /// the prior ratios so callers can restore them, or `None` when the
+    /// ratios were already balanced and nothing changed.
+    pub fn balance_splits(&mut self, name: &str) -> Result<Option<crate::tiling::SplitRatios>> {
+        let workspace = self
+            .workspaces
+            .iter_mut()
+            .find(|w| w.name == name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "workspace",
+                name: name.to_string(),
+            })?;
+        if workspace.splits.is_balanced() {
+            return Ok(None);
+        }
+        let prior = workspace.splits.clone();
+        workspace.splits.balance();
+        Ok(Some(prior))
+    }
+
+    /// Overwrite a workspace's split ratios; the balance rollback path.
+    pub fn set_splits(&mut self, name: &str, splits: crate::tiling::SplitRatios) -> Result<()> {
+        let workspace = self
+            .workspaces
+            .iter_mut()
+            .find(|w| w.name == name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "workspace",
+                name: name.to_string(),
+            })?;
+        workspace.splits = splits;
+        Ok(())
+    }
+
+    pub fn bus(&self) -> &EventBus {
+        &self.bus
+    }
+}
@@ -0,0 +1,42 @@
+//! Workspace runtime: managers, orchestration, and per-app suspensions.
+
+pub mod adoption;
+pub mod archival;
+pub mod catch_all;
+pub mod clamshell;
+pub mod clipboard;
+pub mod coalesce;
+pub mod compliance;
+pub mod converge;
+pub mod cosmetics;
+pub mod creation_guard;
+pub mod cursor_warp;
+pub mod damping;
+pub mod deadline;
+pub mod focus_guard;
+pub mod focus_timer;
+pub mod groups;
+pub mod locks;
+pub mod manager;
+pub mod monitor_presets;
+pub mod multi_display;
+pub mod orchestrator;
+pub mod pause;
+pub mod readiness;
+pub mod relations;
+pub mod sequence;
+pub mod suspension;
+pub mod tabs;
+pub mod temporary;
+pub mod topology;
+pub mod visibility;
+pub mod window_manager;
+
+pub use archival::{ArchivalPolicy, Archiver};
+pub use deadline::DeadlineBudgets;
+pub use focus_timer::{FocusSession, FocusTimer, FocusTimerEvent};
+pub use manager::WorkspaceManager;
+pub use orchestrator::{OrchestratorState, WorkspaceOrchestrator};
+pub use relations::WindowRelations;
+pub use suspension::{Suspension, SuspensionRegistry};
+pub use window_manager::WindowManager;
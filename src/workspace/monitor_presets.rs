@@ -0,0 +1,176 @@
+//! Named display-arrangement presets.
+//!
+//! A desk with two externals and a couch with just the laptop screen want
+//! different workspace-to-monitor assignments. A preset captures the
+//! physical arrangement (display names and frames) together with the
+//! current assignments; activating one later re-pins workspaces to the
+//! displays that are actually attached, warns about the ones that are
+//! not, and applies the subset that matches.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, TilleRSError};
+use crate::models::display::DisplayInfo;
+use crate::models::{Rect, Workspace};
+
+/// One display as remembered by a preset. Identity is the name first and
+/// the frame second, so a renamed-but-identical monitor still matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplaySlot {
+    pub name: String,
+    pub frame: Rect,
+}
+
+/// A saved arrangement: the displays that were attached and which
+/// workspace was pinned to which of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorPreset {
+    pub displays: Vec<DisplaySlot>,
+    /// Workspace name to display name.
+    pub assignments: BTreeMap<String, String>,
+}
+
+impl MonitorPreset {
+    /// Capture the current arrangement and pinnings as a preset.
+    pub fn capture(displays: &[DisplayInfo], workspaces: &[Workspace]) -> Self {
+        let assignments = workspaces
+            .iter()
+            .filter_map(|ws| {
+                ws.display
+                    .as_ref()
+                    .map(|display| (ws.name.clone(), display.clone()))
+            })
+            .collect();
+        MonitorPreset {
+            displays: displays
+                .iter()
+                .map(|d| DisplaySlot {
+                    name: d.name.clone(),
+                    frame: d.frame,
+                })
+                .collect(),
+            assignments,
+        }
+    }
+
+    /// What activating this preset against the attached displays would do.
+    pub fn plan(&self, attached: &[DisplayInfo]) -> ActivationPlan {
+        let resolve = |slot: &DisplaySlot| {
+            attached
+                .iter()
+                .find(|d| d.name == slot.name)
+                .or_else(|| {
+                    attached.iter().find(|d| {
+                        d.frame.width == slot.frame.width && d.frame.height == slot.frame.height
+                    })
+                })
+                .map(|d| d.name.clone())
+        };
+        let mut plan = ActivationPlan::default();
+        let mut resolved: BTreeMap<&str, Option<String>> = BTreeMap::new();
+        for slot in &self.displays {
+            let target = resolve(slot);
+            if target.is_none() {
+                plan.missing_displays.push(slot.name.clone());
+            }
+            resolved.insert(&slot.name, target);
+        }
+        for (workspace, display) in &self.assignments {
+            match resolved.get(display.as_str()).cloned().flatten() {
+                Some(target) => plan.pin.push((workspace.clone(), target)),
+                None => plan.skipped_workspaces.push(workspace.clone()),
+            }
+        }
+        plan
+    }
+}
+
+/// The applicable subset of a preset, plus what had to be skipped.
+#[derive(Debug, Default)]
+pub struct ActivationPlan {
+    /// Workspace name to attached-display name, for the matching part.
+    pub pin: Vec<(String, String)>,
+    /// Preset displays with no attached counterpart.
+    pub missing_displays: Vec<String>,
+    /// Assignments targeting a missing display; left as they are.
+    pub skipped_workspaces: Vec<String>,
+}
+
+impl ActivationPlan {
+    /// Whether the physical topology matches the preset exactly.
+    pub fn is_exact(&self) -> bool {
+        self.missing_displays.is_empty() && self.skipped_workspaces.is_empty()
+    }
+}
+
+/// All saved presets, persisted as JSON in the data directory.
+#[derive(Debug, Default)]
+pub struct PresetStore {
+    path: PathBuf,
+    presets: BTreeMap<String, MonitorPreset>,
+}
+
+impl PresetStore {
+    /// Default store location: `~/.local/share/tillers/monitor-presets.json`.
+    pub fn default_path() -> PathBuf {
+        let home = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        home.join(".local")
+            .join("share")
+            .join("tillers")
+            .join("monitor-presets.json")
+    }
+
+    pub fn load_default() -> Result<Self> {
+        Self::load(Self::default_path())
+    }
+
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let presets = if path.exists() {
+            let raw = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&raw)?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(PresetStore { path, presets })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.presets)?)?;
+        Ok(())
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, preset: MonitorPreset) {
+        self.presets.insert(name.into(), preset);
+    }
+
+    pub fn get(&self, name: &str) -> Result<&MonitorPreset> {
+        self.presets
+            .get(name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "monitor preset",
+                name: name.to_string(),
+            })
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<MonitorPreset> {
+        self.presets
+            .remove(name)
+            .ok_or_else(|| TilleRSError::NotFound {
+                kind: "monitor preset",
+                name: name.to_string(),
+            })
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.keys().map(String::as_str)
+    }
+}
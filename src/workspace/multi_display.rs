@@ -0,0 +1,81 @@
+//! Concurrent arrangement across displays.
+//!
+//! A switch touching several displays used to arrange them one after
+//! another, so the second monitor visibly lagged the first. Each display's
+//! workspace is now arranged in its own task; a join barrier keeps the
+//! completion event honest — it fires once, after every display settled.
+
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use crate::daemon::Effects;
+use crate::errors::{Result, TilleRSError};
+use crate::events::{Event, EventBus, WorkspaceEvent};
+use crate::models::display::DisplayId;
+use crate::models::{Rect, WindowId};
+
+use super::orchestrator;
+
+/// The arrange work for one display.
+#[derive(Debug, Clone)]
+pub struct DisplayArrangePlan {
+    pub display: DisplayId,
+    pub workspace: String,
+    /// Frames ordered most-important-first (topmost/visible windows lead).
+    pub assignments: Vec<(WindowId, Rect)>,
+}
+
+/// Arrange every display's workspace concurrently, then emit one
+/// `ArrangeCompleted` per workspace after all displays finished.
+///
+/// AX scheduling stays per-task: windows on different displays belong to
+/// disjoint frame sets, so the tasks never contend on a window.
+pub async fn arrange_displays(
+    effects: Effects,
+    bus: &EventBus,
+    plans: Vec<DisplayArrangePlan>,
+    token: &CancellationToken,
+) -> Result<usize> {
+    let mut tasks: JoinSet<Result<(String, usize)>> = JoinSet::new();
+    for plan in plans {
+        let token = token.clone();
+        tasks.spawn_blocking(move || {
+            let (applied, _unverified) =
+                orchestrator::apply_frames(&effects, &plan.assignments, &token)?;
+            Ok((plan.workspace, applied))
+        });
+    }
+
+    // Join barrier: collect every display's outcome before any event is
+    // published. The first error wins but remaining tasks still drain.
+    let mut total = 0;
+    let mut completed = Vec::new();
+    let mut first_error = None;
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(Ok((workspace, applied))) => {
+                total += applied;
+                completed.push((workspace, applied));
+            }
+            Ok(Err(err)) => {
+                first_error.get_or_insert(err);
+            }
+            Err(join_err) => {
+                first_error.get_or_insert(TilleRSError::Validation(format!(
+                    "display arrange task panicked: {join_err}"
+                )));
+            }
+        }
+    }
+    if let Some(err) = first_error {
+        return Err(err);
+    }
+
+    for (workspace, applied) in completed {
+        bus.publish(Event::Workspace(WorkspaceEvent::ArrangeCompleted {
+            name: workspace,
+            windows: applied,
+        }));
+    }
+    Ok(total)
+}
@@ -0,0 +1,251 @@
+//! Workspace orchestration as an explicit state machine.
+//!
+//! Arrange and switch used to guard against re-entrancy with ad-hoc
+//! locking; instead the orchestrator now has one explicit state and every
+//! operation is a typed transition that either succeeds or returns
+//! [`TransitionError`]. A switch requested mid-arrange is rejected at the
+//! transition level, not discovered as a race later. The current state is
+//! introspectable (and exposed over IPC) for diagnostics.
+
+use std::fmt;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::daemon::Effects;
+use crate::errors::Result;
+use crate::models::{Rect, WindowId};
+
+/// The orchestrator's exhaustive state set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrchestratorState {
+    /// Nothing in flight; the only state that accepts new work.
+    Idle,
+    /// An arrange pass is applying frames for one workspace.
+    Arranging { workspace: String },
+    /// A workspace switch (hide + show + arrange) is in flight.
+    Switching { from: String, to: String },
+    /// Tiling is administratively paused (conflict detected, user pause).
+    Suspended { reason: String },
+    /// Something is wrong (permissions lost, repeated AX failures);
+    /// only diagnostics and resume transitions are accepted.
+    Degraded { reason: String },
+}
+
+impl OrchestratorState {
+    /// Short machine-readable name for IPC and metrics.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrchestratorState::Idle => "idle",
+            OrchestratorState::Arranging { .. } => "arranging",
+            OrchestratorState::Switching { .. } => "switching",
+            OrchestratorState::Suspended { .. } => "suspended",
+            OrchestratorState::Degraded { .. } => "degraded",
+        }
+    }
+}
+
+impl fmt::Display for OrchestratorState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrchestratorState::Idle => write!(f, "idle"),
+            OrchestratorState::Arranging { workspace } => write!(f, "arranging {workspace}"),
+            OrchestratorState::Switching { from, to } => write!(f, "switching {from} -> {to}"),
+            OrchestratorState::Suspended { reason } => write!(f, "suspended ({reason})"),
+            OrchestratorState::Degraded { reason } => write!(f, "degraded ({reason})"),
+        }
+    }
+}
+
+/// A transition that the current state does not permit.
+#[derive(Debug, Clone)]
+pub struct TransitionError {
+    pub from: OrchestratorState,
+    pub attempted: &'static str,
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot {} while orchestrator is {}",
+            self.attempted, self.from
+        )
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+/// Drives workspace arrangement and switching.
+#[derive(Debug)]
+pub struct WorkspaceOrchestrator {
+    state: OrchestratorState,
+    /// Cancels the in-flight arrange or switch, when one exists. A newer
+    /// switch request preempts the current pass instead of waiting for
+    /// work that is about to be redone.
+    in_flight: Option<CancellationToken>,
+}
+
+impl Default for WorkspaceOrchestrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkspaceOrchestrator {
+    pub fn new() -> Self {
+        WorkspaceOrchestrator {
+            state: OrchestratorState::Idle,
+            in_flight: None,
+        }
+    }
+
+    /// Cancel the in-flight operation, if any, so a newer request can
+    /// take over once the aborted pass unwinds to `finish_*`.
+    pub fn preempt(&mut self) {
+        if let Some(token) = &self.in_flight {
+            tracing::debug!(state = %self.state, "preempting in-flight operation");
+            token.cancel();
+        }
+    }
+
+    pub fn state(&self) -> &OrchestratorState {
+        &self.state
+    }
+
+    /// Idle -> Arranging. Rejected while anything else is in flight, which
+    /// is exactly the re-entrant arrange the old locking tried to prevent.
+    /// The returned token cancels this pass when [`Self::preempt`] fires.
+    pub fn begin_arrange(
+        &mut self,
+        workspace: impl Into<String>,
+    ) -> std::result::Result<CancellationToken, TransitionError> {
+        match &self.state {
+            OrchestratorState::Idle => {
+                self.state = OrchestratorState::Arranging {
+                    workspace: workspace.into(),
+                };
+                let token = CancellationToken::new();
+                self.in_flight = Some(token.clone());
+                Ok(token)
+            }
+            other => Err(TransitionError {
+                from: other.clone(),
+                attempted: "begin arrange",
+            }),
+        }
+    }
+
+    /// Arranging -> Idle, whether the pass completed or was cancelled.
+    pub fn finish_arrange(&mut self) -> std::result::Result<(), TransitionError> {
+        match &self.state {
+            OrchestratorState::Arranging { .. } => {
+                self.state = OrchestratorState::Idle;
+                self.in_flight = None;
+                Ok(())
+            }
+            other => Err(TransitionError {
+                from: other.clone(),
+                attempted: "finish arrange",
+            }),
+        }
+    }
+
+    /// Idle -> Switching. See [`Self::begin_arrange`] for the token.
+    pub fn begin_switch(
+        &mut self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> std::result::Result<CancellationToken, TransitionError> {
+        match &self.state {
+            OrchestratorState::Idle => {
+                self.state = OrchestratorState::Switching {
+                    from: from.into(),
+                    to: to.into(),
+                };
+                let token = CancellationToken::new();
+                self.in_flight = Some(token.clone());
+                Ok(token)
+            }
+            other => Err(TransitionError {
+                from: other.clone(),
+                attempted: "begin switch",
+            }),
+        }
+    }
+
+    /// Switching -> Idle, whether the switch completed or was cancelled.
+    pub fn finish_switch(&mut self) -> std::result::Result<(), TransitionError> {
+        match &self.state {
+            OrchestratorState::Switching { .. } => {
+                self.state = OrchestratorState::Idle;
+                self.in_flight = None;
+                Ok(())
+            }
+            other => Err(TransitionError {
+                from: other.clone(),
+                attempted: "finish switch",
+            }),
+        }
+    }
+
+    /// Any state -> Suspended. Always allowed: suspension is how operators
+    /// stop the manager, including from a wedged-looking state.
+    pub fn suspend(&mut self, reason: impl Into<String>) {
+        self.state = OrchestratorState::Suspended {
+            reason: reason.into(),
+        };
+    }
+
+    /// Suspended or Degraded -> Idle.
+    pub fn resume(&mut self) -> std::result::Result<(), TransitionError> {
+        match &self.state {
+            OrchestratorState::Suspended { .. } | OrchestratorState::Degraded { .. } => {
+                self.state = OrchestratorState::Idle;
+                Ok(())
+            }
+            other => Err(TransitionError {
+                from: other.clone(),
+                attempted: "resume",
+            }),
+        }
+    }
+
+    /// Any state -> Degraded; recorded with the failure that caused it.
+    pub fn degrade(&mut self, reason: impl Into<String>) {
+        let reason = reason.into();
+        tracing::warn!(%reason, "orchestrator degraded");
+        self.state = OrchestratorState::Degraded { reason };
+    }
+
+    /// Whether new arrange/switch work can be accepted right now.
+    pub fn is_idle(&self) -> bool {
+        self.state == OrchestratorState::Idle
+    }
+}
+
+/// Apply computed frames with verification, checking for cancellation
+/// between windows.
+///
+/// Returns the number of frames actually applied and the windows whose
+/// frames never verified within tolerance — candidates for floating
+/// reclassification. An aborted pass leaves the remaining windows for the
+/// pass that preempted it.
+pub fn apply_frames(
+    effects: &Effects,
+    assignments: &[(WindowId, Rect)],
+    token: &CancellationToken,
+) -> Result<(usize, Vec<WindowId>)> {
+    let mut applied = 0;
+    let mut unverified = Vec::new();
+    for (window, frame) in assignments {
+        if token.is_cancelled() {
+            tracing::debug!(applied, total = assignments.len(), "arrange pass cancelled");
+            break;
+        }
+        if !effects.set_window_frame_verified(*window, *frame)? {
+            unverified.push(*window);
+        }
+        applied += 1;
+    }
+    Ok((applied, unverified))
+}
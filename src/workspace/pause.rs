@@ -0,0 +1,41 @@
+//! Per-workspace pause state.
+//!
+//! Pausing one workspace stops tiling and rule enforcement there — its
+//! windows float freely — without disabling the manager everywhere, which
+//! is what a chaotic screen-share or design session needs. Resume
+//! re-adopts the windows and re-tiles.
+
+use std::collections::HashSet;
+
+/// Workspaces currently exempt from tiling and rules.
+#[derive(Debug, Default)]
+pub struct WorkspacePauseRegistry {
+    paused: HashSet<String>,
+}
+
+impl WorkspacePauseRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pause a workspace. Returns false if it was already paused.
+    pub fn pause(&mut self, workspace: &str) -> bool {
+        self.paused.insert(workspace.to_string())
+    }
+
+    /// Resume a workspace. Returns false if it was not paused; the caller
+    /// re-tiles only on a true transition.
+    pub fn resume(&mut self, workspace: &str) -> bool {
+        self.paused.remove(workspace)
+    }
+
+    /// Whether arrange passes and rule enforcement skip this workspace.
+    pub fn is_paused(&self, workspace: &str) -> bool {
+        self.paused.contains(workspace)
+    }
+
+    /// Paused workspaces, for the tray and diagnostics.
+    pub fn paused(&self) -> impl Iterator<Item = &str> {
+        self.paused.iter().map(String::as_str)
+    }
+}
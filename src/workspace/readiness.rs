@@ -0,0 +1,153 @@
+//! Startup grace period and per-app readiness probes.
+//!
+//! At login, apps restore their windows over several seconds; adopting
+//! and arranging too early tiles half-restored windows into frames they
+//! immediately outgrow. The first adoption pass therefore waits out a
+//! global grace period, plus per-app probes for known slow restorers:
+//! wait until the app's window count has stopped changing, or until a
+//! specific title shows up. Every probe has a timeout so a hung app can
+//! never hold the whole startup hostage.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::WindowInfo;
+
+/// What "ready" means for one app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "wait_for", rename_all = "snake_case")]
+pub enum ReadinessCondition {
+    /// The app's window count has not changed for this long.
+    WindowCountStable {
+        #[serde(default = "default_stable_ms")]
+        stable_ms: u64,
+    },
+    /// A window whose title matches this regex exists.
+    TitleAppears { pattern: String },
+}
+
+fn default_stable_ms() -> u64 {
+    1000
+}
+
+/// A per-app readiness probe.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppReadinessProbe {
+    pub bundle_id: String,
+    #[serde(flatten)]
+    pub condition: ReadinessCondition,
+    /// The probe is forced ready after this long, restored or not.
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_timeout_ms() -> u64 {
+    10_000
+}
+
+/// Startup pacing for the first adoption/arrange pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartupConfig {
+    /// Global grace period before the first pass.
+    pub grace_ms: u64,
+    /// Per-app probes that must all pass (or time out) first.
+    pub readiness: Vec<AppReadinessProbe>,
+}
+
+impl Default for StartupConfig {
+    fn default() -> Self {
+        StartupConfig {
+            grace_ms: 750,
+            readiness: Vec::new(),
+        }
+    }
+}
+
+/// How often the daemon re-polls the window list while waiting for
+/// readiness.
+pub const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Evaluates the grace period and probes against window snapshots the
+/// daemon feeds it while waiting to run the first pass.
+#[derive(Debug)]
+pub struct ReadinessTracker {
+    started: Instant,
+    config: StartupConfig,
+    /// Per bundle id: last seen window count and when it last changed.
+    counts: HashMap<String, (usize, Instant)>,
+}
+
+impl ReadinessTracker {
+    pub fn new(config: StartupConfig, now: Instant) -> Self {
+        ReadinessTracker {
+            started: now,
+            config,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Feed the current window list; call on every poll while waiting.
+    pub fn observe(&mut self, windows: &[WindowInfo], now: Instant) {
+        let mut current: HashMap<&str, usize> = HashMap::new();
+        for window in windows {
+            *current.entry(window.app_bundle_id.as_str()).or_insert(0) += 1;
+        }
+        for probe in &self.config.readiness {
+            let count = current.get(probe.bundle_id.as_str()).copied().unwrap_or(0);
+            match self.counts.get_mut(&probe.bundle_id) {
+                Some((last, changed_at)) if *last != count => {
+                    *last = count;
+                    *changed_at = now;
+                }
+                Some(_) => {}
+                None => {
+                    self.counts.insert(probe.bundle_id.clone(), (count, now));
+                }
+            }
+        }
+    }
+
+    /// Whether the first pass may run: grace period elapsed and every
+    /// probe passed or timed out.
+    pub fn ready(&self, windows: &[WindowInfo], now: Instant) -> bool {
+        if now.duration_since(self.started) < Duration::from_millis(self.config.grace_ms) {
+            return false;
+        }
+        self.config
+            .readiness
+            .iter()
+            .all(|probe| self.probe_ready(probe, windows, now))
+    }
+
+    fn probe_ready(&self, probe: &AppReadinessProbe, windows: &[WindowInfo], now: Instant) -> bool {
+        if now.duration_since(self.started) >= Duration::from_millis(probe.timeout_ms) {
+            tracing::warn!(
+                bundle_id = probe.bundle_id,
+                "readiness probe timed out; proceeding anyway"
+            );
+            return true;
+        }
+        match &probe.condition {
+            ReadinessCondition::WindowCountStable { stable_ms } => self
+                .counts
+                .get(&probe.bundle_id)
+                .map(|(_, changed_at)| {
+                    now.duration_since(*changed_at) >= Duration::from_millis(*stable_ms)
+                })
+                .unwrap_or(false),
+            ReadinessCondition::TitleAppears { pattern } => {
+                let Ok(re) = regex::Regex::new(pattern) else {
+                    // An invalid pattern can never match; don't stall
+                    // startup on a config typo.
+                    return true;
+                };
+                windows
+                    .iter()
+                    .any(|w| w.app_bundle_id == probe.bundle_id && re.is_match(&w.title))
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Parent-child window relationships (sheets, modal dialogs).
+//!
+//! macOS keeps an attached sheet glued to its parent when the parent moves,
+//! but the sheet still appears as a separate window in CGWindowList. The
+//! relations registry records which windows are attached children so the
+//! layout engine never tiles them and arrange passes skip them entirely.
+
+use std::collections::HashMap;
+
+use crate::models::{WindowId, WindowInfo};
+
+/// Tracks which windows are attached children of which parents.
+#[derive(Debug, Default)]
+pub struct WindowRelations {
+    parent_of: HashMap<WindowId, WindowId>,
+    children_of: HashMap<WindowId, Vec<WindowId>>,
+}
+
+impl WindowRelations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `child` as attached to `parent`, replacing any previous
+    /// parent. Called when AX reports a sheet or modal child appearing.
+    pub fn attach(&mut self, parent: WindowId, child: WindowId) {
+        if let Some(previous) = self.parent_of.insert(child, parent) {
+            if let Some(siblings) = self.children_of.get_mut(&previous) {
+                siblings.retain(|&w| w != child);
+            }
+        }
+        let siblings = self.children_of.entry(parent).or_default();
+        if !siblings.contains(&child) {
+            siblings.push(child);
+        }
+    }
+
+    /// Remove a child relationship (sheet dismissed).
+    pub fn detach(&mut self, child: WindowId) {
+        if let Some(parent) = self.parent_of.remove(&child) {
+            if let Some(siblings) = self.children_of.get_mut(&parent) {
+                siblings.retain(|&w| w != child);
+            }
+        }
+    }
+
+    /// Drop all relationships involving a closed window.
+    pub fn forget(&mut self, window: WindowId) {
+        self.detach(window);
+        if let Some(children) = self.children_of.remove(&window) {
+            for child in children {
+                self.parent_of.remove(&child);
+            }
+        }
+    }
+
+    /// Whether this window is an attached child and must never be tiled.
+    pub fn is_attached_child(&self, window: WindowId) -> bool {
+        self.parent_of.contains_key(&window)
+    }
+
+    pub fn parent(&self, child: WindowId) -> Option<WindowId> {
+        self.parent_of.get(&child).copied()
+    }
+
+    pub fn children(&self, parent: WindowId) -> &[WindowId] {
+        self.children_of
+            .get(&parent)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Filter a window list down to the ones the layout engine may tile.
+    /// Attached children are excluded; the OS moves them with their parent.
+    pub fn tileable<'a>(
+        &self,
+        windows: impl IntoIterator<Item = &'a WindowInfo>,
+    ) -> Vec<&'a WindowInfo> {
+        windows
+            .into_iter()
+            .filter(|w| !self.is_attached_child(w.id))
+            .collect()
+    }
+
+    /// Refresh the children of `parent` from an AX probe, attaching new
+    /// ones and detaching dismissed ones.
+    pub fn sync_children(&mut self, parent: WindowId, current: &[WindowId]) {
+        let known: Vec<WindowId> = self.children(parent).to_vec();
+        for &child in current {
+            self.attach(parent, child);
+        }
+        for child in known {
+            if !current.contains(&child) {
+                self.detach(child);
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Atomic execution of action sequences.
+//!
+//! `ActionType::Sequence` lets one binding do several things — move a
+//! window, switch workspace, change layout — but partial execution would
+//! leave the session in a state the user never asked for. Each step
+//! therefore returns its own undo; when a later step fails, completed
+//! steps are rolled back in reverse order and the original error is
+//! surfaced. The orchestrator runs one arrange pass after the whole
+//! sequence, never between steps.
+
+use crate::errors::Result;
+use crate::models::ActionType;
+
+/// Undo for one completed step. `None` for steps with nothing to restore
+/// (a retile, a notification).
+pub type Rollback = Option<Box<dyn FnOnce() -> Result<()> + Send>>;
+
+/// Execute `actions` in order via `apply`, which performs one action and
+/// returns how to undo it.
+///
+/// On failure the error of the failing step is returned after completed
+/// steps were unwound; a rollback that itself fails is logged and skipped,
+/// since aborting mid-unwind would strand the session in an even less
+/// predictable state.
+pub fn run_sequence(
+    actions: &[ActionType],
+    mut apply: impl FnMut(&ActionType) -> Result<Rollback>,
+) -> Result<()> {
+    let mut undo_stack: Vec<Box<dyn FnOnce() -> Result<()> + Send>> = Vec::new();
+    for (step, action) in actions.iter().enumerate() {
+        match apply(action) {
+            Ok(rollback) => {
+                if let Some(rollback) = rollback {
+                    undo_stack.push(rollback);
+                }
+            }
+            Err(err) => {
+                tracing::warn!(
+                    step,
+                    total = actions.len(),
+                    %err,
+                    "sequence step failed; rolling back"
+                );
+                for rollback in undo_stack.into_iter().rev() {
+                    if let Err(undo_err) = rollback() {
+                        tracing::error!(%undo_err, "sequence rollback step failed");
+                    }
+                }
+                return Err(err);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn successful_sequence_runs_every_step_without_rollback() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let actions = vec![ActionType::Retile, ActionType::Retile];
+        let apply_log = Arc::clone(&log);
+        run_sequence(&actions, move |_| {
+            apply_log.lock().unwrap().push("step");
+            let undo_log = Arc::clone(&apply_log);
+            Ok(Some(Box::new(move || {
+                undo_log.lock().unwrap().push("undo");
+                Ok(())
+            })))
+        })
+        .unwrap();
+        assert_eq!(*log.lock().unwrap(), ["step", "step"]);
+    }
+
+    #[test]
+    fn failure_unwinds_completed_steps_in_reverse() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let actions = vec![ActionType::Retile, ActionType::Retile, ActionType::Retile];
+        let apply_log = Arc::clone(&log);
+        let mut step = 0usize;
+        let result = run_sequence(&actions, move |_| {
+            step += 1;
+            if step == 3 {
+                return Err(crate::errors::TilleRSError::Validation("boom".into()));
+            }
+            let undo_log = Arc::clone(&apply_log);
+            let undone = step;
+            Ok(Some(Box::new(move || {
+                undo_log.lock().unwrap().push(format!("undo {undone}"));
+                Ok(())
+            })))
+        });
+        assert!(result.is_err());
+        // Steps 1 and 2 completed; they unwind newest-first.
+        assert_eq!(*log.lock().unwrap(), ["undo 2", "undo 1"]);
+    }
+}
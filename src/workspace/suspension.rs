@@ -0,0 +1,86 @@
+//! Temporary per-application suspension of rules and tiling.
+//!
+//! A suspension makes TilleRS treat an application as unmanaged: no rules
+//! match its windows and the tiling engine leaves them where they are.
+//! Suspensions always carry a deadline so a forgotten "pause" cannot
+//! permanently disable management.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// An active suspension for one application.
+#[derive(Debug, Clone)]
+pub struct Suspension {
+    pub bundle_id: String,
+    pub expires_at: Instant,
+}
+
+impl Suspension {
+    pub fn remaining(&self) -> Duration {
+        self.expires_at.saturating_duration_since(Instant::now())
+    }
+}
+
+/// Tracks which applications are currently suspended.
+///
+/// Callers are expected to invoke [`SuspensionRegistry::purge_expired`]
+/// before querying, typically once per event-loop tick.
+#[derive(Debug, Default)]
+pub struct SuspensionRegistry {
+    by_app: HashMap<String, Suspension>,
+}
+
+impl SuspensionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Suspend `bundle_id` for `duration`, extending any existing
+    /// suspension.
+    pub fn suspend(&mut self, bundle_id: impl Into<String>, duration: Duration) -> &Suspension {
+        let bundle_id = bundle_id.into();
+        let suspension = Suspension {
+            bundle_id: bundle_id.clone(),
+            expires_at: Instant::now() + duration,
+        };
+        self.by_app.insert(bundle_id.clone(), suspension);
+        &self.by_app[&bundle_id]
+    }
+
+    /// Lift a suspension early. Returns `false` when none was active.
+    pub fn resume(&mut self, bundle_id: &str) -> bool {
+        self.by_app.remove(bundle_id).is_some()
+    }
+
+    /// Drop suspensions whose deadline has passed, returning the bundle ids
+    /// that expired so callers can re-adopt those applications' windows.
+    pub fn purge_expired(&mut self) -> Vec<String> {
+        let now = Instant::now();
+        let expired: Vec<String> = self
+            .by_app
+            .values()
+            .filter(|s| s.expires_at <= now)
+            .map(|s| s.bundle_id.clone())
+            .collect();
+        for id in &expired {
+            self.by_app.remove(id);
+        }
+        expired
+    }
+
+    /// Whether rules and tiling are currently suspended for `bundle_id`.
+    pub fn is_suspended(&self, bundle_id: &str) -> bool {
+        self.by_app
+            .get(bundle_id)
+            .is_some_and(|s| s.expires_at > Instant::now())
+    }
+
+    /// All active suspensions, for the tray and `rules list` output.
+    pub fn active(&self) -> Vec<&Suspension> {
+        self.by_app.values().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_app.is_empty()
+    }
+}
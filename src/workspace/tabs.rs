@@ -0,0 +1,66 @@
+//! Native macOS tab group awareness.
+//!
+//! Safari, Finder, and Terminal can merge windows into native tabs. Each
+//! tab is still a distinct window to CGWindowList, but only the selected
+//! tab is on screen; the rest sit stacked behind it with an identical
+//! frame. Tiling them individually would assign N slots to what the user
+//! sees as one window, so tab groups are collapsed to their visible
+//! representative before layout.
+
+use std::collections::HashMap;
+
+use crate::models::{WindowId, WindowInfo};
+
+/// A detected native tab group.
+#[derive(Debug, Clone)]
+pub struct TabGroup {
+    /// The selected tab — the only member the layout engine sees.
+    pub representative: WindowId,
+    /// Every window in the group, representative included.
+    pub members: Vec<WindowId>,
+}
+
+/// Collapse tab groups: returns the windows to tile (one per group) and
+/// the groups that were detected.
+///
+/// Detection heuristic: same app, same frame, stacked — CGWindowList gives
+/// no direct tab relationship, and this is the same signal the selected
+/// tab's z-order gives the user. The first window in list order is on top
+/// and becomes the representative.
+pub fn collapse_tab_groups(windows: &[WindowInfo]) -> (Vec<&WindowInfo>, Vec<TabGroup>) {
+    let mut buckets: HashMap<(String, i64, i64, i64, i64), Vec<&WindowInfo>> = HashMap::new();
+    for window in windows {
+        if window.minimized {
+            continue;
+        }
+        let key = (
+            window.app_bundle_id.clone(),
+            window.frame.x.round() as i64,
+            window.frame.y.round() as i64,
+            window.frame.width.round() as i64,
+            window.frame.height.round() as i64,
+        );
+        buckets.entry(key).or_default().push(window);
+    }
+
+    let mut tileable = Vec::new();
+    let mut groups = Vec::new();
+    for members in buckets.into_values() {
+        let representative = members[0];
+        tileable.push(representative);
+        if members.len() > 1 {
+            groups.push(TabGroup {
+                representative: representative.id,
+                members: members.iter().map(|w| w.id).collect(),
+            });
+        }
+    }
+    // HashMap iteration order is arbitrary; keep layout assignment stable.
+    tileable.sort_by_key(|w| w.id);
+    (tileable, groups)
+}
+
+/// Look up the group a window belongs to, if any.
+pub fn group_of(groups: &[TabGroup], window: WindowId) -> Option<&TabGroup> {
+    groups.iter().find(|g| g.members.contains(&window))
+}
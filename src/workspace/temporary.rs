@@ -0,0 +1,106 @@
+//! Time-boxed temporary workspaces.
+//!
+//! `tillers workspace create scratch --temporary 2h` makes a workspace
+//! that self-destructs when the clock runs out: windows still on it move
+//! to the configured fallback, then the workspace is removed. The tray
+//! shows a countdown so the deadline is never a surprise, and a
+//! temporary workspace can be promoted to a permanent one before it
+//! expires.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// The `[temporary]` config section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TemporaryConfig {
+    /// Workspace that inherits windows left behind at expiry; created on
+    /// demand, like the catch-all.
+    pub fallback: String,
+}
+
+impl Default for TemporaryConfig {
+    fn default() -> Self {
+        TemporaryConfig {
+            fallback: "overflow".to_string(),
+        }
+    }
+}
+
+/// One running time box.
+#[derive(Debug, Clone)]
+pub struct TemporaryWorkspace {
+    pub workspace: String,
+    pub created_at: Instant,
+    pub duration: Duration,
+}
+
+impl TemporaryWorkspace {
+    pub fn remaining(&self) -> Duration {
+        (self.created_at + self.duration).saturating_duration_since(Instant::now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+}
+
+/// Owns every running time box; the workspace manager's periodic tick
+/// drains expirations through [`tick`](Self::tick).
+#[derive(Debug, Default)]
+pub struct TemporaryRegistry {
+    entries: Vec<TemporaryWorkspace>,
+}
+
+impl TemporaryRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start (or restart) the time box for a workspace.
+    pub fn register(&mut self, workspace: impl Into<String>, duration: Duration) {
+        let workspace = workspace.into();
+        self.entries.retain(|e| e.workspace != workspace);
+        self.entries.push(TemporaryWorkspace {
+            workspace,
+            created_at: Instant::now(),
+            duration,
+        });
+    }
+
+    /// Promote a temporary workspace to a permanent one. Returns whether
+    /// a time box existed.
+    pub fn promote(&mut self, workspace: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.workspace != workspace);
+        self.entries.len() != before
+    }
+
+    /// Drop the time box of a workspace removed through other means.
+    pub fn forget(&mut self, workspace: &str) {
+        self.entries.retain(|e| e.workspace != workspace);
+    }
+
+    /// Drain and return every expired time box; the caller moves leftover
+    /// windows to the fallback and removes the workspace.
+    pub fn tick(&mut self) -> Vec<TemporaryWorkspace> {
+        let (expired, live) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(TemporaryWorkspace::is_expired);
+        self.entries = live;
+        expired
+    }
+
+    /// Remaining time for a workspace's time box, for the countdown.
+    pub fn remaining(&self, workspace: &str) -> Option<Duration> {
+        self.entries
+            .iter()
+            .find(|e| e.workspace == workspace)
+            .map(TemporaryWorkspace::remaining)
+    }
+
+    pub fn entries(&self) -> &[TemporaryWorkspace] {
+        &self.entries
+    }
+}
@@ -0,0 +1,142 @@
+//! Logical display topology: mirrored vs extended.
+//!
+//! A mirrored display shows the same content as its primary, so tiling
+//! must treat the mirror set as one monitor — assigning a workspace to the
+//! projector *and* the laptop screen would double-manage every window.
+//! Mirroring also toggles at the worst times (plugging into a projector),
+//! so the topology is re-derived on every display event and workspace
+//! assignments are revalidated when it changes.
+
+use std::collections::HashMap;
+
+use crate::models::display::{DisplayId, DisplayInfo};
+
+/// The displays tiling actually targets, after collapsing mirror sets.
+#[derive(Debug, Default)]
+pub struct DisplayTopology {
+    /// One entry per logical monitor; the mirror-set primary represents
+    /// the whole set.
+    logical: Vec<DisplayInfo>,
+    /// Mirror secondaries, keyed by the primary they duplicate.
+    mirrors: HashMap<DisplayId, Vec<DisplayId>>,
+}
+
+impl DisplayTopology {
+    /// Derive the logical topology from the physical display list.
+    /// `mirror_of` reports the primary a display mirrors, if any
+    /// (`macos::windows::mirror_primary` on macOS).
+    pub fn derive(
+        displays: Vec<DisplayInfo>,
+        mirror_of: impl Fn(DisplayId) -> Option<DisplayId>,
+    ) -> Self {
+        let mut logical = Vec::new();
+        let mut mirrors: HashMap<DisplayId, Vec<DisplayId>> = HashMap::new();
+        for display in displays {
+            match mirror_of(display.id) {
+                Some(primary) => mirrors.entry(primary).or_default().push(display.id),
+                None => logical.push(display),
+            }
+        }
+        DisplayTopology { logical, mirrors }
+    }
+
+    /// The monitors tiling assigns workspaces to.
+    pub fn logical_displays(&self) -> &[DisplayInfo] {
+        &self.logical
+    }
+
+    /// Whether any mirror set is active.
+    pub fn has_mirroring(&self) -> bool {
+        !self.mirrors.is_empty()
+    }
+
+    /// Resolve a physical display to its logical monitor: mirrors map to
+    /// their primary, everything else to itself.
+    pub fn logical_for(&self, display: DisplayId) -> DisplayId {
+        for (&primary, secondaries) in &self.mirrors {
+            if secondaries.contains(&display) {
+                return primary;
+            }
+        }
+        display
+    }
+
+    /// Whether `other` describes a different logical topology — the signal
+    /// to revalidate every workspace-to-monitor assignment.
+    pub fn changed_from(&self, other: &DisplayTopology) -> bool {
+        let mut a: Vec<DisplayId> = self.logical.iter().map(|d| d.id).collect();
+        let mut b: Vec<DisplayId> = other.logical.iter().map(|d| d.id).collect();
+        a.sort_unstable();
+        b.sort_unstable();
+        a != b || self.mirrors != other.mirrors
+    }
+}
+
+/// Workspace display assignments that no longer point at a logical
+/// monitor; these fall back to the main display until the topology
+/// changes back.
+pub fn stale_assignments<'a>(
+    topology: &DisplayTopology,
+    assignments: impl IntoIterator<Item = (&'a str, DisplayId)>,
+) -> Vec<&'a str> {
+    let logical: Vec<DisplayId> = topology.logical_displays().iter().map(|d| d.id).collect();
+    assignments
+        .into_iter()
+        .filter(|(_, display)| !logical.contains(&topology.logical_for(*display)))
+        .map(|(workspace, _)| workspace)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Rect;
+
+    fn display(id: DisplayId) -> DisplayInfo {
+        let frame = Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 1920.0,
+            height: 1080.0,
+        };
+        DisplayInfo {
+            id,
+            name: format!("display {id}"),
+            frame,
+            work_area: frame,
+        }
+    }
+
+    #[test]
+    fn mirrors_collapse_into_their_primary() {
+        let displays = vec![display(1), display(2), display(3)];
+        // Display 2 mirrors display 1; display 3 is extended.
+        let topology = DisplayTopology::derive(displays, |id| (id == 2).then_some(1));
+        let logical: Vec<DisplayId> = topology.logical_displays().iter().map(|d| d.id).collect();
+        assert_eq!(logical, [1, 3]);
+        assert!(topology.has_mirroring());
+        assert_eq!(topology.logical_for(2), 1);
+        assert_eq!(topology.logical_for(3), 3);
+    }
+
+    #[test]
+    fn changed_from_reflects_logical_differences_only() {
+        let mirrored =
+            DisplayTopology::derive(vec![display(1), display(2)], |id| (id == 2).then_some(1));
+        let extended = DisplayTopology::derive(vec![display(1), display(2)], |_| None);
+        let mirrored_again =
+            DisplayTopology::derive(vec![display(1), display(2)], |id| (id == 2).then_some(1));
+        assert!(mirrored.changed_from(&extended));
+        assert!(!mirrored.changed_from(&mirrored_again));
+    }
+
+    #[test]
+    fn stale_assignments_resolve_mirrors_before_flagging() {
+        let topology =
+            DisplayTopology::derive(vec![display(1), display(2)], |id| (id == 2).then_some(1));
+        // A workspace pinned to the mirror secondary still has a home (its
+        // primary); one pinned to an unplugged display does not.
+        let stale = stale_assignments(&topology, [("code", 2), ("chat", 9)]);
+        assert_eq!(stale, ["chat"]);
+    }
+}
@@ -0,0 +1,109 @@
+//! Exact per-workspace visibility snapshots.
+//!
+//! Re-running the layout on switch-back recomputes tiled frames but loses
+//! everything else: float positions, minimized state, and stacking order.
+//! Instead, deactivating a workspace snapshots each window's full
+//! visibility state — frame, floating, minimized, z-order — and
+//! switch-back replays the snapshot verbatim, raising windows back to
+//! front in reverse z-order.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::daemon::Effects;
+use crate::errors::Result;
+use crate::models::{Rect, WindowId, WindowInfo};
+
+/// Everything needed to put one window back the way it was.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowVisibility {
+    pub window: WindowId,
+    pub frame: Rect,
+    pub floating: bool,
+    pub minimized: bool,
+}
+
+/// One workspace's captured state. `windows` is ordered front-to-back —
+/// index 0 was on top.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceSnapshot {
+    pub windows: Vec<WindowVisibility>,
+}
+
+/// Snapshots per workspace, captured on deactivation.
+#[derive(Debug, Default)]
+pub struct VisibilityStore {
+    snapshots: HashMap<String, WorkspaceSnapshot>,
+}
+
+impl VisibilityStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot a workspace before deactivating it. `windows` must be in
+    /// z-order, frontmost first (CGWindowList enumeration order).
+    pub fn capture<'a>(
+        &mut self,
+        workspace: &str,
+        windows: impl IntoIterator<Item = &'a WindowInfo>,
+    ) {
+        let snapshot = WorkspaceSnapshot {
+            windows: windows
+                .into_iter()
+                .map(|w| WindowVisibility {
+                    window: w.id,
+                    frame: w.frame,
+                    floating: w.floating,
+                    minimized: w.minimized,
+                })
+                .collect(),
+        };
+        self.snapshots.insert(workspace.to_string(), snapshot);
+    }
+
+    /// The snapshot for a workspace, if one was captured.
+    pub fn get(&self, workspace: &str) -> Option<&WorkspaceSnapshot> {
+        self.snapshots.get(workspace)
+    }
+
+    /// Replay a workspace's snapshot exactly: frames first (back-to-front
+    /// is irrelevant for geometry), then minimized state, then raises in
+    /// reverse z-order so the front window ends up on top.
+    ///
+    /// Returns the replayed snapshot, or `None` when this workspace was
+    /// never captured — the caller falls back to a fresh layout pass.
+    pub fn restore(
+        &self,
+        workspace: &str,
+        effects: &Effects,
+    ) -> Result<Option<&WorkspaceSnapshot>> {
+        let Some(snapshot) = self.snapshots.get(workspace) else {
+            return Ok(None);
+        };
+        for state in &snapshot.windows {
+            effects.set_window_frame(state.window, state.frame)?;
+            effects.set_window_minimized(state.window, state.minimized)?;
+        }
+        for state in snapshot.windows.iter().rev() {
+            if !state.minimized {
+                effects.raise_window(state.window)?;
+            }
+        }
+        Ok(Some(snapshot))
+    }
+
+    /// Drop a closed window from every snapshot so restores never touch
+    /// dead window ids.
+    pub fn forget_window(&mut self, window: WindowId) {
+        for snapshot in self.snapshots.values_mut() {
+            snapshot.windows.retain(|s| s.window != window);
+        }
+    }
+
+    /// Drop a removed workspace's snapshot.
+    pub fn forget_workspace(&mut self, workspace: &str) {
+        self.snapshots.remove(workspace);
+    }
+}
@@ -0,0 +1,229 @@
+//! The window manager: the daemon's model of every managed window.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::daemon::Effects;
+use crate::errors::Result;
+use crate::models::{Rect, WindowId, WindowInfo};
+
+/// How often the model is reconciled against CGWindowList.
+pub const RECONCILE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Frames closer than this (per edge, in points) are considered equal;
+/// sub-pixel jitter from scaling must not trigger AX traffic.
+pub const FRAME_TOLERANCE: f64 = 1.0;
+
+/// Outcome of one arrange pass's frame application.
+#[derive(Debug, Default)]
+pub struct ApplyReport {
+    /// Windows whose frames were applied and verified.
+    pub verified: Vec<WindowId>,
+    /// Windows that accepted the set-frame calls but never moved; the
+    /// compliance tracker decides when they stop being worth tiling.
+    pub unverified: Vec<WindowId>,
+    /// Windows skipped because their target already matched the cache.
+    pub skipped: usize,
+    /// Windows left unapplied because the deadline budget ran out; their
+    /// cache entries stay cold, so a follow-up pass re-asserts them.
+    pub deferred: usize,
+}
+
+impl ApplyReport {
+    /// Frames actually pushed through AX this pass.
+    pub fn applied(&self) -> usize {
+        self.verified.len() + self.unverified.len()
+    }
+}
+
+/// Owns window state and minimizes the AX calls needed to realize a
+/// layout.
+#[derive(Debug, Default)]
+pub struct WindowManager {
+    windows: HashMap<WindowId, WindowInfo>,
+    /// Last frame we successfully applied per window. Arrange passes diff
+    /// against this so a no-op re-tile issues zero AX calls.
+    last_applied: HashMap<WindowId, Rect>,
+}
+
+impl WindowManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, window: WindowInfo) {
+        self.windows.insert(window.id, window);
+    }
+
+    pub fn remove(&mut self, window: WindowId) -> Option<WindowInfo> {
+        self.last_applied.remove(&window);
+        self.windows.remove(&window)
+    }
+
+    pub fn get(&self, window: WindowId) -> Option<&WindowInfo> {
+        self.windows.get(&window)
+    }
+
+    pub fn windows(&self) -> impl Iterator<Item = &WindowInfo> {
+        self.windows.values()
+    }
+
+    /// Apply target frames, skipping windows already within tolerance of
+    /// their target. Frames apply in assignment order until `budget` runs
+    /// out; the remainder is deferred for a follow-up pass so the visible
+    /// work (callers put the important windows first) lands on time.
+    pub fn apply_assignments(
+        &mut self,
+        effects: &Effects,
+        assignments: &[(WindowId, Rect)],
+        token: &CancellationToken,
+        budget: Duration,
+    ) -> Result<ApplyReport> {
+        let changed: Vec<(WindowId, Rect)> = assignments
+            .iter()
+            .filter(|(window, target)| {
+                self.last_applied
+                    .get(window)
+                    .map(|last| !frames_equal(last, target))
+                    .unwrap_or(true)
+            })
+            .copied()
+            .collect();
+        let skipped = assignments.len() - changed.len();
+
+        let mut applied = 0;
+        let mut unverified = Vec::new();
+        let mut failure = None;
+        let outcome =
+            super::deadline::run_with_budget(budget, changed.clone(), |&(window, frame)| {
+                // A failed or cancelled pass stops applying; the budget
+                // runner still drains the list, so nothing past this point
+                // may count as applied.
+                if failure.is_some() || token.is_cancelled() {
+                    return;
+                }
+                match effects.set_window_frame_verified(window, frame) {
+                    Ok(true) => applied += 1,
+                    Ok(false) => {
+                        applied += 1;
+                        unverified.push(window);
+                    }
+                    Err(err) => failure = Some(err),
+                }
+            });
+        if let Some(err) = failure {
+            return Err(err);
+        }
+        let deferred = outcome.deferred.len();
+        let mut verified = Vec::with_capacity(applied);
+        for (window, frame) in changed.iter().take(applied) {
+            // An unverified frame must stay out of the cache, or the next
+            // pass would diff against a frame the window never took and
+            // skip the retry.
+            if unverified.contains(window) {
+                continue;
+            }
+            verified.push(*window);
+            self.last_applied.insert(*window, *frame);
+            if let Some(info) = self.windows.get_mut(window) {
+                info.frame = *frame;
+            }
+        }
+        tracing::debug!(
+            applied,
+            skipped,
+            deferred,
+            unverified = unverified.len(),
+            "arrange pass frame diff"
+        );
+        Ok(ApplyReport {
+            verified,
+            unverified,
+            skipped,
+            deferred,
+        })
+    }
+
+    /// Invalidate the applied-frame cache for a window the user moved
+    /// manually, so the next arrange re-asserts its frame.
+    pub fn invalidate(&mut self, window: WindowId) {
+        self.last_applied.remove(&window);
+    }
+
+    /// Cross-check the model against a fresh CGWindowList enumeration and
+    /// repair drift.
+    ///
+    /// AX observer callbacks occasionally drop creation or destruction
+    /// events (app hangs, notification coalescing); rather than trust the
+    /// model forever, it is periodically reconciled — adopted windows are
+    /// inserted, vanished windows removed, and moved frames invalidated so
+    /// the next arrange re-asserts them. Every repair is a warning: a
+    /// nonzero report means events were missed.
+    pub fn reconcile(&mut self, actual: &[WindowInfo]) -> ReconcileReport {
+        let mut report = ReconcileReport::default();
+
+        let actual_ids: Vec<WindowId> = actual.iter().map(|w| w.id).collect();
+        let vanished: Vec<WindowId> = self
+            .windows
+            .keys()
+            .copied()
+            .filter(|id| !actual_ids.contains(id))
+            .collect();
+        for id in vanished {
+            tracing::warn!(window = id, "reconcile: tracked window no longer exists");
+            self.remove(id);
+            report.removed += 1;
+        }
+
+        for window in actual {
+            match self.windows.get_mut(&window.id) {
+                None => {
+                    tracing::warn!(
+                        window = window.id,
+                        app = %window.app_bundle_id,
+                        "reconcile: untracked window adopted"
+                    );
+                    self.insert(window.clone());
+                    report.adopted += 1;
+                }
+                Some(known) => {
+                    if !frames_equal(&known.frame, &window.frame) {
+                        tracing::warn!(window = window.id, "reconcile: frame drifted from model");
+                        known.frame = window.frame;
+                        self.last_applied.remove(&window.id);
+                        report.drifted += 1;
+                    }
+                }
+            }
+        }
+        report
+    }
+}
+
+/// What a reconciliation pass had to repair.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ReconcileReport {
+    /// Windows present on screen but missing from the model.
+    pub adopted: usize,
+    /// Windows in the model that no longer exist.
+    pub removed: usize,
+    /// Windows whose on-screen frame no longer matched the model.
+    pub drifted: usize,
+}
+
+impl ReconcileReport {
+    pub fn is_clean(&self) -> bool {
+        self.adopted == 0 && self.removed == 0 && self.drifted == 0
+    }
+}
+
+/// Per-edge comparison within [`FRAME_TOLERANCE`].
+fn frames_equal(a: &Rect, b: &Rect) -> bool {
+    (a.x - b.x).abs() <= FRAME_TOLERANCE
+        && (a.y - b.y).abs() <= FRAME_TOLERANCE
+        && (a.width - b.width).abs() <= FRAME_TOLERANCE
+        && (a.height - b.height).abs() <= FRAME_TOLERANCE
+}